@@ -1,6 +1,8 @@
 use std::env;
 use std::fs;
 use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 fn main() {
     // Build the Windows resource (.rc) at compile time so the embedded
@@ -21,6 +23,8 @@ fn main() {
     let ico = esc(resources.join("app.ico"));
     let manifest = esc(resources.join("app.manifest"));
 
+    // The icon must stay at resource ID 1 (IDI_APP_ICON in src/app/constants.rs);
+    // the tray and window classes load it by that ID.
     let rc = format!(
         "1 ICON \"{ico}\"\n\
          1 24 \"{manifest}\"\n\
@@ -59,7 +63,39 @@ fn main() {
 
     embed_resource::compile(&rc_path, embed_resource::NONE);
 
+    // Expose the commit and build date to the About box. Source tarballs
+    // without a .git directory fall back to "unknown".
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=BUILD_COMMIT={commit}");
+    println!("cargo:rustc-env=BUILD_DATE={}", build_date());
+
     println!("cargo:rerun-if-changed=resources/app.ico");
     println!("cargo:rerun-if-changed=resources/app.manifest");
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+}
+
+/// Today's UTC date as YYYY-MM-DD (civil-from-days, avoids a chrono dependency)
+fn build_date() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let z = secs / 86400 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{year:04}-{month:02}-{day:02}")
 }
@@ -0,0 +1,46 @@
+//! Accounts module
+//! Which Windows accounts have their screen time counted on a shared PC
+
+/// Whether time counts for `user` under the `counted_users` setting: a comma-separated list
+/// of account names, compared case-insensitively like Windows does. An empty list counts
+/// every account.
+pub fn is_counted(list: &str, user: &str) -> bool {
+    let mut names = list.split(',').map(str::trim).filter(|n| !n.is_empty()).peekable();
+    names.peek().is_none() || names.any(|n| n.eq_ignore_ascii_case(user.trim()))
+}
+
+/// Whether time counts in the app's session: `session_in_use` is whether the session is the
+/// one being used (at the console or over Remote Desktop) and `user` its account, each None
+/// when Windows couldn't tell. Anything unknown counts, so a failing query never lifts the limit.
+pub fn counts_in_session(list: &str, user: Option<&str>, session_in_use: Option<bool>) -> bool {
+    session_in_use.unwrap_or(true) && user.is_none_or(|user| is_counted(list, user))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_empty_list_counts_everyone() {
+        assert!(is_counted("", "Mum"));
+        assert!(is_counted(" , ", "Mum"));
+    }
+
+    #[test]
+    fn only_listed_accounts_count() {
+        assert!(is_counted("Max, Lena", "max"));
+        assert!(is_counted("Max, Lena", "Lena"));
+        assert!(!is_counted("Max, Lena", "Mum"));
+        assert!(!is_counted("Max", ""));
+    }
+
+    #[test]
+    fn unknown_sessions_and_accounts_still_count() {
+        assert!(counts_in_session("Max", Some("max"), Some(true)));
+        assert!(!counts_in_session("Max", Some("max"), Some(false)));
+        assert!(!counts_in_session("Max", Some("Mum"), Some(true)));
+        assert!(counts_in_session("Max", None, None));
+        assert!(counts_in_session("Max", Some("max"), None));
+        assert!(counts_in_session("Max", None, Some(true)));
+    }
+}
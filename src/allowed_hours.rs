@@ -0,0 +1,66 @@
+//! Allowed hours module
+//! Windows of the day in which the computer may be used, e.g. "07:00-20:30"
+
+/// Parse an allowed-hours window like "07:00-20:30" into minutes of the day.
+/// An end before the start means the window runs past midnight (e.g. "18:00-01:00").
+pub fn parse_window(text: &str) -> Option<(u32, u32)> {
+    fn parse_time(t: &str) -> Option<u32> {
+        let (h, m) = t.trim().split_once(':')?;
+        let (h, m): (u32, u32) = (h.parse().ok()?, m.parse().ok()?);
+        // 24:00 is allowed as an end-of-day marker
+        if m < 60 && (h < 24 || (h == 24 && m == 0)) {
+            Some(h * 60 + m)
+        } else {
+            None
+        }
+    }
+
+    let (start, end) = text.split_once('-')?;
+    Some((parse_time(start)?, parse_time(end)?))
+}
+
+/// Check whether `now` (minutes of the day) is inside today's or yesterday's window.
+/// After midnight, the previous day's window still applies if it runs past midnight,
+/// so a Saturday "09:00-01:00" keeps Sunday 00:30 allowed.
+pub fn allowed_at(now: u32, today: Option<(u32, u32)>, yesterday: Option<(u32, u32)>) -> bool {
+    let today_allows = match today {
+        None => true,
+        Some((start, end)) if start == end => true,
+        Some((start, end)) if start < end => now >= start && now < end,
+        // Runs past midnight: only the evening part belongs to today
+        Some((start, _)) => now >= start,
+    };
+    let yesterday_allows = match yesterday {
+        Some((start, end)) if end < start => now < end,
+        _ => false,
+    };
+
+    today_allows || yesterday_allows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_past_midnight_carries_into_the_next_day() {
+        let saturday = parse_window("09:00-01:00");
+        let sunday = parse_window("10:00-20:00");
+
+        // Sunday 00:30 is still inside Saturday's window, 01:30 is not
+        assert!(allowed_at(30, sunday, saturday));
+        assert!(!allowed_at(90, sunday, saturday));
+        // Saturday 23:00 is inside its own window
+        assert!(allowed_at(23 * 60, saturday, None));
+        // Sunday 20:00 is the exclusive end
+        assert!(!allowed_at(20 * 60, sunday, saturday));
+    }
+
+    #[test]
+    fn missing_or_empty_window_allows_everything() {
+        assert!(allowed_at(3 * 60, None, None));
+        assert!(allowed_at(3 * 60, parse_window("08:00-08:00"), None));
+        assert_eq!(parse_window("25:00-26:00"), None);
+        assert_eq!(parse_window("00:00-24:00"), Some((0, 1440)));
+    }
+}
@@ -0,0 +1,372 @@
+//! The Windows app: tray icon, overlays, dialogs, the database and the remote channels.
+//! Only built on Windows; main.rs re-exports these modules at the crate root, so they
+//! refer to each other as `crate::database` and so on.
+
+pub mod apps;
+pub mod backup;
+pub mod blocking;
+pub mod cleanup;
+pub mod clock;
+pub mod constants;
+pub mod curfew;
+pub mod database;
+pub mod dialogs;
+pub mod dpi;
+pub mod elevation;
+pub mod email;
+pub mod headless;
+pub mod hotkey;
+pub mod httpapi;
+pub mod i18n;
+pub mod ipc;
+pub mod locale;
+pub mod logging;
+pub mod mini_overlay;
+pub mod mqtt;
+pub mod notifications;
+pub mod overlay;
+pub mod secrets;
+pub mod security;
+pub mod session;
+pub mod telegram;
+pub mod theme;
+pub mod tray;
+pub mod webhook;
+pub mod wizard;
+
+// The shared logic, under the same `crate::` paths as the app's own modules
+pub use screen_time_manager::{accounts, allowed_hours, dates, duration, json, pause, timefmt};
+
+use std::mem::zeroed;
+use windows::{
+    core::{w, PCWSTR},
+    Win32::{
+        Foundation::{BOOL, GetLastError, CloseHandle, ERROR_ALREADY_EXISTS, HANDLE, HWND},
+        System::{
+            Console::{AttachConsole, ATTACH_PARENT_PROCESS},
+            LibraryLoader::GetModuleHandleW,
+            Threading::CreateMutexW,
+        },
+        UI::HiDpi::{SetProcessDpiAwareness, PROCESS_PER_MONITOR_DPI_AWARE},
+        UI::WindowsAndMessaging::*,
+    },
+};
+
+use blocking::{create_blocking_overlay, create_secondary_overlays, register_blocking_class, REMAINING_SECONDS};
+use constants::MUTEX_NAME;
+use database::{init_database, load_remaining_time, get_current_weekday, get_daily_limit};
+use mini_overlay::{create_mini_overlay, register_mini_overlay_class, show_mini_overlay};
+use overlay::{create_overlay_window, register_overlay_class};
+use tray::{add_tray_icon, remove_tray_icon, window_proc};
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+/// Handle of the single-instance mutex, held until a clean shutdown
+static INSTANCE_MUTEX: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+
+/// Run the app: set up the database and windows, then the message loop until it quits
+pub fn run() {
+    unsafe {
+        // DPI awareness is declared in the embedded manifest; this call is a fallback
+        // for when the binary runs without it (it fails harmlessly if already set)
+        let _ = SetProcessDpiAwareness(PROCESS_PER_MONITOR_DPI_AWARE);
+        dpi::init_dpi();
+
+        // `--uninstall` removes the autostart entry and the data directory, then exits
+        if std::env::args().any(|a| a == "--uninstall") {
+            run_uninstall();
+            return;
+        }
+
+        // `--command "<command>" --passcode <code>` hands the command to the running app
+        if let Some((command, passcode)) = ipc::command_from_args(&std::env::args().collect::<Vec<_>>()) {
+            std::process::exit(run_forwarded_command(&command, &passcode));
+        }
+
+        // `--headless` runs without any windows (lab machines, servers)
+        let headless = std::env::args().any(|a| a == headless::HEADLESS_ARG);
+        if headless {
+            headless::enable();
+        }
+
+        // Check for single instance. A restart as administrator starts this copy while
+        // the old instance is still shutting down, so it waits a few seconds for it.
+        let restarted = std::env::args().any(|a| a == elevation::RESTART_ARG);
+        let mut single = ensure_single_instance();
+        for _ in 0..40 {
+            if single || !restarted {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(250));
+            single = ensure_single_instance();
+        }
+        if !single {
+            if headless {
+                return;
+            }
+            MessageBoxW(
+                None,
+                w!("Screen Time Manager is already running."),
+                w!("Already Running"),
+                MB_OK | MB_ICONWARNING,
+            );
+            return;
+        }
+
+        // Log to app.log in the data directory (there's no console to print to)
+        logging::init();
+        log::info!("Starting version {} ({})", env!("CARGO_PKG_VERSION"), env!("BUILD_COMMIT"));
+        if headless {
+            log::info!("Running headless: no tray icon, overlays or dialogs");
+        }
+
+        // Initialize database
+        if let Err(e) = init_database() {
+            log::error!("Failed to initialize database: {}", e);
+            if headless {
+                return;
+            }
+            let msg: Vec<u16> = format!("Failed to initialize database: {}\0", e)
+                .encode_utf16()
+                .collect();
+            MessageBoxW(
+                None,
+                PCWSTR(msg.as_ptr()),
+                w!("Database Error"),
+                MB_OK | MB_ICONERROR,
+            );
+            return;
+        }
+        logging::apply_level();
+
+        // Let the user know if a corrupt database was replaced with defaults
+        if let Some(backup) = database::take_recovered_backup().filter(|_| !headless) {
+            let msg: Vec<u16> = format!("{}\n\n{}\0", i18n::t("db.recovered"), backup.display())
+                .encode_utf16()
+                .collect();
+            let title = i18n::wide("db.recovered_title");
+            MessageBoxW(
+                None,
+                PCWSTR(msg.as_ptr()),
+                PCWSTR(title.as_ptr()),
+                MB_OK | MB_ICONWARNING,
+            );
+        }
+
+        // A new installation starts with the setup wizard (passcode, limits, ...)
+        if !headless {
+            wizard::show_if_needed();
+        }
+
+        // Pick up high-contrast / transparency preferences before creating the overlays
+        theme::refresh();
+
+        // Get the module handle
+        let hinstance = GetModuleHandleW(None).expect("Failed to get module handle");
+
+        // Register main window class
+        let class_name = w!("ScreenTimeManagerClass");
+        let wnd_class = WNDCLASSW {
+            style: CS_HREDRAW | CS_VREDRAW,
+            lpfnWndProc: Some(window_proc),
+            hInstance: hinstance.into(),
+            hIcon: LoadIconW(hinstance, PCWSTR(constants::IDI_APP_ICON as usize as *const u16)).unwrap_or_default(),
+            lpszClassName: class_name,
+            ..zeroed()
+        };
+
+        if RegisterClassW(&wnd_class) == 0 {
+            panic!("Failed to register window class");
+        }
+
+        // Register overlay and blocking window classes
+        if !headless {
+            register_overlay_class(hinstance);
+            register_blocking_class(hinstance);
+            register_mini_overlay_class(hinstance);
+        }
+
+        // Create a hidden window for message handling
+        let hwnd = CreateWindowExW(
+            Default::default(),
+            class_name,
+            w!("Screen Time Manager"),
+            WS_POPUP,
+            CW_USEDEFAULT,
+            CW_USEDEFAULT,
+            CW_USEDEFAULT,
+            CW_USEDEFAULT,
+            None,
+            None,
+            hinstance,
+            None,
+        )
+        .expect("Failed to create window");
+
+        // Create the overlay windows (initially hidden)
+        if !headless {
+            create_overlay_window(hinstance);
+            create_blocking_overlay(hinstance);
+            create_secondary_overlays(hinstance);  // Create overlays for secondary monitors
+            create_mini_overlay(hinstance);
+        }
+
+        // Undo a clock change made while the app was closed before any date is looked at
+        security::check_clock_at_startup();
+
+        // Apply this week's limit ramp-down before today's limit is read
+        database::apply_limit_rampdown();
+
+        // Initialize remaining time from database or daily limit
+        let remaining = load_remaining_time().unwrap_or_else(|| {
+            // No saved time for today, use daily limit
+            let weekday = get_current_weekday();
+            (get_daily_limit(weekday) * 60) as i32  // Convert minutes to seconds
+        });
+        REMAINING_SECONDS.store(remaining, Ordering::SeqCst);
+
+        // Initialize session active time from database
+        let session_active = database::get_session_active_time();
+        mini_overlay::SESSION_ACTIVE_SECONDS.store(session_active, Ordering::SeqCst);
+        mini_overlay::ACTIVE_SINCE_RESUME.store(database::get_active_since_pause(), Ordering::SeqCst);
+
+        // Pick up a pause or focus mode that was active when the app was closed
+        mini_overlay::restore_pause_state();
+        mini_overlay::restore_focus_state();
+
+        // Remember which day the counters belong to
+        mini_overlay::check_day_rollover();
+
+        // Show the mini overlay with remaining time (its timer drives the countdown;
+        // headless the countdown runs on the hidden main window)
+        if headless {
+            mini_overlay::start_headless_countdown(hwnd);
+        } else {
+            show_mini_overlay();
+        }
+
+        // Show the block that was up when the app last ran (lock, used-up time, changed clock),
+        // or the blocking overlay (or warn-only banner) if time is already exhausted, and the curfew
+        blocking::restore_block();
+        headless::enforce_block();
+
+        // Add the system tray icon
+        if !headless {
+            add_tray_icon(hwnd);
+
+            // Ctrl+Alt+T (or the configured shortcut) shows the remaining time
+            hotkey::register(hwnd);
+        }
+
+        // Stop counting while another account is switched to the console
+        session::register(hwnd);
+
+        // Nag at every start until the default passcode is changed
+        if database::is_default_passcode() && !headless {
+            tray::show_balloon(i18n::t("passcode.default_title"), i18n::t("passcode.default_warning"));
+        }
+
+        // Start Telegram bot in background thread (if configured)
+        telegram::start_bot_thread();
+
+        // Send the daily email report at the configured time (idle while it's off)
+        email::start_report_thread();
+
+        // Publish the timer state over MQTT (if configured)
+        mqtt::start_mqtt_thread();
+
+        // Serve Prometheus metrics on the local HTTP endpoint (if configured)
+        httpapi::start_server_thread();
+
+        // Accept commands forwarded by `--command` (sets the ready event once listening)
+        ipc::start_server_thread();
+
+        // Message loop
+        let mut msg: MSG = zeroed();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            if blocking::translate_dialog_message(&msg) {
+                continue;
+            }
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+
+        // Cleanup: remove the tray icon
+        remove_tray_icon();
+        release_single_instance();
+    }
+}
+
+/// Ensures only one instance of the application is running.
+///
+/// The named mutex is only used for presence detection, so it is created without initial
+/// ownership. Windows destroys it when the last handle closes, which also happens when the
+/// process crashes or is killed, so the next launch always starts normally.
+unsafe fn ensure_single_instance() -> bool {
+    let mutex_name: Vec<u16> = MUTEX_NAME.encode_utf16().chain(std::iter::once(0)).collect();
+
+    let handle = CreateMutexW(
+        None,
+        BOOL::from(false),
+        PCWSTR(mutex_name.as_ptr()),
+    );
+
+    match handle {
+        Ok(h) => {
+            if GetLastError() == ERROR_ALREADY_EXISTS {
+                let _ = CloseHandle(h);
+                false
+            } else {
+                INSTANCE_MUTEX.store(h.0, Ordering::SeqCst);
+                true
+            }
+        }
+        Err(_) => false,
+    }
+}
+
+/// The `--uninstall` command line: the same prompts as the tray's Uninstall... item.
+/// The running app holds the database open, so it has to be quit first.
+unsafe fn run_uninstall() {
+    if !ensure_single_instance() {
+        let msg = i18n::wide("uninstall.quit_first");
+        let title = i18n::wide("uninstall.title");
+        MessageBoxW(None, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONWARNING);
+        return;
+    }
+    // Opened for the passcode check and the optional backup
+    match init_database() {
+        Ok(()) => {
+            dialogs::show_uninstall(HWND::default());
+        }
+        Err(e) => {
+            let msg = i18n::to_wide(&format!("Failed to initialize database: {}", e));
+            MessageBoxW(None, PCWSTR(msg.as_ptr()), w!("Database Error"), MB_OK | MB_ICONERROR);
+        }
+    }
+    release_single_instance();
+}
+
+/// The `--command` command line: forward the command, print the reply to the console the
+/// script runs in and return the exit code (0 once the app answered, 1 if it couldn't be reached)
+unsafe fn run_forwarded_command(command: &str, passcode: &str) -> i32 {
+    // A GUI app has no console of its own
+    let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    match ipc::forward(command, passcode) {
+        Ok(reply) => {
+            println!("{}", reply);
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// Close the single-instance mutex so a new instance can start right away
+unsafe fn release_single_instance() {
+    let handle = INSTANCE_MUTEX.swap(std::ptr::null_mut(), Ordering::SeqCst);
+    if !handle.is_null() {
+        let _ = CloseHandle(HANDLE(handle));
+    }
+}
@@ -0,0 +1,86 @@
+//! Foreground application module
+//! Finds the program in front so time spent in productive apps isn't counted
+
+use windows::{
+    core::PWSTR,
+    Win32::{
+        Foundation::CloseHandle,
+        System::Threading::{OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION},
+        UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId},
+    },
+};
+
+use std::sync::Mutex;
+
+use crate::database;
+
+/// The productive list, read from the database on first use and again after a settings save
+static PRODUCTIVE_APPS: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+/// Process id of the last foreground app and its executable name; the name is only
+/// looked up again when another process comes to the front
+static LAST_FOREGROUND: Mutex<Option<(u32, String)>> = Mutex::new(None);
+
+/// Executable name of the foreground window's process, lowercased (e.g. "winword.exe")
+pub fn foreground_exe() -> Option<String> {
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.0.is_null() {
+            return None;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        if pid == 0 {
+            return None;
+        }
+
+        let mut last = LAST_FOREGROUND.lock().unwrap();
+        if let Some((last_pid, exe)) = last.as_ref() {
+            if *last_pid == pid {
+                return Some(exe.clone());
+            }
+        }
+
+        // Limited access is enough for the image name and works for elevated processes too
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut buffer = [0u16; 1024];
+        let mut len = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(buffer.as_mut_ptr()), &mut len);
+        let _ = CloseHandle(process);
+        result.ok()?;
+
+        let path = String::from_utf16_lossy(&buffer[..len as usize]);
+        let exe = path.rsplit('\\').next().map(|name| name.to_lowercase())?;
+        *last = Some((pid, exe.clone()));
+        Some(exe)
+    }
+}
+
+/// Normalize an app name typed in settings ("Word", "WINWORD.EXE") to the stored form ("winword.exe")
+pub fn normalize_exe(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() || name.ends_with(".exe") {
+        name
+    } else {
+        format!("{}.exe", name)
+    }
+}
+
+/// Check if time in front of the current foreground app counts against the limit.
+/// Everything counts unless it's on the productive list.
+pub fn foreground_counts() -> bool {
+    let mut productive = PRODUCTIVE_APPS.lock().unwrap();
+    let productive = productive.get_or_insert_with(database::get_productive_apps);
+    if productive.is_empty() {
+        return true;
+    }
+    match foreground_exe() {
+        Some(exe) => !productive.contains(&exe),
+        None => true,
+    }
+}
+
+/// Read the productive list again (after it was changed in settings)
+pub fn reload_productive_apps() {
+    *PRODUCTIVE_APPS.lock().unwrap() = Some(database::get_productive_apps());
+}
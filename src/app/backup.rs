@@ -0,0 +1,451 @@
+//! Backup module
+//! Full backup of the settings table (settings and usage history) for moving to a new
+//! computer. The file is JSON, encrypted with AES-256-GCM when a backup password is set.
+
+use std::fmt;
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use windows::Win32::Security::Cryptography::{
+    BCryptCloseAlgorithmProvider, BCryptDecrypt, BCryptDeriveKeyPBKDF2, BCryptDestroyKey, BCryptEncrypt,
+    BCryptGenRandom, BCryptGenerateSymmetricKey, BCryptOpenAlgorithmProvider, BCryptSetProperty,
+    BCRYPT_AES_ALGORITHM, BCRYPT_ALG_HANDLE, BCRYPT_ALG_HANDLE_HMAC_FLAG, BCRYPT_AUTHENTICATED_CIPHER_MODE_INFO,
+    BCRYPT_AUTHENTICATED_CIPHER_MODE_INFO_VERSION, BCRYPT_CHAINING_MODE, BCRYPT_CHAIN_MODE_GCM, BCRYPT_FLAGS,
+    BCRYPT_HANDLE, BCRYPT_KEY_HANDLE, BCRYPT_OPEN_ALGORITHM_PROVIDER_FLAGS, BCRYPT_SHA256_ALGORITHM,
+    BCRYPT_USE_SYSTEM_PREFERRED_RNG,
+};
+
+use crate::database::{self, DbError, TELEGRAM_BOT_TOKEN, WEEKDAY_KEYS};
+use crate::json;
+
+/// First line of every backup file, followed by the mode
+const MAGIC: &str = "STM-BACKUP 1";
+const MODE_PLAIN: &str = "plain";
+const MODE_ENCRYPTED: &str = "aes-256-gcm";
+
+/// Written into the JSON and checked on restore
+const APP_NAME: &str = "screen-time-manager";
+
+/// Secrets the settings table holds in plain text (the others are DPAPI values)
+const PLAIN_SECRETS: [&str; 2] = ["passcode", TELEGRAM_BOT_TOKEN];
+
+/// Key derivation (PBKDF2-HMAC-SHA256) and AES-GCM parameters
+const PBKDF2_ITERATIONS: u64 = 200_000;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Errors while creating or restoring a backup
+#[derive(Debug)]
+pub enum BackupError {
+    /// Reading or writing the backup file failed
+    Io(std::io::Error),
+    /// Reading or replacing the settings failed
+    Db(DbError),
+    /// The backup is encrypted and no password was given
+    PasswordRequired,
+    /// Decryption failed: wrong password or a damaged file
+    WrongPassword,
+    /// Not a backup of this app, or its contents are malformed
+    Invalid(String),
+    /// Windows cryptography (CNG) reported an error
+    Crypto(String),
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackupError::Io(e) => write!(f, "file error: {}", e),
+            BackupError::Db(e) => write!(f, "database error: {}", e),
+            BackupError::PasswordRequired => write!(f, "this backup is encrypted, enter its password"),
+            BackupError::WrongPassword => write!(f, "wrong password or damaged file"),
+            BackupError::Invalid(reason) => write!(f, "not a valid backup: {}", reason),
+            BackupError::Crypto(e) => write!(f, "encryption error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BackupError {}
+
+impl From<std::io::Error> for BackupError {
+    fn from(e: std::io::Error) -> Self {
+        BackupError::Io(e)
+    }
+}
+
+impl From<DbError> for BackupError {
+    fn from(e: DbError) -> Self {
+        BackupError::Db(e)
+    }
+}
+
+impl From<windows::core::Error> for BackupError {
+    fn from(e: windows::core::Error) -> Self {
+        BackupError::Crypto(e.message())
+    }
+}
+
+/// Whether a settings row is a secret: the parent passcode, the bot token or a stored password
+fn is_secret(key: &str, value: &str) -> bool {
+    value.starts_with("dpapi:") || PLAIN_SECRETS.contains(&key)
+}
+
+/// Write all settings and history to `path`. With a password the file is encrypted and
+/// also carries the passcode, the stored passwords and the bot token; without one those are
+/// left out, so a plain backup a child finds gives nothing away.
+/// Returns the number of settings entries written.
+pub fn create(path: &Path, password: &str) -> Result<usize, BackupError> {
+    let mut settings = Vec::new();
+    let mut secrets = Vec::new();
+    for (key, value) in database::get_all_settings()? {
+        if value.starts_with("dpapi:") {
+            // DPAPI values only decrypt for this Windows user, so they travel in plain text
+            // inside the encrypted backup and are encrypted again on restore
+            if let Some(plain) = crate::secrets::unprotect(&value) {
+                secrets.push((key, plain));
+            }
+        } else if is_secret(&key, &value) {
+            secrets.push((key, value));
+        } else {
+            settings.push((key, value));
+        }
+    }
+    if password.is_empty() {
+        secrets.clear();
+    }
+
+    let mut json = format!(
+        "{{\"app\":\"{}\",\"version\":\"{}\",\"created\":\"{}\"",
+        APP_NAME,
+        env!("CARGO_PKG_VERSION"),
+        database::get_today_date()
+    );
+    for (name, entries) in [("settings", &settings), ("secrets", &secrets)] {
+        let fields: Vec<String> = entries
+            .iter()
+            .map(|(key, value)| {
+                format!("\"{}\":\"{}\"", json::escape(key), json::escape(value))
+            })
+            .collect();
+        json.push_str(&format!(",\"{}\":{{{}}}", name, fields.join(",")));
+    }
+    json.push('}');
+
+    let contents = if password.is_empty() {
+        format!("{} {}\n{}", MAGIC, MODE_PLAIN, json)
+    } else {
+        format!("{} {}\n{}", MAGIC, MODE_ENCRYPTED, BASE64.encode(encrypt(password, json.as_bytes())?))
+    };
+    std::fs::write(path, contents)?;
+    log::info!("Saved {} entries to {}", settings.len() + secrets.len(), path.display());
+    Ok(settings.len() + secrets.len())
+}
+
+/// Replace all settings and history with the backup at `path` after validating it.
+/// Secrets a plain backup doesn't carry (the passcode, passwords, bot token) keep their
+/// current values. Returns the number of settings entries restored.
+pub fn restore(path: &Path, password: &str) -> Result<usize, BackupError> {
+    let contents = std::fs::read_to_string(path)?;
+    let (header, body) = contents.split_once('\n').unwrap_or((contents.as_str(), ""));
+    let json = match header.trim_end().strip_prefix(MAGIC).map(str::trim) {
+        Some(MODE_PLAIN) => body.to_string(),
+        Some(MODE_ENCRYPTED) => {
+            if password.is_empty() {
+                return Err(BackupError::PasswordRequired);
+            }
+            let data = BASE64
+                .decode(body.trim())
+                .map_err(|_| BackupError::Invalid("damaged contents".to_string()))?;
+            String::from_utf8(decrypt(password, &data)?)
+                .map_err(|_| BackupError::Invalid("damaged contents".to_string()))?
+        }
+        _ => return Err(BackupError::Invalid("unknown file format".to_string())),
+    };
+
+    let backup = parse_backup(&json).ok_or_else(|| BackupError::Invalid("malformed contents".to_string()))?;
+    if backup.app != APP_NAME {
+        return Err(BackupError::Invalid("made by another app".to_string()));
+    }
+    if !backup.settings.iter().any(|(key, _)| WEEKDAY_KEYS.contains(&key.as_str())) {
+        return Err(BackupError::Invalid("no settings found".to_string()));
+    }
+    if backup.settings.iter().chain(&backup.secrets).any(|(key, _)| key.is_empty()) {
+        return Err(BackupError::Invalid("empty setting name".to_string()));
+    }
+
+    let mut rows = backup.settings;
+    for (key, value) in backup.secrets {
+        let stored = if PLAIN_SECRETS.contains(&key.as_str()) { value } else { crate::secrets::protect(&value) };
+        rows.push((key, stored));
+    }
+    let restored = rows.len();
+    for (key, value) in database::get_all_settings()? {
+        if is_secret(&key, &value) && !rows.iter().any(|(restored, _)| *restored == key) {
+            rows.push((key, value));
+        }
+    }
+    database::replace_all_settings(&rows)?;
+    log::info!("Restored {} entries from {}", restored, path.display());
+    Ok(restored)
+}
+
+// ============================================================================
+// Encryption (Windows CNG)
+// ============================================================================
+
+/// Encrypt with a key derived from the password: salt | nonce | tag | ciphertext
+fn encrypt(password: &str, plain: &[u8]) -> Result<Vec<u8>, BackupError> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    random_bytes(&mut salt)?;
+    random_bytes(&mut nonce)?;
+    let key = derive_key(password, &salt)?;
+    let mut tag = [0u8; TAG_LEN];
+    let ciphertext = aes_gcm(&key, &nonce, plain, &mut tag, true)?;
+
+    let mut data = Vec::with_capacity(SALT_LEN + NONCE_LEN + TAG_LEN + ciphertext.len());
+    data.extend_from_slice(&salt);
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&tag);
+    data.extend_from_slice(&ciphertext);
+    Ok(data)
+}
+
+/// Decrypt data written by `encrypt`; the GCM tag rejects a wrong password
+fn decrypt(password: &str, data: &[u8]) -> Result<Vec<u8>, BackupError> {
+    if data.len() < SALT_LEN + NONCE_LEN + TAG_LEN {
+        return Err(BackupError::Invalid("file is too short".to_string()));
+    }
+    let (salt, rest) = data.split_at(SALT_LEN);
+    let (nonce, rest) = rest.split_at(NONCE_LEN);
+    let (tag, ciphertext) = rest.split_at(TAG_LEN);
+    let key = derive_key(password, salt)?;
+    let mut tag: [u8; TAG_LEN] = tag.try_into().unwrap_or_default();
+    aes_gcm(&key, nonce, ciphertext, &mut tag, false).map_err(|_| BackupError::WrongPassword)
+}
+
+fn random_bytes(buffer: &mut [u8]) -> Result<(), BackupError> {
+    unsafe { BCryptGenRandom(BCRYPT_ALG_HANDLE::default(), buffer, BCRYPT_USE_SYSTEM_PREFERRED_RNG).ok()? };
+    Ok(())
+}
+
+/// 256-bit key from the password (PBKDF2-HMAC-SHA256)
+fn derive_key(password: &str, salt: &[u8]) -> Result<[u8; 32], BackupError> {
+    let mut key = [0u8; 32];
+    unsafe {
+        let mut prf = BCRYPT_ALG_HANDLE::default();
+        BCryptOpenAlgorithmProvider(&mut prf, BCRYPT_SHA256_ALGORITHM, None, BCRYPT_ALG_HANDLE_HMAC_FLAG).ok()?;
+        let status = BCryptDeriveKeyPBKDF2(prf, Some(password.as_bytes()), Some(salt), PBKDF2_ITERATIONS, &mut key, 0);
+        let _ = BCryptCloseAlgorithmProvider(prf, 0);
+        status.ok()?;
+    }
+    Ok(key)
+}
+
+/// AES-256-GCM; `tag` is written when encrypting and checked when decrypting
+fn aes_gcm(key: &[u8; 32], nonce: &[u8], input: &[u8], tag: &mut [u8; TAG_LEN], encrypt: bool) -> Result<Vec<u8>, BackupError> {
+    unsafe {
+        let mut algorithm = BCRYPT_ALG_HANDLE::default();
+        BCryptOpenAlgorithmProvider(&mut algorithm, BCRYPT_AES_ALGORITHM, None, BCRYPT_OPEN_ALGORITHM_PROVIDER_FLAGS(0)).ok()?;
+        let result = (|| {
+            // The property value is the mode name as a NUL-terminated UTF-16 string
+            let mode: Vec<u8> = BCRYPT_CHAIN_MODE_GCM
+                .as_wide()
+                .iter()
+                .chain(std::iter::once(&0))
+                .flat_map(|c| c.to_le_bytes())
+                .collect();
+            BCryptSetProperty(BCRYPT_HANDLE(algorithm.0), BCRYPT_CHAINING_MODE, &mode, 0).ok()?;
+
+            let mut key_handle = BCRYPT_KEY_HANDLE::default();
+            BCryptGenerateSymmetricKey(algorithm, &mut key_handle, None, key, 0).ok()?;
+            let info = BCRYPT_AUTHENTICATED_CIPHER_MODE_INFO {
+                cbSize: std::mem::size_of::<BCRYPT_AUTHENTICATED_CIPHER_MODE_INFO>() as u32,
+                dwInfoVersion: BCRYPT_AUTHENTICATED_CIPHER_MODE_INFO_VERSION,
+                pbNonce: nonce.as_ptr() as *mut u8,
+                cbNonce: nonce.len() as u32,
+                pbTag: tag.as_mut_ptr(),
+                cbTag: TAG_LEN as u32,
+                ..Default::default()
+            };
+            let mut output = vec![0u8; input.len()];
+            let mut written = 0u32;
+            let info_ptr = Some(&info as *const _ as *const std::ffi::c_void);
+            let status = if encrypt {
+                BCryptEncrypt(key_handle, Some(input), info_ptr, None, Some(&mut output), &mut written, BCRYPT_FLAGS(0))
+            } else {
+                BCryptDecrypt(key_handle, Some(input), info_ptr, None, Some(&mut output), &mut written, BCRYPT_FLAGS(0))
+            };
+            let _ = BCryptDestroyKey(key_handle);
+            status.ok()?;
+            output.truncate(written as usize);
+            Ok(output)
+        })();
+        let _ = BCryptCloseAlgorithmProvider(algorithm, 0);
+        result
+    }
+}
+
+// ============================================================================
+// JSON parsing (only what `create` writes: string fields and string-to-string objects)
+// ============================================================================
+
+/// Parsed backup contents
+struct Backup {
+    app: String,
+    settings: Vec<(String, String)>,
+    secrets: Vec<(String, String)>,
+}
+
+fn parse_backup(json: &str) -> Option<Backup> {
+    let mut parser = Parser { chars: json.chars().peekable() };
+    let mut backup = Backup { app: String::new(), settings: Vec::new(), secrets: Vec::new() };
+    parser.object(|parser, field| {
+        match field.as_str() {
+            "settings" => backup.settings = parser.string_map()?,
+            "secrets" => backup.secrets = parser.string_map()?,
+            "app" => backup.app = parser.string()?,
+            _ => {
+                parser.string()?;
+            }
+        }
+        Some(())
+    })?;
+    parser.skip_whitespace();
+    parser.chars.peek().is_none().then_some(backup)
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Option<()> {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&expected).map(|_| ())
+    }
+
+    /// `{ "name": <value>, ... }`, calling `value` to parse each field's value
+    fn object(&mut self, mut value: impl FnMut(&mut Self, String) -> Option<()>) -> Option<()> {
+        self.expect('{')?;
+        if self.expect('}').is_some() {
+            return Some(());
+        }
+        loop {
+            let name = self.string()?;
+            self.expect(':')?;
+            value(self, name)?;
+            if self.expect(',').is_none() {
+                return self.expect('}');
+            }
+        }
+    }
+
+    /// An object whose values are all strings
+    fn string_map(&mut self) -> Option<Vec<(String, String)>> {
+        let mut entries = Vec::new();
+        self.object(|parser, name| {
+            entries.push((name, parser.string()?));
+            Some(())
+        })?;
+        Some(entries)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            match self.chars.next()? {
+                '"' => return Some(text),
+                '\\' => match self.chars.next()? {
+                    'n' => text.push('\n'),
+                    'r' => text.push('\r'),
+                    't' => text.push('\t'),
+                    'b' => text.push('\u{8}'),
+                    'f' => text.push('\u{c}'),
+                    'u' => {
+                        let hex: String = (0..4).filter_map(|_| self.chars.next()).collect();
+                        text.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    }
+                    c => text.push(c),
+                },
+                c => text.push(c),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{get_setting, set_setting, with_test_database};
+
+    /// A day's limit, the passcode, the bot token and an SMTP password, as a configured install has them
+    fn fill_settings() {
+        set_setting("limit_monday", "90");
+        set_setting("session_active_2026-10-12", "3600");
+        set_setting("passcode", "4711");
+        set_setting(TELEGRAM_BOT_TOKEN, "123456:bot-token");
+        set_setting("smtp_password", &crate::secrets::protect("mail-secret"));
+    }
+
+    /// Settings of the computer the backup is restored on
+    fn other_settings() {
+        set_setting("limit_monday", "30");
+        set_setting("session_active_2026-10-12", "60");
+        set_setting("passcode", "0000");
+        set_setting(TELEGRAM_BOT_TOKEN, "999:other-token");
+    }
+
+    #[test]
+    fn plain_backup_leaves_out_and_keeps_the_secrets() {
+        let path = database::test_dir("backup-plain").join("plain.stmbackup");
+        with_test_database(|_| {
+            fill_settings();
+            create(&path, "").unwrap();
+            let contents = std::fs::read_to_string(&path).unwrap();
+            assert!(contents.starts_with("STM-BACKUP 1 plain\n"));
+            assert!(contents.contains("\"limit_monday\":\"90\""));
+            for secret in ["4711", "bot-token", "mail-secret", "dpapi:"] {
+                assert!(!contents.contains(secret), "{} in a plain backup", secret);
+            }
+
+            other_settings();
+            restore(&path, "").unwrap();
+            assert_eq!(get_setting("limit_monday").as_deref(), Some("90"));
+            assert_eq!(get_setting("session_active_2026-10-12").as_deref(), Some("3600"));
+            // The secrets weren't in the file, so this computer's stay
+            assert_eq!(get_setting("passcode").as_deref(), Some("0000"));
+            assert_eq!(get_setting(TELEGRAM_BOT_TOKEN).as_deref(), Some("999:other-token"));
+            assert_eq!(get_setting("smtp_password"), None);
+        });
+    }
+
+    #[test]
+    fn encrypted_backup_carries_the_secrets() {
+        let path = database::test_dir("backup-encrypted").join("encrypted.stmbackup");
+        with_test_database(|_| {
+            fill_settings();
+            create(&path, "backup pw").unwrap();
+            let contents = std::fs::read_to_string(&path).unwrap();
+            assert!(contents.starts_with("STM-BACKUP 1 aes-256-gcm\n"));
+            assert!(!contents.contains("limit_monday"));
+
+            other_settings();
+            assert!(matches!(restore(&path, ""), Err(BackupError::PasswordRequired)));
+            assert!(matches!(restore(&path, "wrong"), Err(BackupError::WrongPassword)));
+            assert_eq!(get_setting("limit_monday").as_deref(), Some("30"));
+
+            restore(&path, "backup pw").unwrap();
+            assert_eq!(get_setting("limit_monday").as_deref(), Some("90"));
+            assert_eq!(get_setting("passcode").as_deref(), Some("4711"));
+            assert_eq!(get_setting(TELEGRAM_BOT_TOKEN).as_deref(), Some("123456:bot-token"));
+            let smtp = get_setting("smtp_password").unwrap();
+            assert_eq!(crate::secrets::unprotect(&smtp).as_deref(), Some("mail-secret"));
+        });
+    }
+}
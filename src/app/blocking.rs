@@ -0,0 +1,2498 @@
+//! Blocking overlay module
+//! Full-screen overlay that requires passcode to dismiss
+
+use std::mem::zeroed;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicPtr, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use windows::{
+    core::w,
+    Win32::{
+        Foundation::{BOOL, COLORREF, GENERIC_READ, HWND, LPARAM, LRESULT, RECT, WPARAM},
+        Graphics::Gdi::{
+            BeginPaint, BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, CreateDIBSection,
+            CreateFontW, CreatePen, CreateSolidBrush, DeleteDC, DeleteObject, DrawTextW, EndPaint,
+            EnumDisplayMonitors, FillRect, InvalidateRect, RoundRect, SelectObject, SetBkMode,
+            SetStretchBltMode, SetTextColor, StretchBlt, BITMAPINFO, BITMAPINFOHEADER, BI_RGB,
+            DIB_RGB_COLORS, DT_CALCRECT, DT_CENTER, DT_SINGLELINE, DT_WORDBREAK, FW_BOLD, FW_NORMAL, HALFTONE,
+            HBITMAP, HDC, HFONT, HMONITOR, PAINTSTRUCT, PS_SOLID, SRCCOPY, TRANSPARENT,
+        },
+        Graphics::Imaging::{
+            CLSID_WICImagingFactory, GUID_WICPixelFormat32bppBGR, IWICImagingFactory,
+            WICBitmapDitherTypeNone, WICBitmapPaletteTypeCustom, WICDecodeMetadataCacheOnDemand,
+        },
+        System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED},
+        Media::Audio::{PlaySoundW, SND_ALIAS, SND_ASYNC},
+        System::LibraryLoader::GetModuleHandleW,
+        System::Shutdown::{
+            AbortSystemShutdownW, ExitWindowsEx, InitiateSystemShutdownExW, LockWorkStation, EWX_LOGOFF, EWX_SHUTDOWN,
+            SHTDN_REASON_FLAG_PLANNED, SHTDN_REASON_MAJOR_OTHER, SHTDN_REASON_MINOR_OTHER, SHUTDOWN_REASON,
+        },
+        System::Threading::{
+            AttachThreadInput, GetCurrentProcessId, GetCurrentThreadId,
+        },
+        Security::Cryptography::{BCryptGenRandom, BCRYPT_ALG_HANDLE, BCRYPT_USE_SYSTEM_PREFERRED_RNG},
+        UI::{
+            Accessibility::{SetWinEventHook, UnhookWinEvent, HWINEVENTHOOK},
+            Controls::*,
+            Input::KeyboardAndMouse::{EnableWindow, SetFocus},
+            WindowsAndMessaging::*,
+        },
+    },
+};
+
+use windows::core::PCWSTR;
+
+use crate::constants::*;
+use crate::database::get_passcode;
+use crate::dpi::scale;
+use crate::theme;
+use crate::i18n;
+use crate::timefmt::format_countdown;
+
+/// Initiates a Windows shutdown with proper privilege handling
+unsafe fn initiate_shutdown() -> bool {
+    if !crate::elevation::can_shut_down() {
+        return false;
+    }
+
+    // Now perform the shutdown
+    ExitWindowsEx(EWX_SHUTDOWN, SHUTDOWN_REASON(0)).is_ok()
+}
+
+/// Storage for secondary monitor overlay handles (stores raw pointers as isize for Send+Sync)
+static SECONDARY_OVERLAY_HWNDS: Mutex<Vec<isize>> = Mutex::new(Vec::new());
+
+/// Monitor information collected during enumeration
+struct MonitorInfo {
+    rect: RECT,
+    is_primary: bool,
+}
+
+/// Global state for blocking overlay
+pub static BLOCKING_HWND: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+pub static BLOCKING_TEXT: Mutex<Option<String>> = Mutex::new(None);
+
+/// Text shown on the secondary monitor overlays while blocked
+static SECONDARY_TEXT: Mutex<Option<String>> = Mutex::new(None);
+pub static BLOCKING_EDIT_HWND: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+pub static PASSCODE_ERROR: AtomicBool = AtomicBool::new(false);
+
+/// Whether the lockout or minimum-block message is on screen (repainted every second
+/// while it counts down)
+static LOCKOUT_SHOWN: AtomicBool = AtomicBool::new(false);
+
+/// Time was granted during the minimum block duration; the block is lifted once it's over
+static RELEASE_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Longest passcode lockout, however many wrong attempts there were
+const MAX_LOCKOUT_SECONDS: i64 = 60 * 60;
+
+/// Minutes an unlock adds, so a used-up limit doesn't block again right away
+const UNLOCK_MINUTES: i32 = 15;
+
+/// Cached background image: (path, bitmap handle or 0 if it failed to load, width, height)
+static BACKGROUND_IMAGE: Mutex<Option<(String, isize, i32, i32)>> = Mutex::new(None);
+
+/// Blocking panel size in percent from settings, limited so the panel fits the screen
+static PANEL_SCALE_PERCENT: AtomicI32 = AtomicI32::new(100);
+
+/// Fonts of the panel buttons and passcode field (recreated when the panel size changes)
+static BUTTON_FONT: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+static EDIT_FONT: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+
+/// Overlay is shown as a preview (Esc closes it, buttons do nothing)
+static PREVIEW_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Remaining time in seconds (negative means no limit/extension active)
+pub static REMAINING_SECONDS: AtomicI32 = AtomicI32::new(-1);
+
+/// Shutdown countdown in seconds (negative means inactive)
+pub static SHUTDOWN_COUNTDOWN_SECONDS: AtomicI32 = AtomicI32::new(-1);
+
+/// Countdown in seconds until the computer is locked with the "lock" expiry action
+/// (negative means inactive)
+static LOCK_COUNTDOWN_SECONDS: AtomicI32 = AtomicI32::new(-1);
+
+/// A system shutdown started by the "shutdown" expiry action is in its grace period
+static SYSTEM_SHUTDOWN_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// How long a one-time unlock code from Telegram stays valid
+pub const UNLOCK_CODE_VALIDITY: Duration = Duration::from_secs(5 * 60);
+
+/// Active one-time unlock code and when it was issued
+static UNLOCK_CODE: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+/// Foreground-change hook used to reclaim focus immediately (focus mode 2)
+static FOREGROUND_HOOK: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+
+/// Get remaining time in seconds
+pub fn get_remaining_seconds() -> i32 {
+    REMAINING_SECONDS.load(Ordering::SeqCst)
+}
+
+/// Get the seconds used today (today's limit plus extensions, minus what's left)
+pub fn get_used_seconds() -> i32 {
+    let limit = crate::database::get_effective_daily_limit();
+    (limit * 60 - REMAINING_SECONDS.load(Ordering::SeqCst).max(0)).max(0)
+}
+
+/// Timer IDs
+pub const TIMER_REASSERT_TOPMOST: usize = 2;
+pub const TIMER_COUNTDOWN: usize = 3;
+
+/// Control IDs
+const ID_PASSCODE_EDIT: i32 = 101;
+const ID_UNLOCK_BUTTON: i32 = 102;
+const ID_EXTEND_15: i32 = 103;
+const ID_EXTEND_30: i32 = 104;
+const ID_EXTEND_60: i32 = 105;
+const ID_SHUTDOWN_BUTTON: i32 = 106;
+const ID_LOCK_BUTTON: i32 = 107;
+const ID_LOGOFF_BUTTON: i32 = 108;
+const ID_REVEAL_BUTTON: i32 = 109;
+const ID_EXTEND_CUSTOM: i32 = 110;
+const ID_SELF_EXTEND: i32 = 111;
+
+pub unsafe fn create_blocking_overlay(hinstance: windows::Win32::Foundation::HMODULE) {
+    let class_name = w!("ScreenTimeBlockingClass");
+
+    let screen_width = GetSystemMetrics(SM_CXSCREEN);
+    let screen_height = GetSystemMetrics(SM_CYSCREEN);
+
+    let ex_style = WS_EX_TOPMOST | WS_EX_TOOLWINDOW;
+
+    let window_title = i18n::wide("window.blocking");
+    let hwnd = CreateWindowExW(
+        ex_style,
+        class_name,
+        PCWSTR(window_title.as_ptr()),
+        WS_POPUP | WS_CLIPCHILDREN,  // CLIPCHILDREN prevents drawing over child controls
+        0,
+        0,
+        screen_width,
+        screen_height,
+        None,
+        None,
+        hinstance,
+        None,
+    )
+    .expect("Failed to create blocking overlay");
+
+    BLOCKING_HWND.store(hwnd.0, Ordering::SeqCst);
+}
+
+/// Shows the full-screen blocking overlay
+/// `reason` is persisted ("limit", "curfew", "clock", "tray", "telegram") so a restart shows the block again.
+pub unsafe fn show_blocking_overlay(text: &str, reason: &str) {
+    crate::database::save_active_block(reason);
+    show_blocking_overlay_with_time(text, -1);
+}
+
+/// Block to show again at startup, from the block persisted when the app last ran
+/// (reason, date), today's date, the remaining seconds and whether focus mode is on.
+/// A block from an earlier day is over and a curfew is re-checked on its own; used-up
+/// time always blocks, except during focus mode.
+fn startup_block<'a>(saved: Option<(&'a str, &str)>, today: &str, remaining: i32, focus: bool) -> Option<&'a str> {
+    match saved {
+        Some(("curfew", _)) => None,
+        Some(("limit", _)) if focus => None,
+        // The app may have been killed before the saved remaining time reached zero
+        Some((reason, date)) if date == today => Some(reason),
+        _ if remaining <= 0 && !focus => Some("limit"),
+        _ => None,
+    }
+}
+
+/// Show the block that was up when the app last ran (also if it was killed), so restarting
+/// the app never opens a gap. Called once at startup after the counters are restored.
+pub unsafe fn restore_block() {
+    let saved = crate::database::get_active_block();
+    let today = crate::database::get_today_date();
+    let remaining = REMAINING_SECONDS.load(Ordering::SeqCst);
+    let saved = saved.as_ref().map(|(reason, date)| (reason.as_str(), date.as_str()));
+
+    match startup_block(saved, &today, remaining, crate::mini_overlay::is_focus_mode()) {
+        None => crate::database::clear_active_block(),
+        Some("limit") => {
+            if remaining > 0 {
+                REMAINING_SECONDS.store(0, Ordering::SeqCst);
+                crate::database::save_remaining_time(0);
+            }
+            crate::mini_overlay::enforce_time_up();
+        }
+        Some("clock") => show_blocking_overlay(i18n::t("security.clock_blocked"), "clock"),
+        Some(reason) => show_blocking_overlay(&crate::database::get_blocking_message(), reason),
+    }
+
+    // Outside the allowed hours block right away rather than on the first timer tick
+    crate::curfew::check_curfew();
+}
+
+/// Shows the full-screen blocking overlay with optional remaining time in seconds
+pub unsafe fn show_blocking_overlay_with_time(text: &str, remaining_seconds: i32) {
+    let hwnd = HWND(BLOCKING_HWND.load(Ordering::SeqCst));
+    if hwnd.0.is_null() {
+        return;
+    }
+
+    // A real block replaces a preview that may still be open
+    PREVIEW_MODE.store(false, Ordering::SeqCst);
+
+    // Hide the mini overlay and any warning banner while blocking screen is shown
+    crate::mini_overlay::hide_mini_overlay();
+    crate::overlay::hide_overlay();
+
+    *BLOCKING_TEXT.lock().unwrap() = Some(text.to_string());
+    *SECONDARY_TEXT.lock().unwrap() = Some(crate::database::get_secondary_block_message().unwrap_or_else(|| text.to_string()));
+    PASSCODE_ERROR.store(false, Ordering::SeqCst);
+    if remaining_seconds >= 0 {
+        REMAINING_SECONDS.store(remaining_seconds, Ordering::SeqCst);
+    }
+
+    // When this block began; kept when it's shown again or restored after a restart,
+    // so neither restarts the minimum block duration. A simulated block isn't real, so
+    // like a preview it has no minimum duration.
+    let simulating = crate::mini_overlay::is_simulating();
+    if !simulating {
+        record_block_start();
+    }
+
+    let timeout = crate::database::get_lock_screen_timeout();
+    SHUTDOWN_COUNTDOWN_SECONDS.store(block_shutdown_countdown(timeout, crate::elevation::can_shut_down(), simulating), Ordering::SeqCst);
+    if simulating {
+        LOCK_COUNTDOWN_SECONDS.store(-1, Ordering::SeqCst);
+    }
+
+    let edit_ptr = BLOCKING_EDIT_HWND.load(Ordering::SeqCst);
+    if !edit_ptr.is_null() {
+        SetWindowTextW(HWND(edit_ptr), w!("")).ok();
+    }
+
+    // Settings may have changed which buttons are available since the last time
+    layout_blocking_controls(hwnd);
+
+    let _ = InvalidateRect(hwnd, None, false);
+
+    SetWindowPos(
+        hwnd,
+        HWND_TOPMOST,
+        0, 0, 0, 0,
+        SWP_SHOWWINDOW | SWP_NOMOVE | SWP_NOSIZE,
+    ).ok();
+
+    let _ = ShowWindow(hwnd, SW_SHOW);
+    let _ = SetForegroundWindow(hwnd);
+
+    let edit_ptr = BLOCKING_EDIT_HWND.load(Ordering::SeqCst);
+    if !edit_ptr.is_null() {
+        let _ = SetFocus(HWND(edit_ptr));
+    }
+
+    let _ = PlaySoundW(w!("SystemHand"), None, SND_ALIAS | SND_ASYNC);
+    let _ = SetTimer(hwnd, TIMER_REASSERT_TOPMOST, 500, None);
+
+    // Reclaim focus as soon as another window takes the foreground
+    if crate::database::get_blocking_focus_mode() >= 2 && FOREGROUND_HOOK.load(Ordering::SeqCst).is_null() {
+        let hook = SetWinEventHook(
+            EVENT_SYSTEM_FOREGROUND,
+            EVENT_SYSTEM_FOREGROUND,
+            None,
+            Some(foreground_event_proc),
+            0,
+            0,
+            WINEVENT_OUTOFCONTEXT | WINEVENT_SKIPOWNPROCESS,
+        );
+        FOREGROUND_HOOK.store(hook.0, Ordering::SeqCst);
+    }
+
+    // Start countdown timer (updates every second)
+    let _ = SetTimer(hwnd, TIMER_COUNTDOWN, 1000, None);
+
+    // Show secondary monitor overlays (blanks other monitors)
+    show_secondary_overlays();
+}
+
+/// Seconds the blocking screen counts down before it shuts the computer down (-1 = never),
+/// from the lock screen timeout (0 = no auto-shutdown). Without the shutdown privilege no
+/// countdown is shown that couldn't end in a shutdown, and a simulated block never shuts down.
+fn block_shutdown_countdown(timeout: i32, can_shut_down: bool, simulating: bool) -> i32 {
+    if timeout > 0 && can_shut_down && !simulating {
+        timeout
+    } else {
+        -1
+    }
+}
+
+/// Shows the blocking overlay as a preview of the message and colors.
+/// Closes with Esc and needs no passcode; no countdown, topmost reassert,
+/// focus reclaiming or secondary monitor overlays.
+pub unsafe fn show_blocking_overlay_preview(text: &str) {
+    let hwnd = HWND(BLOCKING_HWND.load(Ordering::SeqCst));
+    if hwnd.0.is_null() || IsWindowVisible(hwnd).as_bool() {
+        return;
+    }
+
+    PREVIEW_MODE.store(true, Ordering::SeqCst);
+    *BLOCKING_TEXT.lock().unwrap() = Some(text.to_string());
+    PASSCODE_ERROR.store(false, Ordering::SeqCst);
+    SHUTDOWN_COUNTDOWN_SECONDS.store(-1, Ordering::SeqCst);
+
+    layout_blocking_controls(hwnd);
+    let _ = InvalidateRect(hwnd, None, false);
+
+    SetWindowPos(
+        hwnd,
+        HWND_TOPMOST,
+        0, 0, 0, 0,
+        SWP_SHOWWINDOW | SWP_NOMOVE | SWP_NOSIZE,
+    ).ok();
+    let _ = ShowWindow(hwnd, SW_SHOW);
+    let _ = SetForegroundWindow(hwnd);
+
+    // Keyboard focus on the overlay itself so Esc reaches the dialog manager
+    let _ = SetFocus(hwnd);
+}
+
+/// Route keyboard input for the blocking overlay through the dialog manager, so it can be
+/// used without a mouse: Tab/Shift+Tab move between controls, Space presses the focused
+/// button and Enter unlocks. Returns true if the message was handled.
+pub unsafe fn translate_dialog_message(msg: &MSG) -> bool {
+    let hwnd = HWND(BLOCKING_HWND.load(Ordering::SeqCst));
+    !hwnd.0.is_null() && IsWindowVisible(hwnd).as_bool() && IsDialogMessageW(hwnd, msg).as_bool()
+}
+
+/// Closes the blocking overlay preview
+unsafe fn hide_blocking_overlay_preview(hwnd: HWND) {
+    PREVIEW_MODE.store(false, Ordering::SeqCst);
+    let _ = ShowWindow(hwnd, SW_HIDE);
+    *BLOCKING_TEXT.lock().unwrap() = None;
+    RELEASE_PENDING.store(false, Ordering::SeqCst);
+    crate::database::set_setting("block_started_at", "0");
+    crate::database::clear_active_block();
+}
+
+/// Decode an image file (PNG, JPG, BMP, GIF, ...) with WIC into a 32-bit bitmap
+unsafe fn load_image_bitmap(path: &str) -> Option<(HBITMAP, i32, i32)> {
+    // S_FALSE if COM is already initialized on this thread; both are fine
+    let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+    let factory: IWICImagingFactory = CoCreateInstance(&CLSID_WICImagingFactory, None, CLSCTX_INPROC_SERVER).ok()?;
+
+    let wide_path = i18n::to_wide(path);
+    let decoder = factory
+        .CreateDecoderFromFilename(PCWSTR(wide_path.as_ptr()), None, GENERIC_READ, WICDecodeMetadataCacheOnDemand)
+        .ok()?;
+    let frame = decoder.GetFrame(0).ok()?;
+    let converter = factory.CreateFormatConverter().ok()?;
+    converter
+        .Initialize(&frame, &GUID_WICPixelFormat32bppBGR, WICBitmapDitherTypeNone, None, 0.0, WICBitmapPaletteTypeCustom)
+        .ok()?;
+
+    let (mut width, mut height) = (0u32, 0u32);
+    converter.GetSize(&mut width, &mut height).ok()?;
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let bmi = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width as i32,
+            biHeight: -(height as i32), // Top-down rows, matching WIC
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut bits: *mut std::ffi::c_void = std::ptr::null_mut();
+    let bitmap = CreateDIBSection(None, &bmi, DIB_RGB_COLORS, &mut bits, None, 0).ok()?;
+
+    let stride = width * 4;
+    let buffer = std::slice::from_raw_parts_mut(bits as *mut u8, (stride * height) as usize);
+    if converter.CopyPixels(std::ptr::null(), stride, buffer).is_err() {
+        let _ = DeleteObject(bitmap);
+        return None;
+    }
+
+    Some((bitmap, width as i32, height as i32))
+}
+
+/// Get the configured background image, loading it on first use or when the path changed.
+/// A file that fails to load is remembered so it is not retried on every paint.
+unsafe fn background_image() -> Option<(HBITMAP, i32, i32)> {
+    let path = crate::database::get_block_background_image();
+    let mut cache = BACKGROUND_IMAGE.lock().unwrap();
+
+    if cache.as_ref().map(|(cached, ..)| cached != &path).unwrap_or(true) {
+        if let Some((_, handle, _, _)) = cache.take() {
+            if handle != 0 {
+                let _ = DeleteObject(HBITMAP(handle as *mut std::ffi::c_void));
+            }
+        }
+        if path.is_empty() {
+            return None;
+        }
+        let loaded = load_image_bitmap(&path);
+        if loaded.is_none() {
+            log::warn!("Could not load background image {}", path);
+        }
+        let (handle, width, height) = loaded.map(|(b, w, h)| (b.0 as isize, w, h)).unwrap_or((0, 0, 0));
+        *cache = Some((path, handle, width, height));
+    }
+
+    match cache.as_ref() {
+        Some((_, handle, width, height)) if *handle != 0 => {
+            Some((HBITMAP(*handle as *mut std::ffi::c_void), *width, *height))
+        }
+        _ => None,
+    }
+}
+
+/// Free the cached background image
+unsafe fn free_background_image() {
+    if let Some((_, handle, _, _)) = BACKGROUND_IMAGE.lock().unwrap().take() {
+        if handle != 0 {
+            let _ = DeleteObject(HBITMAP(handle as *mut std::ffi::c_void));
+        }
+    }
+}
+
+/// Resize the blocking overlay to the primary screen and re-place its controls.
+/// Called when the resolution or display scaling changes.
+pub unsafe fn refresh_blocking_layout() {
+    let hwnd = HWND(BLOCKING_HWND.load(Ordering::SeqCst));
+    if hwnd.0.is_null() {
+        return;
+    }
+
+    let _ = SetWindowPos(
+        hwnd,
+        HWND_TOPMOST,
+        0,
+        0,
+        GetSystemMetrics(SM_CXSCREEN),
+        GetSystemMetrics(SM_CYSCREEN),
+        SWP_NOACTIVATE,
+    );
+    layout_blocking_controls(hwnd);
+    let _ = InvalidateRect(hwnd, None, false);
+}
+
+/// Remaining seconds after extending `current` by `minutes`.
+/// Time that has run out (or never started) counts as zero, so the result is always positive.
+fn extended_remaining(current: i32, minutes: i32) -> i32 {
+    current.max(0).saturating_add(minutes.saturating_mul(60))
+}
+
+/// Extend the remaining time by the specified minutes and return the new remaining seconds.
+/// Updated atomically so a countdown tick on the UI thread can't overwrite a remote extension.
+pub fn extend_time(minutes: i32) -> i32 {
+    let previous = REMAINING_SECONDS
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| Some(extended_remaining(current, minutes)))
+        .unwrap_or_default();
+    extended_remaining(previous, minutes)
+}
+
+/// Bring the blocking overlay back to the foreground if another application took it.
+/// Our own dialogs (e.g. the shutdown confirmation) and the secure desktop used by
+/// UAC prompts (no foreground window) are left alone.
+unsafe fn reclaim_focus(hwnd: HWND) {
+    let foreground = GetForegroundWindow();
+    if foreground.0.is_null() || foreground == hwnd {
+        return;
+    }
+
+    let mut foreground_pid = 0u32;
+    let foreground_thread = GetWindowThreadProcessId(foreground, Some(&mut foreground_pid));
+    if foreground_pid == GetCurrentProcessId() {
+        return;
+    }
+
+    // Windows only lets the foreground thread hand over focus, so borrow its input state
+    let our_thread = GetCurrentThreadId();
+    let attached = AttachThreadInput(our_thread, foreground_thread, true).as_bool();
+    let _ = BringWindowToTop(hwnd);
+    let _ = SetForegroundWindow(hwnd);
+    let edit_ptr = BLOCKING_EDIT_HWND.load(Ordering::SeqCst);
+    if !edit_ptr.is_null() {
+        let _ = SetFocus(HWND(edit_ptr));
+    }
+    if attached {
+        let _ = AttachThreadInput(our_thread, foreground_thread, false);
+    }
+}
+
+/// WinEvent callback for foreground changes while the blocking overlay is shown
+unsafe extern "system" fn foreground_event_proc(
+    _hook: HWINEVENTHOOK,
+    _event: u32,
+    _hwnd: HWND,
+    _id_object: i32,
+    _id_child: i32,
+    _event_thread: u32,
+    _event_time: u32,
+) {
+    let hwnd = HWND(BLOCKING_HWND.load(Ordering::SeqCst));
+    if !hwnd.0.is_null() && IsWindowVisible(hwnd).as_bool() {
+        reclaim_focus(hwnd);
+    }
+}
+
+/// Where a time extension was requested from (recorded in the extension log)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtendSource {
+    BlockingOverlay,
+    Tray,
+    Telegram,
+    Mqtt,
+    /// The child's own extension on the blocking screen, without the passcode
+    SelfService,
+    /// A command forwarded from `--command` (with the passcode)
+    CommandLine,
+}
+
+impl ExtendSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExtendSource::BlockingOverlay => "overlay",
+            ExtendSource::Tray => "tray",
+            ExtendSource::Telegram => "telegram",
+            ExtendSource::Mqtt => "mqtt",
+            ExtendSource::SelfService => "self",
+            ExtendSource::CommandLine => "cli",
+        }
+    }
+
+    /// Source recorded in the extension log (None for names this version doesn't know)
+    pub fn parse(name: &str) -> Option<Self> {
+        [
+            ExtendSource::BlockingOverlay,
+            ExtendSource::Tray,
+            ExtendSource::Telegram,
+            ExtendSource::Mqtt,
+            ExtendSource::SelfService,
+            ExtendSource::CommandLine,
+        ]
+        .into_iter()
+        .find(|source| source.as_str() == name)
+    }
+
+    /// Localized name for the stats dialog and /history
+    pub fn label(&self) -> &'static str {
+        i18n::t(match self {
+            ExtendSource::BlockingOverlay => "extend.source.overlay",
+            ExtendSource::Tray => "extend.source.tray",
+            ExtendSource::Telegram => "extend.source.telegram",
+            ExtendSource::Mqtt => "extend.source.mqtt",
+            ExtendSource::SelfService => "extend.source.self",
+            ExtendSource::CommandLine => "extend.source.cli",
+        })
+    }
+}
+
+/// Localized name of a source as recorded in the extension log
+pub fn extend_source_label(name: &str) -> &str {
+    ExtendSource::parse(name).map(|source| source.label()).unwrap_or(name)
+}
+
+/// Minutes added per source from extension log entries ("HH:MM:SS:+15m:tray"),
+/// in the order the sources first appear
+pub fn extension_minutes_by_source(entries: &[String]) -> Vec<(String, i32)> {
+    let mut totals: Vec<(String, i32)> = Vec::new();
+    for entry in entries {
+        let parts: Vec<&str> = entry.splitn(5, ':').collect();
+        let [_, _, _, amount, source] = parts.as_slice() else {
+            continue;
+        };
+        let Some(minutes) = amount.strip_prefix('+').and_then(|a| a.strip_suffix('m')).and_then(|a| a.parse::<i32>().ok()) else {
+            continue;
+        };
+        match totals.iter_mut().find(|(name, _)| name == source) {
+            Some((_, total)) => *total += minutes,
+            None => totals.push((source.to_string(), minutes)),
+        }
+    }
+    totals
+}
+
+/// Reasons an extension request can be rejected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtendError {
+    NotPositive,
+    ExceedsMax { max_minutes: i32 },
+    DailyCapReached { minutes_left: i32 },
+    /// The allowed number of extensions for today has been given
+    CountReached,
+    /// Today's limit is 0; only an explicit parent override can give time
+    NoScreenTimeToday,
+    /// "Until" a time of day that has already passed today (and not asked for tomorrow)
+    TimeInPast,
+    /// The remaining time already lasts past the requested time of day
+    AlreadyUntil,
+    /// A simulation is running; its time isn't real, so it can't be extended
+    Simulation,
+}
+
+impl ExtendError {
+    /// Localized, user-facing description of the error
+    pub fn message(&self) -> String {
+        match self {
+            ExtendError::NotPositive => i18n::t("extend.error.not_positive").to_string(),
+            ExtendError::ExceedsMax { max_minutes } => {
+                i18n::t("extend.error.max").replace("{}", &max_minutes.to_string())
+            }
+            ExtendError::DailyCapReached { minutes_left } => {
+                i18n::t("extend.error.daily_cap").replace("{}", &minutes_left.to_string())
+            }
+            ExtendError::CountReached => i18n::t("extend.error.count").to_string(),
+            ExtendError::NoScreenTimeToday => i18n::t("extend.error.no_screen_time").to_string(),
+            ExtendError::TimeInPast => i18n::t("extend.error.time_in_past").to_string(),
+            ExtendError::AlreadyUntil => i18n::t("extend.error.already_until").to_string(),
+            ExtendError::Simulation => i18n::t("extend.error.simulation").to_string(),
+        }
+    }
+}
+
+/// Extend the remaining time, enforcing the extension policy (no extensions on a day
+/// without screen time, per-request maximum, daily cap and number per day). Returns the new remaining time in seconds.
+pub fn extend_time_checked(minutes: i32, source: ExtendSource) -> Result<i32, ExtendError> {
+    if crate::database::is_no_screen_time_today() {
+        return Err(ExtendError::NoScreenTimeToday);
+    }
+    grant_time(minutes, source)
+}
+
+/// Check an extension against the per-request maximum, the daily cap and the number per day
+fn check_extension(minutes: i32, config: &crate::database::ExtensionConfig, extended_today: i32, count_today: i32) -> Result<(), ExtendError> {
+    if minutes <= 0 {
+        return Err(ExtendError::NotPositive);
+    }
+    if count_reached(config, count_today) {
+        return Err(ExtendError::CountReached);
+    }
+    if config.max_minutes > 0 && minutes > config.max_minutes {
+        return Err(ExtendError::ExceedsMax { max_minutes: config.max_minutes });
+    }
+    if config.daily_cap_minutes > 0 {
+        let minutes_left = (config.daily_cap_minutes - extended_today).max(0);
+        if minutes > minutes_left {
+            return Err(ExtendError::DailyCapReached { minutes_left });
+        }
+    }
+    Ok(())
+}
+
+/// Whether today's number of extensions is used up (never when unlimited)
+fn count_reached(config: &crate::database::ExtensionConfig, count_today: i32) -> bool {
+    config.max_per_day > 0 && count_today >= config.max_per_day
+}
+
+/// Most minutes a single extension may add right now (None if neither limit is set)
+fn extension_limit(config: &crate::database::ExtensionConfig, extended_today: i32) -> Option<i32> {
+    let max = (config.max_minutes > 0).then_some(config.max_minutes);
+    let minutes_left = (config.daily_cap_minutes > 0).then(|| (config.daily_cap_minutes - extended_today).max(0));
+    match (max, minutes_left) {
+        (Some(max), Some(left)) => Some(max.min(left)),
+        (max, left) => max.or(left),
+    }
+}
+
+/// Extension typed in by the parent: a number of minutes, or a time of day to play until
+/// (today, or tomorrow for e.g. "0:30 tomorrow" late in the evening)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CustomExtension {
+    Minutes(i32),
+    Until { hour: u32, minute: u32, tomorrow: bool },
+}
+
+/// Check an extension typed in by the parent without giving the time yet, so the
+/// amount dialog can stay open on a wrong entry
+pub fn validate_extension(text: &str) -> Result<CustomExtension, ExtendError> {
+    if crate::database::is_no_screen_time_today() {
+        return Err(ExtendError::NoScreenTimeToday);
+    }
+    let extension = parse_custom_extension(text)?;
+    let minutes = match extension {
+        CustomExtension::Minutes(minutes) => minutes,
+        CustomExtension::Until { hour, minute, tomorrow } => minutes_until(hour, minute, tomorrow)?.0,
+    };
+    check_extension(
+        minutes,
+        &crate::database::get_extension_config(),
+        crate::database::get_extended_today(),
+        crate::database::get_extension_count_today(),
+    )?;
+    Ok(extension)
+}
+
+/// Parse the custom extension field: a positive whole number of minutes or a time like "20:00"
+fn parse_custom_extension(text: &str) -> Result<CustomExtension, ExtendError> {
+    if text.contains(':') {
+        return parse_until(text)
+            .map(|(hour, minute, tomorrow)| CustomExtension::Until { hour, minute, tomorrow })
+            .ok_or(ExtendError::NotPositive);
+    }
+    match text.trim().parse::<i32>() {
+        Ok(minutes) if minutes > 0 => Ok(CustomExtension::Minutes(minutes)),
+        _ => Err(ExtendError::NotPositive),
+    }
+}
+
+/// Parse a time of day like "20:00" or "7:30" into (hour, minute)
+fn parse_clock_time(text: &str) -> Option<(u32, u32)> {
+    let (h, m) = text.trim().split_once(':')?;
+    let (h, m): (u32, u32) = (h.parse().ok()?, m.parse().ok()?);
+    (h < 24 && m < 60).then_some((h, m))
+}
+
+/// Parse the time to play until, like "20:00" or "0:30 tomorrow" ("morgen" in German),
+/// into (hour, minute, tomorrow)
+pub fn parse_until(text: &str) -> Option<(u32, u32, bool)> {
+    let text = text.trim();
+    let (time, tomorrow) = match text.rsplit_once(char::is_whitespace) {
+        Some((time, word)) if word.eq_ignore_ascii_case("tomorrow") || word.eq_ignore_ascii_case("morgen") => (time, true),
+        _ => (text, false),
+    };
+    parse_clock_time(time).map(|(hour, minute)| (hour, minute, tomorrow))
+}
+
+/// The whole minutes that have to be added to `remaining` so it lasts from `now` until
+/// `target` (both seconds of the day), and the seconds those minutes overshoot it by.
+/// A time that has already passed is rejected unless `tomorrow` is set; then it's read as
+/// that time tomorrow (the new day's time still starts over at midnight).
+fn time_until(now: i32, target: i32, remaining: i32, tomorrow: bool) -> Result<(i32, i32), ExtendError> {
+    let seconds = match target - now {
+        seconds if seconds <= 0 && tomorrow => seconds + 24 * 3600,
+        seconds if seconds <= 0 => return Err(ExtendError::TimeInPast),
+        seconds => seconds,
+    };
+    let missing = seconds - remaining.max(0);
+    if missing <= 0 {
+        return Err(ExtendError::AlreadyUntil);
+    }
+    let minutes = (missing + 59) / 60;
+    Ok((minutes, minutes * 60 - missing))
+}
+
+/// Minutes to add so the remaining time lasts until `hour:minute` (today, or tomorrow if
+/// asked for and it has passed), and the seconds they overshoot it by
+fn minutes_until(hour: u32, minute: u32, tomorrow: bool) -> Result<(i32, i32), ExtendError> {
+    let st = crate::clock::local_time();
+    let now = st.wHour as i32 * 3600 + st.wMinute as i32 * 60 + st.wSecond as i32;
+    let target = hour as i32 * 3600 + minute as i32 * 60;
+    time_until(now, target, REMAINING_SECONDS.load(Ordering::SeqCst), tomorrow)
+}
+
+/// Extend the remaining time so it lasts until `hour:minute` ("until dinner"); a time that
+/// has passed means tomorrow if `tomorrow` is set. The minutes this adds count against the
+/// same maximum and daily cap as any extension. Returns the new remaining time in seconds.
+pub fn extend_until(hour: u32, minute: u32, tomorrow: bool, source: ExtendSource) -> Result<i32, ExtendError> {
+    let (minutes, surplus) = minutes_until(hour, minute, tomorrow)?;
+    extend_time_checked(minutes, source)?;
+    // Whole minutes were added; take back the part past the chosen time. Subtracted rather
+    // than stored so a countdown tick in between isn't lost.
+    let remaining = REMAINING_SECONDS.fetch_sub(surplus, Ordering::SeqCst) - surplus;
+    crate::database::save_remaining_time(remaining);
+    Ok(remaining)
+}
+
+/// Most minutes a single extension may add right now, for the amount dialog's hint
+pub fn current_extension_limit() -> Option<i32> {
+    extension_limit(&crate::database::get_extension_config(), crate::database::get_extended_today())
+}
+
+/// Ask for a custom number of minutes (or a time to play until) and extend by it
+/// (the caller has checked the passcode).
+/// Returns true if time was added.
+pub unsafe fn extend_custom(hwnd: HWND, source: ExtendSource) -> bool {
+    // Don't ask for an amount that can't be granted anyway
+    if crate::database::is_no_screen_time_today() {
+        show_extend_error(hwnd, ExtendError::NoScreenTimeToday);
+        return false;
+    }
+    if count_reached(&crate::database::get_extension_config(), crate::database::get_extension_count_today()) {
+        show_extend_error(hwnd, ExtendError::CountReached);
+        return false;
+    }
+    if let Some(minutes_left @ 0) = current_extension_limit() {
+        show_extend_error(hwnd, ExtendError::DailyCapReached { minutes_left });
+        return false;
+    }
+    let extended = match crate::dialogs::prompt_extend_minutes(hwnd) {
+        Some(CustomExtension::Minutes(minutes)) => extend_time_checked(minutes, source),
+        Some(CustomExtension::Until { hour, minute, tomorrow }) => extend_until(hour, minute, tomorrow, source),
+        None => return false,
+    };
+    match extended {
+        Ok(_) => true,
+        Err(e) => {
+            show_extend_error(hwnd, e);
+            false
+        }
+    }
+}
+
+/// Give time even on a day without screen time (limit 0). Only for explicit parent overrides
+/// (/grant); the per-request maximum, daily cap and number per day still apply.
+pub fn grant_time(minutes: i32, source: ExtendSource) -> Result<i32, ExtendError> {
+    if crate::mini_overlay::is_simulating() {
+        return Err(ExtendError::Simulation);
+    }
+    let extended_today = crate::database::get_extended_today();
+    let count_today = crate::database::get_extension_count_today();
+    check_extension(minutes, &crate::database::get_extension_config(), extended_today, count_today)?;
+
+    let remaining = extend_time(minutes);
+    cancel_pending_shutdown();
+    crate::database::save_extended_today(extended_today + minutes);
+    crate::database::save_extension_count_today(count_today + 1);
+    crate::database::log_extension_event(minutes, source.as_str());
+    crate::notifications::dispatch(crate::notifications::Event::Extended { minutes, source });
+    // Don't wait for the next periodic save
+    crate::database::save_remaining_time(remaining);
+
+    Ok(remaining)
+}
+
+/// Add the minutes of an unlock. Unlocking is the parent's override rather than an
+/// extension: the extension limits don't apply, it works on a day without screen time, and
+/// it isn't logged or counted. A simulated block only ends the simulation; the real time is
+/// left as it was. Returns the new remaining time in seconds.
+fn grant_unlock_time() -> i32 {
+    if crate::mini_overlay::is_simulating() {
+        crate::mini_overlay::stop_simulation();
+        return REMAINING_SECONDS.load(Ordering::SeqCst);
+    }
+    let remaining = extend_time(UNLOCK_MINUTES);
+    cancel_pending_shutdown();
+    crate::database::save_remaining_time(remaining);
+    remaining
+}
+
+/// Unlock the blocking screen (the caller has checked the passcode)
+pub unsafe fn unlock() {
+    // A curfew is only lifted; time is added only if it's used up as well, so the limit
+    // doesn't block again right away
+    if crate::curfew::is_curfew_blocking() {
+        crate::curfew::override_curfew();
+        if REMAINING_SECONDS.load(Ordering::SeqCst) > 0 {
+            cancel_pending_shutdown();
+            hide_blocking_overlay();
+            return;
+        }
+    }
+    grant_unlock_time();
+    hide_blocking_overlay();
+}
+
+/// Self-service extensions left today, from uses per day (0 = off) and uses so far
+fn self_extends_left(per_day: i32, used_today: i32) -> i32 {
+    (per_day - used_today).max(0)
+}
+
+/// Whether the blocking screen offers the self-service extension right now: only for a
+/// used-up limit (not a curfew or lock) on a day with screen time, while uses are left
+pub fn self_extend_available() -> bool {
+    let (_, per_day) = crate::database::get_self_extend_config();
+    self_extends_left(per_day, crate::database::get_self_extend_count_today()) > 0
+        && crate::database::get_active_block().is_some_and(|(reason, _)| reason == "limit")
+        && !crate::database::is_no_screen_time_today()
+}
+
+/// The child gives themselves the self-service extension from the blocking screen. No
+/// passcode, and the parent's extension limits don't apply; instead it's limited to
+/// `self_extend_per_day` uses. Logged and reported like any extension.
+pub fn self_extend() -> Result<i32, ExtendError> {
+    if crate::mini_overlay::is_simulating() {
+        return Err(ExtendError::Simulation);
+    }
+    let (minutes, per_day) = crate::database::get_self_extend_config();
+    let used_today = crate::database::get_self_extend_count_today();
+    if self_extends_left(per_day, used_today) == 0 {
+        return Err(ExtendError::CountReached);
+    }
+
+    let remaining = extend_time(minutes);
+    cancel_pending_shutdown();
+    crate::database::save_self_extend_count_today(used_today + 1);
+    crate::database::log_extension_event(minutes, ExtendSource::SelfService.as_str());
+    crate::notifications::dispatch(crate::notifications::Event::Extended { minutes, source: ExtendSource::SelfService });
+    crate::database::save_remaining_time(remaining);
+
+    Ok(remaining)
+}
+
+/// Show an extension error to the user
+pub unsafe fn show_extend_error(hwnd: HWND, error: ExtendError) {
+    let message = i18n::to_wide(&error.message());
+    let title = i18n::wide("extend.error.title");
+    MessageBoxW(
+        hwnd,
+        PCWSTR(message.as_ptr()),
+        PCWSTR(title.as_ptr()),
+        MB_OK | MB_ICONWARNING,
+    );
+}
+
+/// Reduce the remaining time by the specified minutes
+pub fn reduce_time(minutes: i32) {
+    let current = REMAINING_SECONDS.load(Ordering::SeqCst);
+    let reduction_seconds = minutes * 60;
+
+    let new_time = (current - reduction_seconds).max(0);
+    REMAINING_SECONDS.store(new_time, Ordering::SeqCst);
+}
+
+
+/// Count the auto-shutdown down by one second; true when it has run out and the machine
+/// should shut down. One atomic step, so a cancel from another thread (a Telegram
+/// extension) can't be overwritten by a tick that read the old value.
+fn tick_shutdown_countdown() -> bool {
+    tick_countdown(&SHUTDOWN_COUNTDOWN_SECONDS)
+}
+
+/// Count a countdown down by a second; true exactly when it runs out
+fn tick_countdown(countdown: &AtomicI32) -> bool {
+    let previous = countdown
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |seconds| (seconds >= 0).then_some(seconds - 1))
+        .unwrap_or(-1);
+    previous == 0
+}
+
+/// Cancel a running auto-shutdown countdown. Called by every path that grants time or stops
+/// the timer (extensions, focus mode, pauses, resets, unlocking) before the overlay is hidden,
+/// so the grant wins even if the countdown is about to run out. A curfew block keeps its
+/// countdown: more time today doesn't lift it.
+pub fn cancel_pending_shutdown() {
+    if !crate::curfew::is_curfew_blocking() {
+        SHUTDOWN_COUNTDOWN_SECONDS.store(-1, Ordering::SeqCst);
+        LOCK_COUNTDOWN_SECONDS.store(-1, Ordering::SeqCst);
+        if SYSTEM_SHUTDOWN_PENDING.swap(false, Ordering::SeqCst) {
+            log::info!("Time was granted; aborting the shutdown");
+            let _ = unsafe { AbortSystemShutdownW(None) };
+        }
+    }
+}
+
+/// Lock or shut down the computer when the time has run out, as set in `expiry_action`,
+/// after the configured warning. The blocking screen is already up and stays behind it,
+/// so unlocking Windows or aborting the shutdown doesn't get around the limit.
+pub unsafe fn apply_expiry_action() {
+    let delay = crate::database::get_expiry_action_delay_seconds();
+    match crate::database::get_expiry_action().as_str() {
+        // Headless there's no blocking screen to count down on (or to fall back to)
+        "lock" | "block" if crate::headless::is_headless() => crate::headless::lock_session(),
+        "lock" => {
+            if delay > 0 {
+                // The blocking screen shows "Locking in ..." until then
+                LOCK_COUNTDOWN_SECONDS.store(delay, Ordering::SeqCst);
+            } else {
+                let _ = LockWorkStation();
+            }
+        }
+        "shutdown" => {
+            if !crate::elevation::can_shut_down() {
+                log::warn!("Expiry action is shutdown, but the shutdown privilege isn't held; blocking only");
+                return;
+            }
+            // Windows shows the message and counts down the grace period itself
+            let message = i18n::wide("expiry.shutdown_message");
+            let reason = SHUTDOWN_REASON(SHTDN_REASON_MAJOR_OTHER.0 | SHTDN_REASON_MINOR_OTHER.0 | SHTDN_REASON_FLAG_PLANNED.0);
+            match InitiateSystemShutdownExW(None, PCWSTR(message.as_ptr()), delay as u32, false, false, reason) {
+                Ok(()) => {
+                    log::info!("Time is up; shutting down in {} seconds", delay);
+                    SYSTEM_SHUTDOWN_PENDING.store(true, Ordering::SeqCst);
+                    // Also counted down on the blocking screen, which shuts down itself
+                    // if the system shutdown is aborted some other way (`shutdown /a`)
+                    SHUTDOWN_COUNTDOWN_SECONDS.store(delay, Ordering::SeqCst);
+                }
+                Err(e) => log::warn!("Could not start the shutdown: {}", e.message()),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Seconds until a block that began at `started_at` may be lifted (0 = now)
+fn unlock_wait(started_at: i64, min_seconds: i64, now: i64) -> i64 {
+    if started_at <= 0 || min_seconds <= 0 {
+        return 0;
+    }
+    (started_at + min_seconds - now).clamp(0, min_seconds)
+}
+
+/// Seconds left of the minimum block duration of the current block (0 = can be unlocked)
+pub fn unlock_wait_remaining() -> i64 {
+    unlock_wait(
+        crate::database::get_block_started_at(),
+        crate::database::get_min_block_duration_seconds(),
+        crate::database::get_current_timestamp(),
+    )
+}
+
+/// Lift the block after time was granted remotely (Telegram, focus mode) or with the
+/// extend buttons. Within the minimum block duration the time is kept and the block stays
+/// up until it's over.
+pub unsafe fn release_block() {
+    if crate::overlay::is_blocking_active() && unlock_wait_remaining() > 0 {
+        RELEASE_PENDING.store(true, Ordering::SeqCst);
+        return;
+    }
+    hide_blocking_overlay();
+}
+
+/// Hides the blocking overlay
+pub unsafe fn hide_blocking_overlay() {
+    let hwnd = HWND(BLOCKING_HWND.load(Ordering::SeqCst));
+    if hwnd.0.is_null() {
+        return;
+    }
+
+    let _ = KillTimer(hwnd, TIMER_REASSERT_TOPMOST);
+    let _ = KillTimer(hwnd, TIMER_COUNTDOWN);
+    let hook = FOREGROUND_HOOK.swap(std::ptr::null_mut(), Ordering::SeqCst);
+    if !hook.is_null() {
+        let _ = UnhookWinEvent(HWINEVENTHOOK(hook));
+    }
+    let _ = ShowWindow(hwnd, SW_HIDE);
+    *BLOCKING_TEXT.lock().unwrap() = None;
+
+    // Reset shutdown and lock countdowns
+    SHUTDOWN_COUNTDOWN_SECONDS.store(-1, Ordering::SeqCst);
+    LOCK_COUNTDOWN_SECONDS.store(-1, Ordering::SeqCst);
+
+    // Hide secondary monitor overlays
+    hide_secondary_overlays();
+
+    // Unlocking a simulated block ends the simulation; save the real time, not the simulated one
+    crate::mini_overlay::stop_simulation();
+
+    let remaining = REMAINING_SECONDS.load(Ordering::SeqCst);
+    record_unlock(remaining);
+
+    // Show mini overlay again (with the new value right away) if there's remaining time
+    if remaining > 0 {
+        crate::mini_overlay::show_mini_overlay();
+        crate::mini_overlay::update_mini_overlay();
+    }
+}
+
+/// Remember when the block began, unless it's already up (shown again or restored)
+fn record_block_start() {
+    if crate::database::get_block_started_at() == 0 {
+        crate::database::set_setting("block_started_at", &crate::database::get_current_timestamp().to_string());
+    }
+}
+
+/// Persist the end of a block: save the remaining time and forget the block, so restarting
+/// the app later the same day doesn't show it again. The next block gets its own minimum
+/// duration, and a release still waiting for this one's doesn't carry over.
+fn record_unlock(remaining: i32) {
+    crate::database::save_remaining_time(remaining);
+    crate::database::clear_active_block();
+    crate::database::set_setting("block_started_at", "0");
+    RELEASE_PENDING.store(false, Ordering::SeqCst);
+}
+
+/// Vertical layout of the blocking panel (offsets relative to the panel top).
+/// Shared by control placement and painting so hidden sections leave no gaps.
+struct PanelLayout {
+    height: i32,
+    show_extend: bool,
+    /// The child's self-service extension button, below the extend buttons
+    show_self_extend: bool,
+    /// Session buttons shown in the bottom row (shutdown / lock / sign out)
+    session_buttons: Vec<i32>,
+    extend_label_y: i32,
+    extend_buttons_y: i32,
+    self_extend_y: i32,
+    separator_y: i32,
+    passcode_label_y: i32,
+    passcode_edit_y: i32,
+}
+
+/// Compute the panel layout from the current settings (unscaled units)
+fn panel_layout() -> PanelLayout {
+    // On a day without screen time only Unlock (the parent's override) gives time
+    let show_extend = crate::database::is_block_extend_allowed() && !crate::database::is_no_screen_time_today();
+    let show_self_extend = self_extend_available();
+    let session_buttons: Vec<i32> = [
+        (ID_SHUTDOWN_BUTTON, crate::database::is_block_shutdown_allowed()),
+        (ID_LOCK_BUTTON, crate::database::is_block_lock_allowed()),
+        (ID_LOGOFF_BUTTON, crate::database::is_block_logoff_allowed()),
+    ]
+    .into_iter()
+    .filter(|&(_, allowed)| allowed)
+    .map(|(id, _)| id)
+    .collect();
+
+    // Layout: Icon -> Title -> Countdown -> Message -> [Extend label -> Extend buttons]
+    //         -> [Self-service button] -> Separator -> Passcode section -> [Session buttons]
+    let extend_section = if show_extend { 65 } else { 0 };
+    let self_extend_section = if show_self_extend { 50 } else { 0 };
+    let session_section = if session_buttons.is_empty() { 0 } else { 50 };
+    let above_separator = extend_section + self_extend_section;
+
+    PanelLayout {
+        height: 405 + above_separator + session_section,
+        show_extend,
+        show_self_extend,
+        session_buttons,
+        extend_label_y: 200,
+        extend_buttons_y: 220,
+        self_extend_y: 205 + extend_section,
+        separator_y: 200 + above_separator,
+        passcode_label_y: 210 + above_separator,
+        passcode_edit_y: 245 + above_separator,
+    }
+}
+
+/// Apply the panel size setting to an unscaled value (DPI scaling still goes through `scale`)
+fn sized(value: i32) -> i32 {
+    value * PANEL_SCALE_PERCENT.load(Ordering::SeqCst) / 100
+}
+
+/// Re-read the panel size setting, reducing it if the enlarged panel would not fit the screen
+unsafe fn refresh_panel_scale(layout: &PanelLayout) {
+    let screen_width = GetSystemMetrics(SM_CXSCREEN);
+    let screen_height = GetSystemMetrics(SM_CYSCREEN);
+    let fit_width = screen_width * 90 / scale(480).max(1);
+    let fit_height = screen_height * 90 / scale(layout.height).max(1);
+    let percent = crate::database::get_block_text_scale().min(fit_width).min(fit_height).max(50);
+    PANEL_SCALE_PERCENT.store(percent, Ordering::SeqCst);
+}
+
+/// Give the buttons and passcode field fonts matching the current panel size
+unsafe fn apply_control_fonts(hwnd: HWND) {
+    let create = |size: i32| {
+        CreateFontW(
+            scale(sized(size)), 0, 0, 0,
+            FW_BOLD.0 as i32,
+            0, 0, 0, 0, 0, 0, 5, 0,
+            w!("Segoe UI"),
+        )
+    };
+    let button_font = create(16);
+    let edit_font = create(28);
+
+    for id in [
+        ID_EXTEND_15, ID_EXTEND_30, ID_EXTEND_60, ID_EXTEND_CUSTOM, ID_SELF_EXTEND, ID_UNLOCK_BUTTON,
+        ID_SHUTDOWN_BUTTON, ID_LOCK_BUTTON, ID_LOGOFF_BUTTON, ID_REVEAL_BUTTON,
+    ] {
+        if let Ok(ctrl) = GetDlgItem(hwnd, id) {
+            SendMessageW(ctrl, WM_SETFONT, WPARAM(button_font.0 as usize), LPARAM(0));
+        }
+    }
+    if let Ok(edit) = GetDlgItem(hwnd, ID_PASSCODE_EDIT) {
+        SendMessageW(edit, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(0));
+    }
+
+    // The controls no longer use the previous fonts
+    for (slot, font) in [(&BUTTON_FONT, button_font), (&EDIT_FONT, edit_font)] {
+        let old = slot.swap(font.0, Ordering::SeqCst);
+        if !old.is_null() {
+            let _ = DeleteObject(HFONT(old));
+        }
+    }
+}
+
+/// Position the blocking overlay controls, hiding the sections disabled in settings
+unsafe fn layout_blocking_controls(hwnd: HWND) {
+    let layout = panel_layout();
+    refresh_panel_scale(&layout);
+    apply_control_fonts(hwnd);
+    let screen_width = GetSystemMetrics(SM_CXSCREEN);
+    let screen_height = GetSystemMetrics(SM_CYSCREEN);
+    let panel_y = (screen_height - scale(sized(layout.height))) / 2;
+
+    let place = |id: i32, visible: bool, x: i32, y: i32, w: i32, h: i32| {
+        if let Ok(ctrl) = GetDlgItem(hwnd, id) {
+            let _ = MoveWindow(ctrl, x, y, w, h, false);
+            let _ = ShowWindow(ctrl, if visible { SW_SHOW } else { SW_HIDE });
+        }
+    };
+
+    // Extend time buttons
+    let extend_btn_width = scale(sized(90));
+    let extend_btn_height = scale(sized(36));
+    let extend_spacing = scale(sized(15));
+    let total_extend_width = extend_btn_width * 4 + extend_spacing * 3;
+    let extend_start_x = (screen_width - total_extend_width) / 2;
+    let extend_y = panel_y + scale(sized(layout.extend_buttons_y));
+    for (i, id) in [ID_EXTEND_15, ID_EXTEND_30, ID_EXTEND_60, ID_EXTEND_CUSTOM].into_iter().enumerate() {
+        let x = extend_start_x + (extend_btn_width + extend_spacing) * i as i32;
+        place(id, layout.show_extend, x, extend_y, extend_btn_width, extend_btn_height);
+    }
+
+    // Self-service extension, labelled with its amount and the uses left today
+    let self_width = scale(sized(280));
+    let self_y = panel_y + scale(sized(layout.self_extend_y));
+    place(ID_SELF_EXTEND, layout.show_self_extend, (screen_width - self_width) / 2, self_y, self_width, extend_btn_height);
+    if layout.show_self_extend {
+        if let Ok(ctrl) = GetDlgItem(hwnd, ID_SELF_EXTEND) {
+            let (minutes, per_day) = crate::database::get_self_extend_config();
+            let left = self_extends_left(per_day, crate::database::get_self_extend_count_today());
+            let text = i18n::to_wide(
+                &i18n::t("blocking.self_extend")
+                    .replacen("{}", &minutes.to_string(), 1)
+                    .replacen("{}", &left.to_string(), 1),
+            );
+            SetWindowTextW(ctrl, PCWSTR(text.as_ptr())).ok();
+        }
+    }
+
+    // Passcode edit control
+    let edit_width = scale(sized(180));
+    let edit_height = scale(sized(44));
+    let edit_x = (screen_width - edit_width) / 2;
+    let edit_y = panel_y + scale(sized(layout.passcode_edit_y));
+    place(ID_PASSCODE_EDIT, true, edit_x, edit_y, edit_width, edit_height);
+    place(ID_REVEAL_BUTTON, true, edit_x + edit_width + scale(sized(8)), edit_y, scale(sized(70)), edit_height);
+
+    // Unlock and shutdown buttons
+    let btn_width = scale(sized(180));
+    let btn_height = scale(sized(40));
+    let btn_x = (screen_width - btn_width) / 2;
+    let btn_y = edit_y + edit_height + scale(sized(12));
+    place(ID_UNLOCK_BUTTON, true, btn_x, btn_y, btn_width, btn_height);
+
+    // Session buttons share one centered row below Unlock
+    let session_y = btn_y + btn_height + scale(sized(10));
+    let count = layout.session_buttons.len() as i32;
+    let session_spacing = scale(sized(10));
+    let session_width = if count <= 1 { btn_width } else { scale(sized(130)) };
+    let session_start_x = (screen_width - (session_width * count + session_spacing * (count - 1).max(0))) / 2;
+    for id in [ID_SHUTDOWN_BUTTON, ID_LOCK_BUTTON, ID_LOGOFF_BUTTON] {
+        match layout.session_buttons.iter().position(|&b| b == id) {
+            Some(i) => {
+                let x = session_start_x + (session_width + session_spacing) * i as i32;
+                place(id, true, x, session_y, session_width, btn_height);
+            }
+            None => place(id, false, 0, 0, 0, 0),
+        }
+    }
+
+    // Without the shutdown privilege the button stays greyed and says why
+    if let Ok(ctrl) = GetDlgItem(hwnd, ID_SHUTDOWN_BUTTON) {
+        let allowed = crate::elevation::can_shut_down();
+        let _ = EnableWindow(ctrl, allowed);
+        let text = i18n::wide(if allowed { "blocking.shutdown" } else { "blocking.shutdown_unavailable" });
+        SetWindowTextW(ctrl, PCWSTR(text.as_ptr())).ok();
+    }
+}
+
+/// Show the "incorrect passcode" state: error text, cleared field, refocus and a beep
+unsafe fn reject_passcode(hwnd: HWND) {
+    PASSCODE_ERROR.store(true, Ordering::SeqCst);
+    let _ = InvalidateRect(hwnd, None, false);
+    let edit_ptr = BLOCKING_EDIT_HWND.load(Ordering::SeqCst);
+    if !edit_ptr.is_null() {
+        let edit = HWND(edit_ptr);
+        SetWindowTextW(edit, w!("")).ok();
+        let _ = SetFocus(edit);
+    }
+    let _ = PlaySoundW(w!("SystemExclamation"), None, SND_ALIAS | SND_ASYNC);
+}
+
+/// Generate a new 6-digit one-time unlock code, replacing any previous one.
+/// Returns None if the system random number generator is unavailable.
+pub fn generate_unlock_code() -> Option<String> {
+    let mut bytes = [0u8; 4];
+    unsafe { BCryptGenRandom(BCRYPT_ALG_HANDLE::default(), &mut bytes, BCRYPT_USE_SYSTEM_PREFERRED_RNG) }
+        .ok()
+        .ok()?;
+    let code = format!("{:06}", u32::from_le_bytes(bytes) % 1_000_000);
+    *UNLOCK_CODE.lock().unwrap() = Some((code.clone(), Instant::now()));
+    Some(code)
+}
+
+/// Whether `entered` is the current one-time unlock code. An expired code is forgotten; a
+/// matching one stays valid until `clear_unlock_code` uses it up.
+fn unlock_code_matches(entered: &str) -> bool {
+    let mut active = UNLOCK_CODE.lock().unwrap();
+    let Some((code, issued)) = active.as_ref() else {
+        return false;
+    };
+    if issued.elapsed() >= UNLOCK_CODE_VALIDITY {
+        *active = None;
+        return false;
+    }
+    code == entered
+}
+
+/// Use up the one-time unlock code once it has unlocked the screen
+fn clear_unlock_code() {
+    *UNLOCK_CODE.lock().unwrap() = None;
+}
+
+/// What was entered in the blocking overlay's passcode field
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Entry {
+    Passcode,
+    UnlockCode,
+}
+
+/// Verify the passcode entered in the blocking overlay
+unsafe fn check_blocking_passcode() -> bool {
+    check_blocking_entry(false).is_some()
+}
+
+/// Verify the passcode for the extend buttons. Unlike the other buttons they also work
+/// during the minimum block duration: the time is granted right away and the block lifted
+/// once the duration is over (see `release_block`).
+unsafe fn check_extend_passcode() -> bool {
+    passcode_lockout_remaining() == 0 && read_blocking_entry(false).is_some()
+}
+
+/// Check the blocking overlay's passcode field. With `accept_unlock_code` a valid one-time
+/// unlock code passes as well (only the Unlock button takes one).
+unsafe fn check_blocking_entry(accept_unlock_code: bool) -> Option<Entry> {
+    // Nothing is checked during a lockout or the minimum block duration, not even the
+    // right passcode (and it doesn't count as a wrong attempt either)
+    if passcode_lockout_remaining() > 0 || unlock_wait_remaining() > 0 {
+        return None;
+    }
+    read_blocking_entry(accept_unlock_code)
+}
+
+/// Read the passcode field and check it; wrong entries count toward the lockout
+unsafe fn read_blocking_entry(accept_unlock_code: bool) -> Option<Entry> {
+    let edit_ptr = BLOCKING_EDIT_HWND.load(Ordering::SeqCst);
+    if edit_ptr.is_null() {
+        return None;
+    }
+    let mut buffer = [0u16; 16];
+    let len = GetWindowTextW(HWND(edit_ptr), &mut buffer);
+    // Pasted codes may carry spaces or a line break
+    let entered = String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string();
+
+    let entry = if get_passcode().is_some_and(|stored| entered == stored) {
+        Some(Entry::Passcode)
+    } else if accept_unlock_code && !entered.is_empty() && unlock_code_matches(&entered) {
+        Some(Entry::UnlockCode)
+    } else {
+        None
+    };
+    match entry {
+        Some(_) => reset_failed_passcodes(),
+        // Pressing a button with an empty field is not an attempt
+        None if !entered.is_empty() => record_failed_passcode(),
+        None => {}
+    }
+    entry
+}
+
+/// Lockout after `failures` wrong passcodes in a row: none before `max_attempts`, then
+/// `delay_seconds`, doubling with every further wrong passcode (capped at an hour)
+fn lockout_seconds(failures: u32, max_attempts: u32, delay_seconds: u32) -> i64 {
+    if max_attempts == 0 || failures < max_attempts {
+        return 0;
+    }
+    let doublings = (failures - max_attempts).min(20);
+    (delay_seconds as i64 * (1i64 << doublings)).min(MAX_LOCKOUT_SECONDS)
+}
+
+/// Seconds until passcodes are accepted again (0 = not locked out)
+pub fn passcode_lockout_remaining() -> i64 {
+    (crate::database::get_lockout_until() - crate::database::get_current_timestamp()).max(0)
+}
+
+/// Count a wrong passcode and start a lockout once there were too many. Both are stored
+/// in the database, so restarting the app doesn't reset them.
+pub fn record_failed_passcode() {
+    let config = crate::database::get_lockout_config();
+    let failures = crate::database::get_failed_attempts().saturating_add(1);
+    let lockout = lockout_seconds(failures, config.max_attempts, config.delay_seconds);
+
+    // The count and the lockout are written together, so a crash can't leave one without the other
+    let failures_text = failures.to_string();
+    let until_text = (crate::database::get_current_timestamp() + lockout).to_string();
+    let mut updates = vec![("failed_attempts", failures_text.as_str())];
+    if lockout > 0 {
+        updates.push(("lockout_until", until_text.as_str()));
+    }
+    let _ = crate::database::set_settings_batch(&updates);
+
+    if lockout > 0 {
+        log::warn!("{} wrong attempts, locked out for {}s", failures, lockout);
+        if config.notify {
+            crate::notifications::dispatch(crate::notifications::Event::PasscodeLockout { attempts: failures, seconds: lockout });
+        }
+    }
+}
+
+/// A correct passcode clears the count of wrong ones
+pub fn reset_failed_passcodes() {
+    if crate::database::get_failed_attempts() > 0 {
+        let _ = crate::database::set_settings_batch(&[("failed_attempts", "0"), ("lockout_until", "0")]);
+    }
+}
+
+pub unsafe extern "system" fn blocking_overlay_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_CREATE => {
+            let hinstance = GetModuleHandleW(None).unwrap();
+
+            // Controls are created unpositioned; layout_blocking_controls places them
+            // (and hides the ones disabled in settings) whenever the overlay is shown
+            let buttons = [
+                (ID_EXTEND_15, "blocking.extend_15"),
+                (ID_EXTEND_30, "blocking.extend_30"),
+                (ID_EXTEND_60, "blocking.extend_60"),
+                (ID_EXTEND_CUSTOM, "blocking.extend_custom"),
+                (ID_SELF_EXTEND, "blocking.self_extend"),
+                (ID_UNLOCK_BUTTON, "blocking.unlock"),
+                (ID_SHUTDOWN_BUTTON, "blocking.shutdown"),
+                (ID_LOCK_BUTTON, "blocking.lock"),
+                (ID_LOGOFF_BUTTON, "blocking.logoff"),
+            ];
+            for (id, key) in buttons {
+                let text = i18n::wide(key);
+                let _ = CreateWindowExW(
+                    WINDOW_EX_STYLE(0),
+                    w!("BUTTON"),
+                    PCWSTR(text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    0, 0, 0, 0,
+                    hwnd,
+                    HMENU(id as _),
+                    hinstance,
+                    None,
+                );
+            }
+
+            // Passcode edit control
+            let edit = CreateWindowExW(
+                WINDOW_EX_STYLE(0),
+                w!("EDIT"),
+                w!(""),
+                WS_CHILD | WS_VISIBLE | WS_BORDER | WS_TABSTOP
+                    | WINDOW_STYLE(ES_CENTER as u32 | ES_PASSWORD as u32 | ES_NUMBER as u32),
+                0, 0, 0, 0,
+                hwnd,
+                HMENU(ID_PASSCODE_EDIT as _),
+                hinstance,
+                None,
+            ).ok();
+
+            if let Some(e) = edit {
+                BLOCKING_EDIT_HWND.store(e.0, Ordering::SeqCst);
+                // Room for a 6-digit one-time unlock code as well as the 4-digit passcode
+                SendMessageW(e, EM_SETLIMITTEXT, WPARAM(6), LPARAM(0));
+                // Tab order follows z-order: extend buttons, self-service, passcode, unlock, session buttons
+                if let Ok(self_extend) = GetDlgItem(hwnd, ID_SELF_EXTEND) {
+                    let _ = SetWindowPos(e, self_extend, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE);
+                }
+                // Hold-to-show button next to the field (parents mistyping only see dots)
+                let _ = crate::dialogs::create_reveal_button(hwnd, e, ID_REVEAL_BUTTON);
+            }
+
+            // Also sets the button and passcode fonts
+            layout_blocking_controls(hwnd);
+
+            LRESULT(0)
+        }
+        WM_PAINT => {
+            let mut ps: PAINTSTRUCT = zeroed();
+            let hdc_screen = BeginPaint(hwnd, &mut ps);
+
+            let mut rect: RECT = zeroed();
+            GetClientRect(hwnd, &mut rect).ok();
+
+            // Double buffering to prevent flicker
+            let hdc_mem = CreateCompatibleDC(hdc_screen);
+            let hbm = CreateCompatibleBitmap(hdc_screen, rect.right, rect.bottom);
+            let hbm_old = SelectObject(hdc_mem, hbm);
+            let hdc = hdc_mem;
+
+            // Draw to memory DC
+            let bg_brush = CreateSolidBrush(COLORREF(theme::bg(COLOR_OVERLAY_BG)));
+            FillRect(hdc, &rect, bg_brush);
+            let _ = DeleteObject(bg_brush);
+
+            // Custom background image stretched over the solid color (not in high-contrast mode)
+            if !theme::is_high_contrast() {
+                if let Some((bitmap, width, height)) = background_image() {
+                    let hdc_image = CreateCompatibleDC(hdc);
+                    let old_bitmap = SelectObject(hdc_image, bitmap);
+                    SetStretchBltMode(hdc, HALFTONE);
+                    let _ = StretchBlt(hdc, 0, 0, rect.right, rect.bottom, hdc_image, 0, 0, width, height, SRCCOPY);
+                    SelectObject(hdc_image, old_bitmap);
+                    let _ = DeleteDC(hdc_image);
+                }
+            }
+
+            let screen_width = rect.right;
+            let screen_height = rect.bottom;
+
+            // Panel dimensions
+            let layout = panel_layout();
+            let panel_width = scale(sized(480));
+            let panel_height = scale(sized(layout.height));
+            let panel_x = (screen_width - panel_width) / 2;
+            let panel_y = (screen_height - panel_height) / 2;
+
+            // Panel with rounded corners and accent border
+            let panel_brush = CreateSolidBrush(COLORREF(theme::bg(COLOR_PANEL_BG)));
+            let old_brush = SelectObject(hdc, panel_brush);
+            let pen = CreatePen(PS_SOLID, theme::outline(2), COLORREF(theme::fg(COLOR_ACCENT)));
+            let old_pen = SelectObject(hdc, pen);
+
+            let _ = RoundRect(hdc, panel_x, panel_y, panel_x + panel_width, panel_y + panel_height, scale(sized(24)), scale(sized(24)));
+
+            SelectObject(hdc, old_brush);
+            SelectObject(hdc, old_pen);
+            let _ = DeleteObject(panel_brush);
+            let _ = DeleteObject(pen);
+
+            SetBkMode(hdc, TRANSPARENT);
+
+            // Preview hint above the panel
+            if PREVIEW_MODE.load(Ordering::SeqCst) {
+                let hint_font = CreateFontW(
+                    theme::font(sized(20)), 0, 0, 0,
+                    FW_BOLD.0 as i32,
+                    0, 0, 0, 0, 0, 0, 5, 0,
+                    w!("Segoe UI"),
+                );
+                let old_font = SelectObject(hdc, hint_font);
+                SetTextColor(hdc, COLORREF(theme::alert(0x0000CCFF))); // Amber
+                let mut hint_rect = RECT {
+                    left: 0,
+                    top: panel_y - scale(sized(50)),
+                    right: screen_width,
+                    bottom: panel_y - scale(sized(15)),
+                };
+                DrawTextW(
+                    hdc,
+                    &mut i18n::t("blocking.preview_hint").encode_utf16().collect::<Vec<_>>(),
+                    &mut hint_rect,
+                    DT_CENTER | DT_SINGLELINE,
+                );
+                SelectObject(hdc, old_font);
+                let _ = DeleteObject(hint_font);
+            }
+
+            // Large icon at top
+            let icon_font = CreateFontW(
+                scale(sized(64)), 0, 0, 0,
+                FW_NORMAL.0 as i32,
+                0, 0, 0, 0, 0, 0, 5, 0,
+                w!("Segoe UI Emoji"),
+            );
+            let old_font = SelectObject(hdc, icon_font);
+            SetTextColor(hdc, COLORREF(theme::alert(0x006060FF))); // Orange-red
+            let mut icon_rect = RECT {
+                left: panel_x,
+                top: panel_y + scale(sized(20)),
+                right: panel_x + panel_width,
+                bottom: panel_y + scale(sized(90)),
+            };
+            DrawTextW(hdc, &mut "⏰".encode_utf16().collect::<Vec<_>>(), &mut icon_rect, DT_CENTER | DT_SINGLELINE);
+
+            // Title
+            let title_font = CreateFontW(
+                scale(sized(36)), 0, 0, 0,
+                FW_BOLD.0 as i32,
+                0, 0, 0, 0, 0, 0, 5, 0,
+                w!("Segoe UI"),
+            );
+            SelectObject(hdc, title_font);
+            SetTextColor(hdc, COLORREF(theme::fg(COLOR_TEXT_WHITE)));
+
+            let mut title_rect = RECT {
+                left: panel_x,
+                top: panel_y + scale(sized(90)),
+                right: panel_x + panel_width,
+                bottom: panel_y + scale(sized(130)),
+            };
+            DrawTextW(
+                hdc,
+                &mut i18n::t("blocking.times_up").encode_utf16().collect::<Vec<_>>(),
+                &mut title_rect,
+                DT_CENTER | DT_SINGLELINE,
+            );
+
+            // Shutdown countdown
+            let shutdown_countdown = SHUTDOWN_COUNTDOWN_SECONDS.load(Ordering::SeqCst);
+            let time_font = CreateFontW(
+                theme::font(sized(28)), 0, 0, 0,
+                FW_BOLD.0 as i32,
+                0, 0, 0, 0, 0, 0, 5, 0,
+                w!("Segoe UI"),
+            );
+            SelectObject(hdc, time_font);
+
+            let lock_countdown = LOCK_COUNTDOWN_SECONDS.load(Ordering::SeqCst);
+            let time_str = if lock_countdown >= 0 {
+                SetTextColor(hdc, COLORREF(theme::alert(0x004040FF))); // Red
+                format!("{} {}", i18n::t("blocking.lock_in"), format_countdown(lock_countdown))
+            } else if shutdown_countdown >= 0 {
+                if shutdown_countdown <= 60 {
+                    SetTextColor(hdc, COLORREF(theme::alert(0x004040FF))); // Red
+                    format!("{} {}", i18n::t("blocking.shutdown_now"), format_countdown(shutdown_countdown))
+                } else {
+                    SetTextColor(hdc, COLORREF(theme::fg(COLOR_ACCENT)));
+                    format!("{} {}", i18n::t("blocking.shutdown_in"), format_countdown(shutdown_countdown))
+                }
+            } else {
+                SetTextColor(hdc, COLORREF(theme::fg(COLOR_ACCENT)));
+                i18n::t("blocking.time_exceeded").to_string()
+            };
+            let mut time_rect = RECT {
+                left: panel_x,
+                top: panel_y + scale(sized(135)),
+                right: panel_x + panel_width,
+                bottom: panel_y + scale(sized(170)),
+            };
+            DrawTextW(hdc, &mut time_str.encode_utf16().collect::<Vec<_>>(), &mut time_rect, DT_CENTER | DT_SINGLELINE);
+
+            // Message
+            let msg_font = CreateFontW(
+                theme::font(sized(16)), 0, 0, 0,
+                FW_NORMAL.0 as i32,
+                0, 0, 0, 0, 0, 0, 5, 0,
+                w!("Segoe UI"),
+            );
+            SelectObject(hdc, msg_font);
+            SetTextColor(hdc, COLORREF(theme::fg(COLOR_TEXT_LIGHT)));
+
+            let blocking_text_guard = BLOCKING_TEXT.lock().unwrap();
+            let message = blocking_text_guard.as_ref().map(|s| s.as_str()).unwrap_or(i18n::t("blocking.limit_reached"));
+            let mut msg_rect = RECT {
+                left: panel_x + scale(sized(20)),
+                top: panel_y + scale(sized(175)),
+                right: panel_x + panel_width - scale(sized(20)),
+                bottom: panel_y + scale(sized(200)),
+            };
+            DrawTextW(hdc, &mut message.encode_utf16().collect::<Vec<_>>(), &mut msg_rect, DT_CENTER | DT_SINGLELINE);
+            drop(blocking_text_guard);
+
+            // "Extend time:" label
+            let label_font = CreateFontW(
+                theme::font(sized(14)), 0, 0, 0,
+                FW_NORMAL.0 as i32,
+                0, 0, 0, 0, 0, 0, 5, 0,
+                w!("Segoe UI"),
+            );
+            SelectObject(hdc, label_font);
+            SetTextColor(hdc, COLORREF(theme::fg(0x00AAAAAA)));
+
+            if layout.show_extend {
+                let mut extend_label_rect = RECT {
+                    left: panel_x,
+                    top: panel_y + scale(sized(layout.extend_label_y)),
+                    right: panel_x + panel_width,
+                    bottom: panel_y + scale(sized(layout.extend_label_y + 18)),
+                };
+                DrawTextW(
+                    hdc,
+                    &mut i18n::t("blocking.extend_label").encode_utf16().collect::<Vec<_>>(),
+                    &mut extend_label_rect,
+                    DT_CENTER | DT_SINGLELINE,
+                );
+            }
+
+            // Separator line before passcode section
+            let sep_pen = CreatePen(PS_SOLID, 1, COLORREF(theme::fg(0x00444444)));
+            SelectObject(hdc, sep_pen);
+            let _ = windows::Win32::Graphics::Gdi::MoveToEx(hdc, panel_x + scale(sized(40)), panel_y + scale(sized(layout.separator_y)), None);
+            let _ = windows::Win32::Graphics::Gdi::LineTo(hdc, panel_x + panel_width - scale(sized(40)), panel_y + scale(sized(layout.separator_y)));
+            let _ = DeleteObject(sep_pen);
+
+            // "Enter passcode:" label
+            let mut passcode_label_rect = RECT {
+                left: panel_x,
+                top: panel_y + scale(sized(layout.passcode_label_y)),
+                right: panel_x + panel_width,
+                bottom: panel_y + scale(sized(layout.passcode_label_y + 20)),
+            };
+            DrawTextW(
+                hdc,
+                &mut i18n::t("blocking.passcode_label").encode_utf16().collect::<Vec<_>>(),
+                &mut passcode_label_rect,
+                DT_CENTER | DT_SINGLELINE,
+            );
+
+            // Message at the bottom: the lockout countdown, then the minimum block duration,
+            // then a wrong passcode
+            let lockout = passcode_lockout_remaining();
+            let unlock_wait = if PREVIEW_MODE.load(Ordering::SeqCst) { 0 } else { unlock_wait_remaining() };
+            LOCKOUT_SHOWN.store(lockout > 0 || unlock_wait > 0, Ordering::SeqCst);
+            if lockout > 0 || unlock_wait > 0 || PASSCODE_ERROR.load(Ordering::SeqCst) {
+                SetTextColor(hdc, COLORREF(theme::alert(COLOR_ERROR)));
+                let error_font = CreateFontW(
+                    theme::font(sized(15)), 0, 0, 0,
+                    FW_BOLD.0 as i32,
+                    0, 0, 0, 0, 0, 0, 5, 0,
+                    w!("Segoe UI"),
+                );
+                SelectObject(hdc, error_font);
+                let mut error_rect = RECT {
+                    left: panel_x,
+                    top: panel_y + panel_height - scale(sized(40)),
+                    right: panel_x + panel_width,
+                    bottom: panel_y + panel_height - scale(sized(15)),
+                };
+                let error_text = if lockout > 0 {
+                    i18n::t("blocking.locked_out").replace("{}", &lockout.to_string())
+                } else if unlock_wait > 0 {
+                    i18n::t("blocking.unlock_wait").replace("{}", &unlock_wait.to_string())
+                } else {
+                    i18n::t("blocking.incorrect").to_string()
+                };
+                DrawTextW(
+                    hdc,
+                    &mut error_text.encode_utf16().collect::<Vec<_>>(),
+                    &mut error_rect,
+                    DT_CENTER | DT_SINGLELINE,
+                );
+                let _ = DeleteObject(error_font);
+            }
+
+            // Cleanup fonts
+            SelectObject(hdc, old_font);
+            let _ = DeleteObject(icon_font);
+            let _ = DeleteObject(title_font);
+            let _ = DeleteObject(time_font);
+            let _ = DeleteObject(msg_font);
+            let _ = DeleteObject(label_font);
+
+            // Blit from memory DC to screen (no flicker)
+            let _ = BitBlt(hdc_screen, 0, 0, rect.right, rect.bottom, hdc_mem, 0, 0, SRCCOPY);
+
+            // Cleanup double buffer
+            SelectObject(hdc_mem, hbm_old);
+            let _ = DeleteObject(hbm);
+            let _ = DeleteDC(hdc_mem);
+
+            let _ = EndPaint(hwnd, &ps);
+            LRESULT(0)
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xFFFF) as i32;
+            let notification = ((wparam.0 >> 16) & 0xFFFF) as u32;
+
+            // Esc arrives as IDCANCEL from the dialog manager; it only closes the preview
+            if id == IDCANCEL.0 {
+                if PREVIEW_MODE.load(Ordering::SeqCst) {
+                    hide_blocking_overlay_preview(hwnd);
+                }
+                return LRESULT(0);
+            }
+
+            // Buttons are inert in preview mode
+            if PREVIEW_MODE.load(Ordering::SeqCst) {
+                return LRESULT(0);
+            }
+
+            if notification == BN_CLICKED {
+                match id {
+                    ID_UNLOCK_BUTTON => {
+                        match check_blocking_entry(true) {
+                            Some(entry) => {
+                                unlock();
+                                // A one-time code is used up only once it has unlocked
+                                if entry == Entry::UnlockCode {
+                                    clear_unlock_code();
+                                }
+                            }
+                            None => reject_passcode(hwnd),
+                        }
+                    }
+                    ID_EXTEND_15 | ID_EXTEND_30 | ID_EXTEND_60 => {
+                        // Require passcode for extension
+                        if check_extend_passcode() {
+                            let minutes = match id {
+                                ID_EXTEND_15 => 15,
+                                ID_EXTEND_30 => 30,
+                                ID_EXTEND_60 => 60,
+                                _ => 0,
+                            };
+                            PASSCODE_ERROR.store(false, Ordering::SeqCst);
+
+                            // Clear the passcode field
+                            let edit_ptr = BLOCKING_EDIT_HWND.load(Ordering::SeqCst);
+                            if !edit_ptr.is_null() {
+                                SetWindowTextW(HWND(edit_ptr), w!("")).ok();
+                            }
+
+                            match extend_time_checked(minutes, ExtendSource::BlockingOverlay) {
+                                // Hide overlay and let the user continue (after the minimum block duration)
+                                Ok(_) => release_block(),
+                                Err(e) => show_extend_error(hwnd, e),
+                            }
+                        } else {
+                            reject_passcode(hwnd);
+                        }
+                    }
+                    ID_EXTEND_CUSTOM => {
+                        if check_extend_passcode() {
+                            PASSCODE_ERROR.store(false, Ordering::SeqCst);
+                            let edit_ptr = BLOCKING_EDIT_HWND.load(Ordering::SeqCst);
+                            if !edit_ptr.is_null() {
+                                SetWindowTextW(HWND(edit_ptr), w!("")).ok();
+                            }
+
+                            if extend_custom(hwnd, ExtendSource::BlockingOverlay) {
+                                release_block();
+                            }
+                        } else {
+                            reject_passcode(hwnd);
+                        }
+                    }
+                    // No passcode: limited per day instead, and the parent is told. Unlike the
+                    // extend buttons it does nothing during the minimum block duration.
+                    ID_SELF_EXTEND if unlock_wait_remaining() == 0 => match self_extend() {
+                        Ok(_) => hide_blocking_overlay(),
+                        Err(e) => show_extend_error(hwnd, e),
+                    },
+                    ID_SHUTDOWN_BUTTON => {
+                        // Show confirmation dialog
+                        let confirm_msg = i18n::wide("blocking.confirm_shutdown");
+                        let confirm_title = i18n::wide("blocking.confirm_title");
+                        let result = MessageBoxW(
+                            hwnd,
+                            PCWSTR(confirm_msg.as_ptr()),
+                            PCWSTR(confirm_title.as_ptr()),
+                            MB_YESNO | MB_ICONQUESTION | MB_DEFBUTTON2,
+                        );
+                        if result == IDYES {
+                            // Initiate system shutdown with proper privilege
+                            initiate_shutdown();
+                        }
+                    }
+                    ID_LOCK_BUTTON => {
+                        if check_blocking_passcode() {
+                            PASSCODE_ERROR.store(false, Ordering::SeqCst);
+                            let edit_ptr = BLOCKING_EDIT_HWND.load(Ordering::SeqCst);
+                            if !edit_ptr.is_null() {
+                                SetWindowTextW(HWND(edit_ptr), w!("")).ok();
+                            }
+                            // The overlay stays up behind the lock screen
+                            let _ = LockWorkStation();
+                        } else {
+                            reject_passcode(hwnd);
+                        }
+                    }
+                    ID_LOGOFF_BUTTON => {
+                        if check_blocking_passcode() {
+                            let _ = ExitWindowsEx(EWX_LOGOFF, SHUTDOWN_REASON(0));
+                        } else {
+                            reject_passcode(hwnd);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            LRESULT(0)
+        }
+        WM_TIMER => {
+            match wparam.0 {
+                TIMER_REASSERT_TOPMOST => {
+                    SetWindowPos(
+                        hwnd,
+                        HWND_TOPMOST,
+                        0, 0, 0, 0,
+                        SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+                    ).ok();
+                    reassert_secondary_overlays();
+                    if crate::database::get_blocking_focus_mode() >= 1 {
+                        reclaim_focus(hwnd);
+                    }
+                }
+                TIMER_COUNTDOWN => {
+                    // The mini overlay timer is stopped while blocked, so watch for a new day here
+                    if crate::mini_overlay::check_day_rollover() {
+                        return LRESULT(0);
+                    }
+                    if crate::curfew::lift_if_over() {
+                        return LRESULT(0);
+                    }
+
+                    // Decrement shutdown countdown
+                    if tick_shutdown_countdown() {
+                        // Trigger shutdown with proper privilege
+                        initiate_shutdown();
+                    }
+                    if tick_countdown(&LOCK_COUNTDOWN_SECONDS) {
+                        let _ = LockWorkStation();
+                    }
+
+                    // Only invalidate the countdown region, not the entire window
+                    // This prevents child controls (buttons, edit) from flickering
+                    let mut client_rect: RECT = zeroed();
+                    GetClientRect(hwnd, &mut client_rect).ok();
+                    let screen_width = client_rect.right;
+                    let screen_height = client_rect.bottom;
+                    let panel_width = scale(sized(480));
+                    let panel_height = scale(sized(panel_layout().height));
+                    let panel_x = (screen_width - panel_width) / 2;
+                    let panel_y = (screen_height - panel_height) / 2;
+
+                    // Countdown region (from panel_y + 90 to 200 covers title + time + message)
+                    let countdown_rect = RECT {
+                        left: panel_x,
+                        top: panel_y + scale(sized(90)),
+                        right: panel_x + panel_width,
+                        bottom: panel_y + scale(sized(200)),
+                    };
+                    let _ = InvalidateRect(hwnd, Some(&countdown_rect), false);
+
+                    // Time granted during the minimum block duration lifts the block once it's over
+                    if RELEASE_PENDING.load(Ordering::SeqCst) && unlock_wait_remaining() == 0 {
+                        RELEASE_PENDING.store(false, Ordering::SeqCst);
+                        if !crate::curfew::is_curfew_blocking() {
+                            hide_blocking_overlay();
+                            return LRESULT(0);
+                        }
+                    }
+
+                    // Count the lockout message down; once it is over, the field is open again
+                    if LOCKOUT_SHOWN.load(Ordering::SeqCst) {
+                        if passcode_lockout_remaining() == 0 && unlock_wait_remaining() == 0 {
+                            PASSCODE_ERROR.store(false, Ordering::SeqCst);
+                        }
+                        let error_rect = RECT {
+                            left: panel_x,
+                            top: panel_y + panel_height - scale(sized(40)),
+                            right: panel_x + panel_width,
+                            bottom: panel_y + panel_height - scale(sized(15)),
+                        };
+                        let _ = InvalidateRect(hwnd, Some(&error_rect), false);
+                    }
+                }
+                _ => {}
+            }
+            LRESULT(0)
+        }
+        WM_ERASEBKGND => {
+            // Return non-zero to indicate we handle background erasing (prevents flickering)
+            LRESULT(1)
+        }
+        WM_CLOSE => {
+            LRESULT(0)
+        }
+        DM_GETDEFID => {
+            // Enter anywhere except on another button presses Unlock
+            LRESULT(((DC_HASDEFID as isize) << 16) | ID_UNLOCK_BUTTON as isize)
+        }
+        WM_DESTROY => {
+            free_background_image();
+            LRESULT(0)
+        }
+        WM_ACTIVATE => {
+            let edit_ptr = BLOCKING_EDIT_HWND.load(Ordering::SeqCst);
+            if PREVIEW_MODE.load(Ordering::SeqCst) {
+                let _ = SetFocus(hwnd);
+            } else if !edit_ptr.is_null() {
+                let _ = SetFocus(HWND(edit_ptr));
+            }
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+pub unsafe fn register_blocking_class(hinstance: windows::Win32::Foundation::HMODULE) {
+    let blocking_class_name = w!("ScreenTimeBlockingClass");
+    let blocking_wnd_class = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(blocking_overlay_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: blocking_class_name,
+        hbrBackground: CreateSolidBrush(COLORREF(COLOR_OVERLAY_BG)),
+        hCursor: LoadCursorW(None, IDC_ARROW).ok().unwrap_or_default(),
+        ..zeroed()
+    };
+
+    if RegisterClassW(&blocking_wnd_class) == 0 {
+        panic!("Failed to register blocking overlay window class");
+    }
+
+    // Register secondary overlay class (simpler, no controls)
+    let secondary_class_name = w!("ScreenTimeSecondaryBlockingClass");
+    let secondary_wnd_class = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(secondary_overlay_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: secondary_class_name,
+        hbrBackground: CreateSolidBrush(COLORREF(COLOR_OVERLAY_BG)),
+        hCursor: LoadCursorW(None, IDC_ARROW).ok().unwrap_or_default(),
+        ..zeroed()
+    };
+
+    if RegisterClassW(&secondary_wnd_class) == 0 {
+        panic!("Failed to register secondary blocking overlay window class");
+    }
+}
+
+/// Window procedure for secondary monitor overlays (simple blank screen)
+pub unsafe extern "system" fn secondary_overlay_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_PAINT => {
+            let mut ps: PAINTSTRUCT = zeroed();
+            let hdc = BeginPaint(hwnd, &mut ps);
+
+            let mut rect: RECT = zeroed();
+            GetClientRect(hwnd, &mut rect).ok();
+
+            // Fill with dark background
+            let bg_brush = CreateSolidBrush(COLORREF(theme::bg(COLOR_OVERLAY_BG)));
+            FillRect(hdc, &rect, bg_brush);
+            let _ = DeleteObject(bg_brush);
+
+            // Draw the message in the center (DPI scaled, ClearType quality = 5);
+            // "Screen Locked" if none was set (e.g. painted before the first block)
+            let text = SECONDARY_TEXT
+                .lock()
+                .unwrap()
+                .clone()
+                .unwrap_or_else(|| i18n::t("blocking.screen_locked").to_string());
+            let font = CreateFontW(
+                theme::font(sized(48)), 0, 0, 0,
+                FW_BOLD.0 as i32,
+                0, 0, 0, 0, 0, 0, 5, 0,
+                w!("Segoe UI"),
+            );
+            let old_font = SelectObject(hdc, font);
+            SetTextColor(hdc, COLORREF(theme::fg(COLOR_TEXT_LIGHT)));
+            SetBkMode(hdc, TRANSPARENT);
+
+            // Wrap a long message and center the whole block vertically
+            let mut wide_text: Vec<u16> = text.encode_utf16().collect();
+            let margin = scale(40);
+            let mut text_rect = RECT { left: rect.left + margin, top: 0, right: rect.right - margin, bottom: 0 };
+            DrawTextW(hdc, &mut wide_text, &mut text_rect, DT_CALCRECT | DT_CENTER | DT_WORDBREAK);
+            let text_height = text_rect.bottom - text_rect.top;
+            text_rect.left = rect.left + margin;
+            text_rect.right = rect.right - margin;
+            text_rect.top = rect.top + (rect.bottom - rect.top - text_height) / 2;
+            text_rect.bottom = text_rect.top + text_height;
+            DrawTextW(hdc, &mut wide_text, &mut text_rect, DT_CENTER | DT_WORDBREAK);
+
+            SelectObject(hdc, old_font);
+            let _ = DeleteObject(font);
+
+            let _ = EndPaint(hwnd, &ps);
+            LRESULT(0)
+        }
+        WM_ERASEBKGND => LRESULT(1),
+        WM_CLOSE => LRESULT(0), // Prevent closing
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+/// Callback for EnumDisplayMonitors to collect monitor information
+unsafe extern "system" fn monitor_enum_callback(
+    _hmonitor: HMONITOR,
+    _hdc: HDC,
+    lprect: *mut RECT,
+    lparam: LPARAM,
+) -> BOOL {
+    let monitors = &mut *(lparam.0 as *mut Vec<MonitorInfo>);
+
+    if let Some(rect) = lprect.as_ref() {
+        // Check if this is the primary monitor (origin at 0,0)
+        let is_primary = rect.left == 0 && rect.top == 0;
+
+        monitors.push(MonitorInfo {
+            rect: *rect,
+            is_primary,
+        });
+    }
+
+    BOOL::from(true) // Continue enumeration
+}
+
+/// Enumerate all monitors and return their information
+unsafe fn enumerate_monitors() -> Vec<MonitorInfo> {
+    let mut monitors: Vec<MonitorInfo> = Vec::new();
+
+    let _ = EnumDisplayMonitors(
+        None,
+        None,
+        Some(monitor_enum_callback),
+        LPARAM(&mut monitors as *mut Vec<MonitorInfo> as isize),
+    );
+
+    monitors
+}
+
+/// Create secondary overlay windows for all non-primary monitors
+pub unsafe fn create_secondary_overlays(hinstance: windows::Win32::Foundation::HMODULE) {
+    let monitors = enumerate_monitors();
+    let class_name = w!("ScreenTimeSecondaryBlockingClass");
+
+    let mut secondary_hwnds = SECONDARY_OVERLAY_HWNDS.lock().unwrap();
+    secondary_hwnds.clear();
+
+    for monitor in monitors {
+        // Skip the primary monitor (main blocking overlay handles it)
+        if monitor.is_primary {
+            continue;
+        }
+
+        // The enumerated rect is the full monitor area (not the work area), so the
+        // overlay also covers a taskbar placed on this monitor
+        let width = monitor.rect.right - monitor.rect.left;
+        let height = monitor.rect.bottom - monitor.rect.top;
+
+        let locked_title = i18n::wide("blocking.screen_locked");
+        let hwnd = CreateWindowExW(
+            WS_EX_TOPMOST | WS_EX_TOOLWINDOW,
+            class_name,
+            PCWSTR(locked_title.as_ptr()),
+            WS_POPUP,
+            monitor.rect.left,
+            monitor.rect.top,
+            width,
+            height,
+            None,
+            None,
+            hinstance,
+            None,
+        );
+
+        if let Ok(h) = hwnd {
+            secondary_hwnds.push(h.0 as isize);
+        }
+    }
+}
+
+/// Show all secondary monitor overlays
+unsafe fn show_secondary_overlays() {
+    let secondary_hwnds = SECONDARY_OVERLAY_HWNDS.lock().unwrap();
+
+    for &hwnd_ptr in secondary_hwnds.iter() {
+        let hwnd = HWND(hwnd_ptr as *mut std::ffi::c_void);
+        SetWindowPos(
+            hwnd,
+            HWND_TOPMOST,
+            0, 0, 0, 0,
+            SWP_SHOWWINDOW | SWP_NOMOVE | SWP_NOSIZE,
+        ).ok();
+        // The message may differ from the last block
+        let _ = InvalidateRect(hwnd, None, false);
+        let _ = ShowWindow(hwnd, SW_SHOW);
+    }
+}
+
+/// Bring visible secondary overlays back to the top of the topmost band.
+/// Driven by the primary overlay's reassert timer so the taskbar or other
+/// topmost windows on secondary monitors cannot poke through.
+unsafe fn reassert_secondary_overlays() {
+    let secondary_hwnds = SECONDARY_OVERLAY_HWNDS.lock().unwrap();
+
+    for &hwnd_ptr in secondary_hwnds.iter() {
+        let hwnd = HWND(hwnd_ptr as *mut std::ffi::c_void);
+        if IsWindowVisible(hwnd).as_bool() {
+            SetWindowPos(
+                hwnd,
+                HWND_TOPMOST,
+                0, 0, 0, 0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+            ).ok();
+        }
+    }
+}
+
+/// Hide all secondary monitor overlays
+unsafe fn hide_secondary_overlays() {
+    let secondary_hwnds = SECONDARY_OVERLAY_HWNDS.lock().unwrap();
+
+    for &hwnd_ptr in secondary_hwnds.iter() {
+        let hwnd = HWND(hwnd_ptr as *mut std::ffi::c_void);
+        let _ = ShowWindow(hwnd, SW_HIDE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn startup_restores_todays_block() {
+        let today = "2026-10-15";
+        // A lock from Telegram survives a restart
+        assert_eq!(startup_block(Some(("telegram", today)), today, 600, false), Some("telegram"));
+        // Killed near zero: the saved time is ignored, the limit block comes back
+        assert_eq!(startup_block(Some(("limit", today)), today, 20, false), Some("limit"));
+        // The curfew is re-checked on its own
+        assert_eq!(startup_block(Some(("curfew", today)), today, 600, false), None);
+        assert_eq!(startup_block(None, today, 600, false), None);
+    }
+
+    #[test]
+    fn startup_block_from_an_earlier_day_is_over() {
+        let today = "2026-10-15";
+        assert_eq!(startup_block(Some(("tray", "2026-10-14")), today, 7200, false), None);
+        assert_eq!(startup_block(Some(("limit", "2026-10-14")), today, 7200, false), None);
+        // Unless today's time is used up too (e.g. a day without screen time)
+        assert_eq!(startup_block(Some(("limit", "2026-10-14")), today, 0, false), Some("limit"));
+        assert_eq!(startup_block(None, today, 0, false), Some("limit"));
+    }
+
+    #[test]
+    fn focus_mode_lifts_only_the_time_limit_block() {
+        let today = "2026-10-15";
+        assert_eq!(startup_block(Some(("limit", today)), today, 0, true), None);
+        assert_eq!(startup_block(None, today, 0, true), None);
+        assert_eq!(startup_block(Some(("tray", today)), today, 0, true), Some("tray"));
+    }
+
+    #[test]
+    fn unlocked_block_stays_gone_after_restart() {
+        crate::database::with_test_database(|restart| {
+            crate::database::save_active_block("tray");
+            record_unlock(600);
+
+            restart();
+            let today = crate::database::get_today_date();
+            let saved = crate::database::get_active_block();
+            let saved = saved.as_ref().map(|(reason, date)| (reason.as_str(), date.as_str()));
+            assert_eq!(startup_block(saved, &today, 600, false), None);
+        });
+    }
+
+    #[test]
+    fn each_block_waits_its_own_minimum_duration() {
+        use crate::clock::{with_clock, MockClock};
+        let at = |timestamp| MockClock { timestamp, ..MockClock::at(2026, 10, 15, 4, 18, 0) };
+
+        crate::database::with_test_database(|_| {
+            crate::database::set_setting("min_block_duration_seconds", "600");
+
+            // First block, with time granted remotely during its minimum duration
+            with_clock(at(1_800_000_000), record_block_start);
+            RELEASE_PENDING.store(true, Ordering::SeqCst);
+            with_clock(at(1_800_000_900), || record_unlock(600));
+            assert!(!RELEASE_PENDING.load(Ordering::SeqCst));
+
+            // The second block starts its minimum duration over
+            with_clock(at(1_800_001_000), || {
+                record_block_start();
+                assert_eq!(unlock_wait_remaining(), 600);
+            });
+        });
+    }
+
+    #[test]
+    fn unlock_code_works_until_it_is_used_up_or_expires() {
+        *UNLOCK_CODE.lock().unwrap() = Some(("123456".to_string(), Instant::now()));
+        assert!(!unlock_code_matches("654321"));
+        // Checking doesn't use it up; unlocking does
+        assert!(unlock_code_matches("123456"));
+        assert!(unlock_code_matches("123456"));
+        clear_unlock_code();
+        assert!(!unlock_code_matches("123456"));
+
+        let issued = Instant::now().checked_sub(UNLOCK_CODE_VALIDITY).unwrap();
+        *UNLOCK_CODE.lock().unwrap() = Some(("123456".to_string(), issued));
+        assert!(!unlock_code_matches("123456"));
+        assert!(UNLOCK_CODE.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn unlocking_ignores_the_extension_limits() {
+        crate::database::with_test_database(|_| {
+            crate::database::set_setting("max_extensions_per_day", "3");
+            crate::database::save_extended_today(240);
+            crate::database::save_extension_count_today(3);
+            REMAINING_SECONDS.store(0, Ordering::SeqCst);
+
+            assert_eq!(grant_unlock_time(), UNLOCK_MINUTES * 60);
+            assert_eq!(crate::database::load_remaining_time(), Some(UNLOCK_MINUTES * 60));
+            // Not an extension: nothing is counted or logged
+            assert_eq!(crate::database::get_extended_today(), 240);
+            assert_eq!(crate::database::get_extension_count_today(), 3);
+            assert!(crate::database::get_extension_log_today().is_empty());
+        });
+    }
+
+    #[test]
+    fn extending_used_up_time_starts_from_zero() {
+        assert_eq!(extended_remaining(0, 15), 900);
+        assert_eq!(extended_remaining(-1, 15), 900);
+        assert_eq!(extended_remaining(-300, 1), 60);
+        assert_eq!(extended_remaining(30, 1), 90);
+        assert_eq!(extended_remaining(i32::MAX - 10, 60), i32::MAX);
+    }
+
+    #[test]
+    fn extensions_respect_the_maximum_and_daily_cap() {
+        let config = crate::database::ExtensionConfig { max_minutes: 120, daily_cap_minutes: 240, max_per_day: 0 };
+        assert_eq!(check_extension(90, &config, 0, 0), Ok(()));
+        assert_eq!(check_extension(0, &config, 0, 0), Err(ExtendError::NotPositive));
+        assert_eq!(check_extension(121, &config, 0, 0), Err(ExtendError::ExceedsMax { max_minutes: 120 }));
+        assert_eq!(check_extension(60, &config, 200, 0), Err(ExtendError::DailyCapReached { minutes_left: 40 }));
+        let unlimited = crate::database::ExtensionConfig { max_minutes: 0, daily_cap_minutes: 0, max_per_day: 0 };
+        assert_eq!(check_extension(600, &unlimited, 1000, 50), Ok(()));
+    }
+
+    #[test]
+    fn extensions_stop_after_the_number_per_day() {
+        let config = crate::database::ExtensionConfig { max_minutes: 0, daily_cap_minutes: 0, max_per_day: 3 };
+        assert_eq!(check_extension(15, &config, 30, 2), Ok(()));
+        assert_eq!(check_extension(15, &config, 45, 3), Err(ExtendError::CountReached));
+        assert_eq!(check_extension(15, &config, 45, 4), Err(ExtendError::CountReached));
+    }
+
+    #[test]
+    fn self_service_extension_is_off_by_default_and_limited_per_day() {
+        assert_eq!(self_extends_left(0, 0), 0);
+        assert_eq!(self_extends_left(1, 0), 1);
+        assert_eq!(self_extends_left(1, 1), 0);
+        assert_eq!(self_extends_left(2, 5), 0);
+    }
+
+    #[test]
+    fn self_service_extension_leaves_the_parent_limits_alone() {
+        crate::database::with_test_database(|_| {
+            crate::database::set_setting("self_extend_per_day", "1");
+            crate::database::save_extended_today(60);
+            crate::database::save_extension_count_today(2);
+            REMAINING_SECONDS.store(0, Ordering::SeqCst);
+
+            assert_eq!(self_extend(), Ok(5 * 60));
+            assert_eq!(crate::database::get_extended_today(), 60);
+            assert_eq!(crate::database::get_extension_count_today(), 2);
+            assert_eq!(self_extend(), Err(ExtendError::CountReached));
+        });
+    }
+
+    #[test]
+    fn self_service_source_round_trips_through_the_log() {
+        assert_eq!(ExtendSource::parse("self"), Some(ExtendSource::SelfService));
+    }
+
+    #[test]
+    fn extension_limit_is_the_smaller_of_maximum_and_cap_left() {
+        let config = crate::database::ExtensionConfig { max_minutes: 120, daily_cap_minutes: 240, max_per_day: 0 };
+        assert_eq!(extension_limit(&config, 0), Some(120));
+        assert_eq!(extension_limit(&config, 200), Some(40));
+        assert_eq!(extension_limit(&config, 300), Some(0));
+        let no_cap = crate::database::ExtensionConfig { max_minutes: 0, daily_cap_minutes: 240, max_per_day: 0 };
+        assert_eq!(extension_limit(&no_cap, 30), Some(210));
+        let unlimited = crate::database::ExtensionConfig { max_minutes: 0, daily_cap_minutes: 0, max_per_day: 0 };
+        assert_eq!(extension_limit(&unlimited, 30), None);
+    }
+
+    #[test]
+    fn custom_extension_is_whole_minutes_or_a_time_of_day() {
+        assert_eq!(parse_custom_extension(" 25 "), Ok(CustomExtension::Minutes(25)));
+        assert_eq!(parse_custom_extension("20:00"), Ok(CustomExtension::Until { hour: 20, minute: 0, tomorrow: false }));
+        assert_eq!(parse_custom_extension("0:30 Tomorrow"), Ok(CustomExtension::Until { hour: 0, minute: 30, tomorrow: true }));
+        assert_eq!(parse_custom_extension("0:30 morgen"), Ok(CustomExtension::Until { hour: 0, minute: 30, tomorrow: true }));
+        assert_eq!(parse_custom_extension("0:30 later"), Err(ExtendError::NotPositive));
+        assert_eq!(parse_custom_extension("0"), Err(ExtendError::NotPositive));
+        assert_eq!(parse_custom_extension(""), Err(ExtendError::NotPositive));
+        assert_eq!(parse_custom_extension("-5"), Err(ExtendError::NotPositive));
+        assert_eq!(parse_custom_extension("1.5"), Err(ExtendError::NotPositive));
+        assert_eq!(parse_custom_extension("24:00"), Err(ExtendError::NotPositive));
+    }
+
+    #[test]
+    fn time_until_rounds_up_to_whole_minutes() {
+        let at = |h: i32, m: i32| h * 3600 + m * 60;
+        // 18:30 with 10 minutes left, until 20:00: 80 more minutes, 90 minutes in total
+        assert_eq!(time_until(at(18, 30), at(20, 0), 600, false), Ok((80, 0)));
+        // 20 seconds into the minute: 90 whole minutes, 20 seconds too many
+        assert_eq!(time_until(at(18, 30) + 20, at(20, 0), 0, false), Ok((90, 20)));
+        assert_eq!(time_until(at(18, 30), at(20, 0), 7200, false), Err(ExtendError::AlreadyUntil));
+    }
+
+    #[test]
+    fn time_until_a_passed_time_is_tomorrow_only_when_asked() {
+        let at = |h: i32, m: i32| h * 3600 + m * 60;
+        assert_eq!(time_until(at(20, 30), at(20, 0), 0, false), Err(ExtendError::TimeInPast));
+        assert_eq!(time_until(at(20, 0), at(20, 0), 0, false), Err(ExtendError::TimeInPast));
+        // 23:30 until 0:30 tomorrow: one hour, 10 minutes of it already left
+        assert_eq!(time_until(at(23, 30), at(0, 30), 600, true), Ok((50, 0)));
+        // A time still ahead today isn't pushed to tomorrow
+        assert_eq!(time_until(at(18, 30), at(20, 0), 0, true), Ok((90, 0)));
+    }
+
+    #[test]
+    fn extensions_are_summed_per_source() {
+        let entries: Vec<String> = ["08:00:00:+15m:tray", "09:30:12:+30m:telegram", "10:00:00:+15m:tray", "garbage"]
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            extension_minutes_by_source(&entries),
+            vec![("tray".to_string(), 30), ("telegram".to_string(), 30)]
+        );
+    }
+
+    #[test]
+    fn lockout_starts_at_the_limit_and_doubles() {
+        assert_eq!(lockout_seconds(4, 5, 30), 0);
+        assert_eq!(lockout_seconds(5, 5, 30), 30);
+        assert_eq!(lockout_seconds(6, 5, 30), 60);
+        assert_eq!(lockout_seconds(8, 5, 30), 240);
+        assert_eq!(lockout_seconds(40, 5, 30), MAX_LOCKOUT_SECONDS);
+        assert_eq!(lockout_seconds(100, 0, 30), 0);
+    }
+
+    #[test]
+    fn minimum_block_duration_counts_from_the_block_start() {
+        assert_eq!(unlock_wait(1_000, 60, 1_000), 60);
+        assert_eq!(unlock_wait(1_000, 60, 1_045), 15);
+        assert_eq!(unlock_wait(1_000, 60, 1_060), 0);
+        // Off, or no block running
+        assert_eq!(unlock_wait(1_000, 0, 1_000), 0);
+        assert_eq!(unlock_wait(0, 60, 1_000), 0);
+        // A clock set back doesn't stretch the wait past the setting
+        assert_eq!(unlock_wait(1_000, 60, 500), 60);
+    }
+
+    #[test]
+    fn extension_during_shutdown_countdown_prevents_the_shutdown() {
+        // The countdown runs out on the tick after it reaches zero
+        SHUTDOWN_COUNTDOWN_SECONDS.store(1, Ordering::SeqCst);
+        assert!(!tick_shutdown_countdown());
+        assert!(tick_shutdown_countdown());
+        assert!(!tick_shutdown_countdown());
+
+        // Time granted in the last seconds cancels it for good
+        SHUTDOWN_COUNTDOWN_SECONDS.store(1, Ordering::SeqCst);
+        assert!(!tick_shutdown_countdown());
+        cancel_pending_shutdown();
+        for _ in 0..3 {
+            assert!(!tick_shutdown_countdown());
+        }
+        assert_eq!(SHUTDOWN_COUNTDOWN_SECONDS.load(Ordering::SeqCst), -1);
+    }
+
+    #[test]
+    fn simulated_block_never_counts_down_to_a_shutdown() {
+        assert_eq!(block_shutdown_countdown(300, true, false), 300);
+        assert_eq!(block_shutdown_countdown(300, true, true), -1);
+        assert_eq!(block_shutdown_countdown(300, false, false), -1);
+        assert_eq!(block_shutdown_countdown(0, true, false), -1);
+    }
+
+    #[test]
+    fn time_granted_during_the_lock_warning_prevents_the_lock() {
+        LOCK_COUNTDOWN_SECONDS.store(2, Ordering::SeqCst);
+        assert!(!tick_countdown(&LOCK_COUNTDOWN_SECONDS));
+        cancel_pending_shutdown();
+        for _ in 0..3 {
+            assert!(!tick_countdown(&LOCK_COUNTDOWN_SECONDS));
+        }
+        assert_eq!(LOCK_COUNTDOWN_SECONDS.load(Ordering::SeqCst), -1);
+    }
+}
@@ -0,0 +1,63 @@
+//! Cleanup module
+//! Removes what the app leaves on the computer: the autostart entry, the session registry
+//! key and the hidden data directory (database, logs, moved-aside corrupt databases).
+//! The app installs no service or scheduled task, so there's nothing else to unregister.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use windows::Win32::{
+    Foundation::{ERROR_FILE_NOT_FOUND, WIN32_ERROR},
+    System::Registry::{RegDeleteKeyValueW, RegDeleteKeyW, HKEY_CURRENT_USER},
+};
+
+use crate::database;
+
+/// Errors while uninstalling
+#[derive(Debug)]
+pub enum CleanupError {
+    /// A registry entry exists but couldn't be removed
+    Registry(windows::core::Error),
+    /// The data directory couldn't be deleted (e.g. a file in it is open elsewhere)
+    Io(PathBuf, std::io::Error),
+}
+
+impl fmt::Display for CleanupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CleanupError::Registry(e) => write!(f, "registry error: {}", e.message()),
+            CleanupError::Io(path, e) => write!(f, "could not delete {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for CleanupError {}
+
+/// Treat an entry that is already gone as removed
+fn removed(result: WIN32_ERROR) -> Result<(), CleanupError> {
+    if result == ERROR_FILE_NOT_FOUND {
+        Ok(())
+    } else {
+        result.ok().map_err(CleanupError::Registry)
+    }
+}
+
+/// Remove the autostart entry, the session key and the data directory.
+/// Closes the database and the log file first; the app has to exit afterwards.
+pub fn uninstall() -> Result<(), CleanupError> {
+    unsafe {
+        removed(RegDeleteKeyValueW(HKEY_CURRENT_USER, crate::wizard::RUN_KEY, crate::wizard::RUN_VALUE))?;
+        removed(RegDeleteKeyW(HKEY_CURRENT_USER, crate::security::BOOT_KEY))?;
+    }
+
+    let data_dir = crate::logging::log_dir();
+    log::info!("Uninstalling: deleting {}", data_dir.display());
+    crate::logging::close();
+    database::close_database();
+
+    match std::fs::remove_dir_all(&data_dir) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(CleanupError::Io(data_dir, e)),
+    }
+}
@@ -0,0 +1,144 @@
+//! Clock module
+//! Source of the current local time and Unix timestamp; tests swap in a fixed clock
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicI64, Ordering};
+use windows::Win32::{
+    Foundation::{FILETIME, SYSTEMTIME},
+    System::SystemInformation::GetLocalTime,
+    System::Time::{FileTimeToSystemTime, SystemTimeToFileTime},
+};
+
+/// Seconds the system clock was moved away from the real time (as measured by the
+/// monotonic clock). The system clock is corrected by this, so setting the clock
+/// forward or back doesn't start a new day or get around the allowed hours.
+static OFFSET: AtomicI64 = AtomicI64::new(0);
+
+/// Record that the system clock was moved by `seconds` (negative = set back)
+pub fn add_offset(seconds: i64) {
+    OFFSET.fetch_add(seconds, Ordering::SeqCst);
+}
+
+/// Seconds the system clock is currently off from the real time
+pub fn offset() -> i64 {
+    OFFSET.load(Ordering::SeqCst)
+}
+
+/// `st` moved by `seconds` (the weekday is recalculated)
+fn shift_local_time(st: SYSTEMTIME, seconds: i64) -> SYSTEMTIME {
+    // FILETIME ticks are 100ns
+    const TICKS_PER_SECOND: i64 = 10_000_000;
+
+    unsafe {
+        let mut ft = FILETIME::default();
+        if SystemTimeToFileTime(&st, &mut ft).is_err() {
+            return st;
+        }
+        let ticks = (((ft.dwHighDateTime as u64) << 32) | ft.dwLowDateTime as u64) as i64 + seconds * TICKS_PER_SECOND;
+        let ft = FILETIME {
+            dwLowDateTime: ticks as u32,
+            dwHighDateTime: (ticks >> 32) as u32,
+        };
+        let mut out = SYSTEMTIME::default();
+        if FileTimeToSystemTime(&ft, &mut out).is_err() {
+            return st;
+        }
+        out
+    }
+}
+
+/// Where the current time comes from
+pub trait Clock {
+    /// Local wall-clock time (date, weekday and time of day as the user sees them)
+    fn local_time(&self) -> SYSTEMTIME;
+    /// Seconds since the Unix epoch
+    fn timestamp(&self) -> i64;
+}
+
+/// The real system clock, corrected by any detected clock change
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn local_time(&self) -> SYSTEMTIME {
+        let st = unsafe { GetLocalTime() };
+        match offset() {
+            0 => st,
+            offset => shift_local_time(st, -offset),
+        }
+    }
+
+    fn timestamp(&self) -> i64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0)
+            - offset()
+    }
+}
+
+thread_local! {
+    // Replacement clock for the current thread (only set by tests)
+    static OVERRIDE: RefCell<Option<Box<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+/// Current local time
+pub fn local_time() -> SYSTEMTIME {
+    OVERRIDE
+        .with(|clock| clock.borrow().as_ref().map(|c| c.local_time()))
+        .unwrap_or_else(|| SystemClock.local_time())
+}
+
+/// Current Unix timestamp in seconds
+pub fn timestamp() -> i64 {
+    OVERRIDE
+        .with(|clock| clock.borrow().as_ref().map(|c| c.timestamp()))
+        .unwrap_or_else(|| SystemClock.timestamp())
+}
+
+/// Run `f` with `clock` as the time source on this thread
+#[cfg(test)]
+pub fn with_clock<R>(clock: impl Clock + 'static, f: impl FnOnce() -> R) -> R {
+    let previous = OVERRIDE.with(|c| c.borrow_mut().replace(Box::new(clock)));
+    let result = f();
+    OVERRIDE.with(|c| *c.borrow_mut() = previous);
+    result
+}
+
+/// A clock standing still at a fixed moment
+#[cfg(test)]
+#[derive(Clone, Copy)]
+pub struct MockClock {
+    pub local: SYSTEMTIME,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+impl MockClock {
+    /// Local time at `hour:minute` on the given date; `day_of_week` uses the Windows
+    /// numbering (0 = Sunday). The timestamp is left at 0.
+    pub fn at(year: u16, month: u16, day: u16, day_of_week: u16, hour: u16, minute: u16) -> Self {
+        MockClock {
+            local: SYSTEMTIME {
+                wYear: year,
+                wMonth: month,
+                wDay: day,
+                wDayOfWeek: day_of_week,
+                wHour: hour,
+                wMinute: minute,
+                ..Default::default()
+            },
+            timestamp: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn local_time(&self) -> SYSTEMTIME {
+        self.local
+    }
+
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
@@ -14,10 +14,33 @@ pub const IDM_TODAYS_STATS: u16 = 1006;
 pub const IDM_PAUSE_TOGGLE: u16 = 1007;
 pub const IDM_EXTEND_15: u16 = 1008;
 pub const IDM_EXTEND_45: u16 = 1009;
+pub const IDM_LOCK_WORKSTATION: u16 = 1010;
+pub const IDM_SIGN_OUT: u16 = 1011;
+pub const IDM_HIBERNATE: u16 = 1012;
+pub const IDM_TOGGLE_MINI_OVERLAY: u16 = 1013;
+pub const IDM_PREVIEW_BLOCKING: u16 = 1014;
+pub const IDM_FOCUS_30: u16 = 1015;
+pub const IDM_FOCUS_60: u16 = 1016;
+pub const IDM_FOCUS_120: u16 = 1017;
+pub const IDM_FOCUS_UNTIL_OFF: u16 = 1018;
+pub const IDM_FOCUS_END: u16 = 1019;
+pub const IDM_OPEN_LOG_FOLDER: u16 = 1020;
+pub const IDM_EXTEND_CUSTOM: u16 = 1021;
+pub const IDM_SIMULATION: u16 = 1022;
+pub const IDM_RESTART_ADMIN: u16 = 1023;
+pub const IDM_SHOW_STATUS: u16 = 1024;
+pub const IDM_UNINSTALL: u16 = 1025;
+
+// Resource ID of the application icon embedded by build.rs ("1 ICON ...")
+pub const IDI_APP_ICON: u16 = 1;
 
 // Mutex name for single instance
 pub const MUTEX_NAME: &str = "Global\\ScreenTimeManager_SingleInstance_7F3A9B2E";
 
+// Pipe for forwarded commands, and the event set once it is listening
+pub const PIPE_NAME: &str = "\\\\.\\pipe\\ScreenTimeManager_Commands_7F3A9B2E";
+pub const READY_EVENT_NAME: &str = "Global\\ScreenTimeManager_Ready_7F3A9B2E";
+
 // Colors (BGR format)
 pub const COLOR_OVERLAY_BG: u32 = 0x00331a00;      // Dark blue-ish
 pub const COLOR_PANEL_BG: u32 = 0x00442200;        // Slightly lighter
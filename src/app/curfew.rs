@@ -0,0 +1,139 @@
+//! Curfew module
+//! Allowed hours per day type (weekdays / weekends); outside them the screen is blocked
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::allowed_hours::{allowed_at, parse_window};
+use crate::database;
+use crate::i18n;
+
+/// Blocking overlay is currently shown because of the curfew
+static CURFEW_BLOCKING: AtomicBool = AtomicBool::new(false);
+
+/// Curfew was lifted with the passcode; stays lifted until the next allowed window starts
+static CURFEW_OVERRIDDEN: AtomicBool = AtomicBool::new(false);
+
+/// Allowed window for a weekday (0 = Monday, 6 = Sunday); None = no restriction
+fn window_for(weekday: u32) -> Option<(u32, u32)> {
+    parse_window(&database::get_allowed_hours(weekday >= 5))
+}
+
+/// Check whether the current local time is inside the allowed hours
+pub fn is_within_allowed_hours() -> bool {
+    let st = crate::clock::local_time();
+    let now = st.wHour as u32 * 60 + st.wMinute as u32;
+    let today = database::get_current_weekday();
+    let yesterday = (today + 6) % 7;
+
+    allowed_at(now, window_for(today), window_for(yesterday))
+}
+
+/// Check if the blocking overlay is currently up because of the curfew
+pub fn is_curfew_blocking() -> bool {
+    CURFEW_BLOCKING.load(Ordering::SeqCst)
+}
+
+/// Lift the curfew with the passcode until the next allowed window starts. A plain
+/// override: it doesn't add time.
+pub fn override_curfew() {
+    if CURFEW_BLOCKING.swap(false, Ordering::SeqCst) {
+        CURFEW_OVERRIDDEN.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Whether a curfew block is up although the allowed hours have started again. Clears the
+/// curfew state then, so the caller can take the block down.
+fn curfew_over() -> bool {
+    if !is_curfew_blocking() || !is_within_allowed_hours() {
+        return false;
+    }
+    CURFEW_BLOCKING.store(false, Ordering::SeqCst);
+    CURFEW_OVERRIDDEN.store(false, Ordering::SeqCst);
+    true
+}
+
+/// End a curfew block once the allowed hours start again; called every second from the
+/// blocking screen, since `check_curfew` doesn't run while it's up. If today's time is used
+/// up, the limit block takes its place. Returns true if the block was ended.
+pub unsafe fn lift_if_over() -> bool {
+    if !curfew_over() {
+        return false;
+    }
+    log::info!("Allowed hours started; lifting the curfew block");
+    if crate::blocking::REMAINING_SECONDS.load(Ordering::SeqCst) <= 0 {
+        crate::mini_overlay::enforce_time_up();
+    } else {
+        crate::blocking::hide_blocking_overlay();
+    }
+    true
+}
+
+/// Enforce the curfew; called every second from the countdown timer.
+/// Returns true if the curfew is blocking (time should not be counted).
+pub unsafe fn check_curfew() -> bool {
+    if is_within_allowed_hours() {
+        CURFEW_BLOCKING.store(false, Ordering::SeqCst);
+        CURFEW_OVERRIDDEN.store(false, Ordering::SeqCst);
+        return false;
+    }
+
+    // Headless there's no blocking screen (and the countdown keeps running): lock the
+    // session when the curfew starts; `headless::enforce_block` locks it again after unlocking
+    if crate::headless::is_headless() {
+        if !CURFEW_BLOCKING.swap(true, Ordering::SeqCst) {
+            database::log_block_event("curfew");
+            crate::notifications::dispatch(crate::notifications::Event::Curfew);
+            crate::headless::lock_session();
+        }
+        return true;
+    }
+
+    // The countdown timer only runs while the blocking overlay is hidden, so getting here
+    // while blocking means the curfew was unlocked with the passcode
+    if CURFEW_BLOCKING.swap(false, Ordering::SeqCst) {
+        CURFEW_OVERRIDDEN.store(true, Ordering::SeqCst);
+    }
+    if CURFEW_OVERRIDDEN.load(Ordering::SeqCst) {
+        return false;
+    }
+
+    CURFEW_BLOCKING.store(true, Ordering::SeqCst);
+    let hours = database::get_allowed_hours(database::get_current_weekday() >= 5);
+    let msg = i18n::t("curfew.message").replace("{}", &hours);
+    database::log_block_event("curfew");
+    crate::notifications::dispatch(crate::notifications::Event::Curfew);
+    crate::blocking::show_blocking_overlay(&msg, "curfew");
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{with_clock, MockClock};
+
+    #[test]
+    fn curfew_block_ends_when_the_allowed_hours_start() {
+        database::with_test_database(|_| {
+            database::set_setting("allowed_hours_weekday", "07:00-20:00");
+            CURFEW_BLOCKING.store(true, Ordering::SeqCst);
+
+            // Thursday 06:59: still blocked
+            with_clock(MockClock::at(2026, 10, 15, 4, 6, 59), || assert!(!curfew_over()));
+            assert!(is_curfew_blocking());
+
+            with_clock(MockClock::at(2026, 10, 15, 4, 7, 0), || assert!(curfew_over()));
+            assert!(!is_curfew_blocking());
+            assert!(!CURFEW_OVERRIDDEN.load(Ordering::SeqCst));
+        });
+    }
+
+    #[test]
+    fn overriding_lifts_the_curfew_until_the_next_window() {
+        database::with_test_database(|_| {
+            CURFEW_BLOCKING.store(true, Ordering::SeqCst);
+            override_curfew();
+            assert!(!is_curfew_blocking());
+            assert!(CURFEW_OVERRIDDEN.swap(false, Ordering::SeqCst));
+        });
+    }
+}
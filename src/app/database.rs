@@ -0,0 +1,1979 @@
+//! Database module for Screen Time Manager
+//! Handles SQLite database initialization and settings management
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use rusqlite::{Connection, OptionalExtension, params};
+use windows::core::PCWSTR;
+
+/// Global database connection (thread-safe)
+pub static DB_CONNECTION: Mutex<Option<Connection>> = Mutex::new(None);
+
+/// Set when a database error occurred that the user has not been told about yet
+static DB_ERROR_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Set while database operations are failing (cleared by the next successful operation)
+static DB_FAILING: AtomicBool = AtomicBool::new(false);
+
+/// Errors from database access (distinct from "setting not found")
+#[derive(Debug)]
+pub enum DbError {
+    /// The connection was never opened (or has been closed)
+    Unavailable,
+    /// A thread panicked while holding the connection lock
+    Poisoned,
+    /// SQLite reported an error (locked, corrupt, I/O, ...)
+    Sqlite(rusqlite::Error),
+    /// Filesystem error while managing the database file
+    Io(std::io::Error),
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DbError::Unavailable => write!(f, "database connection is not available"),
+            DbError::Poisoned => write!(f, "database lock is poisoned"),
+            DbError::Sqlite(e) => write!(f, "sqlite error: {}", e),
+            DbError::Io(e) => write!(f, "file error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DbError {}
+
+impl From<rusqlite::Error> for DbError {
+    fn from(e: rusqlite::Error) -> Self {
+        DbError::Sqlite(e)
+    }
+}
+
+impl From<std::io::Error> for DbError {
+    fn from(e: std::io::Error) -> Self {
+        DbError::Io(e)
+    }
+}
+
+/// Run a closure against the global connection, surfacing lock/availability errors
+fn with_connection<T>(f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T, DbError> {
+    let guard = DB_CONNECTION.lock().map_err(|_| DbError::Poisoned)?;
+    let conn = guard.as_ref().ok_or(DbError::Unavailable)?;
+    let result = f(conn)?;
+    DB_FAILING.store(false, Ordering::SeqCst);
+    Ok(result)
+}
+
+/// Log a database error and remember it so the UI can notify the user (once per outage)
+fn report_db_error(context: &str, e: &DbError) {
+    log::error!("{} failed: {}", context, e);
+    if !DB_FAILING.swap(true, Ordering::SeqCst) {
+        DB_ERROR_PENDING.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Returns true (once) if a database error occurred since the last call
+pub fn take_db_error() -> bool {
+    DB_ERROR_PENDING.swap(false, Ordering::SeqCst)
+}
+
+/// Passcode of a new installation
+pub const DEFAULT_PASSCODE: &str = "0000";
+
+/// Weekday keys for database
+pub const WEEKDAY_KEYS: [&str; 7] = [
+    "limit_monday", "limit_tuesday", "limit_wednesday", "limit_thursday",
+    "limit_friday", "limit_saturday", "limit_sunday"
+];
+
+/// Get the path to the database file in a hidden location
+pub fn get_database_path() -> PathBuf {
+    let data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".screen-time-manager");
+
+    if !data_dir.exists() {
+        let _ = std::fs::create_dir_all(&data_dir);
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::ffi::OsStrExt;
+            let path: Vec<u16> = data_dir.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+            unsafe {
+                let _ = windows::Win32::Storage::FileSystem::SetFileAttributesW(
+                    PCWSTR(path.as_ptr()),
+                    windows::Win32::Storage::FileSystem::FILE_ATTRIBUTE_HIDDEN,
+                );
+            }
+        }
+    }
+
+    data_dir.join("data.db")
+}
+
+/// Path of the corrupt database file moved aside during startup recovery (if any)
+static RECOVERED_BACKUP: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Returns the backup path if the database was found corrupt and recreated at startup
+pub fn take_recovered_backup() -> Option<PathBuf> {
+    RECOVERED_BACKUP.lock().ok()?.take()
+}
+
+/// How long a connection waits for another one's lock before failing with SQLITE_BUSY
+const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(3000);
+
+/// Whether an SQLite error means the file itself is damaged, as opposed to e.g. locked
+/// by another instance during an elevated restart
+fn is_corruption(err: &rusqlite::Error) -> bool {
+    matches!(
+        err.sqlite_error_code(),
+        Some(rusqlite::ErrorCode::DatabaseCorrupt | rusqlite::ErrorCode::NotADatabase)
+    )
+}
+
+/// Run SQLite's integrity check on an opened database.
+/// Ok(false) means the file is corrupt; errors that don't point at corruption
+/// (a locked file, I/O trouble) are passed through so the file is left alone.
+fn is_database_healthy(conn: &Connection) -> rusqlite::Result<bool> {
+    match conn.query_row("PRAGMA integrity_check", [], |row| row.get::<_, String>(0)) {
+        Ok(result) => Ok(result == "ok"),
+        Err(e) if is_corruption(&e) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Run SQLite's quick check on the open database (false if it fails or can't run)
+pub fn check_integrity() -> bool {
+    with_connection(|conn| conn.query_row("PRAGMA quick_check", [], |row| row.get::<_, String>(0)))
+        .map(|result| result == "ok")
+        .unwrap_or(false)
+}
+
+/// Open the database, moving a corrupt file aside and starting fresh if needed.
+/// The corrupt file is kept as `data.corrupt-<timestamp>.db` for manual recovery.
+pub fn open_or_recover() -> Result<Connection, DbError> {
+    open_or_recover_at(&get_database_path())
+}
+
+/// [`open_or_recover`] for a given database file
+fn open_or_recover_at(db_path: &Path) -> Result<Connection, DbError> {
+    match Connection::open(db_path) {
+        Ok(conn) => {
+            // Wait out another instance's lock instead of mistaking it for corruption
+            conn.busy_timeout(BUSY_TIMEOUT)?;
+            if is_database_healthy(&conn)? {
+                return Ok(conn);
+            }
+            // Write what's still only in the WAL into the file before it's moved aside, so
+            // the copy kept for recovery has the latest data (may fail on a damaged file)
+            if let Err(e) = conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(())) {
+                log::warn!("Could not checkpoint the corrupt database: {}", e);
+            }
+        }
+        Err(e) if is_corruption(&e) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let backup_path = db_path.with_file_name(format!("data.corrupt-{}.db", timestamp));
+
+    log::error!("Integrity check failed, moving database to {}", backup_path.display());
+    move_database(db_path, &backup_path)?;
+    *RECOVERED_BACKUP.lock().unwrap() = Some(backup_path);
+
+    Ok(Connection::open(db_path)?)
+}
+
+/// Move a database file aside. The WAL belongs to it and goes along, in case a checkpoint
+/// didn't get everything; the shared-memory index is rebuilt from it when needed.
+fn move_database(from: &Path, to: &Path) -> std::io::Result<()> {
+    let side_file = |path: &Path, suffix: &str| {
+        let mut side_file = path.as_os_str().to_os_string();
+        side_file.push(suffix);
+        PathBuf::from(side_file)
+    };
+    std::fs::rename(from, to)?;
+    let _ = std::fs::rename(side_file(from, "-wal"), side_file(to, "-wal"));
+    let _ = std::fs::remove_file(side_file(from, "-shm"));
+    Ok(())
+}
+
+/// Configure a connection for concurrent access: WAL journaling lets readers
+/// (e.g. an export or external tool) work alongside the writer, and the busy
+/// timeout makes SQLite wait for a lock instead of failing with SQLITE_BUSY.
+fn configure_connection(conn: &Connection) -> rusqlite::Result<()> {
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+    Ok(())
+}
+
+/// Create the schema if it does not exist yet
+fn create_tables(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS notification_queue (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            created INTEGER NOT NULL,
+            text TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Initialize the SQLite database
+pub fn init_database() -> Result<(), DbError> {
+    let conn = open_or_recover()?;
+
+    configure_connection(&conn)?;
+    create_tables(&conn)?;
+
+    // A new installation gets the setup wizard; existing ones were set up before it existed
+    let fresh: bool = conn.query_row("SELECT NOT EXISTS(SELECT 1 FROM settings)", [], |row| row.get(0))?;
+
+    // Default settings to initialize
+    let defaults = [
+        ("passcode", DEFAULT_PASSCODE),
+        ("setup_complete", if fresh { "0" } else { "1" }), // 1 = the first-run setup wizard was finished
+        // Daily limits in minutes (default 120 = 2 hours)
+        ("limit_monday", "120"),
+        ("limit_tuesday", "120"),
+        ("limit_wednesday", "120"),
+        ("limit_thursday", "120"),
+        ("limit_friday", "120"),
+        ("limit_saturday", "120"),
+        ("limit_sunday", "120"),
+        // Allowed hours ("HH:MM-HH:MM", empty = no curfew; end before start runs past midnight)
+        ("allowed_hours_weekday", ""),      // Monday - Friday
+        ("allowed_hours_weekend", ""),      // Saturday - Sunday
+        // Weekly limit ramp-down (opt-in: step 0 = off)
+        ("limit_rampdown_step", "0"),       // Minutes to subtract from each daily limit every Monday
+        ("limit_rampdown_floor", "60"),     // Limits are never reduced below this
+        // First warning (minutes before limit)
+        ("warning1_minutes", "10"),
+        ("warning1_message", "10 minutes remaining!"),
+        ("warning1_duration", "10"),        // Seconds the banner stays on screen
+        ("warning1_minutes_weekend", ""),   // Saturday - Sunday (empty = same as above)
+        ("warning1_message_weekend", ""),
+        // Second warning (minutes before limit)
+        ("warning2_minutes", "5"),
+        ("warning2_message", "5 minutes remaining!"),
+        ("warning2_duration", "10"),
+        ("warning2_minutes_weekend", ""),
+        ("warning2_message_weekend", ""),
+        // Blocking message
+        ("blocking_message", "Your screen time limit has been reached."),
+        ("secondary_block_message", ""),    // Text on the other monitors while blocked (empty = same as the blocking screen)
+        ("block_background_image", ""),     // Image file shown behind the blocking panel (empty = solid color)
+        ("block_text_scale", "100"),        // Blocking panel and text size in percent (on top of DPI scaling)
+        // Pause mode settings
+        ("pause_enabled", "1"),              // 1 = enabled, 0 = disabled
+        ("pause_daily_budget", "45"),        // Total pause minutes per day
+        ("pause_max_duration", "20"),        // Max minutes per single pause
+        ("pause_cooldown", "15"),            // Minutes between pauses
+        ("pause_min_active_time", "10"),     // Min active minutes before each pause (since the last one)
+        ("pause_wall_clock", "1"),           // 1 = pause keeps counting while app is closed
+        ("pause_requires_passcode", "0"),    // 1 = pausing from the tray asks for the passcode (parent-only)
+        ("kiosk_mode", "0"),                 // 1 = tray shows only the status; parent actions only via Telegram
+        ("enforcement_mode", "block"),       // At zero: "block" = blocking screen, "warn_only" = repeated warning banner
+        // Lock screen timeout (seconds before shutdown, default 10 minutes)
+        ("lock_screen_timeout", "600"),
+        // Mini overlay (timer in the corner)
+        ("mini_overlay_visible", "1"),      // 1 = shown, 0 = hidden (countdown still runs)
+        ("mini_overlay_scale", "100"),      // Mini overlay size in percent (on top of DPI scaling)
+        ("display_mode", "mini"),           // Countdown display: "mini" (corner timer), "large" (big countdown) or "hidden"
+        ("display_corner", "top_right"),    // Screen corner of the countdown: top_right, top_left, bottom_right or bottom_left
+        ("mini_overlay_format", "compact"), // Corner timer text: "compact" (1:30:45), "hhmm" (1:30), "minutes" (90m) or "fraction" (90/120)
+        ("defer_warnings_fullscreen", "1"), // 1 = tray notification instead of the warning banner over fullscreen apps
+        ("respect_focus_assist", "1"),      // 1 = silent notification instead of warning banners and sounds during Focus Assist
+        ("warning_position", "center"),     // Warning banner position: top, center or bottom
+        ("warning_margin", "0"),            // Distance of a top/bottom banner from the screen edge (px at 100% DPI)
+        ("warning_fade_ms", "300"),         // Warning banner fade-out duration in ms (0 = hide instantly)
+        ("winddown_minutes", "0"),          // Last minutes in which the mini overlay pulses (0 = off)
+        ("winddown_interval", "1"),         // Minutes between reminders during the wind-down
+        ("sim_speed", "60"),                // Simulation: countdown seconds per real second (60 = a minute per second)
+        ("telegram_rate_limit", "5"),       // Seconds between /extend, /pause, ... from one chat
+        ("webhook_url", ""),                // URL that receives notification events as JSON (empty = off)
+        // Daily email report (off by default; the password is stored DPAPI-encrypted)
+        ("email_enabled", "0"),             // 1 = email the daily report
+        ("email_report_time", "20:00"),     // Local time the report is sent (HH:MM)
+        ("smtp_host", ""),
+        ("smtp_port", "587"),               // 465 = implicit TLS, anything else = STARTTLS
+        ("smtp_username", ""),
+        ("smtp_password", ""),
+        ("email_to", ""),                   // Recipients, separated by commas
+        // MQTT for home automation (off by default; the password is stored DPAPI-encrypted)
+        ("mqtt_enabled", "0"),              // 1 = publish state and accept commands
+        ("mqtt_host", ""),
+        ("mqtt_port", "1883"),              // 8883 = TLS
+        ("mqtt_username", ""),
+        ("mqtt_password", ""),
+        ("mqtt_prefix", "screentime"),      // Topics: <prefix>/remaining, /paused, /limit, /available, /cmd
+        // Prometheus metrics endpoint (off by default; needs a bearer token, stored DPAPI-encrypted)
+        ("http_enabled", "0"),              // 1 = serve GET /metrics
+        ("http_listen", "127.0.0.1:9184"),  // Address and port; 0.0.0.0:9184 to allow other machines
+        ("http_token", ""),
+        ("high_contrast", "0"),             // 1 = high-contrast overlays (also on when Windows high contrast is active)
+        ("tray_left_click", "balloon"),     // Tray icon left-click: "balloon" (status) or "stats" (stats dialog)
+        ("status_hotkey", "Ctrl+Alt+T"),    // Global shortcut that shows the remaining time ("" = off)
+        ("counted_users", ""),              // Windows accounts whose time counts, comma-separated ("" = everyone)
+        ("log_level", "info"),              // app.log detail: "error", "warn", "info" or "debug"
+        ("pause_indicator", "bars"),        // Corner timer while paused: "bars" (drawn pause sign), "text" ("II") or "none"
+        // Blocking overlay buttons
+        ("block_allow_shutdown", "1"),      // 1 = show Shut Down button
+        ("block_allow_lock", "1"),          // 1 = show Lock Screen button
+        ("block_allow_logoff", "0"),        // 1 = show Sign Out button
+        ("block_allow_extend", "1"),        // 1 = show extend buttons
+        // Blocking overlay focus: 0 = topmost only, 1 = reclaim periodically, 2 = reclaim immediately
+        ("blocking_focus_mode", "1"),
+        // Whether time asleep counts against the remaining time
+        ("sleep_counts", "0"),              // 1 = count sleep time, 0 = don't
+        ("autosave_interval_seconds", "15"), // Seconds between saves of the remaining time (caps loss on a crash)
+        // Idle detection settings
+        ("idle_enabled", "1"),              // 1 = enabled, 0 = disabled
+        ("idle_timeout_minutes", "5"),      // Minutes of inactivity before auto-pause
+        // Passcode lockout after repeated wrong entries
+        ("lockout_attempts", "5"),          // Wrong passcodes in a row before a lockout (0 = never lock out)
+        ("lockout_delay_seconds", "30"),    // First lockout; doubles with every further wrong passcode (max 1 hour)
+        ("lockout_notify", "1"),            // 1 = tell the parent when a lockout starts
+        ("failed_attempts", "0"),           // Wrong passcodes since the last correct one
+        ("lockout_until", "0"),             // Unix time when passcodes are accepted again
+        ("expiry_action", "block"),         // When time runs out: block, lock or shutdown
+        ("expiry_action_delay_seconds", "30"), // Warning before locking / grace period before shutting down
+        ("min_block_duration_seconds", "0"), // Seconds a block stays up before it can be unlocked (0 = off)
+        ("block_started_at", "0"),          // Unix time the current block began (0 = not blocked)
+        ("clock_change_block", "0"),        // 1 = block the screen when the system clock is changed
+        ("clock_guard", "1"),               // 1 = count by the real time when the clock is changed (no fresh day)
+        ("clock_last_seen", "0"),           // Newest (corrected) Unix time saved; a clock set back below it is noticed
+        // Time extension policy
+        ("extend_max_minutes", "120"),      // Max minutes per single extension
+        ("extend_daily_cap", "240"),        // Total extension minutes per day (0 = unlimited)
+        ("max_extensions_per_day", "0"),    // Number of extensions per day (0 = unlimited)
+        ("self_extend_minutes", "5"),       // Minutes the child may give themselves on the lock screen
+        ("self_extend_per_day", "0"),       // How often per day, without the passcode (0 = off)
+    ];
+
+    for (key, value) in defaults {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM settings WHERE key = ?1)",
+            params![key],
+            |row| row.get(0),
+        )?;
+
+        if !exists {
+            conn.execute(
+                "INSERT INTO settings (key, value) VALUES (?1, ?2)",
+                params![key, value],
+            )?;
+        }
+    }
+
+    *DB_CONNECTION.lock().unwrap() = Some(conn);
+    Ok(())
+}
+
+/// Close the database (before the data directory is deleted). Later reads and writes fail.
+pub fn close_database() {
+    *DB_CONNECTION.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// A fresh directory under the temp dir for one test's files
+#[cfg(test)]
+pub fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("screen-time-manager-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Held by tests that install a database as the global connection, so parallel
+/// tests don't swap it out under each other
+#[cfg(test)]
+static TEST_DATABASE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Run `f` with an empty database file installed as the global connection.
+/// `f` is given a function that closes and reopens the file, like an app restart.
+#[cfg(test)]
+pub fn with_test_database<R>(f: impl FnOnce(&dyn Fn()) -> R) -> R {
+    let _guard = TEST_DATABASE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = test_dir("global-db");
+    let db_path = dir.join("data.db");
+
+    let open = || {
+        let conn = Connection::open(&db_path).unwrap();
+        configure_connection(&conn).unwrap();
+        create_tables(&conn).unwrap();
+        *DB_CONNECTION.lock().unwrap_or_else(|e| e.into_inner()) = Some(conn);
+    };
+    let restart = || {
+        close_database();
+        open();
+    };
+
+    open();
+    let result = f(&restart);
+    close_database();
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
+
+/// Get the passcode from the database
+pub fn get_passcode() -> Option<String> {
+    get_setting("passcode")
+}
+
+/// Check if the passcode is still the default one anybody could guess
+pub fn is_default_passcode() -> bool {
+    get_passcode().is_none_or(|passcode| passcode == DEFAULT_PASSCODE)
+}
+
+/// Set the passcode in the database
+#[allow(dead_code)]
+pub fn set_passcode(code: &str) -> bool {
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE settings SET value = ?1 WHERE key = 'passcode'",
+            params![code],
+        )
+    }).is_ok()
+}
+
+/// Get a setting value from the database, distinguishing "not found" (`Ok(None)`) from errors
+pub fn try_get_setting(key: &str) -> Result<Option<String>, DbError> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        ).optional()
+    })
+}
+
+/// Set a setting value in the database
+pub fn try_set_setting(key: &str, value: &str) -> Result<(), DbError> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )
+    })?;
+    Ok(())
+}
+
+/// Write several settings atomically in a single transaction
+pub fn set_settings_batch(pairs: &[(&str, &str)]) -> Result<(), DbError> {
+    let result = with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare("INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)")?;
+            for (key, value) in pairs {
+                stmt.execute(params![key, value])?;
+            }
+        }
+        tx.commit()
+    });
+    if let Err(ref e) = result {
+        report_db_error("batch write", e);
+    }
+    result
+}
+
+/// Get a setting value from the database (errors are logged and reported as missing)
+pub fn get_setting(key: &str) -> Option<String> {
+    try_get_setting(key).unwrap_or_else(|e| {
+        report_db_error(&format!("reading '{}'", key), &e);
+        None
+    })
+}
+
+/// Set a setting value in the database (errors are logged)
+pub fn set_setting(key: &str, value: &str) -> bool {
+    match try_set_setting(key, value) {
+        Ok(()) => true,
+        Err(e) => {
+            report_db_error(&format!("writing '{}'", key), &e);
+            false
+        }
+    }
+}
+
+/// Get daily limit for a specific weekday (0 = Monday, 6 = Sunday)
+#[allow(dead_code)]
+pub fn get_daily_limit(weekday: u32) -> u32 {
+    let key = match weekday {
+        0 => "limit_monday",
+        1 => "limit_tuesday",
+        2 => "limit_wednesday",
+        3 => "limit_thursday",
+        4 => "limit_friday",
+        5 => "limit_saturday",
+        6 => "limit_sunday",
+        _ => return 120,
+    };
+    get_setting(key)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(120)
+}
+
+/// Get the allowed-hours window for weekdays or weekends ("HH:MM-HH:MM", empty = unrestricted)
+pub fn get_allowed_hours(weekend: bool) -> String {
+    let key = if weekend { "allowed_hours_weekend" } else { "allowed_hours_weekday" };
+    get_setting(key).unwrap_or_default()
+}
+
+// ============================================================================
+// Limit Ramp-down Functions
+// ============================================================================
+
+/// Weekly limit ramp-down configuration
+pub struct RampdownConfig {
+    pub step_minutes: u32,
+    pub floor_minutes: u32,
+}
+
+pub fn get_rampdown_config() -> RampdownConfig {
+    RampdownConfig {
+        step_minutes: get_setting("limit_rampdown_step")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0),
+        floor_minutes: get_setting("limit_rampdown_floor")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(60),
+    }
+}
+
+/// Get the date of the Monday that starts the current week (YYYY-MM-DD)
+pub fn get_week_start_date() -> String {
+    get_date_offset(-(get_current_weekday() as i64))
+}
+
+/// Get the date of the next weekly ramp-down (next Monday, YYYY-MM-DD)
+pub fn get_next_rampdown_date() -> String {
+    get_date_offset(7 - get_current_weekday() as i64)
+}
+
+/// Reduce every daily limit by the ramp-down step, once per week.
+/// The first week after enabling only records the start; reductions begin the following Monday.
+/// Returns true if the limits were reduced.
+pub fn apply_limit_rampdown() -> bool {
+    let config = get_rampdown_config();
+    if config.step_minutes == 0 {
+        return false;
+    }
+
+    let week_start = get_week_start_date();
+    match get_setting("limit_rampdown_last_applied") {
+        Some(last) if last == week_start => return false,
+        Some(last) if !last.is_empty() => {}
+        _ => {
+            set_setting("limit_rampdown_last_applied", &week_start);
+            return false;
+        }
+    }
+
+    let mut updates: Vec<(&str, String)> = Vec::new();
+    for (day, key) in WEEKDAY_KEYS.iter().enumerate() {
+        let limit = get_daily_limit(day as u32);
+        // Limits already at or below the floor are left alone (never raised)
+        if limit > config.floor_minutes {
+            let reduced = limit.saturating_sub(config.step_minutes).max(config.floor_minutes);
+            updates.push((key, reduced.to_string()));
+        }
+    }
+    updates.push(("limit_rampdown_last_applied", week_start));
+
+    let pairs: Vec<(&str, &str)> = updates.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    match set_settings_batch(&pairs) {
+        Ok(()) => true,
+        Err(e) => {
+            report_db_error("applying limit ramp-down", &e);
+            false
+        }
+    }
+}
+
+/// Get warning configuration: (minutes before limit, message, display seconds)
+pub fn get_warning_config(warning_num: u32) -> (u32, String, u32) {
+    let minutes_key = format!("warning{}_minutes", warning_num);
+    let message_key = format!("warning{}_message", warning_num);
+    let duration_key = format!("warning{}_duration", warning_num);
+
+    let minutes = get_setting(&minutes_key)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(5);
+    let message = get_setting(&message_key)
+        .unwrap_or_else(|| format!("{} minutes remaining!", minutes));
+    // Display duration in seconds
+    let duration = get_setting(&duration_key)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(10)
+        .clamp(1, 300);
+
+    (minutes, message, duration)
+}
+
+/// Get the warning configuration for a day (0 = Monday). Saturday and Sunday use their
+/// own minutes and message where set; everything else comes from the global warning.
+pub fn get_warning_config_for_day(warning_num: u32, weekday: u32) -> (u32, String, u32) {
+    let (mut minutes, mut message, duration) = get_warning_config(warning_num);
+    if weekday >= 5 {
+        if let Some(weekend_minutes) =
+            get_setting(&format!("warning{}_minutes_weekend", warning_num)).and_then(|s| s.trim().parse().ok())
+        {
+            minutes = weekend_minutes;
+        }
+        if let Some(weekend_message) =
+            get_setting(&format!("warning{}_message_weekend", warning_num)).filter(|s| !s.trim().is_empty())
+        {
+            message = weekend_message;
+        }
+    }
+    (minutes, message, duration)
+}
+
+/// Get blocking message
+#[allow(dead_code)]
+pub fn get_blocking_message() -> String {
+    get_setting("blocking_message")
+        .unwrap_or_else(|| "Your screen time limit has been reached.".to_string())
+}
+
+/// Get the text shown on the other monitors while blocked (None = mirror the blocking screen)
+pub fn get_secondary_block_message() -> Option<String> {
+    get_setting("secondary_block_message")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Get the blocking overlay background image path (empty = solid color)
+pub fn get_block_background_image() -> String {
+    get_setting("block_background_image")
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Get the blocking panel size in percent (75-200, default 100)
+pub fn get_block_text_scale() -> i32 {
+    get_setting("block_text_scale")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(100)
+        .clamp(75, 200)
+}
+
+/// Get the current local date as a string (YYYY-MM-DD)
+pub fn get_today_date() -> String {
+    let st = crate::clock::local_time();
+
+    format!("{:04}-{:02}-{:02}", st.wYear, st.wMonth, st.wDay)
+}
+
+/// Get the local date `days` away from today as a string (YYYY-MM-DD)
+fn get_date_offset(days: i64) -> String {
+    let st = crate::clock::local_time();
+    let (year, month, day) = crate::dates::offset_date(st.wYear as i32, st.wMonth as u32, st.wDay as u32, days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Save remaining time to database (associated with current date)
+pub fn save_remaining_time(seconds: i32) {
+    let date = get_today_date();
+    let key = format!("remaining_time_{}", date);
+    set_setting(&key, &seconds.to_string());
+}
+
+/// Load remaining time from database for today
+#[allow(dead_code)]
+pub fn load_remaining_time() -> Option<i32> {
+    let date = get_today_date();
+    let key = format!("remaining_time_{}", date);
+    get_setting(&key).and_then(|s| s.parse().ok())
+}
+
+/// Get the current weekday (0 = Monday, 6 = Sunday)
+#[allow(dead_code)]
+pub fn get_current_weekday() -> u32 {
+    let st = crate::clock::local_time();
+
+    // Windows: wDayOfWeek is 0 = Sunday, 1 = Monday, ..., 6 = Saturday
+    // We want: 0 = Monday, 1 = Tuesday, ..., 6 = Sunday
+    if st.wDayOfWeek == 0 {
+        6 // Sunday
+    } else {
+        (st.wDayOfWeek - 1) as u32
+    }
+}
+
+// ============================================================================
+// Lock Screen Timeout Functions
+// ============================================================================
+
+/// Check if the user wants the mini overlay shown (the countdown runs either way).
+/// Off when unticked in the tray menu or when the display mode is "hidden".
+pub fn is_mini_overlay_enabled() -> bool {
+    get_setting("mini_overlay_visible")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+        && get_display_mode() != "hidden"
+}
+
+/// Get the countdown display mode ("mini", "large" or "hidden", default "mini")
+pub fn get_display_mode() -> String {
+    match get_setting("display_mode").as_deref() {
+        Some("large") => "large".to_string(),
+        Some("hidden") => "hidden".to_string(),
+        _ => "mini".to_string(),
+    }
+}
+
+/// Get the screen corner of the countdown (default "top_right")
+pub fn get_display_corner() -> String {
+    match get_setting("display_corner").as_deref() {
+        Some("top_left") => "top_left".to_string(),
+        Some("bottom_right") => "bottom_right".to_string(),
+        Some("bottom_left") => "bottom_left".to_string(),
+        _ => "top_right".to_string(),
+    }
+}
+
+/// Get the mini overlay size in percent (50-300, default 100)
+pub fn get_mini_overlay_scale() -> i32 {
+    get_setting("mini_overlay_scale")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(100)
+        .clamp(50, 300)
+}
+
+/// Get the corner timer format ("compact", "hhmm", "minutes" or "fraction", default "compact")
+pub fn get_mini_overlay_format() -> String {
+    match get_setting("mini_overlay_format").as_deref() {
+        Some("hhmm") => "hhmm".to_string(),
+        Some("minutes") => "minutes".to_string(),
+        Some("fraction") => "fraction".to_string(),
+        _ => "compact".to_string(),
+    }
+}
+
+/// Check if warning banners are replaced by a tray notification while a fullscreen app is in front
+pub fn is_defer_warnings_fullscreen() -> bool {
+    get_setting("defer_warnings_fullscreen")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+}
+
+/// Check if warnings are downgraded to a silent notification while Focus Assist is on
+pub fn is_respect_focus_assist() -> bool {
+    get_setting("respect_focus_assist")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+}
+
+/// Get the warning banner position ("top", "center" or "bottom", default "center")
+pub fn get_warning_position() -> String {
+    match get_setting("warning_position").as_deref() {
+        Some("top") => "top".to_string(),
+        Some("bottom") => "bottom".to_string(),
+        _ => "center".to_string(),
+    }
+}
+
+/// Get the pause indicator of the corner timer ("bars", "text" or "none", default "bars")
+pub fn get_pause_indicator() -> String {
+    match get_setting("pause_indicator").as_deref() {
+        Some("text") => "text".to_string(),
+        Some("none") => "none".to_string(),
+        _ => "bars".to_string(),
+    }
+}
+
+/// Get the log level for app.log ("error", "warn", "info" or "debug", default "info")
+pub fn get_log_level() -> String {
+    match get_setting("log_level").as_deref() {
+        Some(level @ ("error" | "warn" | "debug")) => level.to_string(),
+        _ => "info".to_string(),
+    }
+}
+
+/// Get the global shortcut that shows the remaining time, e.g. "Ctrl+Alt+T" (empty = off)
+pub fn get_status_hotkey() -> String {
+    get_setting("status_hotkey")
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Get the Windows accounts whose time counts on a shared PC, e.g. "Max, Lena" (empty = everyone)
+pub fn get_counted_users() -> String {
+    get_setting("counted_users")
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Get the tray icon left-click action ("balloon" or "stats", default "balloon")
+pub fn get_tray_left_click() -> String {
+    match get_setting("tray_left_click").as_deref() {
+        Some("stats") => "stats".to_string(),
+        _ => "balloon".to_string(),
+    }
+}
+
+/// Get the warning banner margin from the screen edge (0-500 px at 100% DPI)
+pub fn get_warning_margin() -> i32 {
+    get_setting("warning_margin")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+        .clamp(0, 500)
+}
+
+/// Get the warning banner fade-out duration in milliseconds (0 = off, max 2000)
+pub fn get_warning_fade_ms() -> u32 {
+    get_setting("warning_fade_ms")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(300)
+        .min(2000)
+}
+
+/// Check if high-contrast overlays are forced on in settings
+pub fn is_high_contrast_enabled() -> bool {
+    get_setting("high_contrast")
+        .map(|s| s == "1")
+        .unwrap_or(false)
+}
+
+/// Check if the Shut Down button is shown on the blocking overlay
+pub fn is_block_shutdown_allowed() -> bool {
+    get_setting("block_allow_shutdown")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+}
+
+/// Check if the Lock Screen button is shown on the blocking overlay
+pub fn is_block_lock_allowed() -> bool {
+    get_setting("block_allow_lock")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+}
+
+/// Check if the Sign Out button is shown on the blocking overlay
+pub fn is_block_logoff_allowed() -> bool {
+    get_setting("block_allow_logoff")
+        .map(|s| s == "1")
+        .unwrap_or(false)
+}
+
+/// Check if the extend buttons are shown on the blocking overlay
+pub fn is_block_extend_allowed() -> bool {
+    get_setting("block_allow_extend")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+}
+
+/// How hard the blocking overlay fights for focus:
+/// 0 = stay topmost only, 1 = reclaim focus every 500ms, 2 = also reclaim on every foreground change
+pub fn get_blocking_focus_mode() -> u32 {
+    get_setting("blocking_focus_mode")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(1)
+}
+
+/// Check if time spent in sleep/hibernate counts against the remaining time
+pub fn does_sleep_count() -> bool {
+    get_setting("sleep_counts")
+        .map(|s| s == "1")
+        .unwrap_or(false)
+}
+
+/// Check if running out of time only shows warnings instead of blocking the screen
+pub fn is_warn_only() -> bool {
+    get_setting("enforcement_mode")
+        .map(|s| s == "warn_only")
+        .unwrap_or(false)
+}
+
+/// Get the wind-down length in minutes (0-60, 0 = off): the last minutes before zero in which
+/// the mini overlay pulses and reminders repeat
+pub fn get_winddown_minutes() -> u32 {
+    get_setting("winddown_minutes")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+        .min(60)
+}
+
+/// Get the minutes between reminders during the wind-down (1-30, default 1)
+pub fn get_winddown_interval_minutes() -> u32 {
+    get_setting("winddown_interval")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(1)
+        .clamp(1, 30)
+}
+
+/// Get how many countdown seconds pass per real second in a simulation (2-600, default 60)
+pub fn get_sim_speed() -> u32 {
+    get_setting("sim_speed")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(60)
+        .clamp(2, 600)
+}
+
+/// Get lock screen timeout in seconds (time before shutdown when lock screen is active)
+pub fn get_lock_screen_timeout() -> i32 {
+    get_setting("lock_screen_timeout")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(600) // 10 minutes default
+}
+
+/// Get what happens when the time runs out (default "block"): "lock" and "shutdown"
+/// also lock or shut down the computer, with the blocking screen behind it
+pub fn get_expiry_action() -> String {
+    match get_setting("expiry_action").as_deref() {
+        Some("lock") => "lock".to_string(),
+        Some("shutdown") => "shutdown".to_string(),
+        _ => "block".to_string(),
+    }
+}
+
+/// Get the seconds of warning before locking or shutting down when the time runs out (0-600)
+pub fn get_expiry_action_delay_seconds() -> i32 {
+    get_setting("expiry_action_delay_seconds")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(30)
+        .clamp(0, 600)
+}
+
+/// Check if kiosk mode is on: the tray menu offers only the status and pausing, and
+/// settings, statistics, extensions and quitting are managed from Telegram
+pub fn is_kiosk_mode() -> bool {
+    get_setting("kiosk_mode")
+        .map(|s| s == "1")
+        .unwrap_or(false)
+}
+
+// ============================================================================
+// Pause Mode Functions
+// ============================================================================
+
+/// Check if pausing from the tray needs the passcode (resuming never does)
+pub fn is_pause_passcode_required() -> bool {
+    get_setting("pause_requires_passcode")
+        .map(|s| s == "1")
+        .unwrap_or(false)
+}
+
+/// Check if pause mode is enabled
+pub fn is_pause_enabled() -> bool {
+    get_setting("pause_enabled")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+}
+
+pub use crate::pause::PauseConfig;
+
+/// Get pause configuration
+pub fn get_pause_config() -> PauseConfig {
+    PauseConfig {
+        daily_budget_minutes: get_setting("pause_daily_budget")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(45),
+        max_duration_minutes: get_setting("pause_max_duration")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(20),
+        cooldown_minutes: get_setting("pause_cooldown")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(15),
+        min_active_time_minutes: get_setting("pause_min_active_time")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(10),
+    }
+}
+
+/// Passcode lockout configuration
+pub struct LockoutConfig {
+    /// Wrong passcodes in a row before a lockout (0 = never lock out)
+    pub max_attempts: u32,
+    /// Length of the first lockout; doubles with every further wrong passcode
+    pub delay_seconds: u32,
+    /// Tell the parent when a lockout starts
+    pub notify: bool,
+}
+
+pub fn get_lockout_config() -> LockoutConfig {
+    LockoutConfig {
+        max_attempts: get_setting("lockout_attempts")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(5)
+            .min(100),
+        delay_seconds: get_setting("lockout_delay_seconds")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(30)
+            .clamp(1, 3600),
+        notify: get_setting("lockout_notify")
+            .map(|s| s == "1")
+            .unwrap_or(true),
+    }
+}
+
+/// Check if the first-run setup wizard was finished
+pub fn is_setup_complete() -> bool {
+    get_setting("setup_complete")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+}
+
+/// Check if the screen is blocked when the system clock is changed
+pub fn is_clock_change_block() -> bool {
+    get_setting("clock_change_block")
+        .map(|s| s == "1")
+        .unwrap_or(false)
+}
+
+/// Check if detected clock changes are undone for time accounting
+pub fn is_clock_guard_enabled() -> bool {
+    get_setting("clock_guard")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+}
+
+/// Get the newest (corrected) Unix time saved by a previous run
+pub fn get_clock_last_seen() -> i64 {
+    get_setting("clock_last_seen")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Get the number of wrong passcodes since the last correct one
+pub fn get_failed_attempts() -> u32 {
+    get_setting("failed_attempts")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Get the Unix time when passcodes are accepted again (0 or past = no lockout)
+pub fn get_lockout_until() -> i64 {
+    get_setting("lockout_until")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Get how long a block stays up before the passcode or an extension can lift it (0 = off)
+pub fn get_min_block_duration_seconds() -> i64 {
+    get_setting("min_block_duration_seconds")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+        .clamp(0, 3600)
+}
+
+/// Get the Unix time the current block began (0 = not blocked)
+pub fn get_block_started_at() -> i64 {
+    get_setting("block_started_at")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Get pause time used today (in seconds)
+pub fn get_pause_used_today() -> i32 {
+    get_pause_used(&get_today_date())
+}
+
+/// Get pause time used on `date` (in seconds)
+pub fn get_pause_used(date: &str) -> i32 {
+    let key = format!("pause_used_{}", date);
+    get_setting(&key)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Save pause time used on `date` (in seconds)
+pub fn save_pause_used(date: &str, seconds: i32) {
+    let key = format!("pause_used_{}", date);
+    set_setting(&key, &seconds.to_string());
+}
+
+/// Get timestamp of last pause end (Unix timestamp)
+pub fn get_last_pause_end() -> i64 {
+    get_setting("pause_last_end_timestamp")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Save timestamp of last pause end
+pub fn save_last_pause_end(timestamp: i64) {
+    set_setting("pause_last_end_timestamp", &timestamp.to_string());
+}
+
+/// Get current Unix timestamp
+pub fn get_current_timestamp() -> i64 {
+    crate::clock::timestamp()
+}
+
+/// Check if an active pause keeps counting (by wall clock) while the app is closed
+pub fn is_pause_wall_clock() -> bool {
+    get_setting("pause_wall_clock")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+}
+
+/// Get the pause that was active when the app last ran: (start timestamp, seconds counted)
+pub fn get_active_pause() -> Option<(i64, i32)> {
+    let value = get_setting("pause_active")?;
+    let (start, duration) = value.split_once(':')?;
+    Some((start.parse().ok()?, duration.parse().ok()?))
+}
+
+/// Persist the currently active pause so it survives a restart
+pub fn save_active_pause(start_timestamp: i64, duration_seconds: i32) {
+    set_setting("pause_active", &format!("{}:{}", start_timestamp, duration_seconds));
+}
+
+/// Clear the persisted active pause
+pub fn clear_active_pause() {
+    set_setting("pause_active", "");
+}
+
+/// Get the block that was showing when the app last ran: (reason, date it was shown)
+pub fn get_active_block() -> Option<(String, String)> {
+    let value = get_setting("block_active")?;
+    let (reason, date) = value.split_once(':')?;
+    Some((reason.to_string(), date.to_string()))
+}
+
+/// Persist the block being shown so a restart (even after the app was killed) shows it again
+pub fn save_active_block(reason: &str) {
+    set_setting("block_active", &format!("{}:{}", reason, get_today_date()));
+}
+
+/// Clear the persisted block
+pub fn clear_active_block() {
+    set_setting("block_active", "");
+}
+
+/// Get the focus mode that was on when the app last ran: (start timestamp, deadline or 0 if open)
+pub fn get_active_focus() -> Option<(i64, i64)> {
+    let value = get_setting("focus_active")?;
+    let (start, deadline) = value.split_once(':')?;
+    Some((start.parse().ok()?, deadline.parse().ok()?))
+}
+
+/// Persist the running focus mode so it survives a restart
+pub fn save_active_focus(start_timestamp: i64, deadline: i64) {
+    set_setting("focus_active", &format!("{}:{}", start_timestamp, deadline));
+}
+
+/// Clear the persisted focus mode
+pub fn clear_active_focus() {
+    set_setting("focus_active", "");
+}
+
+/// Get the session start time used today (in seconds) - tracks when timer started today
+pub fn get_session_active_time() -> i32 {
+    let date = get_today_date();
+    let key = format!("session_active_{}", date);
+    get_setting(&key)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Save session active time (in seconds)
+pub fn save_session_active_time(seconds: i32) {
+    let date = get_today_date();
+    let key = format!("session_active_{}", date);
+    set_setting(&key, &seconds.to_string());
+}
+
+/// Get active time since the last pause ended today (in seconds)
+pub fn get_active_since_pause() -> i32 {
+    let date = get_today_date();
+    let key = format!("active_since_pause_{}", date);
+    get_setting(&key)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Save active time since the last pause ended (in seconds)
+pub fn save_active_since_pause(seconds: i32) {
+    let date = get_today_date();
+    let key = format!("active_since_pause_{}", date);
+    set_setting(&key, &seconds.to_string());
+}
+
+/// Log a pause event on `date` (the day the pause is charged to)
+pub fn log_pause_event(date: &str, duration_seconds: i32) {
+    let st = crate::clock::local_time();
+    let time_str = format!("{:02}:{:02}:{:02}", st.wHour, st.wMinute, st.wSecond);
+
+    let key = format!("pause_log_{}", date);
+
+    let existing = get_setting(&key).unwrap_or_default();
+    let new_entry = format!("{}:{}s", time_str, duration_seconds);
+
+    let updated = if existing.is_empty() {
+        new_entry
+    } else {
+        format!("{},{}", existing, new_entry)
+    };
+
+    set_setting(&key, &updated);
+}
+
+/// Get pause log for today
+pub fn get_pause_log_today() -> Vec<String> {
+    let date = get_today_date();
+    let key = format!("pause_log_{}", date);
+
+    get_setting(&key)
+        .map(|s| s.split(',').map(|e| e.to_string()).collect())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Time Extension Functions
+// ============================================================================
+
+/// Extension policy configuration
+pub struct ExtensionConfig {
+    pub max_minutes: i32,
+    pub daily_cap_minutes: i32,
+    /// How many extensions may be given per day (0 = unlimited)
+    pub max_per_day: i32,
+}
+
+pub fn get_extension_config() -> ExtensionConfig {
+    ExtensionConfig {
+        max_minutes: get_setting("extend_max_minutes")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(120),
+        daily_cap_minutes: get_setting("extend_daily_cap")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(240),
+        max_per_day: get_setting("max_extensions_per_day")
+            .and_then(|s| s.parse::<i32>().ok())
+            .unwrap_or(0)
+            .max(0),
+    }
+}
+
+/// Get minutes of extension granted today
+pub fn get_extended_today() -> i32 {
+    let date = get_today_date();
+    let key = format!("extended_{}", date);
+    get_setting(&key)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Check if today's limit is 0: no screen time at all unless a parent grants some
+pub fn is_no_screen_time_today() -> bool {
+    get_daily_limit(get_current_weekday()) == 0
+}
+
+/// Get today's limit including extensions granted today, in minutes
+pub fn get_effective_daily_limit() -> i32 {
+    get_daily_limit(get_current_weekday()) as i32 + get_extended_today()
+}
+
+/// Save minutes of extension granted today
+pub fn save_extended_today(minutes: i32) {
+    let date = get_today_date();
+    let key = format!("extended_{}", date);
+    set_setting(&key, &minutes.to_string());
+}
+
+/// Get the number of extensions given today
+pub fn get_extension_count_today() -> i32 {
+    let key = format!("extension_count_{}", get_today_date());
+    get_setting(&key)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Save the number of extensions given today
+pub fn save_extension_count_today(count: i32) {
+    let key = format!("extension_count_{}", get_today_date());
+    set_setting(&key, &count.to_string());
+}
+
+/// Get the self-service extension: minutes per use and uses per day (0 = off)
+pub fn get_self_extend_config() -> (i32, i32) {
+    let minutes = get_setting("self_extend_minutes")
+        .and_then(|s| s.parse::<i32>().ok())
+        .unwrap_or(5)
+        .clamp(1, 60);
+    let per_day = get_setting("self_extend_per_day")
+        .and_then(|s| s.parse::<i32>().ok())
+        .unwrap_or(0)
+        .max(0);
+    (minutes, per_day)
+}
+
+/// Get how often the self-service extension was used today
+pub fn get_self_extend_count_today() -> i32 {
+    let key = format!("self_extend_count_{}", get_today_date());
+    get_setting(&key)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Save how often the self-service extension was used today
+pub fn save_self_extend_count_today(count: i32) {
+    let key = format!("self_extend_count_{}", get_today_date());
+    set_setting(&key, &count.to_string());
+}
+
+/// Append a "HH:MM:SS:<details>" entry to today's `<name>_<date>` log
+fn append_today_log(name: &str, details: &str) {
+    let st = crate::clock::local_time();
+    let time_str = format!("{:02}:{:02}:{:02}", st.wHour, st.wMinute, st.wSecond);
+
+    let date = get_today_date();
+    let key = format!("{}_{}", name, date);
+
+    let existing = get_setting(&key).unwrap_or_default();
+    let new_entry = format!("{}:{}", time_str, details);
+
+    let updated = if existing.is_empty() {
+        new_entry
+    } else {
+        format!("{},{}", existing, new_entry)
+    };
+
+    set_setting(&key, &updated);
+}
+
+/// Get today's entries of a log written by `append_today_log`
+fn get_today_log(name: &str) -> Vec<String> {
+    let date = get_today_date();
+    let key = format!("{}_{}", name, date);
+
+    get_setting(&key)
+        .map(|s| s.split(',').map(|e| e.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Log an extension event for today, including where it came from
+pub fn log_extension_event(minutes: i32, source: &str) {
+    append_today_log("extension_log", &format!("+{}m:{}", minutes, source));
+}
+
+/// Get extension log for today ("HH:MM:SS:+Nm:source")
+pub fn get_extension_log_today() -> Vec<String> {
+    get_today_log("extension_log")
+}
+
+/// Log a manual timer reset (or its undo) with the remaining seconds before and after
+pub fn log_timer_reset(previous_seconds: i32, new_seconds: i32, action: &str) {
+    append_today_log("reset_log", &format!("{}s->{}s:{}", previous_seconds, new_seconds, action));
+}
+
+/// Get timer reset log for today ("HH:MM:SS:600s->7200s:action")
+pub fn get_reset_log_today() -> Vec<String> {
+    get_today_log("reset_log")
+}
+
+/// Log that the screen was blocked and why ("limit", "curfew", "tray", "telegram")
+pub fn log_block_event(reason: &str) {
+    append_today_log("block_log", reason);
+}
+
+/// Get block log for today ("HH:MM:SS:reason")
+pub fn get_block_log_today() -> Vec<String> {
+    get_today_log("block_log")
+}
+
+/// Log an idle interval that just ended: its length and how much of it the timer counted
+pub fn log_idle_interval(seconds: i64, counted_seconds: i64) {
+    append_today_log("idle_log", &format!("{}s:{}s", seconds, counted_seconds));
+}
+
+/// Get idle log for today ("HH:MM:SS:600s:300s" = idle for 600s, 300s of it counted)
+pub fn get_idle_log_today() -> Vec<String> {
+    get_today_log("idle_log")
+}
+
+/// Total idle seconds today and how many of them were counted against the limit
+pub fn get_idle_totals_today() -> (i64, i64) {
+    get_idle_log_today()
+        .iter()
+        .filter_map(|entry| {
+            let mut parts = entry.split(':').skip(3);
+            let idle = parts.next()?.trim_end_matches('s').parse::<i64>().ok()?;
+            let counted = parts.next()?.trim_end_matches('s').parse::<i64>().ok()?;
+            Some((idle, counted))
+        })
+        .fold((0, 0), |(idle, counted), (i, c)| (idle + i, counted + c))
+}
+
+/// Period covered by the stats dialog's summary (each ends today)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SummaryRange {
+    Today,
+    Week,
+    Month,
+}
+
+/// Totals over the per-day history of a `SummaryRange`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UsageSummary {
+    /// First day of the range (YYYY-MM-DD)
+    pub start_date: String,
+    /// Days in the range the computer was used
+    pub days: i64,
+    pub active_seconds: i64,
+    /// Days on which the time ran out
+    pub days_limit_reached: i64,
+    pub extended_minutes: i64,
+    pub pause_seconds: i64,
+}
+
+impl UsageSummary {
+    /// Average active minutes per day the computer was used
+    pub fn average_minutes(&self) -> i64 {
+        if self.days > 0 {
+            self.active_seconds / self.days / 60
+        } else {
+            0
+        }
+    }
+}
+
+/// Sum the per-day history (`<name>_<YYYY-MM-DD>` rows) from `start` to `end` in one query
+fn usage_summary_between(conn: &Connection, start: &str, end: &str) -> rusqlite::Result<UsageSummary> {
+    conn.query_row(
+        "SELECT
+            COUNT(CASE WHEN key LIKE 'session_active_%' THEN 1 END),
+            COALESCE(SUM(CASE WHEN key LIKE 'session_active_%' THEN CAST(value AS INTEGER) END), 0),
+            COUNT(CASE WHEN key LIKE 'remaining_time_%' AND CAST(value AS INTEGER) <= 0 THEN 1 END),
+            COALESCE(SUM(CASE WHEN key LIKE 'extended_%' THEN CAST(value AS INTEGER) END), 0),
+            COALESCE(SUM(CASE WHEN key LIKE 'pause_used_%' THEN CAST(value AS INTEGER) END), 0)
+         FROM settings
+         WHERE (key LIKE 'session_active_%' OR key LIKE 'remaining_time_%'
+                OR key LIKE 'extended_%' OR key LIKE 'pause_used_%')
+           AND substr(key, -10) BETWEEN ?1 AND ?2",
+        params![start, end],
+        |row| {
+            Ok(UsageSummary {
+                start_date: start.to_string(),
+                days: row.get(0)?,
+                active_seconds: row.get(1)?,
+                days_limit_reached: row.get(2)?,
+                extended_minutes: row.get(3)?,
+                pause_seconds: row.get(4)?,
+            })
+        },
+    )
+}
+
+/// Totals for today, this week (from Monday) or this month
+pub fn get_usage_summary(range: SummaryRange) -> UsageSummary {
+    let today = get_today_date();
+    let start = match range {
+        SummaryRange::Today => today.clone(),
+        SummaryRange::Week => get_week_start_date(),
+        SummaryRange::Month => format!("{}-01", &today[..7]),
+    };
+    match with_connection(|conn| usage_summary_between(conn, &start, &today)) {
+        Ok(summary) => summary,
+        Err(e) => {
+            report_db_error("summarizing usage", &e);
+            UsageSummary { start_date: start, ..Default::default() }
+        }
+    }
+}
+
+/// Count the entries of a log written by `append_today_log` over all days (for running totals)
+pub fn count_log_entries_all_days(name: &str) -> u64 {
+    let prefix = format!("{}_", name);
+    let result = with_connection(|conn| {
+        let mut stmt = conn.prepare("SELECT value FROM settings WHERE substr(key, 1, ?1) = ?2")?;
+        let rows = stmt.query_map(params![prefix.len() as i64, prefix], |row| row.get::<_, String>(0))?;
+        rows.collect::<rusqlite::Result<Vec<String>>>()
+    });
+    match result {
+        Ok(values) => values
+            .iter()
+            .filter(|v| !v.is_empty())
+            .map(|v| v.split(',').count() as u64)
+            .sum(),
+        Err(e) => {
+            report_db_error("counting log entries", &e);
+            0
+        }
+    }
+}
+
+/// Get every row of the settings table (settings and the per-day history), sorted by key
+pub fn get_all_settings() -> Result<Vec<(String, String)>, DbError> {
+    let result = with_connection(|conn| {
+        let mut stmt = conn.prepare("SELECT key, value FROM settings ORDER BY key")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    });
+    if let Err(ref e) = result {
+        report_db_error("reading all settings", e);
+    }
+    result
+}
+
+/// Replace the whole settings table in one transaction (restoring a backup)
+pub fn replace_all_settings(rows: &[(String, String)]) -> Result<(), DbError> {
+    let result = with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM settings", [])?;
+        {
+            let mut stmt = tx.prepare("INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)")?;
+            for (key, value) in rows {
+                stmt.execute(params![key, value])?;
+            }
+        }
+        tx.commit()
+    });
+    if let Err(ref e) = result {
+        report_db_error("restoring settings", e);
+    }
+    result
+}
+
+/// Get the executables marked productive (`app_category_<exe>` = "productive").
+/// Time in front of them doesn't count; apps without an entry count as usual.
+pub fn get_productive_apps() -> Vec<String> {
+    let result = with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT substr(key, 14) FROM settings WHERE substr(key, 1, 13) = 'app_category_' AND value = 'productive' ORDER BY key",
+        )?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    });
+    result.unwrap_or_else(|e| {
+        report_db_error("reading app categories", &e);
+        Vec::new()
+    })
+}
+
+/// Settings rows that make `apps` the productive list (names as returned by
+/// `apps::normalize_exe`), for the settings batch. Apps taken off the list are set
+/// back to "counts" rather than deleted, so the whole change is one batch of upserts.
+pub fn productive_app_updates(apps: &[String]) -> Vec<(String, String)> {
+    app_category_updates(&get_productive_apps(), apps)
+}
+
+fn app_category_updates(previous: &[String], apps: &[String]) -> Vec<(String, String)> {
+    let removed = previous.iter().filter(|app| !apps.contains(app)).map(|app| (app, "counts"));
+    let listed = apps.iter().map(|app| (app, "productive"));
+    removed
+        .chain(listed)
+        .map(|(app, category)| (format!("app_category_{}", app), category.to_string()))
+        .collect()
+}
+
+/// Log focus mode turning on ("on:60m:tray", "on:open:telegram") or off ("off:45m")
+pub fn log_focus_event(details: &str) {
+    append_today_log("focus_log", details);
+}
+
+/// Get focus mode log for today ("HH:MM:SS:on:60m:source" / "HH:MM:SS:off:45m")
+pub fn get_focus_log_today() -> Vec<String> {
+    get_today_log("focus_log")
+}
+
+// ============================================================================
+// Idle Detection Functions
+// ============================================================================
+
+/// Check if idle detection is enabled
+pub fn is_idle_enabled() -> bool {
+    get_setting("idle_enabled")
+        .map(|s| s == "1")
+        .unwrap_or(true)
+}
+
+/// Get idle timeout in minutes (minimum 1)
+pub fn get_idle_timeout_minutes() -> u32 {
+    get_setting("idle_timeout_minutes")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(5)
+        .max(1)
+}
+
+/// Get the interval between periodic saves of the counters (5-300 seconds, default 15)
+pub fn get_autosave_interval_seconds() -> u32 {
+    get_setting("autosave_interval_seconds")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(15)
+        .clamp(5, 300)
+}
+
+// ============================================================================
+// Telegram Bot Configuration
+// ============================================================================
+
+/// Settings keys for Telegram bot
+pub const TELEGRAM_BOT_TOKEN: &str = "telegram_bot_token";
+pub const TELEGRAM_ADMIN_CHAT_ID: &str = "telegram_admin_chat_id";
+pub const TELEGRAM_ENABLED: &str = "telegram_enabled";
+
+/// Telegram bot configuration
+pub struct TelegramConfig {
+    pub bot_token: Option<String>,
+    pub admin_chat_id: Option<i64>,
+    pub enabled: bool,
+}
+
+/// Get Telegram bot configuration
+pub fn get_telegram_config() -> TelegramConfig {
+    TelegramConfig {
+        bot_token: get_setting(TELEGRAM_BOT_TOKEN),
+        admin_chat_id: get_setting(TELEGRAM_ADMIN_CHAT_ID)
+            .and_then(|s| s.parse::<i64>().ok()),
+        enabled: get_setting(TELEGRAM_ENABLED)
+            .map(|s| s == "true")
+            .unwrap_or(false),
+    }
+}
+
+/// Minimum seconds between state-changing Telegram commands from one chat (default 5)
+pub fn get_telegram_rate_limit_seconds() -> u64 {
+    get_setting("telegram_rate_limit")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(5)
+}
+
+/// Get the webhook URL for notification events (empty = off)
+pub fn get_webhook_url() -> String {
+    get_setting("webhook_url")
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Daily email report settings
+pub struct EmailConfig {
+    pub enabled: bool,
+    /// Minutes after midnight the report is sent
+    pub report_minute: u32,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    /// DPAPI-encrypted, as stored
+    pub password: String,
+    pub recipients: Vec<String>,
+}
+
+pub fn get_email_config() -> EmailConfig {
+    let report_minute = get_setting("email_report_time")
+        .and_then(|s| {
+            let (h, m) = s.trim().split_once(':')?;
+            let (h, m): (u32, u32) = (h.parse().ok()?, m.parse().ok()?);
+            (h < 24 && m < 60).then_some(h * 60 + m)
+        })
+        .unwrap_or(20 * 60);
+    EmailConfig {
+        enabled: get_setting("email_enabled").map(|s| s == "1").unwrap_or(false),
+        report_minute,
+        host: get_setting("smtp_host").map(|s| s.trim().to_string()).unwrap_or_default(),
+        port: get_setting("smtp_port").and_then(|s| s.parse().ok()).unwrap_or(587),
+        username: get_setting("smtp_username").map(|s| s.trim().to_string()).unwrap_or_default(),
+        password: get_setting("smtp_password").unwrap_or_default(),
+        recipients: get_setting("email_to")
+            .unwrap_or_default()
+            .split(',')
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty())
+            .collect(),
+    }
+}
+
+/// MQTT broker settings
+pub struct MqttConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    /// DPAPI-encrypted, as stored
+    pub password: String,
+    /// Topic prefix without a trailing slash
+    pub prefix: String,
+}
+
+pub fn get_mqtt_config() -> MqttConfig {
+    let prefix = get_setting("mqtt_prefix")
+        .map(|s| s.trim().trim_end_matches('/').to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "screentime".to_string());
+    MqttConfig {
+        enabled: get_setting("mqtt_enabled").map(|s| s == "1").unwrap_or(false),
+        host: get_setting("mqtt_host").map(|s| s.trim().to_string()).unwrap_or_default(),
+        port: get_setting("mqtt_port").and_then(|s| s.parse().ok()).unwrap_or(1883),
+        username: get_setting("mqtt_username").map(|s| s.trim().to_string()).unwrap_or_default(),
+        password: get_setting("mqtt_password").unwrap_or_default(),
+        prefix,
+    }
+}
+
+/// Metrics endpoint settings
+pub struct HttpConfig {
+    pub enabled: bool,
+    /// Address and port to listen on, e.g. "127.0.0.1:9184"
+    pub listen: String,
+    /// DPAPI-encrypted bearer token, as stored
+    pub token: String,
+}
+
+pub fn get_http_config() -> HttpConfig {
+    HttpConfig {
+        enabled: get_setting("http_enabled").map(|s| s == "1").unwrap_or(false),
+        listen: get_setting("http_listen")
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "127.0.0.1:9184".to_string()),
+        token: get_setting("http_token").unwrap_or_default(),
+    }
+}
+
+/// Save Telegram bot configuration
+pub fn set_telegram_config(token: &str, chat_id: &str, enabled: bool) {
+    let _ = set_settings_batch(&[
+        (TELEGRAM_BOT_TOKEN, token),
+        (TELEGRAM_ADMIN_CHAT_ID, chat_id),
+        (TELEGRAM_ENABLED, if enabled { "true" } else { "false" }),
+    ]);
+}
+
+/// Add a Telegram notification to the outgoing queue, dropping the oldest beyond `max_entries`
+pub fn queue_notification(text: &str, max_entries: usize) {
+    let result = with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO notification_queue (created, text) VALUES (?1, ?2)",
+            params![get_current_timestamp(), text],
+        )?;
+        conn.execute(
+            "DELETE FROM notification_queue WHERE id NOT IN
+                (SELECT id FROM notification_queue ORDER BY id DESC LIMIT ?1)",
+            params![max_entries as i64],
+        )?;
+        Ok(())
+    });
+    if let Err(e) = result {
+        report_db_error("queueing notification", &e);
+    }
+}
+
+/// Get queued Telegram notifications, oldest first: (id, text).
+/// Entries older than `max_age_seconds` are discarded first.
+pub fn get_queued_notifications(max_age_seconds: i64) -> Vec<(i64, String)> {
+    let result = with_connection(|conn| {
+        conn.execute(
+            "DELETE FROM notification_queue WHERE created < ?1",
+            params![get_current_timestamp() - max_age_seconds],
+        )?;
+        let mut stmt = conn.prepare("SELECT id, text FROM notification_queue ORDER BY id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    });
+    result.unwrap_or_else(|e| {
+        report_db_error("reading notification queue", &e);
+        Vec::new()
+    })
+}
+
+/// Remove a notification from the queue once it was sent
+pub fn remove_queued_notification(id: i64) {
+    let result = with_connection(|conn| {
+        conn.execute("DELETE FROM notification_queue WHERE id = ?1", params![id])
+    });
+    if let Err(e) = result {
+        report_db_error("removing notification", &e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn dates_follow_the_injected_clock() {
+        use crate::clock::{with_clock, MockClock};
+
+        // Thursday 2026-12-31: the next day is in the next year
+        with_clock(MockClock::at(2026, 12, 31, 4, 23, 59), || {
+            assert_eq!(get_today_date(), "2026-12-31");
+            assert_eq!(get_current_weekday(), 3);
+            assert_eq!(get_date_offset(1), "2027-01-01");
+            assert_eq!(get_date_offset(-365), "2025-12-31");
+        });
+
+        // Sunday maps to 6, and a leap day rolls into March
+        with_clock(MockClock::at(2032, 2, 29, 0, 0, 0), || {
+            assert_eq!(get_current_weekday(), 6);
+            assert_eq!(get_date_offset(1), "2032-03-01");
+        });
+
+        let clock = MockClock { timestamp: 1_800_000_000, ..MockClock::at(2027, 1, 15, 5, 8, 0) };
+        with_clock(clock, || assert_eq!(get_current_timestamp(), 1_800_000_000));
+    }
+
+    #[test]
+    fn concurrent_readers_and_writers_do_not_fail() {
+        let dir = test_dir("concurrent");
+        let db_path = dir.join("data.db");
+
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            configure_connection(&conn).unwrap();
+            create_tables(&conn).unwrap();
+        }
+
+        let handles: Vec<_> = (0..8)
+            .map(|worker| {
+                let path = db_path.clone();
+                thread::spawn(move || {
+                    let conn = Connection::open(&path).unwrap();
+                    configure_connection(&conn).unwrap();
+                    for i in 0..50 {
+                        if worker % 2 == 0 {
+                            conn.execute(
+                                "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+                                params![format!("key_{}", worker), i.to_string()],
+                            )
+                            .unwrap();
+                        } else {
+                            conn.query_row("SELECT COUNT(*) FROM settings", [], |row| {
+                                row.get::<_, i64>(0)
+                            })
+                            .unwrap();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("database worker failed");
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    fn has_corrupt_copy(dir: &Path) -> bool {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .any(|entry| entry.file_name().to_string_lossy().starts_with("data.corrupt-"))
+    }
+
+    #[test]
+    fn garbage_file_is_moved_aside_and_recreated() {
+        let dir = test_dir("recover-garbage");
+        let db_path = dir.join("data.db");
+        std::fs::write(&db_path, vec![0x5a; 8192]).unwrap();
+
+        let conn = open_or_recover_at(&db_path).unwrap();
+        create_tables(&conn).unwrap();
+        drop(conn);
+
+        assert!(has_corrupt_copy(&dir));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn wal_is_kept_with_the_moved_database() {
+        let dir = test_dir("recover-wal");
+        for name in ["data.db", "data.db-wal", "data.db-shm"] {
+            std::fs::write(dir.join(name), name).unwrap();
+        }
+
+        move_database(&dir.join("data.db"), &dir.join("data.corrupt-1.db")).unwrap();
+
+        assert_eq!(std::fs::read_to_string(dir.join("data.corrupt-1.db")).unwrap(), "data.db");
+        assert_eq!(std::fs::read_to_string(dir.join("data.corrupt-1.db-wal")).unwrap(), "data.db-wal");
+        for name in ["data.db", "data.db-wal", "data.db-shm"] {
+            assert!(!dir.join(name).exists());
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn locked_database_is_not_mistaken_for_corruption() {
+        let dir = test_dir("recover-locked");
+        let db_path = dir.join("data.db");
+
+        // Another instance holds an exclusive lock (rollback journal, so readers are blocked too)
+        let holder = Connection::open(&db_path).unwrap();
+        create_tables(&holder).unwrap();
+        holder.execute("INSERT INTO settings (key, value) VALUES ('passcode', '4711')", []).unwrap();
+        holder.execute_batch("BEGIN EXCLUSIVE").unwrap();
+
+        assert!(open_or_recover_at(&db_path).is_err());
+        assert!(!has_corrupt_copy(&dir));
+
+        holder.execute_batch("COMMIT").unwrap();
+        let conn = open_or_recover_at(&db_path).unwrap();
+        let passcode: String = conn
+            .query_row("SELECT value FROM settings WHERE key = 'passcode'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(passcode, "4711");
+        drop((holder, conn));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn usage_summary_adds_up_the_days_in_range() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        for (key, value) in [
+            ("session_active_2026-10-12", "3600"),
+            ("session_active_2026-10-13", "5400"),
+            ("session_active_2026-10-05", "9999"),
+            ("remaining_time_2026-10-12", "0"),
+            ("remaining_time_2026-10-13", "600"),
+            ("extended_2026-10-12", "30"),
+            ("pause_used_2026-10-13", "900"),
+            ("extension_log_2026-10-12", "08:00:00:+30m:tray"),
+            ("limit_monday", "120"),
+        ] {
+            conn.execute("INSERT INTO settings (key, value) VALUES (?1, ?2)", params![key, value]).unwrap();
+        }
+
+        let week = usage_summary_between(&conn, "2026-10-12", "2026-10-15").unwrap();
+        assert_eq!(week.days, 2);
+        assert_eq!(week.active_seconds, 9000);
+        assert_eq!(week.average_minutes(), 75);
+        assert_eq!(week.days_limit_reached, 1);
+        assert_eq!(week.extended_minutes, 30);
+        assert_eq!(week.pause_seconds, 900);
+
+        let empty = usage_summary_between(&conn, "2026-11-01", "2026-11-30").unwrap();
+        assert_eq!(empty.days, 0);
+        assert_eq!(empty.average_minutes(), 0);
+    }
+
+    #[test]
+    fn removed_productive_apps_go_back_to_counting() {
+        let previous = vec!["minecraft.exe".to_string(), "winword.exe".to_string()];
+        let apps = vec!["excel.exe".to_string(), "winword.exe".to_string()];
+        let updates = app_category_updates(&previous, &apps);
+        assert_eq!(
+            updates,
+            vec![
+                ("app_category_minecraft.exe".to_string(), "counts".to_string()),
+                ("app_category_excel.exe".to_string(), "productive".to_string()),
+                ("app_category_winword.exe".to_string(), "productive".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn session_active_time_survives_restart() {
+        with_test_database(|restart| {
+            save_session_active_time(1234);
+
+            // Second run on the same day reads it back
+            restart();
+            assert_eq!(get_session_active_time(), 1234);
+        });
+    }
+}
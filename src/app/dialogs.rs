@@ -0,0 +1,5310 @@
+//! Dialogs module for Screen Time Manager
+//! Contains passcode verification and settings dialog implementations
+
+use std::cell::Cell;
+use std::mem::zeroed;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicIsize, AtomicPtr, Ordering};
+use std::sync::Mutex;
+use windows::{
+    core::{w, PCWSTR, PWSTR},
+    Win32::{
+        Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM},
+        Graphics::Gdi::{
+            BeginPaint, CreateFontW, CreatePen, CreateRoundRectRgn, CreateSolidBrush, DeleteObject,
+            DrawTextW, Ellipse, EndPaint, FillRect, InvalidateRect, LineTo, MoveToEx, SelectObject,
+            SetBkMode, SetTextColor, SetWindowRgn, DT_CENTER, DT_END_ELLIPSIS, DT_SINGLELINE, DT_VCENTER, DT_WORDBREAK, FW_BOLD,
+            FW_NORMAL, HDC, HRGN, PAINTSTRUCT, PS_SOLID, TRANSPARENT,
+        },
+        System::LibraryLoader::GetModuleHandleW,
+        UI::{
+            Controls::*,
+            Controls::Dialogs::{
+                GetOpenFileNameW, GetSaveFileNameW, OFN_FILEMUSTEXIST, OFN_OVERWRITEPROMPT, OFN_PATHMUSTEXIST, OPENFILENAMEW,
+            },
+            Input::KeyboardAndMouse::{EnableWindow, SetFocus, VK_ESCAPE, VK_RETURN, VK_SPACE},
+            WindowsAndMessaging::*,
+        },
+    },
+};
+
+use crate::constants::*;
+use crate::database::{get_passcode, get_setting, set_settings_batch, get_telegram_config, get_webhook_url, get_email_config, get_mqtt_config, get_http_config, WEEKDAY_KEYS, TELEGRAM_BOT_TOKEN, TELEGRAM_ADMIN_CHAT_ID, TELEGRAM_ENABLED, get_pause_used_today, get_pause_config, get_pause_log_today, is_pause_enabled, is_idle_enabled, get_idle_timeout_minutes, get_mini_overlay_scale, get_mini_overlay_format, get_display_mode, get_display_corner, is_mini_overlay_enabled, get_block_text_scale, is_high_contrast_enabled, get_rampdown_config, get_week_start_date, get_next_rampdown_date, get_allowed_hours, is_defer_warnings_fullscreen, is_respect_focus_assist, get_warning_config, get_warning_position, get_warning_margin, get_winddown_minutes, get_winddown_interval_minutes, get_tray_left_click, get_pause_indicator, is_warn_only, get_productive_apps, productive_app_updates, get_counted_users, get_extended_today, get_extension_config, get_extension_count_today, get_self_extend_config, get_effective_daily_limit, get_extension_log_today, get_idle_totals_today, get_lockout_config, get_min_block_duration_seconds, is_clock_change_block, is_clock_guard_enabled, is_default_passcode, DEFAULT_PASSCODE, is_pause_passcode_required, is_kiosk_mode, get_log_level, get_usage_summary, SummaryRange};
+use crate::dpi::scale;
+use crate::duration;
+use crate::i18n::{self, Language};
+use crate::locale;
+
+// Control IDs for settings dialog
+const ID_SETTINGS_BASE: i32 = 2000;
+const ID_SETTINGS_SAVE: i32 = 2100;
+const ID_SETTINGS_CANCEL: i32 = 2101;
+const ID_CURRENT_PASSCODE: i32 = 2110;
+const ID_NEW_PASSCODE: i32 = 2111;
+const ID_CONFIRM_PASSCODE: i32 = 2112;
+const ID_LANGUAGE_COMBO: i32 = 2120;
+const ID_TELEGRAM_WIZARD: i32 = 2130;
+const ID_BACKUP_CREATE: i32 = 2140;
+const ID_BACKUP_RESTORE: i32 = 2141;
+const ID_FILL_ALL_DAYS: i32 = 2150;
+const ID_FILL_WEEKDAYS: i32 = 2151;
+const ID_FILL_WEEKEND: i32 = 2152;
+
+// Settings dialog state (window procedures only run on the UI thread)
+thread_local! {
+    static SETTINGS_EDIT_HANDLES: Cell<Option<SettingsEditHandles>> = const { Cell::new(None) };
+}
+
+/// Handles of the open settings dialog's controls
+fn settings_edit_handles() -> Option<SettingsEditHandles> {
+    SETTINGS_EDIT_HANDLES.with(Cell::get)
+}
+
+/// Mini overlay size presets offered in settings (i18n key, percent)
+const MINI_OVERLAY_SIZE_PRESETS: [(&str, i32); 3] = [
+    ("settings.mini_size.small", 75),
+    ("settings.mini_size.medium", 100),
+    ("settings.mini_size.large", 150),
+];
+
+/// Countdown display modes offered in settings (i18n key, setting value)
+const DISPLAY_MODES: [(&str, &str); 3] = [
+    ("settings.display_mode.mini", "mini"),
+    ("settings.display_mode.large", "large"),
+    ("settings.display_mode.hidden", "hidden"),
+];
+
+/// Screen corners for the countdown offered in settings (i18n key, setting value)
+const DISPLAY_CORNERS: [(&str, &str); 4] = [
+    ("settings.display_corner.top_right", "top_right"),
+    ("settings.display_corner.top_left", "top_left"),
+    ("settings.display_corner.bottom_right", "bottom_right"),
+    ("settings.display_corner.bottom_left", "bottom_left"),
+];
+
+/// Corner timer formats offered in settings (i18n key, setting value)
+const MINI_OVERLAY_FORMATS: [(&str, &str); 4] = [
+    ("settings.mini_format.compact", "compact"),
+    ("settings.mini_format.hhmm", "hhmm"),
+    ("settings.mini_format.minutes", "minutes"),
+    ("settings.mini_format.fraction", "fraction"),
+];
+
+/// Blocking panel size presets offered in settings (i18n key, percent)
+const BLOCK_TEXT_SIZE_PRESETS: [(&str, i32); 4] = [
+    ("settings.block_size.normal", 100),
+    ("settings.block_size.large", 125),
+    ("settings.block_size.larger", 150),
+    ("settings.block_size.largest", 200),
+];
+
+/// What happens when the time runs out (i18n key, setting value). Locking and shutting
+/// down are `expiry_action`s; the blocking screen is shown with them too.
+const ENFORCEMENT_MODES: [(&str, &str); 4] = [
+    ("settings.enforcement.block", "block"),
+    ("settings.enforcement.lock", "lock"),
+    ("settings.enforcement.shutdown", "shutdown"),
+    ("settings.enforcement.warn_only", "warn_only"),
+];
+
+/// A lock screen button that can be switched off in settings (i18n key, setting key,
+/// current value)
+type BlockButtonSetting = (&'static str, &'static str, fn() -> bool);
+
+/// Lock screen buttons offered in settings, laid out two per row
+const BLOCK_BUTTON_SETTINGS: [BlockButtonSetting; 4] = [
+    ("settings.block_allow_extend", "block_allow_extend", crate::database::is_block_extend_allowed),
+    ("settings.block_allow_shutdown", "block_allow_shutdown", crate::database::is_block_shutdown_allowed),
+    ("settings.block_allow_lock", "block_allow_lock", crate::database::is_block_lock_allowed),
+    ("settings.block_allow_logoff", "block_allow_logoff", crate::database::is_block_logoff_allowed),
+];
+
+/// Tray icon left-click actions offered in settings (i18n key, setting value)
+const TRAY_CLICK_ACTIONS: [(&str, &str); 2] = [
+    ("settings.tray_click.balloon", "balloon"),
+    ("settings.tray_click.stats", "stats"),
+];
+
+/// Pause indicators of the corner timer offered in settings (i18n key, setting value)
+const PAUSE_INDICATORS: [(&str, &str); 3] = [
+    ("settings.pause_indicator.bars", "bars"),
+    ("settings.pause_indicator.text", "text"),
+    ("settings.pause_indicator.none", "none"),
+];
+
+/// Log levels for app.log offered in settings (i18n key, setting value)
+const LOG_LEVELS: [(&str, &str); 4] = [
+    ("settings.log_level.error", "error"),
+    ("settings.log_level.warn", "warn"),
+    ("settings.log_level.info", "info"),
+    ("settings.log_level.debug", "debug"),
+];
+
+/// Warning banner positions offered in settings (i18n key, setting value)
+const WARNING_POSITIONS: [(&str, &str); 3] = [
+    ("settings.warning_position.top", "top"),
+    ("settings.warning_position.center", "center"),
+    ("settings.warning_position.bottom", "bottom"),
+];
+
+#[derive(Clone, Copy)]
+struct SettingsEditHandles {
+    daily_limits: [HWND; 7],
+    // Weekly limit ramp-down
+    rampdown_step: HWND,
+    rampdown_floor: HWND,
+    // Allowed hours (curfew)
+    allowed_hours_weekday: HWND,
+    allowed_hours_weekend: HWND,
+    productive_apps: HWND,
+    counted_users: HWND,
+    warning1_minutes: HWND,
+    warning1_message: HWND,
+    warning1_duration: HWND,
+    warning1_minutes_weekend: HWND,
+    warning1_message_weekend: HWND,
+    warning2_minutes: HWND,
+    warning2_message: HWND,
+    warning2_duration: HWND,
+    warning2_minutes_weekend: HWND,
+    warning2_message_weekend: HWND,
+    defer_warnings_fullscreen: HWND,
+    respect_focus_assist: HWND,
+    warning_position: HWND,
+    warning_margin: HWND,
+    winddown_minutes: HWND,
+    winddown_interval: HWND,
+    blocking_message: HWND,
+    secondary_block_message: HWND,
+    block_background_image: HWND,
+    current_passcode: HWND,
+    new_passcode: HWND,
+    confirm_passcode: HWND,
+    lockout_attempts: HWND,
+    lockout_delay: HWND,
+    min_block_duration: HWND,
+    max_extensions_per_day: HWND,
+    self_extend_minutes: HWND,
+    self_extend_per_day: HWND,
+    clock_change_block: HWND,
+    clock_guard: HWND,
+    pause_requires_passcode: HWND,
+    kiosk_mode: HWND,
+    // Telegram settings
+    telegram_token: HWND,
+    telegram_chat_id: HWND,
+    webhook_url: HWND,
+    email_enabled: HWND,
+    email_report_time: HWND,
+    smtp_host: HWND,
+    smtp_port: HWND,
+    smtp_username: HWND,
+    smtp_password: HWND,
+    email_to: HWND,
+    mqtt_enabled: HWND,
+    mqtt_host: HWND,
+    mqtt_port: HWND,
+    mqtt_username: HWND,
+    mqtt_password: HWND,
+    mqtt_prefix: HWND,
+    http_enabled: HWND,
+    http_listen: HWND,
+    http_token: HWND,
+    telegram_enabled: HWND,
+    // Lock screen timeout
+    lock_screen_timeout: HWND,
+    // Blocking panel size preset
+    block_text_size: HWND,
+    // Lock screen buttons, as in BLOCK_BUTTON_SETTINGS
+    block_buttons: [HWND; 4],
+    enforcement_mode: HWND,
+    expiry_action_delay: HWND,
+    // Idle detection settings
+    idle_enabled: HWND,
+    idle_timeout_minutes: HWND,
+    // Language setting
+    language: HWND,
+    // Countdown display mode and corner
+    display_mode: HWND,
+    display_corner: HWND,
+    // Mini overlay size preset
+    mini_overlay_size: HWND,
+    mini_overlay_format: HWND,
+    // High-contrast overlays
+    high_contrast: HWND,
+    tray_left_click: HWND,
+    pause_indicator: HWND,
+    status_hotkey: HWND,
+    log_level: HWND,
+    // Password for Backup.../Restore... (not a setting, never saved)
+    backup_password: HWND,
+}
+
+/// Button procedure replaced by `create_reveal_button` (the same for every BUTTON)
+static BUTTON_PROC: AtomicIsize = AtomicIsize::new(0);
+
+/// Password character if the field's own one is unknown (comctl32 v6 default)
+const PASSWORD_CHAR: usize = 0x25CF;
+
+/// Create a "Show" button that reveals the passcode in `edit` only while it is held down
+/// (mouse or space bar), so the digits never stay visible by accident
+pub unsafe fn create_reveal_button(parent: HWND, edit: HWND, id: i32) -> Option<HWND> {
+    let hinstance = GetModuleHandleW(None).ok()?;
+    let text = i18n::wide("passcode.show");
+    let button = CreateWindowExW(
+        WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(text.as_ptr()),
+        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+        0, 0, 0, 0, parent, HMENU(id as _), hinstance, None,
+    ).ok()?;
+    SetWindowLongPtrW(button, GWLP_USERDATA, edit.0 as isize);
+    let previous = SetWindowLongPtrW(button, GWLP_WNDPROC, reveal_button_proc as *const () as isize);
+    BUTTON_PROC.store(previous, Ordering::SeqCst);
+    Some(button)
+}
+
+unsafe extern "system" fn reveal_button_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    let edit = HWND(GetWindowLongPtrW(hwnd, GWLP_USERDATA) as _);
+    let space = wparam.0 == VK_SPACE.0 as usize;
+    match msg {
+        WM_LBUTTONDOWN => set_passcode_revealed(edit, true),
+        WM_KEYDOWN if space => set_passcode_revealed(edit, true),
+        WM_LBUTTONUP | WM_CAPTURECHANGED | WM_KILLFOCUS => set_passcode_revealed(edit, false),
+        WM_KEYUP if space => set_passcode_revealed(edit, false),
+        _ => {}
+    }
+    let original = std::mem::transmute::<isize, WNDPROC>(BUTTON_PROC.load(Ordering::SeqCst));
+    let result = CallWindowProcW(original, hwnd, msg, wparam, lparam);
+    // Typing continues in the passcode field after a mouse press
+    if msg == WM_LBUTTONUP {
+        let _ = SetFocus(edit);
+    }
+    result
+}
+
+/// Show the digits in a passcode field, or hide them again behind its password character
+/// (kept in the field's GWLP_USERDATA while revealed)
+unsafe fn set_passcode_revealed(edit: HWND, revealed: bool) {
+    if edit.0.is_null() {
+        return;
+    }
+    let current = SendMessageW(edit, EM_GETPASSWORDCHAR, WPARAM(0), LPARAM(0)).0 as usize;
+    if revealed == (current == 0) {
+        return;
+    }
+    let password_char = if revealed {
+        SetWindowLongPtrW(edit, GWLP_USERDATA, current as isize);
+        0
+    } else {
+        match GetWindowLongPtrW(edit, GWLP_USERDATA) {
+            0 => PASSWORD_CHAR,
+            stored => stored as usize,
+        }
+    };
+    SendMessageW(edit, EM_SETPASSWORDCHAR, WPARAM(password_char), LPARAM(0));
+    let _ = InvalidateRect(edit, None, true);
+}
+
+/// Run `dialog` modally: disable `parent` and pump messages until the dialog window is
+/// destroyed. Dialogs must not call PostQuitMessage when they close: WM_QUIT is only for the
+/// loop in `main`. If it arrives here (the app is shutting down), the dialog is closed and
+/// WM_QUIT posted again for the outer loop. `dialog_keys` routes Tab, Enter and Esc through
+/// IsDialogMessage.
+pub unsafe fn run_modal(dialog: HWND, parent: HWND, dialog_keys: bool) {
+    // EnableWindow returns whether the parent was already disabled (e.g. by an outer dialog)
+    let reenable = !parent.0.is_null() && !EnableWindow(parent, false).as_bool();
+
+    let mut msg: MSG = zeroed();
+    while IsWindow(dialog).as_bool() {
+        match GetMessageW(&mut msg, None, 0, 0).0 {
+            0 => {
+                DestroyWindow(dialog).ok();
+                PostQuitMessage(msg.wParam.0 as i32);
+                break;
+            }
+            -1 => break,
+            _ => {}
+        }
+        if dialog_keys && IsDialogMessageW(dialog, &msg).as_bool() {
+            continue;
+        }
+        let _ = TranslateMessage(&msg);
+        DispatchMessageW(&msg);
+    }
+
+    if reenable {
+        let _ = EnableWindow(parent, true);
+        if IsWindowVisible(parent).as_bool() {
+            let _ = SetForegroundWindow(parent);
+        }
+    }
+}
+
+/// Verify passcode before allowing sensitive operations
+pub unsafe fn verify_passcode_for_quit(parent_hwnd: HWND) -> bool {
+    let stored_passcode = match get_passcode() {
+        Some(p) => p,
+        None => return true,
+    };
+
+    let dialog_class = w!("ScreenTimePasscodeDialogNice");
+    let hinstance = GetModuleHandleW(None).expect("Failed to get module handle");
+
+    static DIALOG_RESULT: AtomicBool = AtomicBool::new(false);
+    static DIALOG_EDIT_HWND: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+    static DIALOG_STORED_CODE: Mutex<Option<String>> = Mutex::new(None);
+    static DIALOG_ERROR: AtomicBool = AtomicBool::new(false);
+    static DIALOG_LOCKED_OUT: AtomicBool = AtomicBool::new(false);
+
+    DIALOG_RESULT.store(false, Ordering::SeqCst);
+    *DIALOG_STORED_CODE.lock().unwrap() = Some(stored_passcode);
+    let locked_out = crate::blocking::passcode_lockout_remaining() > 0;
+    DIALOG_ERROR.store(locked_out, Ordering::SeqCst);
+    DIALOG_LOCKED_OUT.store(locked_out, Ordering::SeqCst);
+
+    unsafe extern "system" fn dialog_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        match msg {
+            WM_CREATE => {
+                let hinstance = GetModuleHandleW(None).unwrap();
+
+                let edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0),
+                    w!("EDIT"),
+                    w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER
+                        | WINDOW_STYLE(ES_CENTER as u32 | ES_PASSWORD as u32 | ES_NUMBER as u32),
+                    scale(100), scale(100), scale(150), scale(36),
+                    hwnd,
+                    HMENU(101 as _),
+                    hinstance,
+                    None,
+                ).ok();
+
+                if let Some(e) = edit {
+                    DIALOG_EDIT_HWND.store(e.0, Ordering::SeqCst);
+                    SendMessageW(e, EM_SETLIMITTEXT, WPARAM(4), LPARAM(0));
+
+                    let hfont = CreateFontW(
+                        scale(28), 0, 0, 0,
+                        FW_BOLD.0 as i32,
+                        0, 0, 0, 0, 0, 0, 5, 0,
+                        w!("Segoe UI"),
+                    );
+                    SendMessageW(e, WM_SETFONT, WPARAM(hfont.0 as usize), LPARAM(1));
+                    let _ = SetFocus(e);
+                }
+
+                // Button font
+                let btn_font = CreateFontW(
+                    scale(14), 0, 0, 0,
+                    FW_NORMAL.0 as i32,
+                    0, 0, 0, 0, 0, 0, 5, 0,
+                    w!("Segoe UI"),
+                );
+
+                // OK Button
+                let ok_btn_text = i18n::wide("button.ok");
+                let ok_btn = CreateWindowExW(
+                    WINDOW_EX_STYLE(0),
+                    w!("BUTTON"),
+                    PCWSTR(ok_btn_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    scale(70), scale(200), scale(100), scale(40),
+                    hwnd,
+                    HMENU(1 as _),
+                    hinstance,
+                    None,
+                );
+                if let Ok(h) = ok_btn { SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1)); }
+
+                // Cancel Button
+                let cancel_btn_text = i18n::wide("button.cancel");
+                let cancel_btn = CreateWindowExW(
+                    WINDOW_EX_STYLE(0),
+                    w!("BUTTON"),
+                    PCWSTR(cancel_btn_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    scale(180), scale(200), scale(100), scale(40),
+                    hwnd,
+                    HMENU(2 as _),
+                    hinstance,
+                    None,
+                );
+                if let Ok(h) = cancel_btn { SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1)); }
+
+                // Hold-to-show button next to the passcode field
+                if let Some(e) = edit {
+                    if let Some(reveal) = create_reveal_button(hwnd, e, 3) {
+                        let _ = MoveWindow(reveal, scale(255), scale(100), scale(70), scale(36), true);
+                        SendMessageW(reveal, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1));
+                    }
+                }
+
+                // Ticks the lockout countdown
+                SetTimer(hwnd, 1, 1000, None);
+
+                LRESULT(0)
+            }
+            WM_TIMER => {
+                if DIALOG_ERROR.load(Ordering::SeqCst) {
+                    let mut rect: RECT = zeroed();
+                    GetClientRect(hwnd, &mut rect).ok();
+                    let err_rect = RECT { left: 0, top: scale(150), right: rect.right, bottom: scale(170) };
+                    let _ = InvalidateRect(hwnd, Some(&err_rect), true);
+                    // The error stays up until the next attempt; only the countdown goes away
+                    if crate::blocking::passcode_lockout_remaining() == 0 && DIALOG_LOCKED_OUT.swap(false, Ordering::SeqCst) {
+                        DIALOG_ERROR.store(false, Ordering::SeqCst);
+                    }
+                }
+                LRESULT(0)
+            }
+            WM_PAINT => {
+                let mut ps: PAINTSTRUCT = zeroed();
+                let hdc = BeginPaint(hwnd, &mut ps);
+
+                let mut rect: RECT = zeroed();
+                GetClientRect(hwnd, &mut rect).ok();
+
+                let bg_brush = CreateSolidBrush(COLORREF(0x00F0F0F0));
+                FillRect(hdc, &rect, bg_brush);
+                let _ = DeleteObject(bg_brush);
+
+                let title_font = CreateFontW(
+                    scale(20), 0, 0, 0,
+                    FW_BOLD.0 as i32,
+                    0, 0, 0, 0, 0, 0, 5, 0,
+                    w!("Segoe UI"),
+                );
+                let old_font = SelectObject(hdc, title_font);
+                SetTextColor(hdc, COLORREF(0x00333333));
+                SetBkMode(hdc, TRANSPARENT);
+
+                let mut title_rect = RECT { left: 0, top: scale(25), right: rect.right, bottom: scale(55) };
+                let title_text: Vec<u16> = i18n::t("window.passcode").encode_utf16().collect();
+                DrawTextW(
+                    hdc,
+                    &mut title_text.clone(),
+                    &mut title_rect,
+                    DT_CENTER | DT_SINGLELINE,
+                );
+
+                let sub_font = CreateFontW(
+                    scale(13), 0, 0, 0,
+                    FW_NORMAL.0 as i32,
+                    0, 0, 0, 0, 0, 0, 5, 0,
+                    w!("Segoe UI"),
+                );
+                SelectObject(hdc, sub_font);
+                SetTextColor(hdc, COLORREF(0x00666666));
+
+                let mut sub_rect = RECT { left: 0, top: scale(55), right: rect.right, bottom: scale(80) };
+                let sub_text: Vec<u16> = i18n::t("passcode.subtitle").encode_utf16().collect();
+                DrawTextW(
+                    hdc,
+                    &mut sub_text.clone(),
+                    &mut sub_rect,
+                    DT_CENTER | DT_SINGLELINE,
+                );
+
+                if DIALOG_ERROR.load(Ordering::SeqCst) {
+                    SetTextColor(hdc, COLORREF(COLOR_ERROR));
+                    let mut err_rect = RECT { left: 0, top: scale(150), right: rect.right, bottom: scale(170) };
+                    let lockout = crate::blocking::passcode_lockout_remaining();
+                    let err_text: Vec<u16> = if lockout > 0 {
+                        i18n::t("blocking.locked_out").replace("{}", &lockout.to_string()).encode_utf16().collect()
+                    } else {
+                        i18n::t("passcode.incorrect").encode_utf16().collect()
+                    };
+                    DrawTextW(
+                        hdc,
+                        &mut err_text.clone(),
+                        &mut err_rect,
+                        DT_CENTER | DT_SINGLELINE,
+                    );
+                }
+
+                SelectObject(hdc, old_font);
+                let _ = DeleteObject(title_font);
+                let _ = DeleteObject(sub_font);
+
+                let _ = EndPaint(hwnd, &ps);
+                LRESULT(0)
+            }
+            WM_COMMAND => {
+                let id = (wparam.0 & 0xFFFF) as u16;
+                match id {
+                    1 => { // OK
+                        let edit_hwnd = HWND(DIALOG_EDIT_HWND.load(Ordering::SeqCst));
+                        if !edit_hwnd.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(edit_hwnd, &mut buffer);
+                            let entered: String = String::from_utf16_lossy(&buffer[..len as usize]);
+
+                            let stored = DIALOG_STORED_CODE.lock().unwrap().clone();
+                            if let Some(stored) = stored {
+                                // Nothing is checked during a lockout, not even the right passcode
+                                let locked_out = crate::blocking::passcode_lockout_remaining() > 0;
+                                // Pasted codes may carry spaces or a line break
+                                if !locked_out && entered.trim() == stored {
+                                    crate::blocking::reset_failed_passcodes();
+                                    DIALOG_RESULT.store(true, Ordering::SeqCst);
+                                    DestroyWindow(hwnd).ok();
+                                } else {
+                                    if !locked_out && !entered.trim().is_empty() {
+                                        crate::blocking::record_failed_passcode();
+                                    }
+                                    DIALOG_LOCKED_OUT.store(crate::blocking::passcode_lockout_remaining() > 0, Ordering::SeqCst);
+                                    DIALOG_ERROR.store(true, Ordering::SeqCst);
+                                    let _ = InvalidateRect(hwnd, None, true);
+                                    SetWindowTextW(edit_hwnd, w!("")).ok();
+                                    let _ = SetFocus(edit_hwnd);
+                                }
+                            }
+                        }
+                    }
+                    2 => { // Cancel
+                        DIALOG_RESULT.store(false, Ordering::SeqCst);
+                        DestroyWindow(hwnd).ok();
+                    }
+                    _ => {}
+                }
+                LRESULT(0)
+            }
+            WM_KEYDOWN => {
+                if wparam.0 == VK_RETURN.0 as usize {
+                    SendMessageW(hwnd, WM_COMMAND, WPARAM(1), LPARAM(0));
+                } else if wparam.0 == VK_ESCAPE.0 as usize {
+                    DIALOG_RESULT.store(false, Ordering::SeqCst);
+                    DestroyWindow(hwnd).ok();
+                }
+                LRESULT(0)
+            }
+            WM_CLOSE => {
+                DIALOG_RESULT.store(false, Ordering::SeqCst);
+                DestroyWindow(hwnd).ok();
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+        }
+    }
+
+    let wnd_class = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(dialog_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: dialog_class,
+        hbrBackground: CreateSolidBrush(COLORREF(0x00F0F0F0)),
+        hCursor: LoadCursorW(None, IDC_ARROW).ok().unwrap_or_default(),
+        ..zeroed()
+    };
+    RegisterClassW(&wnd_class);
+
+    let screen_width = GetSystemMetrics(SM_CXSCREEN);
+    let screen_height = GetSystemMetrics(SM_CYSCREEN);
+    let dialog_width = scale(350);
+    let dialog_height = scale(300);
+
+    let dialog_hwnd = CreateWindowExW(
+        WS_EX_TOPMOST | WS_EX_DLGMODALFRAME,
+        dialog_class,
+        w!(""),
+        WS_POPUP | WS_CAPTION | WS_SYSMENU,
+        (screen_width - dialog_width) / 2,
+        (screen_height - dialog_height) / 2,
+        dialog_width,
+        dialog_height,
+        parent_hwnd,
+        HMENU::default(),
+        hinstance,
+        None,
+    );
+
+    if let Ok(dlg) = dialog_hwnd {
+        let rgn = CreateRoundRectRgn(0, 0, dialog_width, dialog_height, scale(10), scale(10));
+        SetWindowRgn(dlg, rgn, true);
+
+        let _ = ShowWindow(dlg, SW_SHOW);
+        let _ = SetForegroundWindow(dlg);
+
+        run_modal(dlg, parent_hwnd, false);
+    }
+
+    DIALOG_RESULT.load(Ordering::SeqCst)
+}
+
+/// Ask for a custom number of minutes (or a time like "20:00") to extend by. The entry is
+/// checked against the extension policy before the dialog closes; None if cancelled.
+pub unsafe fn prompt_extend_minutes(parent_hwnd: HWND) -> Option<crate::blocking::CustomExtension> {
+    let dialog_class = w!("ScreenTimeExtendDialog");
+    let hinstance = GetModuleHandleW(None).expect("Failed to get module handle");
+
+    static DIALOG_RESULT: Mutex<Option<crate::blocking::CustomExtension>> = Mutex::new(None);
+    static DIALOG_EDIT_HWND: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+    static DIALOG_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+    *DIALOG_RESULT.lock().unwrap() = None;
+    *DIALOG_ERROR.lock().unwrap() = None;
+
+    unsafe extern "system" fn dialog_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        match msg {
+            WM_CREATE => {
+                let hinstance = GetModuleHandleW(None).unwrap();
+
+                let edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0),
+                    w!("EDIT"),
+                    w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE((ES_CENTER | ES_AUTOHSCROLL) as u32),
+                    scale(100), scale(100), scale(150), scale(36),
+                    hwnd,
+                    HMENU(101 as _),
+                    hinstance,
+                    None,
+                ).ok();
+
+                if let Some(e) = edit {
+                    DIALOG_EDIT_HWND.store(e.0, Ordering::SeqCst);
+                    // Minutes or a time like "20:00", or "0:30 tomorrow"
+                    SendMessageW(e, EM_SETLIMITTEXT, WPARAM(16), LPARAM(0));
+
+                    let hfont = CreateFontW(
+                        scale(28), 0, 0, 0,
+                        FW_BOLD.0 as i32,
+                        0, 0, 0, 0, 0, 0, 5, 0,
+                        w!("Segoe UI"),
+                    );
+                    SendMessageW(e, WM_SETFONT, WPARAM(hfont.0 as usize), LPARAM(1));
+                    let _ = SetFocus(e);
+                }
+
+                let btn_font = CreateFontW(
+                    scale(14), 0, 0, 0,
+                    FW_NORMAL.0 as i32,
+                    0, 0, 0, 0, 0, 0, 5, 0,
+                    w!("Segoe UI"),
+                );
+
+                for (id, key, x) in [(1, "button.ok", 70), (2, "button.cancel", 180)] {
+                    let text = i18n::wide(key);
+                    let button = CreateWindowExW(
+                        WINDOW_EX_STYLE(0),
+                        w!("BUTTON"),
+                        PCWSTR(text.as_ptr()),
+                        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                        scale(x), scale(200), scale(100), scale(40),
+                        hwnd,
+                        HMENU(id as _),
+                        hinstance,
+                        None,
+                    );
+                    if let Ok(h) = button { SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1)); }
+                }
+
+                LRESULT(0)
+            }
+            WM_PAINT => {
+                let mut ps: PAINTSTRUCT = zeroed();
+                let hdc = BeginPaint(hwnd, &mut ps);
+
+                let mut rect: RECT = zeroed();
+                GetClientRect(hwnd, &mut rect).ok();
+
+                let bg_brush = CreateSolidBrush(COLORREF(0x00F0F0F0));
+                FillRect(hdc, &rect, bg_brush);
+                let _ = DeleteObject(bg_brush);
+
+                let title_font = CreateFontW(
+                    scale(20), 0, 0, 0,
+                    FW_BOLD.0 as i32,
+                    0, 0, 0, 0, 0, 0, 5, 0,
+                    w!("Segoe UI"),
+                );
+                let old_font = SelectObject(hdc, title_font);
+                SetTextColor(hdc, COLORREF(0x00333333));
+                SetBkMode(hdc, TRANSPARENT);
+
+                let mut title_rect = RECT { left: 0, top: scale(25), right: rect.right, bottom: scale(55) };
+                let mut title_text = i18n::to_wide(i18n::t("extend.custom_title"));
+                DrawTextW(hdc, &mut title_text, &mut title_rect, DT_CENTER | DT_SINGLELINE);
+
+                let sub_font = CreateFontW(
+                    scale(13), 0, 0, 0,
+                    FW_NORMAL.0 as i32,
+                    0, 0, 0, 0, 0, 0, 5, 0,
+                    w!("Segoe UI"),
+                );
+                SelectObject(hdc, sub_font);
+                SetTextColor(hdc, COLORREF(0x00666666));
+
+                // Tell the parent up front how much can still be given
+                let hint = match crate::blocking::current_extension_limit() {
+                    Some(limit) => i18n::t("extend.custom_limit").replace("{}", &limit.to_string()),
+                    None => i18n::t("extend.custom_prompt").to_string(),
+                };
+                let mut sub_rect = RECT { left: 0, top: scale(55), right: rect.right, bottom: scale(80) };
+                let mut sub_text = i18n::to_wide(&hint);
+                DrawTextW(hdc, &mut sub_text, &mut sub_rect, DT_CENTER | DT_SINGLELINE);
+
+                let error = DIALOG_ERROR.lock().unwrap().clone();
+                if let Some(error) = error {
+                    SetTextColor(hdc, COLORREF(COLOR_ERROR));
+                    let mut err_rect = RECT { left: scale(10), top: scale(145), right: rect.right - scale(10), bottom: scale(190) };
+                    let mut err_text = i18n::to_wide(&error);
+                    DrawTextW(hdc, &mut err_text, &mut err_rect, DT_CENTER | DT_WORDBREAK);
+                }
+
+                SelectObject(hdc, old_font);
+                let _ = DeleteObject(title_font);
+                let _ = DeleteObject(sub_font);
+
+                let _ = EndPaint(hwnd, &ps);
+                LRESULT(0)
+            }
+            WM_COMMAND => {
+                let id = (wparam.0 & 0xFFFF) as u16;
+                match id {
+                    1 => { // OK
+                        let edit_hwnd = HWND(DIALOG_EDIT_HWND.load(Ordering::SeqCst));
+                        if !edit_hwnd.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(edit_hwnd, &mut buffer);
+                            let entered = String::from_utf16_lossy(&buffer[..len as usize]);
+
+                            match crate::blocking::validate_extension(&entered) {
+                                Ok(extension) => {
+                                    *DIALOG_RESULT.lock().unwrap() = Some(extension);
+                                    DestroyWindow(hwnd).ok();
+                                }
+                                Err(e) => {
+                                    *DIALOG_ERROR.lock().unwrap() = Some(e.message());
+                                    let _ = InvalidateRect(hwnd, None, true);
+                                    let _ = SendMessageW(edit_hwnd, EM_SETSEL, WPARAM(0), LPARAM(-1));
+                                    let _ = SetFocus(edit_hwnd);
+                                }
+                            }
+                        }
+                    }
+                    2 => { // Cancel
+                        DestroyWindow(hwnd).ok();
+                    }
+                    _ => {}
+                }
+                LRESULT(0)
+            }
+            WM_KEYDOWN => {
+                if wparam.0 == VK_RETURN.0 as usize {
+                    SendMessageW(hwnd, WM_COMMAND, WPARAM(1), LPARAM(0));
+                } else if wparam.0 == VK_ESCAPE.0 as usize {
+                    DestroyWindow(hwnd).ok();
+                }
+                LRESULT(0)
+            }
+            WM_CLOSE => {
+                DestroyWindow(hwnd).ok();
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+        }
+    }
+
+    let wnd_class = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(dialog_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: dialog_class,
+        hbrBackground: CreateSolidBrush(COLORREF(0x00F0F0F0)),
+        hCursor: LoadCursorW(None, IDC_ARROW).ok().unwrap_or_default(),
+        ..zeroed()
+    };
+    RegisterClassW(&wnd_class);
+
+    let screen_width = GetSystemMetrics(SM_CXSCREEN);
+    let screen_height = GetSystemMetrics(SM_CYSCREEN);
+    let dialog_width = scale(350);
+    let dialog_height = scale(300);
+
+    let dialog_hwnd = CreateWindowExW(
+        WS_EX_TOPMOST | WS_EX_DLGMODALFRAME,
+        dialog_class,
+        w!(""),
+        WS_POPUP | WS_CAPTION | WS_SYSMENU,
+        (screen_width - dialog_width) / 2,
+        (screen_height - dialog_height) / 2,
+        dialog_width,
+        dialog_height,
+        parent_hwnd,
+        HMENU::default(),
+        hinstance,
+        None,
+    );
+
+    if let Ok(dlg) = dialog_hwnd {
+        let rgn = CreateRoundRectRgn(0, 0, dialog_width, dialog_height, scale(10), scale(10));
+        SetWindowRgn(dlg, rgn, true);
+
+        let _ = ShowWindow(dlg, SW_SHOW);
+        let _ = SetForegroundWindow(dlg);
+
+        run_modal(dlg, parent_hwnd, false);
+    }
+
+    *DIALOG_RESULT.lock().unwrap()
+}
+
+/// Show the settings dialog
+pub unsafe fn show_settings_dialog(parent_hwnd: HWND) {
+    let dialog_class = w!("ScreenTimeSettingsDialog");
+    let hinstance = GetModuleHandleW(None).expect("Failed to get module handle");
+
+    static SETTINGS_DIALOG_OPEN: AtomicBool = AtomicBool::new(false);
+
+    if SETTINGS_DIALOG_OPEN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    unsafe extern "system" fn settings_dialog_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        match msg {
+            WM_CREATE => {
+                let hinstance = GetModuleHandleW(None).unwrap();
+
+                // Font quality: 5 = CLEARTYPE_QUALITY for crisp rendering
+                let label_font = CreateFontW(
+                    scale(14), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+                );
+                let title_font = CreateFontW(
+                    scale(16), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+                );
+                let edit_font = CreateFontW(
+                    scale(14), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+                );
+
+                let mut y_pos = scale(10);
+
+                // ===== Language Section =====
+                let lang_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), w!("Language / Sprache:"),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos + scale(2), scale(140), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = lang_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+
+                let lang_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(160), y_pos, scale(100), scale(200), hwnd, HMENU(ID_LANGUAGE_COMBO as _), hinstance, None,
+                );
+                let mut lang_combo_hwnd = HWND::default();
+                if let Ok(h) = lang_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    // Add language options
+                    for lang in Language::all() {
+                        let name: Vec<u16> = lang.name().encode_utf16().chain(std::iter::once(0)).collect();
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    // Select current language
+                    let current_lang = i18n::current();
+                    let index = match current_lang {
+                        Language::English => 0,
+                        Language::German => 1,
+                    };
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    lang_combo_hwnd = h;
+                }
+                y_pos += scale(28);
+
+                // ===== Daily Limits Section =====
+                let title1_text = i18n::wide("settings.daily_limits");
+                let title1 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title1_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(350), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title1 { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(22);
+
+                let mut daily_handles: [HWND; 7] = [HWND::default(); 7];
+
+                // Create day controls in pairs (two columns per row)
+                // Row 0: Monday (0), Tuesday (1)
+                // Row 1: Wednesday (2), Thursday (3)
+                // Row 2: Friday (4), Saturday (5)
+                // Row 3: Sunday (6) alone
+                for row in 0..4 {
+                    let i = row * 2; // First column day index
+
+                    // First column
+                    let label_text: Vec<u16> = format!("{}:\0", locale::weekday_name(i)).encode_utf16().collect();
+                    let label = CreateWindowExW(
+                        WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(label_text.as_ptr()),
+                        WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(90), scale(20), hwnd, HMENU::default(), hinstance, None,
+                    );
+                    if let Ok(h) = label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+
+                    let edit = CreateWindowExW(
+                        WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                        WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_CENTER as u32),
+                        scale(120), y_pos, scale(60), scale(22), hwnd, HMENU((ID_SETTINGS_BASE + i as i32) as _), hinstance, None,
+                    );
+                    if let Ok(h) = edit {
+                        SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                        SendMessageW(h, EM_SETLIMITTEXT, WPARAM(8), LPARAM(0));
+                        let value = duration::format_duration(crate::database::get_daily_limit(i as u32));
+                        let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                        SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                        daily_handles[i] = h;
+                    }
+
+                    // Second column (only if there's a second day in this row)
+                    let i2 = i + 1;
+                    if i2 < 7 {
+                        let label_text2: Vec<u16> = format!("{}:\0", locale::weekday_name(i2)).encode_utf16().collect();
+                        let label2 = CreateWindowExW(
+                            WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(label_text2.as_ptr()),
+                            WS_CHILD | WS_VISIBLE, scale(210), y_pos + scale(2), scale(90), scale(20), hwnd, HMENU::default(), hinstance, None,
+                        );
+                        if let Ok(h) = label2 { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+
+                        let edit2 = CreateWindowExW(
+                            WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                            WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_CENTER as u32),
+                            scale(305), y_pos, scale(60), scale(22), hwnd, HMENU((ID_SETTINGS_BASE + i2 as i32) as _), hinstance, None,
+                        );
+                        if let Ok(h) = edit2 {
+                            SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                            SendMessageW(h, EM_SETLIMITTEXT, WPARAM(8), LPARAM(0));
+                            let value = duration::format_duration(crate::database::get_daily_limit(i2 as u32));
+                            let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                            SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                            daily_handles[i2] = h;
+                        }
+                    }
+
+                    y_pos += scale(24);
+                }
+
+                // Quick-fill buttons copying one day's limit into the others
+                for (x, key, id) in [
+                    (25, "settings.fill_all_days", ID_FILL_ALL_DAYS),
+                    (140, "settings.fill_weekdays", ID_FILL_WEEKDAYS),
+                    (255, "settings.fill_weekend", ID_FILL_WEEKEND),
+                ] {
+                    let fill_text = i18n::wide(key);
+                    let fill_btn = CreateWindowExW(
+                        WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(fill_text.as_ptr()),
+                        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                        scale(x), y_pos, scale(110), scale(22), hwnd, HMENU(id as _), hinstance, None,
+                    );
+                    if let Ok(h) = fill_btn { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                }
+                y_pos += scale(28);
+
+                // Weekly ramp-down: step (0 = off) and floor
+                let rampdown = get_rampdown_config();
+                let rampdown_label_text = i18n::wide("settings.rampdown_step");
+                let rampdown_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(rampdown_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(90), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = rampdown_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let rampdown_step_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(120), y_pos, scale(60), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut rampdown_step_hwnd = HWND::default();
+                if let Ok(h) = rampdown_step_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(3), LPARAM(0));
+                    let wide = i18n::to_wide(&rampdown.step_minutes.to_string());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    rampdown_step_hwnd = h;
+                }
+                let floor_label_text = i18n::wide("settings.rampdown_floor");
+                let floor_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(floor_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(210), y_pos + scale(2), scale(90), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = floor_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let rampdown_floor_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(305), y_pos, scale(60), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut rampdown_floor_hwnd = HWND::default();
+                if let Ok(h) = rampdown_floor_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(4), LPARAM(0));
+                    let wide = i18n::to_wide(&rampdown.floor_minutes.to_string());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    rampdown_floor_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // ===== Allowed Hours Section =====
+                y_pos += scale(10);
+                let title_hours_text = i18n::wide("settings.allowed_hours");
+                let title_hours = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title_hours_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(360), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title_hours { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                let mut allowed_hours_hwnds = [HWND::default(); 2];
+                for (col, (label_key, weekend)) in [("settings.allowed_hours_weekdays", false), ("settings.allowed_hours_weekends", true)]
+                    .into_iter()
+                    .enumerate()
+                {
+                    let x = scale(25) + col as i32 * scale(185);
+                    let label_text = i18n::wide(label_key);
+                    let label = CreateWindowExW(
+                        WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(label_text.as_ptr()),
+                        WS_CHILD | WS_VISIBLE, x, y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                    );
+                    if let Ok(h) = label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                    let edit = CreateWindowExW(
+                        WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                        WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_CENTER as u32),
+                        x + scale(75), y_pos, scale(95), scale(22), hwnd, HMENU::default(), hinstance, None,
+                    );
+                    if let Ok(h) = edit {
+                        SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                        SendMessageW(h, EM_SETLIMITTEXT, WPARAM(11), LPARAM(0));
+                        let wide = i18n::to_wide(&get_allowed_hours(weekend));
+                        SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                        allowed_hours_hwnds[col] = h;
+                    }
+                }
+                y_pos += scale(24);
+
+                // Apps that don't count against the limit (e.g. for homework)
+                let productive_label_text = i18n::wide("settings.productive_apps");
+                let productive_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(productive_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(110), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = productive_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let productive_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(140), y_pos, scale(225), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut productive_hwnd = HWND::default();
+                if let Ok(h) = productive_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide = i18n::to_wide(&get_productive_apps().join(", "));
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    productive_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Windows accounts whose time counts on a shared PC (empty = everyone)
+                let counted_label_text = i18n::wide("settings.counted_users");
+                let counted_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(counted_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(110), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = counted_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let counted_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(140), y_pos, scale(225), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut counted_users_hwnd = HWND::default();
+                if let Ok(h) = counted_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide = i18n::to_wide(&get_counted_users());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    counted_users_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // ===== Warning 1 Section =====
+                y_pos += scale(10);
+                let title2_text = i18n::wide("settings.warning1");
+                let title2 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title2_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(350), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title2 { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                let min_label1_text = i18n::wide("settings.minutes_before");
+                let min_label1 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(min_label1_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(100), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = min_label1 { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let w1_min = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(130), y_pos, scale(50), scale(22), hwnd, HMENU((ID_SETTINGS_BASE + 20) as _), hinstance, None,
+                );
+                let mut w1_min_hwnd = HWND::default();
+                if let Ok(h) = w1_min {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_setting("warning1_minutes").unwrap_or_else(|| "10".to_string());
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    w1_min_hwnd = h;
+                }
+                let dur_label1_text = i18n::wide("settings.show_for");
+                let dur_label1 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(dur_label1_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(195), y_pos + scale(2), scale(115), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = dur_label1 { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let w1_dur = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(315), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut w1_dur_hwnd = HWND::default();
+                if let Ok(h) = w1_dur {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_warning_config(1).2.to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    w1_dur_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                let msg_label1_text = i18n::wide("settings.message");
+                let msg_label1 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(msg_label1_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(60), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = msg_label1 { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let w1_msg = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER,
+                    scale(90), y_pos, scale(275), scale(22), hwnd, HMENU((ID_SETTINGS_BASE + 21) as _), hinstance, None,
+                );
+                let mut w1_msg_hwnd = HWND::default();
+                if let Ok(h) = w1_msg {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_setting("warning1_message").unwrap_or_else(|| "10 minutes remaining!".to_string());
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    w1_msg_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                let weekend_label1_text = i18n::wide("settings.warning_weekend");
+                let weekend_label1 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(weekend_label1_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(100), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = weekend_label1 { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                // Empty fields use the values above on weekends too
+                let w1_weekend_min = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(130), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut w1_weekend_min_hwnd = HWND::default();
+                if let Ok(h) = w1_weekend_min {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide = i18n::to_wide(&get_setting("warning1_minutes_weekend").unwrap_or_default());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    w1_weekend_min_hwnd = h;
+                }
+                let w1_weekend_msg = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER,
+                    scale(195), y_pos, scale(170), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut w1_weekend_msg_hwnd = HWND::default();
+                if let Ok(h) = w1_weekend_msg {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide = i18n::to_wide(&get_setting("warning1_message_weekend").unwrap_or_default());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    w1_weekend_msg_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // ===== Warning 2 Section =====
+                y_pos += scale(10);
+                let title3_text = i18n::wide("settings.warning2");
+                let title3 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title3_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(350), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title3 { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                let min_label2_text = i18n::wide("settings.minutes_before");
+                let min_label2 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(min_label2_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(100), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = min_label2 { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let w2_min = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(130), y_pos, scale(50), scale(22), hwnd, HMENU((ID_SETTINGS_BASE + 30) as _), hinstance, None,
+                );
+                let mut w2_min_hwnd = HWND::default();
+                if let Ok(h) = w2_min {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_setting("warning2_minutes").unwrap_or_else(|| "5".to_string());
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    w2_min_hwnd = h;
+                }
+                let dur_label2_text = i18n::wide("settings.show_for");
+                let dur_label2 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(dur_label2_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(195), y_pos + scale(2), scale(115), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = dur_label2 { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let w2_dur = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(315), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut w2_dur_hwnd = HWND::default();
+                if let Ok(h) = w2_dur {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_warning_config(2).2.to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    w2_dur_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                let msg_label2_text = i18n::wide("settings.message");
+                let msg_label2 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(msg_label2_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(60), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = msg_label2 { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let w2_msg = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER,
+                    scale(90), y_pos, scale(275), scale(22), hwnd, HMENU((ID_SETTINGS_BASE + 31) as _), hinstance, None,
+                );
+                let mut w2_msg_hwnd = HWND::default();
+                if let Ok(h) = w2_msg {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_setting("warning2_message").unwrap_or_else(|| "5 minutes remaining!".to_string());
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    w2_msg_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                let weekend_label2_text = i18n::wide("settings.warning_weekend");
+                let weekend_label2 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(weekend_label2_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(100), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = weekend_label2 { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                // Empty fields use the values above on weekends too
+                let w2_weekend_min = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(130), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut w2_weekend_min_hwnd = HWND::default();
+                if let Ok(h) = w2_weekend_min {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide = i18n::to_wide(&get_setting("warning2_minutes_weekend").unwrap_or_default());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    w2_weekend_min_hwnd = h;
+                }
+                let w2_weekend_msg = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER,
+                    scale(195), y_pos, scale(170), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut w2_weekend_msg_hwnd = HWND::default();
+                if let Ok(h) = w2_weekend_msg {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide = i18n::to_wide(&get_setting("warning2_message_weekend").unwrap_or_default());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    w2_weekend_msg_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Don't cover fullscreen games/videos with the warning banner
+                let defer_text = i18n::wide("settings.defer_warnings_fullscreen");
+                let defer_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(defer_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(340), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut defer_hwnd = HWND::default();
+                if let Ok(h) = defer_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if is_defer_warnings_fullscreen() {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    defer_hwnd = h;
+                }
+                y_pos += scale(22);
+
+                // Downgrade warnings while Focus Assist is on (blocking still happens)
+                let focus_assist_text = i18n::wide("settings.respect_focus_assist");
+                let focus_assist_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(focus_assist_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(340), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut focus_assist_hwnd = HWND::default();
+                if let Ok(h) = focus_assist_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if is_respect_focus_assist() {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    focus_assist_hwnd = h;
+                }
+                y_pos += scale(22);
+
+                // Warning banner position and distance from the screen edge
+                let position_label_text = i18n::wide("settings.warning_position");
+                let position_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(position_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(100), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = position_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let position_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(130), y_pos, scale(95), scale(200), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut position_hwnd = HWND::default();
+                if let Ok(h) = position_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    for (key, _) in WARNING_POSITIONS {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    let current = get_warning_position();
+                    let index = WARNING_POSITIONS.iter().position(|(_, v)| *v == current).unwrap_or(1);
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    position_hwnd = h;
+                }
+                let margin_label_text = i18n::wide("settings.warning_margin");
+                let margin_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(margin_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(235), y_pos + scale(2), scale(75), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = margin_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let margin_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(315), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut margin_hwnd = HWND::default();
+                if let Ok(h) = margin_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_warning_margin().to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    margin_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Wind-down: pulsing mini overlay and repeated reminders in the last minutes
+                let winddown_label_text = i18n::wide("settings.winddown");
+                let winddown_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(winddown_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(100), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = winddown_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let winddown_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(130), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut winddown_hwnd = HWND::default();
+                if let Ok(h) = winddown_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_winddown_minutes().to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    winddown_hwnd = h;
+                }
+                let winddown_interval_label_text = i18n::wide("settings.winddown_interval");
+                let winddown_interval_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(winddown_interval_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(195), y_pos + scale(2), scale(115), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = winddown_interval_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let winddown_interval_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(315), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut winddown_interval_hwnd = HWND::default();
+                if let Ok(h) = winddown_interval_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_winddown_interval_minutes().to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    winddown_interval_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // ===== Blocking Message Section =====
+                y_pos += scale(10);
+                let title4_text = i18n::wide("settings.blocking_message");
+                let title4 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title4_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(350), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title4 { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                let block_msg = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER,
+                    scale(25), y_pos, scale(340), scale(22), hwnd, HMENU((ID_SETTINGS_BASE + 40) as _), hinstance, None,
+                );
+                let mut block_msg_hwnd = HWND::default();
+                if let Ok(h) = block_msg {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_setting("blocking_message").unwrap_or_else(|| "Your screen time limit has been reached.".to_string());
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    block_msg_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Text on the other monitors (empty = same as above)
+                let secondary_label_text = i18n::wide("settings.secondary_block_message");
+                let secondary_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(secondary_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(110), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = secondary_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let secondary_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(140), y_pos, scale(225), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut secondary_msg_hwnd = HWND::default();
+                if let Ok(h) = secondary_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_setting("secondary_block_message").unwrap_or_default();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    secondary_msg_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Optional background image behind the blocking panel
+                let bg_label_text = i18n::wide("settings.block_background_image");
+                let bg_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(bg_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(110), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = bg_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let bg_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(140), y_pos, scale(225), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut bg_image_hwnd = HWND::default();
+                if let Ok(h) = bg_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = get_setting("block_background_image").unwrap_or_default();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    bg_image_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // ===== Change Passcode Section =====
+                y_pos += scale(10);
+                let title5_text = i18n::wide("settings.passcode");
+                let title5 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title5_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(360), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title5 { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                let curr_label_text = i18n::wide("settings.current");
+                let curr_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(curr_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(55), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = curr_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let curr_pass = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_PASSWORD as u32 | ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(80), y_pos, scale(60), scale(22), hwnd, HMENU(ID_CURRENT_PASSCODE as _), hinstance, None,
+                );
+                let mut curr_pass_hwnd = HWND::default();
+                if let Ok(h) = curr_pass {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(4), LPARAM(0));
+                    curr_pass_hwnd = h;
+                }
+
+                let new_label_text = i18n::wide("settings.new");
+                let new_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(new_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(155), y_pos + scale(2), scale(35), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = new_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let new_pass = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_PASSWORD as u32 | ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(190), y_pos, scale(60), scale(22), hwnd, HMENU(ID_NEW_PASSCODE as _), hinstance, None,
+                );
+                let mut new_pass_hwnd = HWND::default();
+                if let Ok(h) = new_pass {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(4), LPARAM(0));
+                    new_pass_hwnd = h;
+                }
+
+                let confirm_label_text = i18n::wide("settings.confirm");
+                let confirm_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(confirm_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(265), y_pos + scale(2), scale(50), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = confirm_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let confirm_pass = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_PASSWORD as u32 | ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(315), y_pos, scale(60), scale(22), hwnd, HMENU(ID_CONFIRM_PASSCODE as _), hinstance, None,
+                );
+                let mut confirm_pass_hwnd = HWND::default();
+                if let Ok(h) = confirm_pass {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(4), LPARAM(0));
+                    confirm_pass_hwnd = h;
+                }
+                y_pos += scale(30);
+
+                // Lockout after repeated wrong passcodes
+                let lockout_config = get_lockout_config();
+                let lockout_label_text = i18n::wide("settings.lockout_attempts");
+                let lockout_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(lockout_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(100), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = lockout_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let lockout_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(130), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut lockout_attempts_hwnd = HWND::default();
+                if let Ok(h) = lockout_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = lockout_config.max_attempts.to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    lockout_attempts_hwnd = h;
+                }
+                let lockout_delay_label_text = i18n::wide("settings.lockout_delay");
+                let lockout_delay_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(lockout_delay_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(195), y_pos + scale(2), scale(115), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = lockout_delay_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let lockout_delay_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(315), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut lockout_delay_hwnd = HWND::default();
+                if let Ok(h) = lockout_delay_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = lockout_config.delay_seconds.to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    lockout_delay_hwnd = h;
+                }
+                y_pos += scale(28);
+
+                // A block can't be lifted (passcode or extension) before this many seconds
+                let min_block_label_text = i18n::wide("settings.min_block_duration");
+                let min_block_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(min_block_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(100), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = min_block_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let min_block_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(130), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut min_block_hwnd = HWND::default();
+                if let Ok(h) = min_block_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(4), LPARAM(0));
+                    let wide = i18n::to_wide(&get_min_block_duration_seconds().to_string());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    min_block_hwnd = h;
+                }
+                y_pos += scale(28);
+
+                // Caps how often time can be topped up in a day, whatever the amount
+                let max_ext_label_text = i18n::wide("settings.max_extensions");
+                let max_ext_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(max_ext_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(100), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = max_ext_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let max_ext_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(130), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut max_ext_hwnd = HWND::default();
+                if let Ok(h) = max_ext_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(2), LPARAM(0));
+                    let wide = i18n::to_wide(&get_extension_config().max_per_day.to_string());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    max_ext_hwnd = h;
+                }
+                let max_ext_hint_text = i18n::wide("settings.max_extensions_hint");
+                let max_ext_hint = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(max_ext_hint_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(190), y_pos + scale(2), scale(175), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = max_ext_hint { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(28);
+
+                // A small extension the child may take on the lock screen without the passcode
+                let (self_minutes, self_per_day) = get_self_extend_config();
+                let self_label_text = i18n::wide("settings.self_extend");
+                let self_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(self_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(100), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = self_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let mut self_extend_hwnds = [HWND::default(); 2];
+                for (i, value) in [self_minutes, self_per_day].into_iter().enumerate() {
+                    let edit = CreateWindowExW(
+                        WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                        WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                        scale(130 + 45 * i as i32), y_pos, scale(40), scale(22), hwnd, HMENU::default(), hinstance, None,
+                    );
+                    if let Ok(h) = edit {
+                        SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                        SendMessageW(h, EM_SETLIMITTEXT, WPARAM(2), LPARAM(0));
+                        let wide = i18n::to_wide(&value.to_string());
+                        SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                        self_extend_hwnds[i] = h;
+                    }
+                }
+                let self_hint_text = i18n::wide("settings.self_extend_hint");
+                let self_hint = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(self_hint_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(225), y_pos + scale(2), scale(140), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = self_hint { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(28);
+
+                // Setting the clock back is a common way around the limits
+                let clock_block_text = i18n::wide("settings.clock_change_block");
+                let clock_block_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(clock_block_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(340), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut clock_block_hwnd = HWND::default();
+                if let Ok(h) = clock_block_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if is_clock_change_block() {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    clock_block_hwnd = h;
+                }
+                y_pos += scale(22);
+
+                let clock_guard_text = i18n::wide("settings.clock_guard");
+                let clock_guard_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(clock_guard_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(340), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut clock_guard_hwnd = HWND::default();
+                if let Ok(h) = clock_guard_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if is_clock_guard_enabled() {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    clock_guard_hwnd = h;
+                }
+                y_pos += scale(22);
+
+                // ===== Telegram Bot Section =====
+                y_pos += scale(10);
+                let title6_text = i18n::wide("settings.telegram");
+                let title6 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title6_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(360), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title6 { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                // Enable checkbox
+                let telegram_chk_text = i18n::wide("settings.enable_telegram");
+                let telegram_enabled_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(telegram_chk_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(200), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut telegram_enabled_hwnd = HWND::default();
+                if let Ok(h) = telegram_enabled_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    let config = get_telegram_config();
+                    if config.enabled {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    telegram_enabled_hwnd = h;
+                }
+                y_pos += scale(22);
+
+                // Whether the running bot got through to Telegram (or why not)
+                let bot_status_text = i18n::to_wide(&crate::telegram::bot_status().describe());
+                let bot_status = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(bot_status_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(0x4000), // SS_ENDELLIPSIS
+                    scale(25), y_pos, scale(340), scale(18), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = bot_status { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                // Bot Token
+                let bot_token_label_text = i18n::wide("settings.bot_token");
+                let bot_token_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(bot_token_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = bot_token_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let telegram_token = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_PASSWORD as u32 | ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(265), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut telegram_token_hwnd = HWND::default();
+                if let Ok(h) = telegram_token {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    // Allow long bot tokens (up to 200 chars)
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(200), LPARAM(0));
+                    let config = get_telegram_config();
+                    if let Some(token) = config.bot_token {
+                        let wide: Vec<u16> = token.encode_utf16().chain(std::iter::once(0)).collect();
+                        SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    }
+                    telegram_token_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Admin Chat ID
+                let chat_id_label_text = i18n::wide("settings.chat_id");
+                let chat_id_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(chat_id_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = chat_id_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let telegram_chat_id = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32),
+                    scale(100), y_pos, scale(120), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut telegram_chat_id_hwnd = HWND::default();
+                if let Ok(h) = telegram_chat_id {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let config = get_telegram_config();
+                    if let Some(chat_id) = config.admin_chat_id {
+                        let value = chat_id.to_string();
+                        let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                        SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    }
+                    telegram_chat_id_hwnd = h;
+                }
+
+                // Setup Wizard button (to the right of chat_id)
+                let wizard_btn_text = i18n::wide("settings.setup_wizard");
+                let wizard_btn = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(wizard_btn_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    scale(230), y_pos, scale(135), scale(22), hwnd, HMENU(ID_TELEGRAM_WIZARD as _), hinstance, None,
+                );
+                if let Ok(h) = wizard_btn { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(24);
+
+                // Webhook (Discord, Slack, IFTTT, ...) receiving the same events as the bot
+                let webhook_label_text = i18n::wide("settings.webhook_url");
+                let webhook_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(webhook_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = webhook_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let webhook_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(265), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut webhook_hwnd = HWND::default();
+                if let Ok(h) = webhook_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide: Vec<u16> = get_webhook_url().encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    webhook_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // ===== Daily Email Report Section =====
+                y_pos += scale(10);
+                let title_email_text = i18n::wide("settings.email");
+                let title_email = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title_email_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(360), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title_email { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                let email_config = get_email_config();
+
+                // Enable checkbox with the report time next to it
+                let email_chk_text = i18n::wide("settings.email_enabled");
+                let email_enabled_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(email_chk_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(200), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut email_enabled_hwnd = HWND::default();
+                if let Ok(h) = email_enabled_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if email_config.enabled {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    email_enabled_hwnd = h;
+                }
+                let report_time_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_CENTER as u32),
+                    scale(230), y_pos, scale(60), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut report_time_hwnd = HWND::default();
+                if let Ok(h) = report_time_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = format!("{:02}:{:02}", email_config.report_minute / 60, email_config.report_minute % 60);
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    report_time_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // SMTP server and port
+                let smtp_host_label_text = i18n::wide("settings.smtp_host");
+                let smtp_host_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(smtp_host_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = smtp_host_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let smtp_host_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(190), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut smtp_host_hwnd = HWND::default();
+                if let Ok(h) = smtp_host_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide: Vec<u16> = email_config.host.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    smtp_host_hwnd = h;
+                }
+                let smtp_port_label_text = i18n::wide("settings.smtp_port");
+                let smtp_port_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(smtp_port_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(298), y_pos + scale(2), scale(30), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = smtp_port_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let smtp_port_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(325), y_pos, scale(40), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut smtp_port_hwnd = HWND::default();
+                if let Ok(h) = smtp_port_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = email_config.port.to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    smtp_port_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // SMTP user
+                let smtp_user_label_text = i18n::wide("settings.smtp_username");
+                let smtp_user_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(smtp_user_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = smtp_user_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let smtp_user_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(265), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut smtp_user_hwnd = HWND::default();
+                if let Ok(h) = smtp_user_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide: Vec<u16> = email_config.username.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    smtp_user_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // SMTP password (sensitive): never shown, only replaced when something is typed
+                let smtp_password_label_text = i18n::wide("settings.smtp_password");
+                let smtp_password_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(smtp_password_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = smtp_password_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let smtp_password_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_PASSWORD as u32 | ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(265), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut smtp_password_hwnd = HWND::default();
+                if let Ok(h) = smtp_password_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    smtp_password_hwnd = h;
+                }
+                y_pos += scale(24);
+                let password_hint_text = i18n::wide(if email_config.password.is_empty() {
+                    "settings.smtp_password_hint"
+                } else {
+                    "settings.smtp_password_saved"
+                });
+                let password_hint = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(password_hint_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(100), y_pos, scale(265), scale(18), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = password_hint { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(18);
+
+                // Recipients
+                let email_to_label_text = i18n::wide("settings.email_to");
+                let email_to_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(email_to_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = email_to_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let email_to_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(265), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut email_to_hwnd = HWND::default();
+                if let Ok(h) = email_to_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide: Vec<u16> = email_config.recipients.join(", ").encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    email_to_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // ===== MQTT Section =====
+                y_pos += scale(10);
+                let title_mqtt_text = i18n::wide("settings.mqtt");
+                let title_mqtt = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title_mqtt_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(360), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title_mqtt { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                let mqtt_config = get_mqtt_config();
+
+                // Enable checkbox with the topic prefix next to it
+                let mqtt_chk_text = i18n::wide("settings.mqtt_enabled");
+                let mqtt_enabled_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(mqtt_chk_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut mqtt_enabled_hwnd = HWND::default();
+                if let Ok(h) = mqtt_enabled_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if mqtt_config.enabled {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    mqtt_enabled_hwnd = h;
+                }
+                let mqtt_prefix_label_text = i18n::wide("settings.mqtt_prefix");
+                let mqtt_prefix_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(mqtt_prefix_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(185), y_pos + scale(2), scale(45), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = mqtt_prefix_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let mqtt_prefix_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(235), y_pos, scale(130), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut mqtt_prefix_hwnd = HWND::default();
+                if let Ok(h) = mqtt_prefix_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide: Vec<u16> = mqtt_config.prefix.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    mqtt_prefix_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Broker and port
+                let mqtt_host_label_text = i18n::wide("settings.mqtt_host");
+                let mqtt_host_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(mqtt_host_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = mqtt_host_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let mqtt_host_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(190), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut mqtt_host_hwnd = HWND::default();
+                if let Ok(h) = mqtt_host_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide: Vec<u16> = mqtt_config.host.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    mqtt_host_hwnd = h;
+                }
+                let mqtt_port_label_text = i18n::wide("settings.mqtt_port");
+                let mqtt_port_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(mqtt_port_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(298), y_pos + scale(2), scale(30), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = mqtt_port_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let mqtt_port_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(325), y_pos, scale(40), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut mqtt_port_hwnd = HWND::default();
+                if let Ok(h) = mqtt_port_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let value = mqtt_config.port.to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    mqtt_port_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Broker user
+                let mqtt_user_label_text = i18n::wide("settings.mqtt_username");
+                let mqtt_user_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(mqtt_user_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = mqtt_user_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let mqtt_user_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(265), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut mqtt_user_hwnd = HWND::default();
+                if let Ok(h) = mqtt_user_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide: Vec<u16> = mqtt_config.username.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    mqtt_user_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Broker password (sensitive): never shown, only replaced when something is typed
+                let mqtt_password_label_text = i18n::wide("settings.mqtt_password");
+                let mqtt_password_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(mqtt_password_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = mqtt_password_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let mqtt_password_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_PASSWORD as u32 | ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(265), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut mqtt_password_hwnd = HWND::default();
+                if let Ok(h) = mqtt_password_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    mqtt_password_hwnd = h;
+                }
+                y_pos += scale(24);
+                let mqtt_hint_text = i18n::wide(if mqtt_config.password.is_empty() {
+                    "settings.mqtt_password_hint"
+                } else {
+                    "settings.mqtt_password_saved"
+                });
+                let mqtt_hint = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(mqtt_hint_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(100), y_pos, scale(265), scale(18), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = mqtt_hint { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(18);
+
+                // ===== Metrics Endpoint Section =====
+                y_pos += scale(10);
+                let title_http_text = i18n::wide("settings.http");
+                let title_http = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title_http_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(360), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title_http { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                let http_config = get_http_config();
+
+                // Enable checkbox with the listen address next to it
+                let http_chk_text = i18n::wide("settings.http_enabled");
+                let http_enabled_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(http_chk_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut http_enabled_hwnd = HWND::default();
+                if let Ok(h) = http_enabled_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if http_config.enabled {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    http_enabled_hwnd = h;
+                }
+                let http_listen_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(185), y_pos, scale(180), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut http_listen_hwnd = HWND::default();
+                if let Ok(h) = http_listen_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    let wide: Vec<u16> = http_config.listen.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    http_listen_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Bearer token (sensitive): never shown, only replaced when something is typed
+                let http_token_label_text = i18n::wide("settings.http_token");
+                let http_token_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(http_token_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = http_token_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let http_token_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_PASSWORD as u32 | ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(265), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut http_token_hwnd = HWND::default();
+                if let Ok(h) = http_token_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    http_token_hwnd = h;
+                }
+                y_pos += scale(24);
+                let http_hint_text = i18n::wide(if http_config.token.is_empty() {
+                    "settings.http_token_hint"
+                } else {
+                    "settings.http_token_saved"
+                });
+                let http_hint = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(http_hint_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(100), y_pos, scale(265), scale(18), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = http_hint { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(18);
+
+                // ===== Lock Screen Timeout =====
+                y_pos += scale(10);
+                let title7_text = i18n::wide("settings.lock_screen");
+                let title7 = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title7_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(360), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title7 { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                // Lock screen timeout input
+                let shutdown_label_text = i18n::wide("settings.shutdown_timeout");
+                let shutdown_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(shutdown_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = shutdown_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let lock_timeout_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32),
+                    scale(180), y_pos, scale(60), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut lock_timeout_hwnd = HWND::default();
+                if let Ok(h) = lock_timeout_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    // Load current value (convert seconds to minutes for display)
+                    let timeout_secs = crate::database::get_lock_screen_timeout();
+                    let timeout_mins = timeout_secs / 60;
+                    let value = timeout_mins.to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    lock_timeout_hwnd = h;
+                }
+                let shutdown_hint_text = i18n::wide("settings.shutdown_timeout_hint");
+                let shutdown_hint = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(shutdown_hint_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(245), y_pos + scale(2), scale(120), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = shutdown_hint { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(24);
+
+
+                // Blocking panel size preset
+                let block_size_label_text = i18n::wide("settings.block_text_size");
+                let block_size_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(block_size_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = block_size_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let block_size_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(180), y_pos, scale(120), scale(200), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut block_size_hwnd = HWND::default();
+                if let Ok(h) = block_size_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    for (key, _) in BLOCK_TEXT_SIZE_PRESETS {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    // Select the preset closest to the stored percentage
+                    let current = get_block_text_scale();
+                    let index = BLOCK_TEXT_SIZE_PRESETS
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, (_, pct))| (pct - current).abs())
+                        .map(|(i, _)| i)
+                        .unwrap_or(0);
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    block_size_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Which buttons the lock screen offers
+                let block_buttons_label_text = i18n::wide("settings.block_buttons");
+                let block_buttons_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(block_buttons_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = block_buttons_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let mut block_button_handles: [HWND; 4] = [HWND::default(); 4];
+                for (i, (key, _, allowed)) in BLOCK_BUTTON_SETTINGS.iter().enumerate() {
+                    let text = i18n::wide(key);
+                    let x = if i % 2 == 0 { 180 } else { 275 };
+                    let chk = CreateWindowExW(
+                        WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(text.as_ptr()),
+                        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                        scale(x), y_pos + scale(24 * (i as i32 / 2)), scale(92), scale(20), hwnd, HMENU::default(), hinstance, None,
+                    );
+                    if let Ok(h) = chk {
+                        SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                        if allowed() {
+                            SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                        }
+                        block_button_handles[i] = h;
+                    }
+                }
+                y_pos += scale(48);
+
+                // Block the screen at zero (optionally locking or shutting down), or only keep warning
+                let enforcement_label_text = i18n::wide("settings.enforcement");
+                let enforcement_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(enforcement_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = enforcement_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let enforcement_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(180), y_pos, scale(185), scale(200), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut enforcement_hwnd = HWND::default();
+                if let Ok(h) = enforcement_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    for (key, _) in ENFORCEMENT_MODES {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    let current = if is_warn_only() { "warn_only".to_string() } else { crate::database::get_expiry_action() };
+                    let index = ENFORCEMENT_MODES.iter().position(|(_, v)| *v == current).unwrap_or(0);
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    enforcement_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Warning before locking, grace period before shutting down
+                let expiry_delay_label_text = i18n::wide("settings.expiry_delay");
+                let expiry_delay_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(expiry_delay_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = expiry_delay_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let expiry_delay_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32),
+                    scale(180), y_pos, scale(60), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut expiry_delay_hwnd = HWND::default();
+                if let Ok(h) = expiry_delay_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(3), LPARAM(0));
+                    let wide = i18n::to_wide(&crate::database::get_expiry_action_delay_seconds().to_string());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    expiry_delay_hwnd = h;
+                }
+                let expiry_delay_hint_text = i18n::wide("settings.expiry_delay_hint");
+                let expiry_delay_hint = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(expiry_delay_hint_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(245), y_pos + scale(2), scale(120), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = expiry_delay_hint { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(24);
+
+                // ===== Idle Detection Section =====
+                y_pos += scale(10);
+                let title_idle_text = i18n::wide("settings.idle");
+                let title_idle = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title_idle_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(360), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title_idle { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                // Enable checkbox
+                let idle_chk_text = i18n::wide("settings.auto_pause_idle");
+                let idle_enabled_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(idle_chk_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(200), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut idle_enabled_hwnd = HWND::default();
+                if let Ok(h) = idle_enabled_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if is_idle_enabled() {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    idle_enabled_hwnd = h;
+                }
+                y_pos += scale(22);
+
+                // Timeout minutes
+                let idle_timeout_label_text = i18n::wide("settings.idle_timeout");
+                let idle_timeout_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(idle_timeout_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = idle_timeout_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let idle_timeout_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32),
+                    scale(180), y_pos, scale(50), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut idle_timeout_hwnd = HWND::default();
+                if let Ok(h) = idle_timeout_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(3), LPARAM(0));
+                    let value = get_idle_timeout_minutes().to_string();
+                    let wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    idle_timeout_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Countdown display: small corner timer, large countdown or hidden
+                let display_mode_label_text = i18n::wide("settings.display_mode");
+                let display_mode_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(display_mode_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = display_mode_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let display_mode_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(180), y_pos, scale(150), scale(200), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut display_mode_hwnd = HWND::default();
+                if let Ok(h) = display_mode_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    for (key, _) in DISPLAY_MODES {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    // Unticked in the tray menu shows as hidden
+                    let current = if is_mini_overlay_enabled() { get_display_mode() } else { "hidden".to_string() };
+                    let index = DISPLAY_MODES.iter().position(|(_, v)| *v == current).unwrap_or(0);
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    display_mode_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Screen corner of the countdown
+                let display_corner_label_text = i18n::wide("settings.display_corner");
+                let display_corner_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(display_corner_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = display_corner_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let display_corner_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(180), y_pos, scale(150), scale(200), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut display_corner_hwnd = HWND::default();
+                if let Ok(h) = display_corner_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    for (key, _) in DISPLAY_CORNERS {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    let current = get_display_corner();
+                    let index = DISPLAY_CORNERS.iter().position(|(_, v)| *v == current).unwrap_or(0);
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    display_corner_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Mini overlay size preset
+                let mini_size_label_text = i18n::wide("settings.mini_overlay_size");
+                let mini_size_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(mini_size_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = mini_size_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let mini_size_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(180), y_pos, scale(100), scale(200), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut mini_size_hwnd = HWND::default();
+                if let Ok(h) = mini_size_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    for (key, _) in MINI_OVERLAY_SIZE_PRESETS {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    // Select the preset closest to the stored percentage
+                    let current = get_mini_overlay_scale();
+                    let index = MINI_OVERLAY_SIZE_PRESETS
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, (_, pct))| (pct - current).abs())
+                        .map(|(i, _)| i)
+                        .unwrap_or(1);
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    mini_size_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Mini overlay time format (the tray tooltip uses it too)
+                let mini_format_label_text = i18n::wide("settings.mini_overlay_format");
+                let mini_format_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(mini_format_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = mini_format_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let mini_format_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(180), y_pos, scale(190), scale(200), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut mini_format_hwnd = HWND::default();
+                if let Ok(h) = mini_format_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    for (key, _) in MINI_OVERLAY_FORMATS {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    let current = get_mini_overlay_format();
+                    let index = MINI_OVERLAY_FORMATS.iter().position(|(_, v)| *v == current).unwrap_or(0);
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    mini_format_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // High-contrast overlays checkbox
+                let high_contrast_text = i18n::wide("settings.high_contrast");
+                let high_contrast_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(high_contrast_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(340), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut high_contrast_hwnd = HWND::default();
+                if let Ok(h) = high_contrast_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if is_high_contrast_enabled() {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    high_contrast_hwnd = h;
+                }
+                y_pos += scale(22);
+
+                // What a left-click on the tray icon does (right-click always opens the menu)
+                let tray_click_label_text = i18n::wide("settings.tray_click");
+                let tray_click_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(tray_click_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = tray_click_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let tray_click_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(180), y_pos, scale(150), scale(200), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut tray_click_hwnd = HWND::default();
+                if let Ok(h) = tray_click_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    for (key, _) in TRAY_CLICK_ACTIONS {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    let current = get_tray_left_click();
+                    let index = TRAY_CLICK_ACTIONS.iter().position(|(_, v)| *v == current).unwrap_or(0);
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    tray_click_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Global shortcut that shows the remaining time (empty = off)
+                let hotkey_label_text = i18n::wide("settings.status_hotkey");
+                let hotkey_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(hotkey_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = hotkey_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let hotkey_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32),
+                    scale(180), y_pos, scale(100), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut status_hotkey_hwnd = HWND::default();
+                if let Ok(h) = hotkey_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    SendMessageW(h, EM_SETLIMITTEXT, WPARAM(30), LPARAM(0));
+                    let wide = i18n::to_wide(&crate::database::get_status_hotkey());
+                    SetWindowTextW(h, PCWSTR(wide.as_ptr())).ok();
+                    status_hotkey_hwnd = h;
+                }
+                let hotkey_hint_text = i18n::wide("settings.status_hotkey_hint");
+                let hotkey_hint = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(hotkey_hint_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(285), y_pos + scale(2), scale(80), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = hotkey_hint { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(26);
+
+                // How the corner timer shows a running pause
+                let pause_indicator_label_text = i18n::wide("settings.pause_indicator");
+                let pause_indicator_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(pause_indicator_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = pause_indicator_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let pause_indicator_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(180), y_pos, scale(150), scale(200), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut pause_indicator_hwnd = HWND::default();
+                if let Ok(h) = pause_indicator_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    for (key, _) in PAUSE_INDICATORS {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    let current = get_pause_indicator();
+                    let index = PAUSE_INDICATORS.iter().position(|(_, v)| *v == current).unwrap_or(0);
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    pause_indicator_hwnd = h;
+                }
+                y_pos += scale(26);
+
+                // Parents can make pausing parent-only
+                let pause_passcode_text = i18n::wide("settings.pause_requires_passcode");
+                let pause_passcode_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(pause_passcode_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(340), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut pause_passcode_hwnd = HWND::default();
+                if let Ok(h) = pause_passcode_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if is_pause_passcode_required() {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    pause_passcode_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // Kiosk mode: the tray only shows the status; parents use Telegram from then on
+                let kiosk_text = i18n::wide("settings.kiosk_mode");
+                let kiosk_chk = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(kiosk_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_AUTOCHECKBOX as u32),
+                    scale(25), y_pos, scale(340), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut kiosk_hwnd = HWND::default();
+                if let Ok(h) = kiosk_chk {
+                    SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1));
+                    if is_kiosk_mode() {
+                        SendMessageW(h, BM_SETCHECK, WPARAM(1), LPARAM(0));
+                    }
+                    kiosk_hwnd = h;
+                }
+                y_pos += scale(24);
+
+                // How much goes into app.log (for diagnosing bot or database problems)
+                let log_level_label_text = i18n::wide("settings.log_level");
+                let log_level_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(log_level_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(150), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = log_level_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let log_level_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    scale(180), y_pos, scale(150), scale(200), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut log_level_hwnd = HWND::default();
+                if let Ok(h) = log_level_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    for (key, _) in LOG_LEVELS {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    let current = get_log_level();
+                    let index = LOG_LEVELS.iter().position(|(_, v)| *v == current).unwrap_or(2);
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(index), LPARAM(0));
+                    log_level_hwnd = h;
+                }
+                y_pos += scale(26);
+
+                // ===== Backup & Restore Section =====
+                y_pos += scale(10);
+                let title_backup_text = i18n::wide("settings.backup");
+                let title_backup = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(title_backup_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(15), y_pos, scale(360), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = title_backup { SendMessageW(h, WM_SETFONT, WPARAM(title_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(20);
+
+                let backup_password_label_text = i18n::wide("settings.backup_password");
+                let backup_password_label = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(backup_password_label_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(25), y_pos + scale(2), scale(70), scale(20), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = backup_password_label { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let backup_password_edit = CreateWindowExW(
+                    WINDOW_EX_STYLE(0x200), w!("EDIT"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_PASSWORD as u32 | ES_AUTOHSCROLL as u32),
+                    scale(100), y_pos, scale(265), scale(22), hwnd, HMENU::default(), hinstance, None,
+                );
+                let mut backup_password_hwnd = HWND::default();
+                if let Ok(h) = backup_password_edit {
+                    SendMessageW(h, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+                    backup_password_hwnd = h;
+                }
+                y_pos += scale(24);
+                let backup_hint_text = i18n::wide("settings.backup_hint");
+                let backup_hint = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("STATIC"), PCWSTR(backup_hint_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE, scale(100), y_pos, scale(265), scale(18), hwnd, HMENU::default(), hinstance, None,
+                );
+                if let Ok(h) = backup_hint { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(18);
+
+                let backup_btn_text = i18n::wide("settings.backup_create");
+                let backup_btn = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(backup_btn_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    scale(100), y_pos, scale(130), scale(22), hwnd, HMENU(ID_BACKUP_CREATE as _), hinstance, None,
+                );
+                if let Ok(h) = backup_btn { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                let restore_btn_text = i18n::wide("settings.backup_restore");
+                let restore_btn = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(restore_btn_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    scale(235), y_pos, scale(130), scale(22), hwnd, HMENU(ID_BACKUP_RESTORE as _), hinstance, None,
+                );
+                if let Ok(h) = restore_btn { SendMessageW(h, WM_SETFONT, WPARAM(label_font.0 as usize), LPARAM(1)); }
+                y_pos += scale(28);
+
+                // ===== Buttons =====
+                let btn_font = CreateFontW(
+                    scale(14), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+                );
+
+                let save_btn_text = i18n::wide("button.save");
+                let save_btn = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(save_btn_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    scale(100), y_pos, scale(90), scale(30), hwnd, HMENU(ID_SETTINGS_SAVE as _), hinstance, None,
+                );
+                if let Ok(h) = save_btn { SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1)); }
+
+                let cancel_btn_text = i18n::wide("button.cancel");
+                let cancel_btn = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(cancel_btn_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    scale(200), y_pos, scale(90), scale(30), hwnd, HMENU(ID_SETTINGS_CANCEL as _), hinstance, None,
+                );
+                if let Ok(h) = cancel_btn { SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1)); }
+
+                SETTINGS_EDIT_HANDLES.set(Some(SettingsEditHandles {
+                    daily_limits: daily_handles,
+                    rampdown_step: rampdown_step_hwnd,
+                    rampdown_floor: rampdown_floor_hwnd,
+                    allowed_hours_weekday: allowed_hours_hwnds[0],
+                    allowed_hours_weekend: allowed_hours_hwnds[1],
+                    productive_apps: productive_hwnd,
+                    counted_users: counted_users_hwnd,
+                    warning1_minutes: w1_min_hwnd,
+                    warning1_message: w1_msg_hwnd,
+                    warning1_duration: w1_dur_hwnd,
+                    warning1_minutes_weekend: w1_weekend_min_hwnd,
+                    warning1_message_weekend: w1_weekend_msg_hwnd,
+                    warning2_minutes: w2_min_hwnd,
+                    warning2_message: w2_msg_hwnd,
+                    warning2_duration: w2_dur_hwnd,
+                    warning2_minutes_weekend: w2_weekend_min_hwnd,
+                    warning2_message_weekend: w2_weekend_msg_hwnd,
+                    defer_warnings_fullscreen: defer_hwnd,
+                    respect_focus_assist: focus_assist_hwnd,
+                    warning_position: position_hwnd,
+                    warning_margin: margin_hwnd,
+                    winddown_minutes: winddown_hwnd,
+                    winddown_interval: winddown_interval_hwnd,
+                    blocking_message: block_msg_hwnd,
+                    secondary_block_message: secondary_msg_hwnd,
+                    block_background_image: bg_image_hwnd,
+                    current_passcode: curr_pass_hwnd,
+                    new_passcode: new_pass_hwnd,
+                    confirm_passcode: confirm_pass_hwnd,
+                    lockout_attempts: lockout_attempts_hwnd,
+                    lockout_delay: lockout_delay_hwnd,
+                    min_block_duration: min_block_hwnd,
+                    max_extensions_per_day: max_ext_hwnd,
+                    self_extend_minutes: self_extend_hwnds[0],
+                    self_extend_per_day: self_extend_hwnds[1],
+                    clock_change_block: clock_block_hwnd,
+                    clock_guard: clock_guard_hwnd,
+                    pause_requires_passcode: pause_passcode_hwnd,
+                    kiosk_mode: kiosk_hwnd,
+                    telegram_token: telegram_token_hwnd,
+                    telegram_chat_id: telegram_chat_id_hwnd,
+                    webhook_url: webhook_hwnd,
+                    email_enabled: email_enabled_hwnd,
+                    email_report_time: report_time_hwnd,
+                    smtp_host: smtp_host_hwnd,
+                    smtp_port: smtp_port_hwnd,
+                    smtp_username: smtp_user_hwnd,
+                    smtp_password: smtp_password_hwnd,
+                    email_to: email_to_hwnd,
+                    mqtt_enabled: mqtt_enabled_hwnd,
+                    mqtt_host: mqtt_host_hwnd,
+                    mqtt_port: mqtt_port_hwnd,
+                    mqtt_username: mqtt_user_hwnd,
+                    mqtt_password: mqtt_password_hwnd,
+                    mqtt_prefix: mqtt_prefix_hwnd,
+                    http_enabled: http_enabled_hwnd,
+                    http_listen: http_listen_hwnd,
+                    http_token: http_token_hwnd,
+                    telegram_enabled: telegram_enabled_hwnd,
+                    lock_screen_timeout: lock_timeout_hwnd,
+                    block_text_size: block_size_hwnd,
+                    enforcement_mode: enforcement_hwnd,
+                    expiry_action_delay: expiry_delay_hwnd,
+                    idle_enabled: idle_enabled_hwnd,
+                    idle_timeout_minutes: idle_timeout_hwnd,
+                    language: lang_combo_hwnd,
+                    display_mode: display_mode_hwnd,
+                    display_corner: display_corner_hwnd,
+                    mini_overlay_size: mini_size_hwnd,
+                    mini_overlay_format: mini_format_hwnd,
+                    block_buttons: block_button_handles,
+                    high_contrast: high_contrast_hwnd,
+                    tray_left_click: tray_click_hwnd,
+                    pause_indicator: pause_indicator_hwnd,
+                    status_hotkey: status_hotkey_hwnd,
+                    log_level: log_level_hwnd,
+                    backup_password: backup_password_hwnd,
+                }));
+
+                LRESULT(0)
+            }
+            WM_COMMAND => {
+                let id = (wparam.0 & 0xFFFF) as i32;
+
+                if id == ID_SETTINGS_SAVE {
+                    if let Some(handles) = settings_edit_handles() {
+                        // All values are collected first and written in a single batch
+                        let mut updates: Vec<(&str, String)> = Vec::new();
+
+                        // Handle passcode change first
+                        let mut current_pass = String::new();
+                        let mut new_pass = String::new();
+                        let mut confirm_pass = String::new();
+
+                        if !handles.current_passcode.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.current_passcode, &mut buffer);
+                            current_pass = String::from_utf16_lossy(&buffer[..len as usize]);
+                        }
+                        if !handles.new_passcode.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.new_passcode, &mut buffer);
+                            new_pass = String::from_utf16_lossy(&buffer[..len as usize]);
+                        }
+                        if !handles.confirm_passcode.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.confirm_passcode, &mut buffer);
+                            confirm_pass = String::from_utf16_lossy(&buffer[..len as usize]);
+                        }
+
+                        // Check if user wants to change passcode
+                        if !new_pass.is_empty() || !confirm_pass.is_empty() {
+                            // Verify current passcode
+                            let stored = get_passcode().unwrap_or_else(|| DEFAULT_PASSCODE.to_string());
+                            if current_pass != stored {
+                                let msg = i18n::wide("settings.error.current_incorrect");
+                                let title = i18n::wide("settings.error");
+                                MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+                                return LRESULT(0);
+                            }
+
+                            // Check new passcode requirements
+                            if new_pass.len() != 4 {
+                                let msg = i18n::wide("settings.error.passcode_length");
+                                let title = i18n::wide("settings.error");
+                                MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+                                return LRESULT(0);
+                            }
+
+                            // Check that new passcodes match
+                            if new_pass != confirm_pass {
+                                let msg = i18n::wide("settings.error.passcode_mismatch");
+                                let title = i18n::wide("settings.error");
+                                MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+                                return LRESULT(0);
+                            }
+
+                            // Save new passcode (written together with the rest below)
+                            updates.push(("passcode", new_pass));
+                        }
+
+                        // Collect other settings
+                        for (i, &edit_hwnd) in handles.daily_limits.iter().enumerate() {
+                            if !edit_hwnd.0.is_null() {
+                                let mut buffer = [0u16; 16];
+                                let len = GetWindowTextW(edit_hwnd, &mut buffer);
+                                let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                                // Typed as minutes or hours ("2:30", "2h 30m"); stored as minutes
+                                match duration::parse_duration(&value) {
+                                    Some(minutes) if minutes <= 1440 => updates.push((WEEKDAY_KEYS[i], minutes.to_string())),
+                                    _ => {
+                                        let msg = i18n::to_wide(&format!("{}: {}", locale::weekday_name(i), i18n::t("settings.error.daily_limit")));
+                                        let title = i18n::wide("settings.error");
+                                        MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+                                        let _ = SetFocus(edit_hwnd);
+                                        return LRESULT(0);
+                                    }
+                                }
+                            }
+                        }
+
+                        // Weekly ramp-down
+                        if !handles.rampdown_step.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.rampdown_step, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(step) = value.parse::<u32>() {
+                                // Turning the schedule on starts counting from this week;
+                                // the first reduction happens next Monday
+                                if step > 0 && get_rampdown_config().step_minutes == 0 {
+                                    updates.push(("limit_rampdown_last_applied", get_week_start_date()));
+                                }
+                                updates.push(("limit_rampdown_step", step.to_string()));
+                            }
+                        }
+                        // Allowed hours: empty clears the curfew, anything else must parse
+                        for (edit_hwnd, key) in [
+                            (handles.allowed_hours_weekday, "allowed_hours_weekday"),
+                            (handles.allowed_hours_weekend, "allowed_hours_weekend"),
+                        ] {
+                            if edit_hwnd.0.is_null() {
+                                continue;
+                            }
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(edit_hwnd, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string();
+                            if !value.is_empty() && crate::allowed_hours::parse_window(&value).is_none() {
+                                let msg = i18n::wide("settings.error.allowed_hours");
+                                let title = i18n::wide("settings.error");
+                                MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+                                return LRESULT(0);
+                            }
+                            updates.push((key, value));
+                        }
+
+                        if !handles.rampdown_floor.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.rampdown_floor, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(floor) = value.parse::<u32>() {
+                                updates.push(("limit_rampdown_floor", floor.to_string()));
+                            }
+                        }
+
+                        if !handles.warning1_minutes.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.warning1_minutes, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            updates.push(("warning1_minutes", value));
+                        }
+                        if !handles.warning1_duration.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.warning1_duration, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(seconds) = value.parse::<u32>() {
+                                updates.push(("warning1_duration", seconds.clamp(1, 300).to_string()));
+                            }
+                        }
+                        if !handles.warning1_message.0.is_null() {
+                            let mut buffer = [0u16; 256];
+                            let len = GetWindowTextW(handles.warning1_message, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            updates.push(("warning1_message", value));
+                        }
+                        if !handles.warning1_minutes_weekend.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.warning1_minutes_weekend, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string();
+                            updates.push(("warning1_minutes_weekend", value));
+                        }
+                        if !handles.warning1_message_weekend.0.is_null() {
+                            let mut buffer = [0u16; 256];
+                            let len = GetWindowTextW(handles.warning1_message_weekend, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            updates.push(("warning1_message_weekend", value));
+                        }
+
+                        if !handles.warning2_minutes.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.warning2_minutes, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            updates.push(("warning2_minutes", value));
+                        }
+                        if !handles.warning2_duration.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.warning2_duration, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(seconds) = value.parse::<u32>() {
+                                updates.push(("warning2_duration", seconds.clamp(1, 300).to_string()));
+                            }
+                        }
+                        if !handles.warning2_message.0.is_null() {
+                            let mut buffer = [0u16; 256];
+                            let len = GetWindowTextW(handles.warning2_message, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            updates.push(("warning2_message", value));
+                        }
+                        if !handles.warning2_minutes_weekend.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.warning2_minutes_weekend, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string();
+                            updates.push(("warning2_minutes_weekend", value));
+                        }
+                        if !handles.warning2_message_weekend.0.is_null() {
+                            let mut buffer = [0u16; 256];
+                            let len = GetWindowTextW(handles.warning2_message_weekend, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            updates.push(("warning2_message_weekend", value));
+                        }
+                        if !handles.defer_warnings_fullscreen.0.is_null() {
+                            let checked = SendMessageW(handles.defer_warnings_fullscreen, BM_GETCHECK, WPARAM(0), LPARAM(0));
+                            updates.push(("defer_warnings_fullscreen", if checked.0 == 1 { "1" } else { "0" }.to_string()));
+                        }
+                        if !handles.respect_focus_assist.0.is_null() {
+                            let checked = SendMessageW(handles.respect_focus_assist, BM_GETCHECK, WPARAM(0), LPARAM(0));
+                            updates.push(("respect_focus_assist", if checked.0 == 1 { "1" } else { "0" }.to_string()));
+                        }
+                        if !handles.warning_position.0.is_null() {
+                            let sel = SendMessageW(handles.warning_position, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            if let Some((_, value)) = WARNING_POSITIONS.get(sel.0 as usize) {
+                                updates.push(("warning_position", value.to_string()));
+                            }
+                        }
+                        if !handles.warning_margin.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.warning_margin, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(margin) = value.parse::<i32>() {
+                                updates.push(("warning_margin", margin.clamp(0, 500).to_string()));
+                            }
+                        }
+                        if !handles.winddown_minutes.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.winddown_minutes, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(minutes) = value.parse::<u32>() {
+                                updates.push(("winddown_minutes", minutes.min(60).to_string()));
+                            }
+                        }
+                        if !handles.winddown_interval.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.winddown_interval, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(minutes) = value.parse::<u32>() {
+                                updates.push(("winddown_interval", minutes.clamp(1, 30).to_string()));
+                            }
+                        }
+                        if !handles.lockout_attempts.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.lockout_attempts, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(attempts) = value.parse::<u32>() {
+                                updates.push(("lockout_attempts", attempts.min(100).to_string()));
+                            }
+                        }
+                        if !handles.lockout_delay.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.lockout_delay, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(seconds) = value.parse::<u32>() {
+                                updates.push(("lockout_delay_seconds", seconds.clamp(1, 3600).to_string()));
+                            }
+                        }
+                        if !handles.min_block_duration.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.min_block_duration, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(seconds) = value.parse::<u32>() {
+                                updates.push(("min_block_duration_seconds", seconds.min(3600).to_string()));
+                            }
+                        }
+                        if !handles.max_extensions_per_day.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.max_extensions_per_day, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(count) = value.parse::<u32>() {
+                                updates.push(("max_extensions_per_day", count.to_string()));
+                            }
+                        }
+                        for (edit, key, max) in [
+                            (handles.self_extend_minutes, "self_extend_minutes", 60),
+                            (handles.self_extend_per_day, "self_extend_per_day", 99),
+                        ] {
+                            if !edit.0.is_null() {
+                                let mut buffer = [0u16; 16];
+                                let len = GetWindowTextW(edit, &mut buffer);
+                                let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                                if let Ok(n) = value.parse::<u32>() {
+                                    updates.push((key, n.min(max).to_string()));
+                                }
+                            }
+                        }
+                        if !handles.clock_change_block.0.is_null() {
+                            let checked = SendMessageW(handles.clock_change_block, BM_GETCHECK, WPARAM(0), LPARAM(0));
+                            updates.push(("clock_change_block", if checked.0 == 1 { "1" } else { "0" }.to_string()));
+                        }
+                        if !handles.pause_requires_passcode.0.is_null() {
+                            let checked = SendMessageW(handles.pause_requires_passcode, BM_GETCHECK, WPARAM(0), LPARAM(0));
+                            updates.push(("pause_requires_passcode", if checked.0 == 1 { "1" } else { "0" }.to_string()));
+                        }
+                        if !handles.clock_guard.0.is_null() {
+                            let checked = SendMessageW(handles.clock_guard, BM_GETCHECK, WPARAM(0), LPARAM(0));
+                            updates.push(("clock_guard", if checked.0 == 1 { "1" } else { "0" }.to_string()));
+                        }
+
+                        if !handles.blocking_message.0.is_null() {
+                            let mut buffer = [0u16; 256];
+                            let len = GetWindowTextW(handles.blocking_message, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            updates.push(("blocking_message", value));
+                        }
+                        if !handles.secondary_block_message.0.is_null() {
+                            let mut buffer = [0u16; 256];
+                            let len = GetWindowTextW(handles.secondary_block_message, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            updates.push(("secondary_block_message", value.trim().to_string()));
+                        }
+                        if !handles.block_background_image.0.is_null() {
+                            let mut buffer = [0u16; 520];
+                            let len = GetWindowTextW(handles.block_background_image, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            updates.push(("block_background_image", value.trim().trim_matches('"').to_string()));
+                        }
+
+                        // Telegram settings
+                        if !handles.telegram_token.0.is_null() {
+                            let mut buffer = [0u16; 512];
+                            let len = GetWindowTextW(handles.telegram_token, &mut buffer);
+                            updates.push((TELEGRAM_BOT_TOKEN, String::from_utf16_lossy(&buffer[..len as usize])));
+                        } else {
+                            updates.push((TELEGRAM_BOT_TOKEN, String::new()));
+                        }
+
+                        if !handles.telegram_chat_id.0.is_null() {
+                            let mut buffer = [0u16; 64];
+                            let len = GetWindowTextW(handles.telegram_chat_id, &mut buffer);
+                            updates.push((TELEGRAM_ADMIN_CHAT_ID, String::from_utf16_lossy(&buffer[..len as usize])));
+                        } else {
+                            updates.push((TELEGRAM_ADMIN_CHAT_ID, String::new()));
+                        }
+
+                        if !handles.webhook_url.0.is_null() {
+                            let mut buffer = [0u16; 1024];
+                            let len = GetWindowTextW(handles.webhook_url, &mut buffer);
+                            updates.push(("webhook_url", String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string()));
+                        }
+
+                        // Daily email report
+                        let email_enabled = !handles.email_enabled.0.is_null()
+                            && SendMessageW(handles.email_enabled, BM_GETCHECK, WPARAM(0), LPARAM(0)).0 == 1;
+                        updates.push(("email_enabled", if email_enabled { "1" } else { "0" }.to_string()));
+                        if !handles.email_report_time.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.email_report_time, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string();
+                            // Same HH:MM format as the allowed hours
+                            if crate::allowed_hours::parse_window(&format!("{}-{}", value, value)).is_some() {
+                                updates.push(("email_report_time", value));
+                            }
+                        }
+                        for (handle, key) in [
+                            (handles.smtp_host, "smtp_host"),
+                            (handles.smtp_username, "smtp_username"),
+                            (handles.email_to, "email_to"),
+                        ] {
+                            if !handle.0.is_null() {
+                                let mut buffer = [0u16; 512];
+                                let len = GetWindowTextW(handle, &mut buffer);
+                                updates.push((key, String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string()));
+                            }
+                        }
+                        if !handles.smtp_port.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.smtp_port, &mut buffer);
+                            if let Ok(port) = String::from_utf16_lossy(&buffer[..len as usize]).parse::<u16>() {
+                                updates.push(("smtp_port", port.to_string()));
+                            }
+                        }
+                        // An empty password field keeps the saved password
+                        if !handles.smtp_password.0.is_null() {
+                            let mut buffer = [0u16; 256];
+                            let len = GetWindowTextW(handles.smtp_password, &mut buffer);
+                            if len > 0 {
+                                let password = String::from_utf16_lossy(&buffer[..len as usize]);
+                                updates.push(("smtp_password", crate::secrets::protect(&password)));
+                            }
+                        }
+
+                        // MQTT (applies after a restart, like the Telegram bot)
+                        let mqtt_enabled = !handles.mqtt_enabled.0.is_null()
+                            && SendMessageW(handles.mqtt_enabled, BM_GETCHECK, WPARAM(0), LPARAM(0)).0 == 1;
+                        updates.push(("mqtt_enabled", if mqtt_enabled { "1" } else { "0" }.to_string()));
+                        for (handle, key) in [
+                            (handles.mqtt_host, "mqtt_host"),
+                            (handles.mqtt_username, "mqtt_username"),
+                            (handles.mqtt_prefix, "mqtt_prefix"),
+                        ] {
+                            if !handle.0.is_null() {
+                                let mut buffer = [0u16; 512];
+                                let len = GetWindowTextW(handle, &mut buffer);
+                                updates.push((key, String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string()));
+                            }
+                        }
+                        if !handles.mqtt_port.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.mqtt_port, &mut buffer);
+                            if let Ok(port) = String::from_utf16_lossy(&buffer[..len as usize]).parse::<u16>() {
+                                updates.push(("mqtt_port", port.to_string()));
+                            }
+                        }
+                        // An empty password field keeps the saved password
+                        if !handles.mqtt_password.0.is_null() {
+                            let mut buffer = [0u16; 256];
+                            let len = GetWindowTextW(handles.mqtt_password, &mut buffer);
+                            if len > 0 {
+                                let password = String::from_utf16_lossy(&buffer[..len as usize]);
+                                updates.push(("mqtt_password", crate::secrets::protect(&password)));
+                            }
+                        }
+
+                        // Metrics endpoint (applies after a restart)
+                        let http_enabled = !handles.http_enabled.0.is_null()
+                            && SendMessageW(handles.http_enabled, BM_GETCHECK, WPARAM(0), LPARAM(0)).0 == 1;
+                        updates.push(("http_enabled", if http_enabled { "1" } else { "0" }.to_string()));
+                        if !handles.http_listen.0.is_null() {
+                            let mut buffer = [0u16; 128];
+                            let len = GetWindowTextW(handles.http_listen, &mut buffer);
+                            updates.push(("http_listen", String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string()));
+                        }
+                        // An empty token field keeps the saved token
+                        if !handles.http_token.0.is_null() {
+                            let mut buffer = [0u16; 256];
+                            let len = GetWindowTextW(handles.http_token, &mut buffer);
+                            let token = String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string();
+                            if !token.is_empty() {
+                                updates.push(("http_token", crate::secrets::protect(&token)));
+                            }
+                        }
+
+                        let telegram_enabled = !handles.telegram_enabled.0.is_null()
+                            && SendMessageW(handles.telegram_enabled, BM_GETCHECK, WPARAM(0), LPARAM(0)).0 == 1;
+                        updates.push((TELEGRAM_ENABLED, if telegram_enabled { "true" } else { "false" }.to_string()));
+
+                        // Kiosk mode removes Settings from the tray, so Telegram has to be the way back
+                        if !handles.kiosk_mode.0.is_null() {
+                            let kiosk = SendMessageW(handles.kiosk_mode, BM_GETCHECK, WPARAM(0), LPARAM(0)).0 == 1;
+                            let has_admin = updates.iter().any(|(key, value)| *key == TELEGRAM_ADMIN_CHAT_ID && value.trim().parse::<i64>().is_ok());
+                            if kiosk && !(telegram_enabled && has_admin) {
+                                let msg = i18n::wide("settings.error.kiosk_needs_telegram");
+                                let title = i18n::wide("settings.error");
+                                MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+                                return LRESULT(0);
+                            }
+                            updates.push(("kiosk_mode", if kiosk { "1" } else { "0" }.to_string()));
+                        }
+
+                        // Lock screen timeout (convert minutes to seconds)
+                        if !handles.lock_screen_timeout.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.lock_screen_timeout, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(minutes) = value.parse::<i32>() {
+                                let seconds = minutes * 60;
+                                updates.push(("lock_screen_timeout", seconds.to_string()));
+                            }
+                        }
+
+                        // Blocking panel size preset
+                        if !handles.block_text_size.0.is_null() {
+                            let sel = SendMessageW(handles.block_text_size, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            if let Some((_, pct)) = BLOCK_TEXT_SIZE_PRESETS.get(sel.0 as usize) {
+                                updates.push(("block_text_scale", pct.to_string()));
+                            }
+                        }
+
+                        // Lock screen buttons
+                        for (h, (_, setting, _)) in handles.block_buttons.iter().zip(BLOCK_BUTTON_SETTINGS) {
+                            if !h.0.is_null() {
+                                let checked = SendMessageW(*h, BM_GETCHECK, WPARAM(0), LPARAM(0));
+                                updates.push((setting, if checked.0 == 1 { "1" } else { "0" }.to_string()));
+                            }
+                        }
+
+                        // Block or warn only when the time runs out
+                        if !handles.enforcement_mode.0.is_null() {
+                            let sel = SendMessageW(handles.enforcement_mode, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            if let Some((_, value)) = ENFORCEMENT_MODES.get(sel.0 as usize) {
+                                let (mode, action) = match *value {
+                                    "warn_only" => ("warn_only", "block"),
+                                    action => ("block", action),
+                                };
+                                updates.push(("enforcement_mode", mode.to_string()));
+                                updates.push(("expiry_action", action.to_string()));
+                            }
+                        }
+                        if !handles.expiry_action_delay.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.expiry_action_delay, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(seconds) = value.parse::<u32>() {
+                                updates.push(("expiry_action_delay_seconds", seconds.min(600).to_string()));
+                            }
+                        }
+
+                        // Idle detection settings
+                        if !handles.idle_enabled.0.is_null() {
+                            let checked = SendMessageW(handles.idle_enabled, BM_GETCHECK, WPARAM(0), LPARAM(0));
+                            updates.push(("idle_enabled", if checked.0 == 1 { "1" } else { "0" }.to_string()));
+                        }
+                        if !handles.idle_timeout_minutes.0.is_null() {
+                            let mut buffer = [0u16; 16];
+                            let len = GetWindowTextW(handles.idle_timeout_minutes, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]);
+                            if let Ok(mins) = value.parse::<u32>() {
+                                let clamped = mins.max(1);
+                                updates.push(("idle_timeout_minutes", clamped.to_string()));
+                            }
+                        }
+
+                        // Language setting
+                        if !handles.language.0.is_null() {
+                            let sel = SendMessageW(handles.language, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            let lang = match sel.0 {
+                                1 => Language::German,
+                                _ => Language::English,
+                            };
+                            updates.push(("language", lang.code().to_string()));
+                        }
+
+                        // Countdown display mode; picking a visible one also undoes unticking it in the tray
+                        if !handles.display_mode.0.is_null() {
+                            let sel = SendMessageW(handles.display_mode, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            if let Some((_, value)) = DISPLAY_MODES.get(sel.0 as usize) {
+                                updates.push(("display_mode", value.to_string()));
+                                if *value != "hidden" {
+                                    updates.push(("mini_overlay_visible", "1".to_string()));
+                                }
+                            }
+                        }
+                        if !handles.display_corner.0.is_null() {
+                            let sel = SendMessageW(handles.display_corner, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            if let Some((_, value)) = DISPLAY_CORNERS.get(sel.0 as usize) {
+                                updates.push(("display_corner", value.to_string()));
+                            }
+                        }
+
+                        // Mini overlay size preset
+                        if !handles.mini_overlay_size.0.is_null() {
+                            let sel = SendMessageW(handles.mini_overlay_size, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            if let Some((_, pct)) = MINI_OVERLAY_SIZE_PRESETS.get(sel.0 as usize) {
+                                updates.push(("mini_overlay_scale", pct.to_string()));
+                            }
+                        }
+                        if !handles.mini_overlay_format.0.is_null() {
+                            let sel = SendMessageW(handles.mini_overlay_format, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            if let Some((_, value)) = MINI_OVERLAY_FORMATS.get(sel.0 as usize) {
+                                updates.push(("mini_overlay_format", value.to_string()));
+                            }
+                        }
+
+                        // High-contrast overlays
+                        if !handles.high_contrast.0.is_null() {
+                            let checked = SendMessageW(handles.high_contrast, BM_GETCHECK, WPARAM(0), LPARAM(0));
+                            updates.push(("high_contrast", if checked.0 == 1 { "1" } else { "0" }.to_string()));
+                        }
+
+                        // Tray icon left-click action
+                        if !handles.tray_left_click.0.is_null() {
+                            let sel = SendMessageW(handles.tray_left_click, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            if let Some((_, value)) = TRAY_CLICK_ACTIONS.get(sel.0 as usize) {
+                                updates.push(("tray_left_click", value.to_string()));
+                            }
+                        }
+                        if !handles.pause_indicator.0.is_null() {
+                            let sel = SendMessageW(handles.pause_indicator, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            if let Some((_, value)) = PAUSE_INDICATORS.get(sel.0 as usize) {
+                                updates.push(("pause_indicator", value.to_string()));
+                            }
+                        }
+                        if !handles.status_hotkey.0.is_null() {
+                            let mut buffer = [0u16; 64];
+                            let len = GetWindowTextW(handles.status_hotkey, &mut buffer);
+                            let value = String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string();
+                            if !value.is_empty() && crate::hotkey::parse_hotkey(&value).is_none() {
+                                let msg = i18n::wide("settings.error.hotkey");
+                                let title = i18n::wide("settings.error");
+                                MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+                                let _ = SetFocus(handles.status_hotkey);
+                                return LRESULT(0);
+                            }
+                            updates.push(("status_hotkey", value));
+                        }
+                        if !handles.counted_users.0.is_null() {
+                            let len = GetWindowTextLengthW(handles.counted_users);
+                            let mut buffer = vec![0u16; len as usize + 1];
+                            let len = GetWindowTextW(handles.counted_users, &mut buffer);
+                            let text = String::from_utf16_lossy(&buffer[..len as usize]);
+                            let users: Vec<&str> = text.split(',').map(str::trim).filter(|u| !u.is_empty()).collect();
+                            updates.push(("counted_users", users.join(", ")));
+                        }
+                        if !handles.log_level.0.is_null() {
+                            let sel = SendMessageW(handles.log_level, CB_GETCURSEL, WPARAM(0), LPARAM(0));
+                            if let Some((_, value)) = LOG_LEVELS.get(sel.0 as usize) {
+                                updates.push(("log_level", value.to_string()));
+                            }
+                        }
+
+                        // Productive apps: comma-separated executable names
+                        let mut app_updates = Vec::new();
+                        if !handles.productive_apps.0.is_null() {
+                            let len = GetWindowTextLengthW(handles.productive_apps);
+                            let mut buffer = vec![0u16; len as usize + 1];
+                            let len = GetWindowTextW(handles.productive_apps, &mut buffer);
+                            let text = String::from_utf16_lossy(&buffer[..len as usize]);
+                            let mut apps: Vec<String> = text.split(',').map(crate::apps::normalize_exe).filter(|a| !a.is_empty()).collect();
+                            apps.sort();
+                            apps.dedup();
+                            app_updates = productive_app_updates(&apps);
+                        }
+
+                        // Write everything in one transaction so a failed save leaves nothing half-applied
+                        let pairs: Vec<(&str, &str)> = updates
+                            .iter()
+                            .map(|(k, v)| (*k, v.as_str()))
+                            .chain(app_updates.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+                            .collect();
+                        if set_settings_batch(&pairs).is_err() {
+                            let msg = i18n::wide("settings.error.save_failed");
+                            let title = i18n::wide("settings.error");
+                            MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+                            return LRESULT(0);
+                        }
+
+                        crate::apps::reload_productive_apps();
+                        crate::mini_overlay::apply_display_settings();
+                        crate::theme::refresh();
+                        crate::logging::apply_level();
+                        crate::hotkey::reregister();
+                        crate::session::refresh();
+
+                        // If today's limit was lowered, cap remaining time to the new limit
+                        {
+                            use crate::blocking::REMAINING_SECONDS;
+                            use crate::database::{get_current_weekday, get_daily_limit, save_remaining_time};
+                            use crate::mini_overlay::update_mini_overlay;
+                            use std::sync::atomic::Ordering;
+
+                            let weekday = get_current_weekday();
+                            let new_limit_seconds = (get_daily_limit(weekday) * 60) as i32;
+                            let remaining = REMAINING_SECONDS.load(Ordering::SeqCst);
+
+                            if remaining > new_limit_seconds {
+                                REMAINING_SECONDS.store(new_limit_seconds, Ordering::SeqCst);
+                                save_remaining_time(new_limit_seconds);
+                                update_mini_overlay();
+                                // Today became a day without screen time
+                                if new_limit_seconds == 0 {
+                                    crate::mini_overlay::enforce_time_up();
+                                }
+                            }
+                        }
+                    }
+
+                    let msg = i18n::wide("settings.success.saved");
+                    let title = i18n::wide("settings.success");
+                    MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONINFORMATION);
+                    DestroyWindow(hwnd).ok();
+                } else if id == ID_SETTINGS_CANCEL {
+                    DestroyWindow(hwnd).ok();
+                } else if id == ID_BACKUP_CREATE || id == ID_BACKUP_RESTORE {
+                    run_backup_command(hwnd, id == ID_BACKUP_RESTORE);
+                } else if id == ID_FILL_ALL_DAYS {
+                    fill_daily_limits(hwnd, 0, 1..7);
+                } else if id == ID_FILL_WEEKDAYS {
+                    fill_daily_limits(hwnd, 0, 1..5);
+                } else if id == ID_FILL_WEEKEND {
+                    fill_daily_limits(hwnd, 5, 6..7);
+                } else if id == ID_TELEGRAM_WIZARD {
+                    // Open Telegram setup wizard
+                    show_telegram_wizard(hwnd);
+                    // Refresh dialog to show new values if wizard completed
+                    let _ = InvalidateRect(hwnd, None, true);
+                }
+
+                LRESULT(0)
+            }
+            WM_VSCROLL => {
+                let mut si = SCROLLINFO { cbSize: std::mem::size_of::<SCROLLINFO>() as u32, fMask: SIF_ALL, ..zeroed() };
+                let _ = GetScrollInfo(hwnd, SB_VERT, &mut si);
+                let delta = match SCROLLBAR_COMMAND((wparam.0 & 0xFFFF) as i32) {
+                    SB_LINEUP => -scale(24),
+                    SB_LINEDOWN => scale(24),
+                    SB_PAGEUP => -(si.nPage as i32),
+                    SB_PAGEDOWN => si.nPage as i32,
+                    SB_THUMBTRACK | SB_THUMBPOSITION => si.nTrackPos - si.nPos,
+                    _ => 0,
+                };
+                scroll_settings_by(hwnd, delta);
+                LRESULT(0)
+            }
+            WM_MOUSEWHEEL => {
+                // Three rows per wheel notch
+                let wheel = ((wparam.0 >> 16) & 0xFFFF) as u16 as i16 as i32;
+                scroll_settings_by(hwnd, -wheel * scale(24) * 3 / WHEEL_DELTA as i32);
+                LRESULT(0)
+            }
+            WM_CLOSE => {
+                DestroyWindow(hwnd).ok();
+                LRESULT(0)
+            }
+            WM_DESTROY => {
+                SETTINGS_EDIT_HANDLES.set(None);
+                SETTINGS_DIALOG_OPEN.store(false, Ordering::SeqCst);
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+        }
+    }
+
+    let wnd_class = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(settings_dialog_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: dialog_class,
+        hbrBackground: CreateSolidBrush(COLORREF(0x00F5F5F5)),
+        hCursor: LoadCursorW(None, IDC_ARROW).ok().unwrap_or_default(),
+        ..zeroed()
+    };
+    RegisterClassW(&wnd_class);
+
+    let screen_width = GetSystemMetrics(SM_CXSCREEN);
+    let content_height = scale(2212);
+
+    // On small screens (or with large display scaling) fit the work area and scroll the rest
+    let mut work_area = RECT::default();
+    let _ = SystemParametersInfoW(
+        SPI_GETWORKAREA,
+        0,
+        Some(&mut work_area as *mut RECT as *mut _),
+        SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+    );
+    let work_height = work_area.bottom - work_area.top;
+    let scrolling = work_height > 0 && content_height > work_height;
+    let (dialog_width, dialog_height, style) = if scrolling {
+        (scale(400) + GetSystemMetrics(SM_CXVSCROLL), work_height, WS_POPUP | WS_CAPTION | WS_SYSMENU | WS_VSCROLL)
+    } else {
+        (scale(400), content_height, WS_POPUP | WS_CAPTION | WS_SYSMENU)
+    };
+    let dialog_y = if scrolling { work_area.top } else { (GetSystemMetrics(SM_CYSCREEN) - dialog_height) / 2 };
+
+    // Flag a passcode that was never changed in the title until it is
+    let mut title = i18n::t("window.settings").to_string();
+    if is_default_passcode() {
+        title.push_str(i18n::t("settings.default_passcode_title"));
+    }
+    let title = i18n::to_wide(&title);
+
+    let dialog_hwnd = CreateWindowExW(
+        WS_EX_TOPMOST | WS_EX_DLGMODALFRAME,
+        dialog_class,
+        PCWSTR(title.as_ptr()),
+        style,
+        (screen_width - dialog_width) / 2,
+        dialog_y,
+        dialog_width,
+        dialog_height,
+        parent_hwnd,
+        HMENU::default(),
+        hinstance,
+        None,
+    );
+
+    if let Ok(dlg) = dialog_hwnd {
+        let rgn = CreateRoundRectRgn(0, 0, dialog_width, dialog_height, scale(10), scale(10));
+        SetWindowRgn(dlg, rgn, true);
+
+        if scrolling {
+            let mut client = RECT::default();
+            let _ = GetClientRect(dlg, &mut client);
+            let si = SCROLLINFO {
+                cbSize: std::mem::size_of::<SCROLLINFO>() as u32,
+                fMask: SIF_RANGE | SIF_PAGE,
+                nMin: 0,
+                nMax: content_height - 1,
+                nPage: client.bottom as u32,
+                ..zeroed()
+            };
+            SetScrollInfo(dlg, SB_VERT, &si, true);
+        }
+
+        let _ = ShowWindow(dlg, SW_SHOW);
+        let _ = SetForegroundWindow(dlg);
+
+        run_modal(dlg, parent_hwnd, false);
+    }
+
+    SETTINGS_DIALOG_OPEN.store(false, Ordering::SeqCst);
+}
+
+/// Scroll the settings dialog content by `delta` pixels (positive = down), within the scroll range
+unsafe fn scroll_settings_by(hwnd: HWND, delta: i32) {
+    let mut si = SCROLLINFO { cbSize: std::mem::size_of::<SCROLLINFO>() as u32, fMask: SIF_ALL, ..zeroed() };
+    if GetScrollInfo(hwnd, SB_VERT, &mut si).is_err() {
+        return;
+    }
+    let max_pos = (si.nMax - si.nPage as i32 + 1).max(0);
+    let new_pos = (si.nPos + delta).clamp(0, max_pos);
+    if new_pos == si.nPos {
+        return;
+    }
+
+    let scrolled = si.nPos - new_pos;
+    si.fMask = SIF_POS;
+    si.nPos = new_pos;
+    SetScrollInfo(hwnd, SB_VERT, &si, true);
+    ScrollWindowEx(hwnd, 0, scrolled, None, None, HRGN::default(), None, SW_SCROLLCHILDREN | SW_INVALIDATE | SW_ERASE);
+}
+
+/// Quick-fill buttons in the settings dialog: copies the limit typed for day `source`
+/// into the fields of `targets`. Only the fields change; nothing is stored until Save.
+unsafe fn fill_daily_limits(hwnd: HWND, source: usize, targets: std::ops::Range<usize>) {
+    let Some(handles) = settings_edit_handles() else { return };
+    let source_hwnd = handles.daily_limits[source];
+    if source_hwnd.0.is_null() {
+        return;
+    }
+    let mut buffer = [0u16; 16];
+    let len = GetWindowTextW(source_hwnd, &mut buffer);
+    let value = String::from_utf16_lossy(&buffer[..len as usize]);
+    let minutes = match duration::parse_duration(&value) {
+        Some(m) if m <= 1440 => m,
+        _ => {
+            let msg = i18n::wide("settings.error.daily_limit");
+            let title = i18n::wide("settings.error");
+            MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+            let _ = SetFocus(source_hwnd);
+            return;
+        }
+    };
+    let wide = i18n::to_wide(&duration::format_duration(minutes));
+    for &edit_hwnd in &handles.daily_limits[targets] {
+        if !edit_hwnd.0.is_null() {
+            SetWindowTextW(edit_hwnd, PCWSTR(wide.as_ptr())).ok();
+        }
+    }
+}
+
+/// Backup.../Restore... in the settings dialog. Restoring replaces everything, so it asks
+/// again for the passcode and closes the dialog afterwards without saving its fields.
+unsafe fn run_backup_command(hwnd: HWND, restore: bool) {
+    let password = match settings_edit_handles() {
+        Some(handles) if !handles.backup_password.0.is_null() => {
+            let mut buffer = [0u16; 256];
+            let len = GetWindowTextW(handles.backup_password, &mut buffer);
+            String::from_utf16_lossy(&buffer[..len as usize])
+        }
+        _ => String::new(),
+    };
+    let title = i18n::wide("settings.backup");
+
+    if restore {
+        let confirm = i18n::wide("backup.confirm_restore");
+        if MessageBoxW(hwnd, PCWSTR(confirm.as_ptr()), PCWSTR(title.as_ptr()), MB_YESNO | MB_ICONQUESTION) != IDYES
+            || !verify_passcode_for_quit(hwnd)
+        {
+            return;
+        }
+    }
+    let path = match pick_backup_file(hwnd, !restore) {
+        Some(path) => path,
+        None => return,
+    };
+
+    let result = if restore {
+        crate::backup::restore(&path, &password).map(|count| i18n::t("backup.restored").replace("{}", &count.to_string()))
+    } else {
+        crate::backup::create(&path, &password).map(|_| {
+            i18n::t(if password.is_empty() { "backup.created_plain" } else { "backup.created" }).to_string()
+        })
+    };
+    match result {
+        Ok(text) => {
+            let msg = i18n::to_wide(&text);
+            MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONINFORMATION);
+            if restore {
+                // The restored day's counters replace the running ones, which would
+                // otherwise be written over them on the next save
+                crate::mini_overlay::reload_counters();
+                crate::apps::reload_productive_apps();
+                DestroyWindow(hwnd).ok();
+            }
+        }
+        Err(e) => {
+            let msg = i18n::to_wide(&i18n::t("backup.failed").replace("{}", &e.to_string()));
+            MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+        }
+    }
+}
+
+/// Ask where to save a backup (`save`) or which one to open; None if cancelled
+unsafe fn pick_backup_file(hwnd: HWND, save: bool) -> Option<std::path::PathBuf> {
+    let mut file = [0u16; 260];
+    if save {
+        let name = format!("screen-time-backup-{}.stmbackup", crate::database::get_today_date());
+        for (slot, c) in file.iter_mut().zip(name.encode_utf16()) {
+            *slot = c;
+        }
+    }
+    // Pairs of description and pattern, each NUL-terminated, ending with an empty pair
+    let filter: Vec<u16> = format!("{}\0*.stmbackup\0{}\0*.*\0\0", i18n::t("backup.file_type"), i18n::t("backup.all_files"))
+        .encode_utf16()
+        .collect();
+    let mut ofn = OPENFILENAMEW {
+        lStructSize: std::mem::size_of::<OPENFILENAMEW>() as u32,
+        hwndOwner: hwnd,
+        lpstrFilter: PCWSTR(filter.as_ptr()),
+        lpstrFile: PWSTR(file.as_mut_ptr()),
+        nMaxFile: file.len() as u32,
+        lpstrDefExt: w!("stmbackup"),
+        Flags: if save { OFN_OVERWRITEPROMPT | OFN_PATHMUSTEXIST } else { OFN_FILEMUSTEXIST | OFN_PATHMUSTEXIST },
+        ..zeroed()
+    };
+    let chosen = if save { GetSaveFileNameW(&mut ofn) } else { GetOpenFileNameW(&mut ofn) };
+    if !chosen.as_bool() {
+        return None;
+    }
+    let len = file.iter().position(|&c| c == 0).unwrap_or(file.len());
+    Some(std::path::PathBuf::from(String::from_utf16_lossy(&file[..len])))
+}
+
+/// Uninstall after confirming, asking for the passcode and offering a backup first.
+/// True when everything was removed; the caller exits right away.
+pub unsafe fn show_uninstall(hwnd: HWND) -> bool {
+    let title = i18n::wide("uninstall.title");
+    let confirm = i18n::wide("uninstall.confirm");
+    if MessageBoxW(hwnd, PCWSTR(confirm.as_ptr()), PCWSTR(title.as_ptr()), MB_YESNO | MB_ICONWARNING | MB_DEFBUTTON2) != IDYES
+        || !verify_passcode_for_quit(hwnd)
+    {
+        return false;
+    }
+
+    // The data directory goes away, so this is the last chance to keep the history
+    let offer = i18n::wide("uninstall.backup_prompt");
+    match MessageBoxW(hwnd, PCWSTR(offer.as_ptr()), PCWSTR(title.as_ptr()), MB_YESNOCANCEL | MB_ICONQUESTION) {
+        IDYES => {
+            let Some(path) = pick_backup_file(hwnd, true) else { return false };
+            if let Err(e) = crate::backup::create(&path, "") {
+                let msg = i18n::to_wide(&i18n::t("backup.failed").replace("{}", &e.to_string()));
+                MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+                return false;
+            }
+        }
+        IDNO => {}
+        _ => return false,
+    }
+
+    match crate::cleanup::uninstall() {
+        Ok(()) => {
+            let msg = i18n::wide("uninstall.done");
+            MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONINFORMATION);
+            true
+        }
+        Err(e) => {
+            let msg = i18n::to_wide(&i18n::t("uninstall.failed").replace("{}", &e.to_string()));
+            MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+            // Deleting the folder comes after the database was closed, so the app can't keep
+            // running then; a registry error happens before anything else is touched
+            matches!(e, crate::cleanup::CleanupError::Io(..))
+        }
+    }
+}
+
+/// Remaining seconds before the last timer reset, and when the reset happened
+static LAST_RESET: Mutex<Option<(i32, i64)>> = Mutex::new(None);
+
+/// How long a timer reset can be undone (seconds)
+const RESET_UNDO_WINDOW: i64 = 5 * 60;
+
+/// Remaining seconds to restore if the last reset is still within the undo window
+fn undoable_reset() -> Option<i32> {
+    let last = *LAST_RESET.lock().unwrap();
+    last.filter(|(_, at)| crate::database::get_current_timestamp() - at <= RESET_UNDO_WINDOW)
+        .map(|(previous, _)| previous)
+}
+
+/// Periods offered by the stats dialog's range selector, in list order
+const STATS_RANGES: [(SummaryRange, &str); 3] = [
+    (SummaryRange::Today, "stats.range_today"),
+    (SummaryRange::Week, "stats.range_week"),
+    (SummaryRange::Month, "stats.range_month"),
+];
+
+/// Draw the totals of this week or month in the stats dialog, below the range selector
+unsafe fn paint_usage_summary(hdc: HDC, rect: &RECT, range: SummaryRange) {
+    let summary = get_usage_summary(range);
+
+    let title_font = CreateFontW(
+        scale(20), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+    );
+    let label_font = CreateFontW(
+        scale(13), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+    );
+    let value_font = CreateFontW(
+        scale(14), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+    );
+
+    let old_font = SelectObject(hdc, title_font);
+    SetTextColor(hdc, COLORREF(0x00333333));
+    SetBkMode(hdc, TRANSPARENT);
+
+    let title_key = if range == SummaryRange::Month { "stats.title_month" } else { "stats.title_week" };
+    let mut title_rect = RECT { left: 0, top: scale(15), right: rect.right, bottom: scale(42) };
+    DrawTextW(hdc, &mut i18n::t(title_key).encode_utf16().collect::<Vec<_>>(), &mut title_rect, DT_CENTER | DT_SINGLELINE);
+
+    let hours_minutes = |seconds: i64| format!("{}h {}m", seconds / 3600, (seconds % 3600) / 60);
+    let rows = [
+        ("stats.period", format!("{} - {}", locale::short_date(&summary.start_date), locale::today_short_date())),
+        ("stats.days_used", summary.days.to_string()),
+        ("stats.total_time", hours_minutes(summary.active_seconds)),
+        ("stats.average", format!("{} min", summary.average_minutes())),
+        (
+            "stats.days_limit_reached",
+            i18n::t("stats.days_limit_reached_value")
+                .replacen("{}", &summary.days_limit_reached.to_string(), 1)
+                .replacen("{}", &summary.days.to_string(), 1),
+        ),
+        (
+            "stats.extensions",
+            if summary.extended_minutes > 0 {
+                format!("+{} min", summary.extended_minutes)
+            } else {
+                i18n::t("stats.extensions_none").to_string()
+            },
+        ),
+        ("stats.pause_used", format!("{} min", summary.pause_seconds / 60)),
+    ];
+
+    let mut y = scale(80);
+    let left_margin = scale(25);
+    let value_x = scale(160);
+    for (label_key, value) in rows {
+        SelectObject(hdc, label_font);
+        SetTextColor(hdc, COLORREF(0x00666666));
+        let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+        DrawTextW(hdc, &mut i18n::t(label_key).encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+        SelectObject(hdc, value_font);
+        SetTextColor(hdc, COLORREF(0x00333333));
+        let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+        DrawTextW(hdc, &mut value.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE | DT_END_ELLIPSIS);
+        y += scale(24);
+    }
+
+    SelectObject(hdc, old_font);
+    let _ = DeleteObject(title_font);
+    let _ = DeleteObject(label_font);
+    let _ = DeleteObject(value_font);
+}
+
+/// Show today's stats dialog
+pub unsafe fn show_stats_dialog(parent_hwnd: HWND) {
+    let dialog_class = w!("ScreenTimeStatsDialog");
+    let hinstance = GetModuleHandleW(None).expect("Failed to get module handle");
+
+    static STATS_DIALOG_OPEN: AtomicBool = AtomicBool::new(false);
+    static STATS_RANGE: Mutex<SummaryRange> = Mutex::new(SummaryRange::Today);
+
+    if STATS_DIALOG_OPEN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    *STATS_RANGE.lock().unwrap() = SummaryRange::Today;
+
+    unsafe extern "system" fn stats_dialog_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        use crate::blocking::REMAINING_SECONDS;
+        use crate::database::{get_current_timestamp, get_current_weekday, get_daily_limit, log_timer_reset, save_remaining_time};
+        use crate::mini_overlay::update_mini_overlay;
+        use std::sync::atomic::Ordering;
+
+        const ID_RESET_TIMER: i32 = 3001;
+        const ID_CLOSE: i32 = 3002;
+        const ID_UNDO_RESET: i32 = 3003;
+        const ID_RANGE_COMBO: i32 = 3004;
+
+        match msg {
+            WM_CREATE => {
+                let hinstance = GetModuleHandleW(None).unwrap();
+
+                let btn_font = CreateFontW(
+                    scale(14), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+                );
+
+                // Buttons sit at the bottom of the client area
+                let mut client: RECT = zeroed();
+                GetClientRect(hwnd, &mut client).ok();
+
+                // Today / This Week / This Month, under the title
+                let range_combo = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("COMBOBOX"), w!(""),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(CBS_DROPDOWNLIST as u32),
+                    (client.right - scale(160)) / 2, scale(46), scale(160), scale(200), hwnd, HMENU(ID_RANGE_COMBO as _), hinstance, None,
+                );
+                if let Ok(h) = range_combo {
+                    SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1));
+                    for (_, key) in STATS_RANGES {
+                        let name = i18n::wide(key);
+                        SendMessageW(h, CB_ADDSTRING, WPARAM(0), LPARAM(name.as_ptr() as isize));
+                    }
+                    SendMessageW(h, CB_SETCURSEL, WPARAM(0), LPARAM(0));
+                }
+                let btn_y = client.bottom - scale(50);
+
+                // Reset Timer button
+                let reset_btn_text = i18n::wide("button.reset_timer");
+                let reset_btn = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(reset_btn_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    scale(15), btn_y, scale(120), scale(35), hwnd, HMENU(ID_RESET_TIMER as _), hinstance, None,
+                );
+                if let Ok(h) = reset_btn { SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1)); }
+
+                // Undo Reset button (only enabled shortly after a reset)
+                let undo_btn_text = i18n::wide("button.undo_reset");
+                let undo_btn = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(undo_btn_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    scale(145), btn_y, scale(90), scale(35), hwnd, HMENU(ID_UNDO_RESET as _), hinstance, None,
+                );
+                if let Ok(h) = undo_btn {
+                    SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1));
+                    let _ = EnableWindow(h, undoable_reset().is_some());
+                }
+
+                // Close button
+                let close_btn_text = i18n::wide("button.close");
+                let close_btn = CreateWindowExW(
+                    WINDOW_EX_STYLE(0), w!("BUTTON"), PCWSTR(close_btn_text.as_ptr()),
+                    WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+                    scale(245), btn_y, scale(80), scale(35), hwnd, HMENU(ID_CLOSE as _), hinstance, None,
+                );
+                if let Ok(h) = close_btn { SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1)); }
+
+                LRESULT(0)
+            }
+            WM_PAINT => {
+                let mut ps: PAINTSTRUCT = zeroed();
+                let hdc = BeginPaint(hwnd, &mut ps);
+
+                let mut rect: RECT = zeroed();
+                GetClientRect(hwnd, &mut rect).ok();
+
+                let bg_brush = CreateSolidBrush(COLORREF(0x00F5F5F5));
+                FillRect(hdc, &rect, bg_brush);
+                let _ = DeleteObject(bg_brush);
+
+                let range = *STATS_RANGE.lock().unwrap();
+                if range != SummaryRange::Today {
+                    paint_usage_summary(hdc, &rect, range);
+                    let _ = EndPaint(hwnd, &ps);
+                    return LRESULT(0);
+                }
+
+                // Get stats
+                let weekday = get_current_weekday();
+                let daily_limit_minutes = get_daily_limit(weekday);
+                let daily_limit_seconds = (daily_limit_minutes * 60) as i32;
+                let remaining_seconds = REMAINING_SECONDS.load(Ordering::SeqCst);
+                // Extensions add to the budget, so used time is measured against the effective limit
+                let extended_minutes = get_extended_today();
+                let effective_limit_seconds = get_effective_daily_limit() * 60;
+                let used_seconds = (effective_limit_seconds - remaining_seconds.max(0)).max(0);
+                let over_seconds = used_seconds - daily_limit_seconds;
+
+                // Get pause stats
+                let pause_enabled = is_pause_enabled();
+                let pause_config = get_pause_config();
+                let pause_used_seconds = get_pause_used_today();
+                let pause_budget_seconds = (pause_config.daily_budget_minutes * 60) as i32;
+                let pause_remaining_seconds = (pause_budget_seconds - pause_used_seconds).max(0);
+                let pause_log = get_pause_log_today();
+
+                // Format time helper
+                fn format_duration(seconds: i32) -> String {
+                    if seconds < 0 {
+                        return String::from("--");
+                    }
+                    let hours = seconds / 3600;
+                    let minutes = (seconds % 3600) / 60;
+                    let secs = seconds % 60;
+                    if hours > 0 {
+                        format!("{}h {}m {}s", hours, minutes, secs)
+                    } else if minutes > 0 {
+                        format!("{}m {}s", minutes, secs)
+                    } else {
+                        format!("{}s", secs)
+                    }
+                }
+
+                // Get weekday name in the UI language
+                let weekday_name = locale::weekday_name(weekday as usize);
+
+                // Title font (DPI scaled, ClearType quality = 5)
+                let title_font = CreateFontW(
+                    scale(20), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+                );
+                let section_font = CreateFontW(
+                    scale(14), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+                );
+                let label_font = CreateFontW(
+                    scale(13), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+                );
+                let value_font = CreateFontW(
+                    scale(14), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+                );
+                let small_font = CreateFontW(
+                    scale(12), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"),
+                );
+
+                let old_font = SelectObject(hdc, title_font);
+                SetTextColor(hdc, COLORREF(0x00333333));
+                SetBkMode(hdc, TRANSPARENT);
+
+                // Title
+                let mut title_rect = RECT { left: 0, top: scale(15), right: rect.right, bottom: scale(42) };
+                let stats_title: Vec<u16> = i18n::t("stats.title").encode_utf16().collect();
+                DrawTextW(
+                    hdc,
+                    &mut stats_title.clone(),
+                    &mut title_rect,
+                    DT_CENTER | DT_SINGLELINE,
+                );
+
+                // Stats (below the range selector)
+                let mut y = scale(80);
+                let left_margin = scale(25);
+                let value_x = scale(160);
+
+                // Day
+                SelectObject(hdc, label_font);
+                SetTextColor(hdc, COLORREF(0x00666666));
+                let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                let day_label: Vec<u16> = i18n::t("stats.day").encode_utf16().collect();
+                DrawTextW(hdc, &mut day_label.clone(), &mut label_rect, DT_SINGLELINE);
+
+                SelectObject(hdc, value_font);
+                SetTextColor(hdc, COLORREF(0x00333333));
+                let day_str = format!("{}, {}", weekday_name, locale::today_short_date());
+                let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                DrawTextW(hdc, &mut day_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE);
+                y += scale(24);
+
+                // Daily limit
+                SelectObject(hdc, label_font);
+                SetTextColor(hdc, COLORREF(0x00666666));
+                let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                DrawTextW(hdc, &mut i18n::t("stats.daily_limit").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+                SelectObject(hdc, value_font);
+                SetTextColor(hdc, COLORREF(0x00333333));
+                let limit_str = format!("{} min", daily_limit_minutes);
+                let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                DrawTextW(hdc, &mut limit_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE);
+                y += scale(24);
+
+                // Time used
+                SelectObject(hdc, label_font);
+                SetTextColor(hdc, COLORREF(0x00666666));
+                let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                DrawTextW(hdc, &mut i18n::t("stats.time_used").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+                SelectObject(hdc, value_font);
+                SetTextColor(hdc, COLORREF(0x00333333));
+                let used_str = format_duration(used_seconds.max(0));
+                let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                DrawTextW(hdc, &mut used_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE);
+                y += scale(24);
+
+                // Percentage of the daily limit, with overage from extensions in red
+                SelectObject(hdc, label_font);
+                SetTextColor(hdc, COLORREF(0x00666666));
+                let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                DrawTextW(hdc, &mut i18n::t("stats.percent_used").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+                SelectObject(hdc, value_font);
+                let percent = if daily_limit_seconds > 0 {
+                    (used_seconds as i64 * 100 / daily_limit_seconds as i64) as i32
+                } else {
+                    100
+                };
+                let percent_str = if daily_limit_seconds == 0 {
+                    // No percentage of a zero limit; any time used was granted by a parent
+                    SetTextColor(hdc, COLORREF(if over_seconds > 0 { COLOR_ERROR } else { 0x00333333 }));
+                    i18n::t("stats.no_screen_time").to_string()
+                } else if over_seconds > 0 {
+                    SetTextColor(hdc, COLORREF(COLOR_ERROR));
+                    format!("{}% ({})", percent, i18n::t("stats.over_by").replace("{}", &format_duration(over_seconds)))
+                } else {
+                    SetTextColor(hdc, COLORREF(0x00333333));
+                    format!("{}%", percent)
+                };
+                let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                DrawTextW(hdc, &mut percent_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE);
+                y += scale(24);
+
+                // Extensions granted today (explains used > limit)
+                SelectObject(hdc, label_font);
+                SetTextColor(hdc, COLORREF(0x00666666));
+                let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                DrawTextW(hdc, &mut i18n::t("stats.extensions").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+                SelectObject(hdc, value_font);
+                SetTextColor(hdc, COLORREF(0x00333333));
+                // Per source, so it's clear who granted the extra time
+                let extensions_str = if extended_minutes > 0 {
+                    let sources: Vec<String> = crate::blocking::extension_minutes_by_source(&get_extension_log_today())
+                        .iter()
+                        .map(|(source, minutes)| format!("{} +{}", crate::blocking::extend_source_label(source), minutes))
+                        .collect();
+                    format!("+{} min ({})", extended_minutes, sources.join(", "))
+                } else {
+                    i18n::t("stats.extensions_none").to_string()
+                };
+                // Number given against the allowed number per day, if there is one
+                let max_per_day = get_extension_config().max_per_day;
+                let extensions_str = if max_per_day > 0 {
+                    format!("{} · {}/{}", extensions_str, get_extension_count_today(), max_per_day)
+                } else {
+                    extensions_str
+                };
+                let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                DrawTextW(hdc, &mut extensions_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE | DT_END_ELLIPSIS);
+                y += scale(24);
+
+                // Idle time today, and how much of it still counted (e.g. with idle detection off)
+                SelectObject(hdc, label_font);
+                SetTextColor(hdc, COLORREF(0x00666666));
+                let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                DrawTextW(hdc, &mut i18n::t("stats.idle").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+                SelectObject(hdc, value_font);
+                SetTextColor(hdc, COLORREF(0x00333333));
+                let (idle_seconds, idle_counted_seconds) = get_idle_totals_today();
+                let idle_str = if idle_seconds > 0 {
+                    i18n::t("stats.idle_value")
+                        .replacen("{}", &(idle_seconds / 60).to_string(), 1)
+                        .replacen("{}", &(idle_counted_seconds / 60).to_string(), 1)
+                } else {
+                    i18n::t("stats.extensions_none").to_string()
+                };
+                let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                DrawTextW(hdc, &mut idle_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE);
+                y += scale(24);
+
+                // Time remaining
+                SelectObject(hdc, label_font);
+                SetTextColor(hdc, COLORREF(0x00666666));
+                let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                DrawTextW(hdc, &mut i18n::t("stats.time_remaining").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+                SelectObject(hdc, value_font);
+                // Color based on remaining time
+                if remaining_seconds <= 60 {
+                    SetTextColor(hdc, COLORREF(COLOR_ERROR));
+                } else if remaining_seconds <= 300 {
+                    SetTextColor(hdc, COLORREF(COLOR_ACCENT));
+                } else {
+                    SetTextColor(hdc, COLORREF(0x00008800)); // Green
+                }
+                let remaining_str = format_duration(remaining_seconds);
+                let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                DrawTextW(hdc, &mut remaining_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE);
+                y += scale(24);
+
+                // Next weekly limit reduction
+                SelectObject(hdc, label_font);
+                SetTextColor(hdc, COLORREF(0x00666666));
+                let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                DrawTextW(hdc, &mut i18n::t("stats.next_reduction").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+                SelectObject(hdc, value_font);
+                SetTextColor(hdc, COLORREF(0x00333333));
+                let rampdown = get_rampdown_config();
+                let rampdown_str = if rampdown.step_minutes == 0 {
+                    i18n::t("stats.rampdown_off").to_string()
+                } else if (0..7).all(|day| get_daily_limit(day) <= rampdown.floor_minutes) {
+                    i18n::t("stats.rampdown_floor_reached").to_string()
+                } else {
+                    format!("-{} min, {}", rampdown.step_minutes, locale::short_date(&get_next_rampdown_date()))
+                };
+                let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                DrawTextW(hdc, &mut rampdown_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE);
+                y += scale(32);
+
+                // ===== Pause Section =====
+                SelectObject(hdc, section_font);
+                SetTextColor(hdc, COLORREF(0x00333333));
+                let mut section_rect = RECT { left: left_margin, top: y, right: rect.right - scale(15), bottom: y + scale(20) };
+                DrawTextW(hdc, &mut i18n::t("stats.pause_mode").encode_utf16().collect::<Vec<_>>(), &mut section_rect, DT_SINGLELINE);
+                y += scale(22);
+
+                if pause_enabled {
+                    // Pause budget used
+                    SelectObject(hdc, label_font);
+                    SetTextColor(hdc, COLORREF(0x00666666));
+                    let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                    DrawTextW(hdc, &mut i18n::t("stats.pause_used").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+                    SelectObject(hdc, value_font);
+                    SetTextColor(hdc, COLORREF(0x00333333));
+                    let pause_used_str = format!("{} / {} min", pause_used_seconds / 60, pause_config.daily_budget_minutes);
+                    let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                    DrawTextW(hdc, &mut pause_used_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE);
+                    y += scale(24);
+
+                    // Pause remaining
+                    SelectObject(hdc, label_font);
+                    SetTextColor(hdc, COLORREF(0x00666666));
+                    let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                    DrawTextW(hdc, &mut i18n::t("stats.pause_remaining").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+                    SelectObject(hdc, value_font);
+                    if pause_remaining_seconds <= 0 {
+                        SetTextColor(hdc, COLORREF(COLOR_ERROR));
+                    } else if pause_remaining_seconds <= 300 {
+                        SetTextColor(hdc, COLORREF(COLOR_ACCENT));
+                    } else {
+                        SetTextColor(hdc, COLORREF(0x00008800)); // Green
+                    }
+                    let pause_remaining_str = format_duration(pause_remaining_seconds);
+                    let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                    DrawTextW(hdc, &mut pause_remaining_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE);
+                    y += scale(24);
+
+                    // Pause count
+                    SelectObject(hdc, label_font);
+                    SetTextColor(hdc, COLORREF(0x00666666));
+                    let mut label_rect = RECT { left: left_margin, top: y, right: value_x, bottom: y + scale(22) };
+                    DrawTextW(hdc, &mut i18n::t("stats.pauses_today").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_SINGLELINE);
+
+                    SelectObject(hdc, value_font);
+                    SetTextColor(hdc, COLORREF(0x00333333));
+                    let pause_count_str = format!("{}", pause_log.len());
+                    let mut value_rect = RECT { left: value_x, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                    DrawTextW(hdc, &mut pause_count_str.encode_utf16().collect::<Vec<_>>(), &mut value_rect, DT_SINGLELINE);
+                    y += scale(24);
+
+                    // Pause log (if any)
+                    if !pause_log.is_empty() {
+                        SelectObject(hdc, small_font);
+                        SetTextColor(hdc, COLORREF(0x00888888));
+                        let log_str = format!("{} {}", i18n::t("stats.log"), pause_log.join(", "));
+                        let mut log_rect = RECT { left: left_margin, top: y, right: rect.right - scale(15), bottom: y + scale(18) };
+                        DrawTextW(hdc, &mut log_str.encode_utf16().collect::<Vec<_>>(), &mut log_rect, DT_SINGLELINE);
+                    }
+                } else {
+                    SelectObject(hdc, label_font);
+                    SetTextColor(hdc, COLORREF(0x00888888));
+                    let mut disabled_rect = RECT { left: left_margin, top: y, right: rect.right - scale(15), bottom: y + scale(22) };
+                    DrawTextW(hdc, &mut i18n::t("stats.pause_disabled").encode_utf16().collect::<Vec<_>>(), &mut disabled_rect, DT_SINGLELINE);
+                }
+
+                SelectObject(hdc, old_font);
+                let _ = DeleteObject(title_font);
+                let _ = DeleteObject(section_font);
+                let _ = DeleteObject(label_font);
+                let _ = DeleteObject(value_font);
+                let _ = DeleteObject(small_font);
+
+                let _ = EndPaint(hwnd, &ps);
+                LRESULT(0)
+            }
+            WM_COMMAND => {
+                let id = (wparam.0 & 0xFFFF) as i32;
+                let notification = ((wparam.0 >> 16) & 0xFFFF) as u32;
+
+                if id == ID_RANGE_COMBO && notification == CBN_SELCHANGE {
+                    let index = SendMessageW(HWND(lparam.0 as _), CB_GETCURSEL, WPARAM(0), LPARAM(0)).0;
+                    if let Some(&(range, _)) = STATS_RANGES.get(index as usize) {
+                        *STATS_RANGE.lock().unwrap() = range;
+                    }
+                    let _ = InvalidateRect(hwnd, None, true);
+                } else if id == ID_RESET_TIMER {
+                    // Handing out a full fresh budget needs an explicit confirm and the passcode again
+                    let msg = i18n::wide("stats.reset_confirm");
+                    let title = i18n::wide("stats.timer_reset_title");
+                    if MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_YESNO | MB_ICONQUESTION) != IDYES
+                        || !verify_passcode_for_quit(hwnd)
+                    {
+                        return LRESULT(0);
+                    }
+
+                    // Reset to daily limit
+                    let weekday = get_current_weekday();
+                    let daily_limit_minutes = get_daily_limit(weekday);
+                    let daily_limit_seconds = (daily_limit_minutes * 60) as i32;
+
+                    // A reset is real: a running simulation would put the old time back when it ends
+                    crate::mini_overlay::stop_simulation();
+                    let previous = REMAINING_SECONDS.swap(daily_limit_seconds, Ordering::SeqCst);
+                    save_remaining_time(daily_limit_seconds);
+                    log_timer_reset(previous, daily_limit_seconds, "reset");
+                    *LAST_RESET.lock().unwrap() = Some((previous, get_current_timestamp()));
+                    update_mini_overlay();
+                    // A day without screen time stays blocked
+                    if daily_limit_seconds == 0 {
+                        crate::mini_overlay::enforce_time_up();
+                    }
+
+                    let _ = EnableWindow(GetDlgItem(hwnd, ID_UNDO_RESET).unwrap_or_default(), true);
+
+                    let msg = i18n::wide("stats.timer_reset");
+                    MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONINFORMATION);
+                    let _ = InvalidateRect(hwnd, None, true);
+                } else if id == ID_UNDO_RESET {
+                    if let Some(previous) = undoable_reset() {
+                        let current = REMAINING_SECONDS.swap(previous, Ordering::SeqCst);
+                        save_remaining_time(previous);
+                        log_timer_reset(current, previous, "undo");
+                        *LAST_RESET.lock().unwrap() = None;
+                        update_mini_overlay();
+
+                        let msg = i18n::wide("stats.reset_undone");
+                        let title = i18n::wide("stats.timer_reset_title");
+                        MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONINFORMATION);
+                    }
+                    let _ = EnableWindow(GetDlgItem(hwnd, ID_UNDO_RESET).unwrap_or_default(), false);
+                    let _ = InvalidateRect(hwnd, None, true);
+                } else if id == ID_CLOSE {
+                    DestroyWindow(hwnd).ok();
+                }
+
+                LRESULT(0)
+            }
+            WM_CLOSE => {
+                DestroyWindow(hwnd).ok();
+                LRESULT(0)
+            }
+            WM_DESTROY => {
+                STATS_DIALOG_OPEN.store(false, Ordering::SeqCst);
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+        }
+    }
+
+    let wnd_class = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(stats_dialog_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: dialog_class,
+        hbrBackground: CreateSolidBrush(COLORREF(0x00F5F5F5)),
+        hCursor: LoadCursorW(None, IDC_ARROW).ok().unwrap_or_default(),
+        ..zeroed()
+    };
+    RegisterClassW(&wnd_class);
+
+    let screen_width = GetSystemMetrics(SM_CXSCREEN);
+    let screen_height = GetSystemMetrics(SM_CYSCREEN);
+    let dialog_width = scale(340);
+    let dialog_height = scale(516);
+
+    let window_title = i18n::wide("window.stats");
+    let dialog_hwnd = CreateWindowExW(
+        WS_EX_TOPMOST | WS_EX_DLGMODALFRAME,
+        dialog_class,
+        PCWSTR(window_title.as_ptr()),
+        WS_POPUP | WS_CAPTION | WS_SYSMENU,
+        (screen_width - dialog_width) / 2,
+        (screen_height - dialog_height) / 2,
+        dialog_width,
+        dialog_height,
+        parent_hwnd,
+        HMENU::default(),
+        hinstance,
+        None,
+    );
+
+    if let Ok(dlg) = dialog_hwnd {
+        let rgn = CreateRoundRectRgn(0, 0, dialog_width, dialog_height, scale(10), scale(10));
+        SetWindowRgn(dlg, rgn, true);
+
+        let _ = ShowWindow(dlg, SW_SHOW);
+        let _ = SetForegroundWindow(dlg);
+
+        run_modal(dlg, parent_hwnd, false);
+    }
+
+    STATS_DIALOG_OPEN.store(false, Ordering::SeqCst);
+}
+
+// ============================================================================
+// Telegram Setup Wizard
+// ============================================================================
+
+// Wizard control IDs
+const ID_WIZARD_NEXT: i32 = 3001;
+const ID_WIZARD_BACK: i32 = 3002;
+const ID_WIZARD_CANCEL: i32 = 3003;
+const ID_WIZARD_TOKEN_EDIT: i32 = 3004;
+
+// Wizard state
+static WIZARD_STEP: AtomicI32 = AtomicI32::new(1);
+static WIZARD_TOKEN: Mutex<Option<String>> = Mutex::new(None);
+static WIZARD_CHAT_ID: Mutex<Option<i64>> = Mutex::new(None);
+static WIZARD_POLLING: AtomicBool = AtomicBool::new(false);
+static WIZARD_HWND: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+const WIZARD_TOTAL_STEPS: i32 = 5;
+const TIMER_POLL_TELEGRAM: usize = 100;
+
+/// Show the Telegram setup wizard
+pub unsafe fn show_telegram_wizard(parent_hwnd: HWND) {
+    // Reset wizard state
+    WIZARD_STEP.store(1, Ordering::SeqCst);
+    *WIZARD_TOKEN.lock().unwrap() = None;
+    *WIZARD_CHAT_ID.lock().unwrap() = None;
+    WIZARD_POLLING.store(false, Ordering::SeqCst);
+
+    let hinstance = GetModuleHandleW(None).unwrap();
+    let wizard_class = w!("ScreenTimeTelegramWizard");
+
+    // Register wizard window class
+    let wc = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(wizard_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: wizard_class,
+        hbrBackground: CreateSolidBrush(COLORREF(0x00FFFFFF)),
+        hCursor: LoadCursorW(None, IDC_ARROW).ok().unwrap_or_default(),
+        ..zeroed()
+    };
+    RegisterClassW(&wc);
+
+    let screen_width = GetSystemMetrics(SM_CXSCREEN);
+    let screen_height = GetSystemMetrics(SM_CYSCREEN);
+    let dialog_width = scale(480);
+    let dialog_height = scale(520);
+
+    let window_title = i18n::wide("wizard.title");
+    let wizard_hwnd = CreateWindowExW(
+        WS_EX_TOPMOST | WS_EX_DLGMODALFRAME,
+        wizard_class,
+        PCWSTR(window_title.as_ptr()),
+        WS_POPUP | WS_CAPTION | WS_SYSMENU,
+        (screen_width - dialog_width) / 2,
+        (screen_height - dialog_height) / 2,
+        dialog_width,
+        dialog_height,
+        parent_hwnd,
+        HMENU::default(),
+        hinstance,
+        None,
+    );
+
+    if let Ok(hwnd) = wizard_hwnd {
+        WIZARD_HWND.store(hwnd.0, Ordering::SeqCst);
+        let rgn = CreateRoundRectRgn(0, 0, dialog_width, dialog_height, scale(12), scale(12));
+        SetWindowRgn(hwnd, rgn, true);
+
+        let _ = ShowWindow(hwnd, SW_SHOW);
+        let _ = SetForegroundWindow(hwnd);
+
+        run_modal(hwnd, parent_hwnd, false);
+    }
+
+    WIZARD_HWND.store(std::ptr::null_mut(), Ordering::SeqCst);
+}
+
+/// Wizard window procedure
+unsafe extern "system" fn wizard_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_CREATE => {
+            create_wizard_buttons(hwnd);
+            LRESULT(0)
+        }
+        WM_PAINT => {
+            paint_wizard(hwnd);
+            LRESULT(0)
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xFFFF) as i32;
+            handle_wizard_command(hwnd, id);
+            LRESULT(0)
+        }
+        WM_TIMER => {
+            if wparam.0 == TIMER_POLL_TELEGRAM {
+                poll_telegram_for_chatid(hwnd);
+            }
+            LRESULT(0)
+        }
+        WM_CLOSE => {
+            DestroyWindow(hwnd).ok();
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            KillTimer(hwnd, TIMER_POLL_TELEGRAM).ok();
+            WIZARD_POLLING.store(false, Ordering::SeqCst);
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+/// Create wizard navigation buttons
+unsafe fn create_wizard_buttons(hwnd: HWND) {
+    let hinstance = GetModuleHandleW(None).unwrap();
+
+    let mut rect: RECT = zeroed();
+    GetClientRect(hwnd, &mut rect).ok();
+    let width = rect.right;
+    let height = rect.bottom;
+
+    let btn_width = scale(100);
+    let btn_height = scale(36);
+    let btn_y = height - scale(60);
+    let margin = scale(20);
+
+    // Button font
+    let btn_font = CreateFontW(
+        scale(15), 0, 0, 0,
+        FW_NORMAL.0 as i32,
+        0, 0, 0, 0, 0, 0, 5, 0,
+        w!("Segoe UI"),
+    );
+
+    // Cancel button (left)
+    let cancel_text = i18n::wide("wizard.cancel");
+    let cancel_btn = CreateWindowExW(
+        WINDOW_EX_STYLE(0),
+        w!("BUTTON"),
+        PCWSTR(cancel_text.as_ptr()),
+        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+        margin,
+        btn_y,
+        btn_width,
+        btn_height,
+        hwnd,
+        HMENU(ID_WIZARD_CANCEL as _),
+        hinstance,
+        None,
+    );
+    if let Ok(h) = cancel_btn {
+        SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1));
+    }
+
+    // Back button
+    let back_text = i18n::wide("wizard.back");
+    let back_btn = CreateWindowExW(
+        WINDOW_EX_STYLE(0),
+        w!("BUTTON"),
+        PCWSTR(back_text.as_ptr()),
+        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+        width - margin - btn_width * 2 - scale(10),
+        btn_y,
+        btn_width,
+        btn_height,
+        hwnd,
+        HMENU(ID_WIZARD_BACK as _),
+        hinstance,
+        None,
+    );
+    if let Ok(h) = back_btn {
+        SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1));
+    }
+
+    // Next/Finish button
+    let next_text = i18n::wide("wizard.next");
+    let next_btn = CreateWindowExW(
+        WINDOW_EX_STYLE(0),
+        w!("BUTTON"),
+        PCWSTR(next_text.as_ptr()),
+        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+        width - margin - btn_width,
+        btn_y,
+        btn_width,
+        btn_height,
+        hwnd,
+        HMENU(ID_WIZARD_NEXT as _),
+        hinstance,
+        None,
+    );
+    if let Ok(h) = next_btn {
+        SendMessageW(h, WM_SETFONT, WPARAM(btn_font.0 as usize), LPARAM(1));
+    }
+}
+
+/// Paint the wizard content based on current step
+unsafe fn paint_wizard(hwnd: HWND) {
+    let mut ps: PAINTSTRUCT = zeroed();
+    let hdc = BeginPaint(hwnd, &mut ps);
+
+    let mut rect: RECT = zeroed();
+    GetClientRect(hwnd, &mut rect).ok();
+    let width = rect.right;
+
+    // Background
+    let bg_brush = CreateSolidBrush(COLORREF(0x00FFFFFF));
+    FillRect(hdc, &rect, bg_brush);
+    let _ = DeleteObject(bg_brush);
+
+    // Header area with gradient-like color
+    let header_rect = RECT { left: 0, top: 0, right: width, bottom: scale(100) };
+    let header_brush = CreateSolidBrush(COLORREF(0x00F5E6D3)); // Warm beige
+    FillRect(hdc, &header_rect, header_brush);
+    let _ = DeleteObject(header_brush);
+
+    SetBkMode(hdc, TRANSPARENT);
+
+    // Draw step indicator circles
+    draw_step_indicators(hdc, width, WIZARD_STEP.load(Ordering::SeqCst));
+
+    // Content area
+    let content_top = scale(110);
+    let content_rect = RECT {
+        left: scale(30),
+        top: content_top,
+        right: width - scale(30),
+        bottom: rect.bottom - scale(80),
+    };
+
+    match WIZARD_STEP.load(Ordering::SeqCst) {
+        1 => paint_step_welcome(hdc, &content_rect),
+        2 => paint_step_botfather(hdc, &content_rect),
+        3 => paint_step_token(hdc, hwnd, &content_rect),
+        4 => paint_step_connect(hdc, &content_rect),
+        5 => paint_step_success(hdc, &content_rect),
+        _ => {}
+    }
+
+    // Update button states
+    update_wizard_buttons(hwnd);
+
+    let _ = EndPaint(hwnd, &ps);
+}
+
+/// Draw step indicator circles at the top
+unsafe fn draw_step_indicators(hdc: HDC, width: i32, current_step: i32) {
+    let center_y = scale(50);
+    let circle_radius = scale(16);
+    let spacing = scale(70);
+    let start_x = (width - (spacing * 4)) / 2;
+
+    // Font for step numbers
+    let num_font = CreateFontW(
+        scale(14), 0, 0, 0,
+        FW_BOLD.0 as i32,
+        0, 0, 0, 0, 0, 0, 5, 0,
+        w!("Segoe UI"),
+    );
+    let old_font = SelectObject(hdc, num_font);
+
+    for i in 1..=5 {
+        let cx = start_x + (i - 1) * spacing;
+
+        // Draw connecting line (except for first circle)
+        if i > 1 {
+            let line_color = if i <= current_step {
+                COLORREF(0x004CAF50) // Green
+            } else {
+                COLORREF(0x00CCCCCC) // Gray
+            };
+            let pen = CreatePen(PS_SOLID, scale(2), line_color);
+            let old_pen = SelectObject(hdc, pen);
+            let _ = MoveToEx(hdc, cx - spacing + circle_radius, center_y, None);
+            let _ = LineTo(hdc, cx - circle_radius, center_y);
+            SelectObject(hdc, old_pen);
+            let _ = DeleteObject(pen);
+        }
+
+        // Circle color based on state
+        let (fill_color, text_color) = if i < current_step {
+            (COLORREF(0x004CAF50), COLORREF(0x00FFFFFF)) // Completed: green
+        } else if i == current_step {
+            (COLORREF(0x002196F3), COLORREF(0x00FFFFFF)) // Current: blue
+        } else {
+            (COLORREF(0x00EEEEEE), COLORREF(0x00666666)) // Future: gray
+        };
+
+        // Draw circle
+        let brush = CreateSolidBrush(fill_color);
+        let pen = CreatePen(PS_SOLID, 1, fill_color);
+        let old_brush = SelectObject(hdc, brush);
+        let old_pen = SelectObject(hdc, pen);
+        let _ = Ellipse(hdc, cx - circle_radius, center_y - circle_radius,
+                cx + circle_radius, center_y + circle_radius);
+        SelectObject(hdc, old_brush);
+        SelectObject(hdc, old_pen);
+        let _ = DeleteObject(brush);
+        let _ = DeleteObject(pen);
+
+        // Draw number or checkmark
+        SetTextColor(hdc, text_color);
+        let text = if i < current_step {
+            "✓".to_string()
+        } else {
+            i.to_string()
+        };
+        let mut text_rect = RECT {
+            left: cx - circle_radius,
+            top: center_y - circle_radius,
+            right: cx + circle_radius,
+            bottom: center_y + circle_radius,
+        };
+        DrawTextW(hdc, &mut text.encode_utf16().collect::<Vec<_>>(), &mut text_rect,
+                  DT_CENTER | DT_VCENTER | DT_SINGLELINE);
+    }
+
+    SelectObject(hdc, old_font);
+    let _ = DeleteObject(num_font);
+}
+
+/// Step 1: Welcome screen
+unsafe fn paint_step_welcome(hdc: HDC, rect: &RECT) {
+    let title_font = CreateFontW(scale(28), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let desc_font = CreateFontW(scale(16), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let icon_font = CreateFontW(scale(48), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI Emoji"));
+
+    let mut y = rect.top;
+
+    // Phone icon
+    SelectObject(hdc, icon_font);
+    SetTextColor(hdc, COLORREF(0x002196F3));
+    let mut icon_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(60) };
+    DrawTextW(hdc, &mut "📱".encode_utf16().collect::<Vec<_>>(), &mut icon_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(70);
+
+    // Title
+    SelectObject(hdc, title_font);
+    SetTextColor(hdc, COLORREF(0x00333333));
+    let mut title_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(35) };
+    DrawTextW(hdc, &mut i18n::t("wizard.welcome.title").encode_utf16().collect::<Vec<_>>(), &mut title_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(45);
+
+    // Description
+    SelectObject(hdc, desc_font);
+    SetTextColor(hdc, COLORREF(0x00666666));
+    let mut desc_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(25) };
+    DrawTextW(hdc, &mut i18n::t("wizard.welcome.desc1").encode_utf16().collect::<Vec<_>>(), &mut desc_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(40);
+
+    // Features with icons
+    let features = [
+        ("✓", i18n::t("wizard.welcome.feature1")),
+        ("✓", i18n::t("wizard.welcome.feature2")),
+        ("✓", i18n::t("wizard.welcome.feature3")),
+        ("✓", i18n::t("wizard.welcome.feature4")),
+    ];
+
+    SetTextColor(hdc, COLORREF(0x004CAF50));
+    for (icon, text) in features.iter() {
+        let feature_text = format!("  {}  {}", icon, text);
+        let mut feature_rect = RECT { left: rect.left + scale(40), top: y, right: rect.right, bottom: y + scale(28) };
+        DrawTextW(hdc, &mut feature_text.encode_utf16().collect::<Vec<_>>(), &mut feature_rect, DT_SINGLELINE);
+        y += scale(30);
+    }
+
+    y += scale(20);
+
+    // Ready message
+    SetTextColor(hdc, COLORREF(0x00333333));
+    let mut ready_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(25) };
+    DrawTextW(hdc, &mut i18n::t("wizard.welcome.ready").encode_utf16().collect::<Vec<_>>(), &mut ready_rect, DT_CENTER | DT_SINGLELINE);
+
+    let _ = DeleteObject(title_font);
+    let _ = DeleteObject(desc_font);
+    let _ = DeleteObject(icon_font);
+}
+
+/// Step 2: BotFather instructions
+unsafe fn paint_step_botfather(hdc: HDC, rect: &RECT) {
+    let title_font = CreateFontW(scale(24), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let step_font = CreateFontW(scale(15), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let icon_font = CreateFontW(scale(40), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI Emoji"));
+    let hint_font = CreateFontW(scale(13), 0, 0, 0, FW_NORMAL.0 as i32, 1, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+
+    let mut y = rect.top;
+
+    // Robot icon
+    SelectObject(hdc, icon_font);
+    SetTextColor(hdc, COLORREF(0x002196F3));
+    let mut icon_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(50) };
+    DrawTextW(hdc, &mut "🤖".encode_utf16().collect::<Vec<_>>(), &mut icon_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(55);
+
+    // Title
+    SelectObject(hdc, title_font);
+    SetTextColor(hdc, COLORREF(0x00333333));
+    let mut title_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(30) };
+    DrawTextW(hdc, &mut i18n::t("wizard.bot.title").encode_utf16().collect::<Vec<_>>(), &mut title_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(45);
+
+    // Steps
+    SelectObject(hdc, step_font);
+    let steps = [
+        i18n::t("wizard.bot.step1"),
+        i18n::t("wizard.bot.step2"),
+        i18n::t("wizard.bot.step3"),
+        i18n::t("wizard.bot.step4"),
+        i18n::t("wizard.bot.step5"),
+        i18n::t("wizard.bot.step6"),
+    ];
+
+    for step in steps.iter() {
+        SetTextColor(hdc, COLORREF(0x00444444));
+        let mut step_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(24) };
+        DrawTextW(hdc, &mut step.encode_utf16().collect::<Vec<_>>(), &mut step_rect, DT_SINGLELINE);
+        y += scale(28);
+    }
+
+    y += scale(15);
+
+    // Hint box
+    let hint_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(40) };
+    let hint_brush = CreateSolidBrush(COLORREF(0x00FFF3E0)); // Light orange
+    FillRect(hdc, &hint_rect, hint_brush);
+    let _ = DeleteObject(hint_brush);
+
+    SelectObject(hdc, hint_font);
+    SetTextColor(hdc, COLORREF(0x00E65100));
+    let mut hint_text_rect = RECT { left: rect.left + scale(10), top: y + scale(10), right: rect.right - scale(10), bottom: y + scale(35) };
+    DrawTextW(hdc, &mut i18n::t("wizard.bot.hint").encode_utf16().collect::<Vec<_>>(), &mut hint_text_rect, DT_CENTER | DT_SINGLELINE);
+
+    let _ = DeleteObject(title_font);
+    let _ = DeleteObject(step_font);
+    let _ = DeleteObject(icon_font);
+    let _ = DeleteObject(hint_font);
+}
+
+/// Step 3: Token entry
+unsafe fn paint_step_token(hdc: HDC, hwnd: HWND, rect: &RECT) {
+    let title_font = CreateFontW(scale(24), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let label_font = CreateFontW(scale(15), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let icon_font = CreateFontW(scale(40), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI Emoji"));
+
+    let mut y = rect.top;
+
+    // Key icon
+    SelectObject(hdc, icon_font);
+    SetTextColor(hdc, COLORREF(0x00FF9800));
+    let mut icon_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(50) };
+    DrawTextW(hdc, &mut "🔑".encode_utf16().collect::<Vec<_>>(), &mut icon_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(55);
+
+    // Title
+    SelectObject(hdc, title_font);
+    SetTextColor(hdc, COLORREF(0x00333333));
+    let mut title_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(30) };
+    DrawTextW(hdc, &mut i18n::t("wizard.token.title").encode_utf16().collect::<Vec<_>>(), &mut title_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(50);
+
+    // Label
+    SelectObject(hdc, label_font);
+    SetTextColor(hdc, COLORREF(0x00666666));
+    let mut label_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(22) };
+    DrawTextW(hdc, &mut i18n::t("wizard.token.label").encode_utf16().collect::<Vec<_>>(), &mut label_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(30);
+
+    // Create/update edit control for token
+    let edit_hwnd = GetDlgItem(hwnd, ID_WIZARD_TOKEN_EDIT).unwrap_or_default();
+    if edit_hwnd.0.is_null() {
+        let hinstance = GetModuleHandleW(None).unwrap();
+        let edit_width = scale(350);
+        let edit_x = (rect.right - rect.left - edit_width) / 2 + rect.left;
+
+        let _ = CreateWindowExW(
+            WS_EX_CLIENTEDGE,
+            w!("EDIT"),
+            w!(""),
+            WS_CHILD | WS_VISIBLE | WS_BORDER | WINDOW_STYLE(ES_CENTER as u32 | ES_AUTOHSCROLL as u32),
+            edit_x,
+            y,
+            edit_width,
+            scale(35),
+            hwnd,
+            HMENU(ID_WIZARD_TOKEN_EDIT as _),
+            hinstance,
+            None,
+        );
+
+        let edit_font = CreateFontW(scale(14), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Consolas"));
+        let new_edit = GetDlgItem(hwnd, ID_WIZARD_TOKEN_EDIT).unwrap_or_default();
+        if !new_edit.0.is_null() {
+            SendMessageW(new_edit, WM_SETFONT, WPARAM(edit_font.0 as usize), LPARAM(1));
+
+            // Set placeholder text if we have a saved token
+            if let Some(token) = WIZARD_TOKEN.lock().unwrap().clone() {
+                let wide: Vec<u16> = token.encode_utf16().chain(std::iter::once(0)).collect();
+                SetWindowTextW(new_edit, PCWSTR(wide.as_ptr())).ok();
+            }
+        }
+    }
+
+    y += scale(55);
+
+    // Validation status
+    let token = WIZARD_TOKEN.lock().unwrap().clone();
+    let token_valid = token.as_ref().map(|t| is_valid_token_format(t)).unwrap_or(false);
+    if token.is_some() {
+        let (status_text, status_color) = if token_valid {
+            (format!("✓ {}", i18n::t("wizard.token.valid")), COLORREF(0x004CAF50))
+        } else {
+            (format!("✗ {}", i18n::t("wizard.token.invalid")), COLORREF(0x00F44336))
+        };
+        SetTextColor(hdc, status_color);
+        let mut status_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(22) };
+        DrawTextW(hdc, &mut status_text.encode_utf16().collect::<Vec<_>>(), &mut status_rect, DT_CENTER | DT_SINGLELINE);
+    }
+
+    let _ = DeleteObject(title_font);
+    let _ = DeleteObject(label_font);
+    let _ = DeleteObject(icon_font);
+}
+
+/// Step 4: Connect to bot
+unsafe fn paint_step_connect(hdc: HDC, rect: &RECT) {
+    let title_font = CreateFontW(scale(24), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let step_font = CreateFontW(scale(15), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let icon_font = CreateFontW(scale(40), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI Emoji"));
+    let status_font = CreateFontW(scale(18), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+
+    let mut y = rect.top;
+
+    // Link icon
+    SelectObject(hdc, icon_font);
+    SetTextColor(hdc, COLORREF(0x009C27B0));
+    let mut icon_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(50) };
+    DrawTextW(hdc, &mut "🔗".encode_utf16().collect::<Vec<_>>(), &mut icon_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(55);
+
+    // Title
+    SelectObject(hdc, title_font);
+    SetTextColor(hdc, COLORREF(0x00333333));
+    let mut title_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(30) };
+    DrawTextW(hdc, &mut i18n::t("wizard.connect.title").encode_utf16().collect::<Vec<_>>(), &mut title_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(50);
+
+    // Steps
+    SelectObject(hdc, step_font);
+    let steps = [
+        i18n::t("wizard.connect.step1"),
+        i18n::t("wizard.connect.step2"),
+        i18n::t("wizard.connect.step3"),
+    ];
+
+    for step in steps.iter() {
+        SetTextColor(hdc, COLORREF(0x00444444));
+        let mut step_rect = RECT { left: rect.left + scale(30), top: y, right: rect.right, bottom: y + scale(24) };
+        DrawTextW(hdc, &mut step.encode_utf16().collect::<Vec<_>>(), &mut step_rect, DT_SINGLELINE);
+        y += scale(32);
+    }
+
+    y += scale(30);
+
+    // Status
+    SelectObject(hdc, status_font);
+    if let Some(chat_id) = *WIZARD_CHAT_ID.lock().unwrap() {
+        // Connected!
+        SetTextColor(hdc, COLORREF(0x004CAF50));
+        let detected = format!("✓ {}", i18n::t("wizard.connect.detected"));
+        let mut status_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(25) };
+        DrawTextW(hdc, &mut detected.encode_utf16().collect::<Vec<_>>(), &mut status_rect, DT_CENTER | DT_SINGLELINE);
+        y += scale(30);
+
+        SetTextColor(hdc, COLORREF(0x00666666));
+        let chatid_text = format!("{} {}", i18n::t("wizard.connect.chatid"), chat_id);
+        let mut chatid_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(22) };
+        DrawTextW(hdc, &mut chatid_text.encode_utf16().collect::<Vec<_>>(), &mut chatid_rect, DT_CENTER | DT_SINGLELINE);
+    } else {
+        // Waiting...
+        SetTextColor(hdc, COLORREF(0x00FF9800));
+        let waiting_anim = match (std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() % 4) as usize {
+            0 => "⏳",
+            1 => "⌛",
+            2 => "⏳",
+            _ => "⌛",
+        };
+        let waiting_text = format!("{} {}", waiting_anim, i18n::t("wizard.connect.waiting"));
+        let mut status_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(25) };
+        DrawTextW(hdc, &mut waiting_text.encode_utf16().collect::<Vec<_>>(), &mut status_rect, DT_CENTER | DT_SINGLELINE);
+    }
+
+    let _ = DeleteObject(title_font);
+    let _ = DeleteObject(step_font);
+    let _ = DeleteObject(icon_font);
+    let _ = DeleteObject(status_font);
+}
+
+/// Step 5: Success
+unsafe fn paint_step_success(hdc: HDC, rect: &RECT) {
+    let title_font = CreateFontW(scale(28), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let desc_font = CreateFontW(scale(15), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let cmd_font = CreateFontW(scale(13), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Consolas"));
+    let icon_font = CreateFontW(scale(56), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI Emoji"));
+
+    let mut y = rect.top;
+
+    // Checkmark icon
+    SelectObject(hdc, icon_font);
+    SetTextColor(hdc, COLORREF(0x004CAF50));
+    let mut icon_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(65) };
+    DrawTextW(hdc, &mut "✅".encode_utf16().collect::<Vec<_>>(), &mut icon_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(75);
+
+    // Title
+    SelectObject(hdc, title_font);
+    SetTextColor(hdc, COLORREF(0x004CAF50));
+    let mut title_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(35) };
+    DrawTextW(hdc, &mut i18n::t("wizard.success.title").encode_utf16().collect::<Vec<_>>(), &mut title_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(45);
+
+    // Description
+    SelectObject(hdc, desc_font);
+    SetTextColor(hdc, COLORREF(0x00666666));
+    let mut desc_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(22) };
+    DrawTextW(hdc, &mut i18n::t("wizard.success.desc").encode_utf16().collect::<Vec<_>>(), &mut desc_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(25);
+
+    let mut test_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(22) };
+    DrawTextW(hdc, &mut i18n::t("wizard.success.test").encode_utf16().collect::<Vec<_>>(), &mut test_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(40);
+
+    // Commands section
+    SetTextColor(hdc, COLORREF(0x00333333));
+    let mut cmd_title_rect = RECT { left: rect.left, top: y, right: rect.right, bottom: y + scale(22) };
+    DrawTextW(hdc, &mut i18n::t("wizard.success.commands").encode_utf16().collect::<Vec<_>>(), &mut cmd_title_rect, DT_CENTER | DT_SINGLELINE);
+    y += scale(30);
+
+    // Command examples with background
+    SelectObject(hdc, cmd_font);
+    let commands = [
+        i18n::t("wizard.success.cmd1"),
+        i18n::t("wizard.success.cmd2"),
+        i18n::t("wizard.success.cmd3"),
+        i18n::t("wizard.success.cmd4"),
+    ];
+
+    let cmd_bg = CreateSolidBrush(COLORREF(0x00F5F5F5));
+    for cmd in commands.iter() {
+        let cmd_rect = RECT { left: rect.left + scale(20), top: y, right: rect.right - scale(20), bottom: y + scale(22) };
+        FillRect(hdc, &cmd_rect, cmd_bg);
+        SetTextColor(hdc, COLORREF(0x00333333));
+        let mut text_rect = RECT { left: rect.left + scale(30), top: y + scale(2), right: rect.right - scale(30), bottom: y + scale(20) };
+        DrawTextW(hdc, &mut cmd.encode_utf16().collect::<Vec<_>>(), &mut text_rect, DT_SINGLELINE);
+        y += scale(26);
+    }
+    let _ = DeleteObject(cmd_bg);
+
+    let _ = DeleteObject(title_font);
+    let _ = DeleteObject(desc_font);
+    let _ = DeleteObject(cmd_font);
+    let _ = DeleteObject(icon_font);
+}
+
+/// Check if token format is valid (basic check)
+fn is_valid_token_format(token: &str) -> bool {
+    // Telegram bot tokens are like: 123456789:ABCdefGHI-jklMNO_pqr
+    let parts: Vec<&str> = token.split(':').collect();
+    if parts.len() != 2 {
+        return false;
+    }
+    // First part should be numeric (bot ID)
+    if parts[0].parse::<i64>().is_err() {
+        return false;
+    }
+    // Second part should be alphanumeric with some special chars
+    parts[1].len() >= 30 && parts[1].chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Update wizard button states based on current step
+unsafe fn update_wizard_buttons(hwnd: HWND) {
+    let back_btn = GetDlgItem(hwnd, ID_WIZARD_BACK).unwrap_or_default();
+    let next_btn = GetDlgItem(hwnd, ID_WIZARD_NEXT).unwrap_or_default();
+
+    let step = WIZARD_STEP.load(Ordering::SeqCst);
+
+    // Back button: disabled on step 1
+    if !back_btn.0.is_null() {
+        let _ = EnableWindow(back_btn, step > 1);
+    }
+
+    // Next button text and state
+    if !next_btn.0.is_null() {
+        let next_text = if step == WIZARD_TOTAL_STEPS {
+            i18n::wide("wizard.finish")
+        } else {
+            i18n::wide("wizard.next")
+        };
+        SetWindowTextW(next_btn, PCWSTR(next_text.as_ptr())).ok();
+
+        // Disable next on step 3 if token invalid, step 4 if not connected
+        let can_proceed = match step {
+            3 => WIZARD_TOKEN.lock().unwrap().as_ref().map(|t| is_valid_token_format(t)).unwrap_or(false),
+            4 => WIZARD_CHAT_ID.lock().unwrap().is_some(),
+            _ => true,
+        };
+        let _ = EnableWindow(next_btn, can_proceed);
+    }
+}
+
+/// Handle wizard command (button clicks)
+unsafe fn handle_wizard_command(hwnd: HWND, id: i32) {
+    match id {
+        ID_WIZARD_CANCEL => {
+            DestroyWindow(hwnd).ok();
+        }
+        ID_WIZARD_BACK => {
+            let step = WIZARD_STEP.load(Ordering::SeqCst);
+            if step > 1 {
+                // Stop polling if we're leaving step 4
+                if step == 4 {
+                    KillTimer(hwnd, TIMER_POLL_TELEGRAM).ok();
+                    WIZARD_POLLING.store(false, Ordering::SeqCst);
+                }
+                WIZARD_STEP.store(step - 1, Ordering::SeqCst);
+                // Destroy token edit if going back from step 3
+                if step - 1 == 2 {
+                    let edit = GetDlgItem(hwnd, ID_WIZARD_TOKEN_EDIT).unwrap_or_default();
+                    if !edit.0.is_null() {
+                        DestroyWindow(edit).ok();
+                    }
+                }
+                let _ = InvalidateRect(hwnd, None, true);
+            }
+        }
+        ID_WIZARD_NEXT => {
+            let step = WIZARD_STEP.load(Ordering::SeqCst);
+
+            // Save token from edit control on step 3
+            if step == 3 {
+                let edit = GetDlgItem(hwnd, ID_WIZARD_TOKEN_EDIT).unwrap_or_default();
+                if !edit.0.is_null() {
+                    let mut buffer = [0u16; 256];
+                    let len = GetWindowTextW(edit, &mut buffer);
+                    let token = String::from_utf16_lossy(&buffer[..len as usize]);
+                    *WIZARD_TOKEN.lock().unwrap() = Some(token.trim().to_string());
+                }
+            }
+
+            if step < WIZARD_TOTAL_STEPS {
+                let step = step + 1;
+                WIZARD_STEP.store(step, Ordering::SeqCst);
+
+                // Start polling on step 4
+                if step == 4 && !WIZARD_POLLING.load(Ordering::SeqCst) {
+                    WIZARD_POLLING.store(true, Ordering::SeqCst);
+                    SetTimer(hwnd, TIMER_POLL_TELEGRAM, 2000, None);
+                }
+
+                // Destroy token edit when leaving step 3
+                if step == 4 {
+                    let edit = GetDlgItem(hwnd, ID_WIZARD_TOKEN_EDIT).unwrap_or_default();
+                    if !edit.0.is_null() {
+                        DestroyWindow(edit).ok();
+                    }
+                }
+
+                // On step 5, save config and send test message
+                if step == 5 {
+                    KillTimer(hwnd, TIMER_POLL_TELEGRAM).ok();
+                    WIZARD_POLLING.store(false, Ordering::SeqCst);
+                    save_wizard_config();
+                }
+
+                let _ = InvalidateRect(hwnd, None, true);
+            } else {
+                // Finish
+                DestroyWindow(hwnd).ok();
+            }
+        }
+        ID_WIZARD_TOKEN_EDIT => {
+            // Token edit changed - update validation
+            let edit = GetDlgItem(hwnd, ID_WIZARD_TOKEN_EDIT).unwrap_or_default();
+            if !edit.0.is_null() {
+                let mut buffer = [0u16; 256];
+                let len = GetWindowTextW(edit, &mut buffer);
+                let token = String::from_utf16_lossy(&buffer[..len as usize]);
+                *WIZARD_TOKEN.lock().unwrap() = Some(token.trim().to_string());
+                let _ = InvalidateRect(hwnd, None, true);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Poll Telegram for incoming messages to detect chat ID
+unsafe fn poll_telegram_for_chatid(hwnd: HWND) {
+    if !WIZARD_POLLING.load(Ordering::SeqCst) || WIZARD_CHAT_ID.lock().unwrap().is_some() {
+        return;
+    }
+
+    if let Some(token) = WIZARD_TOKEN.lock().unwrap().clone() {
+        // Spawn a thread to do the API call
+        std::thread::spawn(move || {
+            if let Some(chat_id) = fetch_telegram_updates(&token) {
+                // Post message back to UI thread
+                *WIZARD_CHAT_ID.lock().unwrap() = Some(chat_id);
+                let wizard_hwnd = HWND(WIZARD_HWND.load(Ordering::SeqCst));
+                if !wizard_hwnd.0.is_null() {
+                    let _ = InvalidateRect(wizard_hwnd, None, true);
+                }
+            }
+        });
+    }
+
+    // Trigger repaint for animation
+    let _ = InvalidateRect(hwnd, None, true);
+}
+
+/// Fetch latest updates from Telegram to get chat ID
+fn fetch_telegram_updates(token: &str) -> Option<i64> {
+    let url = format!("https://api.telegram.org/bot{}/getUpdates?limit=1&timeout=1", token);
+
+    // Use ureq for simple HTTP request (we already have it as a dependency through teloxide)
+    let response = match ureq::get(&url).timeout(std::time::Duration::from_secs(3)).call() {
+        Ok(r) => r,
+        Err(_) => return None,
+    };
+
+    let body = match response.into_string() {
+        Ok(b) => b,
+        Err(_) => return None,
+    };
+
+    // Parse JSON manually (avoid adding serde dependency just for this)
+    // Look for "chat":{"id": pattern
+    if let Some(chat_pos) = body.find("\"chat\":{\"id\":") {
+        let start = chat_pos + 13;
+        if let Some(end_pos) = body[start..].find(|c: char| !c.is_numeric() && c != '-') {
+            if let Ok(id) = body[start..start + end_pos].parse::<i64>() {
+                return Some(id);
+            }
+        }
+    }
+
+    None
+}
+
+/// Save wizard configuration to database
+unsafe fn save_wizard_config() {
+    let token = WIZARD_TOKEN.lock().unwrap().clone();
+    let chat_id = *WIZARD_CHAT_ID.lock().unwrap();
+    if let (Some(token), Some(chat_id)) = (token, chat_id) {
+        // Save to database using the existing function signature
+        crate::database::set_telegram_config(&token, &chat_id.to_string(), true);
+
+        // Send test message
+        std::thread::spawn(move || {
+            let _ = send_test_message(&token, chat_id);
+        });
+    }
+}
+
+/// Send a test message to confirm setup
+fn send_test_message(token: &str, chat_id: i64) -> Result<(), ()> {
+    let message = format!("✅ {} - Screen Time Manager", crate::i18n::t("wizard.success.title"));
+    let url = format!(
+        "https://api.telegram.org/bot{}/sendMessage?chat_id={}&text={}",
+        token,
+        chat_id,
+        urlencoding::encode(&message)
+    );
+
+    let _ = ureq::get(&url).timeout(std::time::Duration::from_secs(5)).call();
+    Ok(())
+}
@@ -2,7 +2,11 @@
 //! Provides utilities for DPI-aware UI scaling
 
 use std::sync::atomic::{AtomicU32, Ordering};
-use windows::Win32::UI::HiDpi::GetDpiForSystem;
+use windows::Win32::{
+    Foundation::POINT,
+    Graphics::Gdi::{MonitorFromPoint, MONITOR_DEFAULTTOPRIMARY},
+    UI::HiDpi::{GetDpiForMonitor, GetDpiForSystem, MDT_EFFECTIVE_DPI},
+};
 
 /// Cached DPI value (0 means not initialized)
 static CACHED_DPI: AtomicU32 = AtomicU32::new(0);
@@ -17,6 +21,21 @@ pub fn init_dpi() {
     CACHED_DPI.store(dpi, Ordering::SeqCst);
 }
 
+/// Re-read the DPI of the primary monitor, where the overlays are shown.
+/// The system DPI is fixed for the lifetime of the process, so this is used
+/// when the scaling or display configuration changes while the app runs.
+pub fn refresh_dpi() {
+    let dpi = unsafe {
+        let monitor = MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_DEFAULTTOPRIMARY);
+        let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+        match GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) {
+            Ok(()) if dpi_x > 0 => dpi_x,
+            _ => GetDpiForSystem(),
+        }
+    };
+    CACHED_DPI.store(dpi, Ordering::SeqCst);
+}
+
 /// Get the current DPI value
 pub fn get_dpi() -> u32 {
     let cached = CACHED_DPI.load(Ordering::SeqCst);
@@ -0,0 +1,128 @@
+//! Elevation module
+//! Detects whether the app runs with administrator rights and whether the rights-dependent
+//! features (shutdown, hibernate) are usable, so they can be greyed out instead of failing
+//! silently. Can restart the app elevated via the UAC prompt when the account is an
+//! administrator running with its filtered (split) token.
+
+use std::sync::OnceLock;
+use windows::{
+    core::{w, PCWSTR},
+    Win32::{
+        Foundation::{CloseHandle, GetLastError, ERROR_NOT_ALL_ASSIGNED, HANDLE, HWND, MAX_PATH},
+        Security::{
+            AdjustTokenPrivileges, GetTokenInformation, LookupPrivilegeValueW, TokenElevation, TokenElevationType,
+            TokenElevationTypeLimited, LUID_AND_ATTRIBUTES, SE_PRIVILEGE_ENABLED, TOKEN_ADJUST_PRIVILEGES,
+            TOKEN_ELEVATION, TOKEN_ELEVATION_TYPE, TOKEN_INFORMATION_CLASS, TOKEN_PRIVILEGES, TOKEN_QUERY,
+        },
+        System::LibraryLoader::GetModuleFileNameW,
+        System::Threading::{GetCurrentProcess, OpenProcessToken},
+        UI::Shell::ShellExecuteW,
+        UI::WindowsAndMessaging::SW_SHOWNORMAL,
+    },
+};
+
+use crate::i18n;
+
+/// Command-line flag passed to the elevated copy, which then waits for this instance to exit
+pub const RESTART_ARG: &str = "--elevated-restart";
+
+/// Result of the shutdown privilege check (the token's privileges don't change while running)
+static SHUTDOWN_ALLOWED: OnceLock<bool> = OnceLock::new();
+
+/// Read a fixed-size piece of information from the process token
+unsafe fn query_token<T: Default>(class: TOKEN_INFORMATION_CLASS) -> Option<T> {
+    let mut token = HANDLE::default();
+    OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).ok()?;
+    let mut value = T::default();
+    let mut size = 0u32;
+    let ok = GetTokenInformation(
+        token,
+        class,
+        Some(&mut value as *mut T as *mut std::ffi::c_void),
+        std::mem::size_of::<T>() as u32,
+        &mut size,
+    )
+    .is_ok();
+    let _ = CloseHandle(token);
+    ok.then_some(value)
+}
+
+/// Check whether the process token is elevated (running as administrator)
+pub fn is_elevated() -> bool {
+    unsafe { query_token::<TOKEN_ELEVATION>(TokenElevation) }.is_some_and(|elevation| elevation.TokenIsElevated != 0)
+}
+
+/// Whether a UAC prompt could give this process administrator rights: only for an
+/// administrator running with the filtered half of a split token. A standard account
+/// (or UAC turned off) would only get a prompt for another account's credentials.
+pub fn can_elevate() -> bool {
+    unsafe { query_token::<TOKEN_ELEVATION_TYPE>(TokenElevationType) }.is_some_and(|kind| kind == TokenElevationTypeLimited)
+}
+
+/// Enables SeShutdownPrivilege for this process (needed for shutdown and hibernate).
+/// False when the account doesn't hold the privilege (e.g. removed by group policy):
+/// AdjustTokenPrivileges succeeds then and only reports ERROR_NOT_ALL_ASSIGNED.
+pub unsafe fn enable_shutdown_privilege() -> bool {
+    let mut token = HANDLE::default();
+    if OpenProcessToken(GetCurrentProcess(), TOKEN_ADJUST_PRIVILEGES | TOKEN_QUERY, &mut token).is_err() {
+        return false;
+    }
+
+    let mut luid = std::mem::zeroed();
+    if LookupPrivilegeValueW(None, w!("SeShutdownPrivilege"), &mut luid).is_err() {
+        let _ = CloseHandle(token);
+        return false;
+    }
+
+    let tp = TOKEN_PRIVILEGES {
+        PrivilegeCount: 1,
+        Privileges: [LUID_AND_ATTRIBUTES { Luid: luid, Attributes: SE_PRIVILEGE_ENABLED }],
+    };
+    let enabled = AdjustTokenPrivileges(token, false, Some(&tp), 0, None, None).is_ok()
+        && GetLastError() != ERROR_NOT_ALL_ASSIGNED;
+
+    let _ = CloseHandle(token);
+    enabled
+}
+
+/// Whether this account may shut down or hibernate the machine (checked once)
+pub fn can_shut_down() -> bool {
+    *SHUTDOWN_ALLOWED.get_or_init(|| {
+        let allowed = unsafe { enable_shutdown_privilege() };
+        if !allowed {
+            log::warn!("Shutdown privilege not held; shutdown and hibernate are disabled");
+        }
+        allowed
+    })
+}
+
+/// Localized one-line elevation status for the About box
+pub fn status_text() -> &'static str {
+    if is_elevated() {
+        i18n::t("elevation.status_admin")
+    } else {
+        i18n::t("elevation.status_user")
+    }
+}
+
+/// Start an elevated copy of the app through the UAC prompt. True when it was started;
+/// the caller then exits so the new instance can take over. False when the prompt was
+/// declined, the account can't be elevated or the executable path couldn't be determined.
+pub unsafe fn restart_as_admin(hwnd: HWND) -> bool {
+    if !can_elevate() {
+        return false;
+    }
+    let mut path = [0u16; MAX_PATH as usize];
+    let len = GetModuleFileNameW(None, &mut path) as usize;
+    if len == 0 || len >= path.len() {
+        return false;
+    }
+    let args = i18n::to_wide(RESTART_ARG);
+    let result = ShellExecuteW(hwnd, w!("runas"), PCWSTR(path.as_ptr()), PCWSTR(args.as_ptr()), None, SW_SHOWNORMAL);
+    // Values above 32 mean success
+    let started = result.0 as isize > 32;
+    if started {
+        log::info!("Restarting with administrator rights");
+    }
+    started
+}
@@ -0,0 +1,291 @@
+//! Email module
+//! Optional daily report sent to a parent's address over SMTP (TLS only)
+
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+use crate::database;
+use crate::i18n;
+
+/// Timeout for connecting and for each SMTP reply
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the report thread checks whether the report is due
+const REPORT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Retry delays after a failed send (doubling, capped)
+const RETRY_MIN: Duration = Duration::from_secs(60);
+const RETRY_MAX: Duration = Duration::from_secs(30 * 60);
+
+/// Errors while sending an email
+#[derive(Debug)]
+pub enum EmailError {
+    /// Email reports are off or server/recipient are missing
+    NotConfigured,
+    /// Network error (connect, read, write, timeout)
+    Io(std::io::Error),
+    /// TLS setup or handshake failed
+    Tls(String),
+    /// The server answered with an unexpected reply (e.g. wrong credentials)
+    Rejected(String),
+}
+
+impl fmt::Display for EmailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmailError::NotConfigured => write!(f, "email reports are not configured"),
+            EmailError::Io(e) => write!(f, "network error: {}", e),
+            EmailError::Tls(e) => write!(f, "TLS error: {}", e),
+            EmailError::Rejected(reply) => write!(f, "server replied: {}", reply),
+        }
+    }
+}
+
+impl std::error::Error for EmailError {}
+
+impl From<std::io::Error> for EmailError {
+    fn from(e: std::io::Error) -> Self {
+        EmailError::Io(e)
+    }
+}
+
+impl From<native_tls::Error> for EmailError {
+    fn from(e: native_tls::Error) -> Self {
+        EmailError::Tls(e.to_string())
+    }
+}
+
+impl<S: fmt::Debug + 'static> From<native_tls::HandshakeError<S>> for EmailError {
+    fn from(e: native_tls::HandshakeError<S>) -> Self {
+        EmailError::Tls(e.to_string())
+    }
+}
+
+/// Start the background thread that sends the daily report at the configured time.
+/// Settings are re-read on every check, so changes apply without a restart.
+pub fn start_report_thread() {
+    std::thread::spawn(|| {
+        let mut retry_delay = RETRY_MIN;
+        // The day and summary of the report being sent. Taken when it falls due and kept
+        // through the retries, so a report still failing at midnight goes out for its own day.
+        let mut pending: Option<(String, String)> = None;
+        loop {
+            if pending.is_none() && report_due() {
+                pending = Some((database::get_today_date(), crate::telegram::daily_summary()));
+            }
+            if let Some((date, summary)) = &pending {
+                match send_report(date, summary) {
+                    Ok(()) => {
+                        database::set_setting("email_last_report", date);
+                        pending = None;
+                        retry_delay = RETRY_MIN;
+                    }
+                    // Nothing to retry until the settings are filled in; it falls due again then
+                    Err(EmailError::NotConfigured) => pending = None,
+                    Err(e) => {
+                        log::warn!("Sending the daily report failed: {} (retrying in {}s)", e, retry_delay.as_secs());
+                        std::thread::sleep(retry_delay);
+                        retry_delay = (retry_delay * 2).min(RETRY_MAX);
+                        continue;
+                    }
+                }
+            }
+            std::thread::sleep(REPORT_CHECK_INTERVAL);
+        }
+    });
+}
+
+/// Check if today's report should go out now (enabled, past the report time, not sent yet)
+fn report_due() -> bool {
+    let config = database::get_email_config();
+    if !config.enabled {
+        return false;
+    }
+    let st = crate::clock::local_time();
+    let now = st.wHour as u32 * 60 + st.wMinute as u32;
+    now >= config.report_minute
+        && database::get_setting("email_last_report").as_deref() != Some(database::get_today_date().as_str())
+}
+
+/// Email the report for `date` (YYYY-MM-DD) to the configured recipients
+pub fn send_report(date: &str, summary: &str) -> Result<(), EmailError> {
+    let config = database::get_email_config();
+    if !config.enabled || config.host.is_empty() || config.recipients.is_empty() {
+        return Err(EmailError::NotConfigured);
+    }
+    let password = crate::secrets::unprotect(&config.password).unwrap_or_default();
+    let subject = i18n::t("email.subject").replace("{}", &crate::locale::short_date(date));
+    // Addresses go into SMTP commands and headers, where a line break would start a new one
+    let recipients: Vec<String> = config.recipients.iter().map(|r| strip_line_breaks(r)).collect();
+    let from = strip_line_breaks(if config.username.contains('@') { &config.username } else { &recipients[0] });
+    let message = build_message(&from, &recipients, &subject, summary);
+
+    let address = (config.host.as_str(), config.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| EmailError::Rejected(format!("{} could not be resolved", config.host)))?;
+    let tcp = TcpStream::connect_timeout(&address, SMTP_TIMEOUT)?;
+    tcp.set_read_timeout(Some(SMTP_TIMEOUT))?;
+    tcp.set_write_timeout(Some(SMTP_TIMEOUT))?;
+    let tls = native_tls::TlsConnector::new()?;
+
+    if config.port == 465 {
+        // Implicit TLS from the first byte
+        let mut session = Session::new(tls.connect(&config.host, tcp)?);
+        session.expect(220)?;
+        session.command("EHLO localhost", 250)?;
+        session.deliver(&config.username, &password, &from, &recipients, &message)
+    } else {
+        // STARTTLS; credentials never go over an unencrypted connection
+        let mut session = Session::new(tcp);
+        session.expect(220)?;
+        session.command("EHLO localhost", 250)?;
+        session.command("STARTTLS", 220)?;
+        let mut session = Session::new(tls.connect(&config.host, session.into_inner())?);
+        session.command("EHLO localhost", 250)?;
+        session.deliver(&config.username, &password, &from, &recipients, &message)
+    }
+}
+
+/// Remove CR and LF, which would end a header or command line early
+fn strip_line_breaks(value: &str) -> String {
+    value.chars().filter(|c| *c != '\r' && *c != '\n').collect()
+}
+
+/// Plain-text UTF-8 message with a base64 body (no line-length or dot-stuffing issues)
+fn build_message(from: &str, recipients: &[String], subject: &str, body: &str) -> String {
+    let recipients: Vec<String> = recipients.iter().map(|r| strip_line_breaks(r)).collect();
+    let body = body.replace("\r\n", "\n").replace('\n', "\r\n");
+    let encoded = BASE64.encode(body.as_bytes());
+    let mut message = format!(
+        "From: Screen Time Manager <{}>\r\n\
+         To: {}\r\n\
+         Subject: =?UTF-8?B?{}?=\r\n\
+         MIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\n\
+         Content-Transfer-Encoding: base64\r\n\
+         \r\n",
+        strip_line_breaks(from),
+        recipients.join(", "),
+        BASE64.encode(strip_line_breaks(subject).as_bytes())
+    );
+    for line in encoded.as_bytes().chunks(76) {
+        message.push_str(&String::from_utf8_lossy(line));
+        message.push_str("\r\n");
+    }
+    message
+}
+
+/// An SMTP conversation over a plain or TLS stream
+struct Session<S: Read + Write> {
+    stream: BufReader<S>,
+}
+
+impl<S: Read + Write> Session<S> {
+    fn new(stream: S) -> Self {
+        Session { stream: BufReader::new(stream) }
+    }
+
+    fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+
+    /// Read one (possibly multi-line) reply and check its code
+    fn expect(&mut self, code: u16) -> Result<String, EmailError> {
+        let mut reply = String::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line)? == 0 {
+                return Err(EmailError::Rejected(format!("connection closed after: {}", reply)));
+            }
+            reply.push_str(line.trim_end());
+            // "250-..." continues, "250 ..." is the last line
+            if line.as_bytes().get(3) != Some(&b'-') {
+                break;
+            }
+            reply.push(' ');
+        }
+        match reply.get(..3).and_then(|c| c.parse::<u16>().ok()) {
+            Some(received) if received == code => Ok(reply),
+            _ => Err(EmailError::Rejected(reply)),
+        }
+    }
+
+    /// Send a command line and check the reply code
+    fn command(&mut self, line: &str, code: u16) -> Result<String, EmailError> {
+        let stream = self.stream.get_mut();
+        stream.write_all(line.as_bytes())?;
+        stream.write_all(b"\r\n")?;
+        stream.flush()?;
+        self.expect(code)
+    }
+
+    /// Log in (if a user is set) and hand over the message
+    fn deliver(
+        &mut self,
+        username: &str,
+        password: &str,
+        from: &str,
+        recipients: &[String],
+        message: &str,
+    ) -> Result<(), EmailError> {
+        if !username.is_empty() {
+            let credentials = BASE64.encode(format!("\0{}\0{}", username, password));
+            self.command(&format!("AUTH PLAIN {}", credentials), 235)?;
+        }
+        self.command(&format!("MAIL FROM:<{}>", from), 250)?;
+        for recipient in recipients {
+            self.command(&format!("RCPT TO:<{}>", recipient), 250)?;
+        }
+        self.command("DATA", 354)?;
+        let stream = self.stream.get_mut();
+        stream.write_all(message.as_bytes())?;
+        self.command(".", 250)?;
+        let _ = self.command("QUIT", 221);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(message: &str) -> Vec<&str> {
+        message.split("\r\n\r\n").next().unwrap().split("\r\n").collect()
+    }
+
+    #[test]
+    fn message_has_encoded_subject_and_wrapped_body() {
+        let body = "Used 45 / 120 min\n".repeat(10);
+        let message = build_message("mum@example.com", &["dad@example.com".into(), "mum@example.com".into()], "Bericht für heute", &body);
+
+        let headers = headers(&message);
+        assert_eq!(headers[0], "From: Screen Time Manager <mum@example.com>");
+        assert_eq!(headers[1], "To: dad@example.com, mum@example.com");
+        assert_eq!(headers[2], format!("Subject: =?UTF-8?B?{}?=", BASE64.encode("Bericht für heute")));
+
+        let encoded: String = message.split("\r\n\r\n").nth(1).unwrap().lines().collect();
+        assert!(message.split("\r\n\r\n").nth(1).unwrap().lines().all(|line| line.len() <= 76));
+        let decoded = String::from_utf8(BASE64.decode(encoded).unwrap()).unwrap();
+        assert_eq!(decoded, body.replace('\n', "\r\n"));
+    }
+
+    #[test]
+    fn line_breaks_cannot_add_headers() {
+        let message = build_message(
+            "kid@example.com\r\nBcc: someone@example.com",
+            &["dad@example.com\nX-Injected: 1".into()],
+            "Report\r\nBcc: someone@example.com",
+            "body",
+        );
+
+        let headers = headers(&message);
+        assert_eq!(headers.len(), 6);
+        assert!(headers.iter().all(|h| !h.starts_with("Bcc:") && !h.starts_with("X-Injected:")));
+        assert_eq!(headers[1], "To: dad@example.comX-Injected: 1");
+    }
+}
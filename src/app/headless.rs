@@ -0,0 +1,48 @@
+//! Headless module
+//! `--headless` runs the app without any windows, for lab machines and servers where a
+//! full-screen block isn't wanted: no tray icon, overlays, dialogs, setup wizard or shortcut.
+//! Time is still counted; limits are enforced by locking the session (or the configured
+//! shutdown) and reported through Telegram, webhooks, MQTT, email and the HTTP endpoint.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use windows::Win32::System::Shutdown::LockWorkStation;
+
+use crate::blocking::REMAINING_SECONDS;
+
+/// Command line switch that starts the app headless
+pub const HEADLESS_ARG: &str = "--headless";
+
+static HEADLESS: AtomicBool = AtomicBool::new(false);
+
+/// Switch to headless mode (once at startup, before any window is created)
+pub fn enable() {
+    HEADLESS.store(true, Ordering::SeqCst);
+}
+
+/// Whether the app runs without windows
+pub fn is_headless() -> bool {
+    HEADLESS.load(Ordering::SeqCst)
+}
+
+/// Lock the session in place of the blocking screen
+pub unsafe fn lock_session() {
+    if let Err(e) = LockWorkStation() {
+        log::warn!("Could not lock the session: {}", e.message());
+    }
+}
+
+/// Enforce the block again while the time is used up or during the curfew: at startup and
+/// whenever the session is unlocked, since there's no blocking screen that stays up
+pub unsafe fn enforce_block() {
+    if !is_headless() {
+        return;
+    }
+    if crate::curfew::is_curfew_blocking() {
+        lock_session();
+    } else if REMAINING_SECONDS.load(Ordering::SeqCst) <= 0
+        && !crate::database::is_warn_only()
+        && !crate::mini_overlay::is_focus_mode()
+    {
+        crate::blocking::apply_expiry_action();
+    }
+}
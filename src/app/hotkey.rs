@@ -0,0 +1,125 @@
+//! Hotkey module
+//! A global keyboard shortcut (Ctrl+Alt+T by default) that briefly shows the remaining time.
+//! Registered on the hidden main window; WM_HOTKEY is handled in `tray::window_proc`.
+
+use std::sync::atomic::{AtomicPtr, Ordering};
+use windows::Win32::{
+    Foundation::HWND,
+    UI::Input::KeyboardAndMouse::{
+        RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_ALT, MOD_CONTROL, MOD_NOREPEAT, MOD_SHIFT, MOD_WIN,
+    },
+};
+
+use crate::database;
+use crate::i18n;
+
+/// Hotkey ID of the status shortcut (WPARAM of WM_HOTKEY)
+pub const HOTKEY_STATUS: i32 = 1;
+
+/// Seconds the status banner stays up
+const STATUS_BANNER_SECONDS: u32 = 3;
+
+/// Window the hotkey is registered on, so settings can re-register it after a change
+static HOTKEY_HWND: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+
+/// Parse a shortcut like "Ctrl+Alt+T" into modifiers and a virtual-key code.
+/// The key is a letter, a digit or F1-F24, with at least one of Ctrl (or the German Strg),
+/// Alt, Shift or Win.
+pub fn parse_hotkey(text: &str) -> Option<(HOT_KEY_MODIFIERS, u32)> {
+    let mut modifiers = HOT_KEY_MODIFIERS(0);
+    let mut key = None;
+    for part in text.split('+').map(|p| p.trim().to_ascii_uppercase()) {
+        match part.as_str() {
+            "CTRL" | "CONTROL" | "STRG" => modifiers |= MOD_CONTROL,
+            "ALT" => modifiers |= MOD_ALT,
+            "SHIFT" => modifiers |= MOD_SHIFT,
+            "WIN" => modifiers |= MOD_WIN,
+            _ if key.is_some() => return None,
+            _ => key = Some(virtual_key(&part)?),
+        }
+    }
+    (modifiers.0 != 0).then_some((modifiers, key?))
+}
+
+/// Virtual-key code of a letter, digit or function key name
+fn virtual_key(name: &str) -> Option<u32> {
+    let mut chars = name.chars();
+    match (chars.next()?, chars.next()) {
+        (c @ ('A'..='Z' | '0'..='9'), None) => Some(c as u32),
+        ('F', Some(_)) => match name[1..].parse::<u32>() {
+            Ok(n @ 1..=24) => Some(0x70 + n - 1), // VK_F1..VK_F24
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Register the configured shortcut on `hwnd`, replacing the previous one. An empty setting
+/// turns it off. If another program already uses the shortcut, the parent is told in a
+/// notification and the app runs on without it.
+pub unsafe fn register(hwnd: HWND) {
+    HOTKEY_HWND.store(hwnd.0, Ordering::SeqCst);
+    let _ = UnregisterHotKey(hwnd, HOTKEY_STATUS);
+
+    let text = database::get_status_hotkey();
+    if text.is_empty() {
+        return;
+    }
+    let Some((modifiers, key)) = parse_hotkey(&text) else {
+        log::warn!("Ignoring invalid status hotkey {:?}", text);
+        return;
+    };
+    if let Err(e) = RegisterHotKey(hwnd, HOTKEY_STATUS, modifiers | MOD_NOREPEAT, key) {
+        log::warn!("Could not register the status hotkey {}: {}", text, e.message());
+        crate::tray::show_balloon(i18n::t("hotkey.conflict_title"), &i18n::t("hotkey.conflict").replace("{}", &text));
+    }
+}
+
+/// Register the shortcut again after it was changed in settings
+pub unsafe fn reregister() {
+    let hwnd = HWND(HOTKEY_HWND.load(Ordering::SeqCst));
+    if !hwnd.0.is_null() {
+        register(hwnd);
+    }
+}
+
+/// Remove the shortcut when the app exits
+pub unsafe fn unregister() {
+    let hwnd = HWND(HOTKEY_HWND.swap(std::ptr::null_mut(), Ordering::SeqCst));
+    if !hwnd.0.is_null() {
+        let _ = UnregisterHotKey(hwnd, HOTKEY_STATUS);
+    }
+}
+
+/// The shortcut was pressed: show the remaining time for a moment (read-only). Without a
+/// running countdown (no limit today) the status notification is shown instead.
+pub unsafe fn show_status() {
+    let remaining = crate::blocking::REMAINING_SECONDS.load(Ordering::SeqCst);
+    if remaining > 0 {
+        let text = crate::telegram::status_lines().join("\n");
+        crate::overlay::show_overlay(&text, STATUS_BANNER_SECONDS, true);
+    } else {
+        crate::tray::show_status_balloon();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_modifiers_and_key() {
+        assert_eq!(parse_hotkey("Ctrl+Alt+T"), Some((MOD_CONTROL | MOD_ALT, 'T' as u32)));
+        assert_eq!(parse_hotkey(" ctrl + shift + 5 "), Some((MOD_CONTROL | MOD_SHIFT, '5' as u32)));
+        assert_eq!(parse_hotkey("Win+F12"), Some((MOD_WIN, 0x7B)));
+        assert_eq!(parse_hotkey("Control+F1"), Some((MOD_CONTROL, 0x70)));
+        assert_eq!(parse_hotkey("Strg+Alt+T"), Some((MOD_CONTROL | MOD_ALT, 'T' as u32)));
+    }
+
+    #[test]
+    fn rejects_shortcuts_without_modifier_or_key() {
+        for text in ["", "T", "Ctrl+Alt", "Ctrl+T+U", "Ctrl+F25", "Ctrl+F0", "Ctrl+Tab", "Ctrl+ä", "Hyper+T"] {
+            assert_eq!(parse_hotkey(text), None, "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! HTTP API module
+//! Optional local HTTP server for self-hosters; serves Prometheus metrics at GET /metrics.
+//! Every request needs the configured bearer token.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blocking::REMAINING_SECONDS;
+use crate::database;
+use crate::mini_overlay;
+
+/// Time a client gets for sending its whole request head, and the timeout for each read and write
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest request head we read (request line plus headers)
+const MAX_HEAD_BYTES: usize = 8 * 1024;
+
+/// Start the metrics server in a background thread (if enabled in settings).
+/// It refuses to start without a token, so the endpoint is never open to anyone.
+pub fn start_server_thread() {
+    let config = database::get_http_config();
+    if !config.enabled {
+        log::info!("Disabled in settings");
+        return;
+    }
+    let token = match crate::secrets::unprotect(&config.token) {
+        Some(token) if !token.is_empty() => token,
+        _ => {
+            log::warn!("Enabled but no token configured, not starting");
+            return;
+        }
+    };
+    let listener = match TcpListener::bind(&config.listen) {
+        Ok(listener) => listener,
+        Err(e) => {
+            log::error!("Could not listen on {}: {}", config.listen, e);
+            return;
+        }
+    };
+    log::info!("Serving metrics on http://{}/metrics", config.listen);
+
+    let token = Arc::new(token);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                // Each connection gets its own short-lived thread, so a slow client can't hold up the others
+                Ok(stream) => {
+                    let token = Arc::clone(&token);
+                    std::thread::spawn(move || {
+                        if let Err(e) = handle_connection(stream, &token) {
+                            log::warn!("Request failed: {}", e);
+                        }
+                    });
+                }
+                Err(e) => log::warn!("Accept failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Request line and the header we care about
+#[derive(Debug, Default, PartialEq)]
+struct RequestHead {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+}
+
+/// Read one request and answer it; the connection is closed afterwards
+fn handle_connection(stream: TcpStream, token: &str) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let head = read_head(stream.try_clone()?, Instant::now() + REQUEST_TIMEOUT)?;
+    let mut stream = stream;
+
+    if !is_authorized(head.authorization.as_deref(), token) {
+        return respond(&mut stream, "401 Unauthorized", "text/plain", "unauthorized\n");
+    }
+    if head.method != "GET" {
+        return respond(&mut stream, "405 Method Not Allowed", "text/plain", "method not allowed\n");
+    }
+    match head.path.split('?').next() {
+        Some("/metrics") => respond(&mut stream, "200 OK", "text/plain; version=0.0.4; charset=utf-8", &metrics()),
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "not found\n"),
+    }
+}
+
+/// Read the request line and headers, up to MAX_HEAD_BYTES and until `deadline`
+/// (so a client trickling in a byte at a time can't keep the connection open). The limit
+/// covers single lines too: one without a line break isn't read past it.
+fn read_head(stream: impl Read, deadline: Instant) -> std::io::Result<RequestHead> {
+    let mut reader = BufReader::new(stream.take(MAX_HEAD_BYTES as u64));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let mut head = RequestHead {
+        method: parts.next().unwrap_or("").to_string(),
+        path: parts.next().unwrap_or("").to_string(),
+        authorization: None,
+    };
+
+    loop {
+        if Instant::now() > deadline {
+            return Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "request head took too long"));
+        }
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("authorization") {
+                head.authorization = Some(value.trim().to_string());
+            }
+        }
+    }
+    Ok(head)
+}
+
+/// Check an Authorization header value against the token
+fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|given| constant_time_eq(given.trim().as_bytes(), token.as_bytes()))
+        .unwrap_or(false)
+}
+
+/// Write a complete HTTP/1.1 response
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> std::io::Result<()> {
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    if status.starts_with("401") {
+        response.push_str("WWW-Authenticate: Bearer\r\n");
+    }
+    response.push_str("\r\n");
+    response.push_str(body);
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
+}
+
+/// Compare without returning early, so the token can't be guessed byte by byte from timings
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Current values in the Prometheus text exposition format
+fn metrics() -> String {
+    let pause_config = database::get_pause_config();
+    let pause_budget = (pause_config.daily_budget_minutes as i64 * 60 - database::get_pause_used_today() as i64).max(0);
+    let gauges = [
+        (
+            "screentime_remaining_seconds",
+            "Screen time left today in seconds",
+            REMAINING_SECONDS.load(Ordering::SeqCst).max(0) as i64,
+        ),
+        (
+            "screentime_used_seconds",
+            "Screen time used today in seconds",
+            crate::blocking::get_used_seconds() as i64,
+        ),
+        (
+            "screentime_paused",
+            "1 while the timer is paused",
+            mini_overlay::is_paused() as i64,
+        ),
+        (
+            "screentime_pause_budget_seconds",
+            "Pause time still available today in seconds",
+            pause_budget,
+        ),
+    ];
+    let counters = [
+        (
+            "screentime_extensions_total",
+            "Time extensions granted, over all days",
+            database::count_log_entries_all_days("extension_log") as i64,
+        ),
+        (
+            "screentime_blocks_total",
+            "Times the screen was blocked, over all days",
+            database::count_log_entries_all_days("block_log") as i64,
+        ),
+    ];
+
+    render_metrics(&gauges, &counters)
+}
+
+/// Gauges and counters (name, help text, value) in the Prometheus text format
+fn render_metrics(gauges: &[(&str, &str, i64)], counters: &[(&str, &str, i64)]) -> String {
+    let mut out = String::new();
+    for (kind, metrics) in [("gauge", gauges), ("counter", counters)] {
+        for (name, help, value) in metrics {
+            out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n{} {}\n", name, help, name, kind, name, value));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn head(request: &str) -> RequestHead {
+        read_head(Cursor::new(request.as_bytes()), Instant::now() + REQUEST_TIMEOUT).unwrap()
+    }
+
+    #[test]
+    fn request_head_is_parsed() {
+        let parsed = head("GET /metrics?x=1 HTTP/1.1\r\nHost: pc:9100\r\nauthorization:  Bearer abc \r\n\r\nignored");
+        assert_eq!(
+            parsed,
+            RequestHead {
+                method: "GET".into(),
+                path: "/metrics?x=1".into(),
+                authorization: Some("Bearer abc".into()),
+            }
+        );
+
+        // Garbage or an empty connection gives an empty head (answered with 401)
+        assert_eq!(head(""), RequestHead::default());
+        assert_eq!(head("\r\n").authorization, None);
+    }
+
+    #[test]
+    fn oversized_head_stops_reading() {
+        let request = format!("GET / HTTP/1.1\r\n{}Authorization: Bearer abc\r\n\r\n", "X-Pad: y\r\n".repeat(1000));
+        assert_eq!(head(&request).authorization, None);
+    }
+
+    #[test]
+    fn endless_line_stops_at_the_limit() {
+        let request = format!("GET /{} HTTP/1.1\r\nAuthorization: Bearer abc\r\n\r\n", "a".repeat(MAX_HEAD_BYTES * 4));
+        let parsed = head(&request);
+        assert_eq!(parsed.method, "GET");
+        assert_eq!(parsed.path.len(), MAX_HEAD_BYTES - "GET /".len() + 1);
+        assert_eq!(parsed.authorization, None);
+    }
+
+    #[test]
+    fn expired_deadline_ends_the_request() {
+        let reader = Cursor::new(b"GET / HTTP/1.1\r\nHost: pc\r\n\r\n".to_vec());
+        let result = read_head(reader, Instant::now() - Duration::from_secs(1));
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn only_the_exact_bearer_token_is_accepted() {
+        assert!(is_authorized(Some("Bearer s3cret"), "s3cret"));
+        assert!(!is_authorized(Some("Bearer s3cre"), "s3cret"));
+        assert!(!is_authorized(Some("Basic s3cret"), "s3cret"));
+        assert!(!is_authorized(None, "s3cret"));
+    }
+
+    #[test]
+    fn metrics_use_the_exposition_format() {
+        let text = render_metrics(
+            &[("screentime_remaining_seconds", "Screen time left today in seconds", 900)],
+            &[("screentime_blocks_total", "Times the screen was blocked, over all days", 3)],
+        );
+        assert_eq!(
+            text,
+            "# HELP screentime_remaining_seconds Screen time left today in seconds\n\
+             # TYPE screentime_remaining_seconds gauge\n\
+             screentime_remaining_seconds 900\n\
+             # HELP screentime_blocks_total Times the screen was blocked, over all days\n\
+             # TYPE screentime_blocks_total counter\n\
+             screentime_blocks_total 3\n"
+        );
+    }
+}
@@ -0,0 +1,1183 @@
+//! Internationalization (i18n) module for Screen Time Manager
+//! Supports English (default) and German
+
+use crate::database;
+use windows::core::PCWSTR;
+
+/// Convert a translated string to a Windows wide string (null-terminated Vec<u16>)
+/// The returned Vec must be kept alive while the PCWSTR is in use
+pub fn wide(key: &str) -> Vec<u16> {
+    t(key).encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Convert a raw string to a Windows wide string
+#[allow(dead_code)]
+pub fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Get PCWSTR from a wide string Vec (helper for cleaner code)
+#[allow(dead_code)]
+pub fn pcwstr(wide: &[u16]) -> PCWSTR {
+    PCWSTR(wide.as_ptr())
+}
+
+/// Supported languages
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Language {
+    English,
+    German,
+}
+
+impl Language {
+    /// Get the language code (for storage)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    /// Create Language from code string
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "de" => Language::German,
+            _ => Language::English,
+        }
+    }
+
+    /// Get the display name in the native language
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    /// Get all supported languages
+    pub fn all() -> &'static [Language] {
+        &[Language::English, Language::German]
+    }
+}
+
+/// Get the current language from settings
+pub fn current() -> Language {
+    Language::from_code(&database::get_setting("language").unwrap_or_default())
+}
+
+/// Main translation function - returns static string for the given key
+pub fn t(key: &str) -> &'static str {
+    match current() {
+        Language::English => en(key),
+        Language::German => de(key),
+    }
+}
+
+/// Get weekday name by index (0 = Monday, 6 = Sunday)
+pub fn weekday(index: usize) -> &'static str {
+    const EN_DAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+    const DE_DAYS: [&str; 7] = ["Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag"];
+
+    let days = match current() {
+        Language::English => &EN_DAYS,
+        Language::German => &DE_DAYS,
+    };
+    days.get(index).unwrap_or(&"Unknown")
+}
+
+/// Get short weekday name by index (0 = Monday, 6 = Sunday)
+#[allow(dead_code)]
+pub fn weekday_short(index: usize) -> &'static str {
+    const EN_DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    const DE_DAYS: [&str; 7] = ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"];
+
+    let days = match current() {
+        Language::English => &EN_DAYS,
+        Language::German => &DE_DAYS,
+    };
+    days.get(index).unwrap_or(&"?")
+}
+
+// ============================================================================
+// English strings
+// ============================================================================
+
+fn en(key: &str) -> &'static str {
+    match key {
+        // ----- Window Titles -----
+        "window.settings" => "Screen Time Settings",
+        "settings.default_passcode_title" => " - passcode not changed yet!",
+        "passcode.default_title" => "Change the passcode",
+        "passcode.default_warning" => "The passcode is still the default one, so anyone can unlock the screen. Open Settings and set your own under \"Change Passcode\".",
+        "window.passcode" => "Enter Passcode",
+        "window.stats" => "Today's Stats",
+        "window.blocking" => "Screen Time - Time's Up!",
+        "window.about" => "About",
+
+        // ----- Settings Dialog - Section Titles -----
+        "settings.daily_limits" => "Daily Time Limits (h:mm)",
+        "settings.rampdown_step" => "Weekly -min:",
+        "settings.fill_all_days" => "Mon → all days",
+        "settings.fill_weekdays" => "Mon → Tue-Fri",
+        "settings.fill_weekend" => "Sat → Sun",
+        "settings.rampdown_floor" => "Down to:",
+        "settings.defer_warnings_fullscreen" => "Notify quietly instead of the banner during fullscreen games/videos",
+        "settings.respect_focus_assist" => "Silent notification instead of warnings while Focus Assist is on",
+        "settings.warning_position" => "Banner position:",
+        "settings.warning_position.top" => "Top",
+        "settings.warning_position.center" => "Center",
+        "settings.warning_position.bottom" => "Bottom",
+        "settings.warning_margin" => "Margin (px):",
+        "settings.winddown" => "Wind-down (min):",
+        "settings.winddown_interval" => "Remind every (min):",
+        "settings.lockout_attempts" => "Lock after (wrong):",
+        "settings.lockout_delay" => "Lockout (sec):",
+        "settings.min_block_duration" => "Min. block (sec):",
+        "settings.max_extensions" => "Extensions/day:",
+        "settings.max_extensions_hint" => "0 = unlimited",
+        "settings.self_extend" => "Self-service:",
+        "settings.self_extend_hint" => "min, times/day (0 = off)",
+        "winddown.warning" => "Only {} minute(s) left - time to wrap up.",
+        "overlay.warning_title" => "Screen Time",
+        "overlay.live_countdown" => "{} remaining",
+        "settings.allowed_hours" => "Allowed Hours (HH:MM-HH:MM, empty = any time)",
+        "settings.allowed_hours_weekdays" => "Mon - Fri:",
+        "settings.allowed_hours_weekends" => "Sat - Sun:",
+        "settings.productive_apps" => "Don't count in:",
+        "settings.counted_users" => "Count only for:",
+        "settings.error.allowed_hours" => "Allowed hours must look like 07:00-20:00 (or be left empty).",
+        "settings.error.kiosk_needs_telegram" => "Kiosk mode hides Settings from the tray. Set up Telegram with your chat ID first; /kiosk off turns it off again.",
+        "settings.error.daily_limit" => "Enter minutes or hours like 2:30 or 2h 30m (at most 24:00).",
+        "curfew.message" => "Screen time is not allowed right now. Allowed hours today: {}",
+        "overtime.warning" => "Your screen time for today is used up. Please finish what you are doing.",
+        "settings.warning1" => "First Warning",
+        "settings.warning2" => "Second Warning",
+        "settings.blocking_message" => "Blocking Screen Message",
+        "settings.block_background_image" => "Background image:",
+        "settings.secondary_block_message" => "Other screens:",
+        "settings.passcode" => "Change Passcode (leave blank to keep)",
+        "settings.telegram" => "Telegram Bot",
+        "tg.state.off" => "Bot: not running",
+        "tg.state.connecting" => "Bot: connecting...",
+        "tg.state.connected" => "Bot: connected as @{}",
+        "tg.state.invalid_token" => "Bot: connection failed - Telegram rejected the bot token",
+        "tg.state.unreachable" => "Bot: can't reach Telegram, retrying ({})",
+        "tg.invalid_token_title" => "Telegram Bot Not Working",
+        "settings.lock_screen" => "Lock Screen",
+        "settings.idle" => "Idle Detection",
+        "settings.language" => "Language",
+
+        // ----- Settings Dialog - Labels -----
+        "settings.minutes_before" => "Minutes before:",
+        "settings.show_for" => "Show for (sec):",
+        "settings.warning_weekend" => "Weekends:",
+        "settings.message" => "Message:",
+        "settings.current" => "Current:",
+        "settings.new" => "New:",
+        "settings.confirm" => "Confirm:",
+        "settings.enable_telegram" => "Enable Telegram Bot",
+        "settings.bot_token" => "Bot Token:",
+        "settings.chat_id" => "Chat ID:",
+        "settings.webhook_url" => "Webhook:",
+        "settings.email" => "Daily Email Report",
+        "settings.email_enabled" => "Email a daily report at:",
+        "settings.smtp_host" => "SMTP server:",
+        "settings.smtp_port" => "Port:",
+        "settings.smtp_username" => "User:",
+        "settings.smtp_password" => "Password:",
+        "settings.smtp_password_hint" => "Sensitive - stored encrypted for this Windows user",
+        "settings.smtp_password_saved" => "Sensitive - saved encrypted; leave empty to keep it",
+        "settings.email_to" => "Send to:",
+        "settings.mqtt" => "MQTT (Home Assistant)",
+        "settings.mqtt_enabled" => "Enable MQTT",
+        "settings.mqtt_prefix" => "Topic:",
+        "settings.mqtt_host" => "Broker:",
+        "settings.mqtt_port" => "Port:",
+        "settings.mqtt_username" => "User:",
+        "settings.mqtt_password" => "Password:",
+        "settings.mqtt_password_hint" => "Sensitive - stored encrypted for this Windows user",
+        "settings.mqtt_password_saved" => "Sensitive - saved encrypted; leave empty to keep it",
+        "settings.http" => "Metrics (Prometheus)",
+        "settings.http_enabled" => "Serve /metrics on",
+        "settings.http_token" => "Token:",
+        "settings.http_token_hint" => "Required - sent as \"Authorization: Bearer <token>\"",
+        "settings.http_token_saved" => "Saved encrypted; leave empty to keep it",
+        "settings.backup" => "Backup & Restore",
+        "settings.backup_password" => "Password:",
+        "settings.backup_hint" => "Optional - encrypts the backup; needed to include passwords",
+        "settings.backup_create" => "Backup...",
+        "settings.backup_restore" => "Restore...",
+        "backup.confirm_restore" => "Replace all settings and history on this computer with the backup?",
+        "backup.restored" => "Backup restored ({} entries). Restart Screen Time Manager to apply all settings.",
+        "backup.created" => "Backup saved and encrypted.",
+        "backup.created_plain" => "Backup saved. Without a password it is not encrypted, and the passcode, saved passwords and the Telegram bot token were left out.",
+        "backup.failed" => "The backup could not be completed: {}",
+        "backup.file_type" => "Screen Time backup (*.stmbackup)",
+        "backup.all_files" => "All files",
+        "email.subject" => "Screen time report ({})",
+        "settings.setup_wizard" => "Setup Wizard...",
+        "settings.shutdown_timeout" => "Shutdown timeout:",
+        "settings.shutdown_timeout_hint" => "min (0 = off)",
+        "settings.expiry_delay" => "Warning before:",
+        "settings.expiry_delay_hint" => "sec (0 = at once)",
+        "settings.block_text_size" => "Lock screen size:",
+        "settings.block_size.normal" => "Normal",
+        "settings.block_size.large" => "Large",
+        "settings.block_size.larger" => "Larger",
+        "settings.block_size.largest" => "Largest",
+        "settings.enforcement" => "When time runs out:",
+        "settings.enforcement.block" => "Block the screen",
+        "settings.enforcement.lock" => "Block and lock Windows",
+        "settings.enforcement.shutdown" => "Block and shut down",
+        "settings.enforcement.warn_only" => "Only show warnings",
+        "settings.auto_pause_idle" => "Auto-pause when idle",
+        "settings.idle_timeout" => "Idle timeout (min):",
+        "settings.display_mode" => "Countdown display:",
+        "settings.display_mode.mini" => "Corner timer",
+        "settings.display_mode.large" => "Large countdown",
+        "settings.display_mode.hidden" => "Hidden",
+        "settings.display_corner" => "Countdown position:",
+        "settings.display_corner.top_right" => "Top right",
+        "settings.display_corner.top_left" => "Top left",
+        "settings.display_corner.bottom_right" => "Bottom right",
+        "settings.display_corner.bottom_left" => "Bottom left",
+        "settings.mini_overlay_size" => "Corner timer size:",
+        "settings.mini_size.small" => "Small",
+        "settings.mini_size.medium" => "Medium",
+        "settings.mini_size.large" => "Large",
+        "settings.mini_overlay_format" => "Corner timer shows:",
+        "settings.mini_format.compact" => "1:30:45",
+        "settings.mini_format.hhmm" => "1:31 (hours:minutes)",
+        "settings.mini_format.minutes" => "91m (minutes)",
+        "settings.mini_format.fraction" => "91/120 (of today's limit)",
+        "settings.block_buttons" => "Lock screen buttons:",
+        "settings.block_allow_extend" => "Extend",
+        "settings.block_allow_shutdown" => "Shut down",
+        "settings.block_allow_lock" => "Lock",
+        "settings.block_allow_logoff" => "Sign out",
+        "settings.high_contrast" => "High-contrast overlays (black/white, larger text)",
+        "settings.tray_click" => "Tray icon click:",
+        "settings.status_hotkey" => "Status shortcut:",
+        "settings.status_hotkey_hint" => "empty = off",
+        "settings.error.hotkey" => "Please enter a shortcut like Ctrl+Alt+T (Ctrl, Alt, Shift or Win plus a letter, digit or F1-F24), or leave it empty",
+        "hotkey.conflict_title" => "Shortcut not available",
+        "hotkey.conflict" => "{} is already used by another program. Choose a different status shortcut in Settings.",
+        "settings.tray_click.balloon" => "Show status",
+        "settings.tray_click.stats" => "Open today's stats",
+        "settings.pause_indicator" => "Timer while paused:",
+        "settings.pause_indicator.bars" => "Pause sign",
+        "settings.pause_indicator.text" => "II (text)",
+        "settings.pause_indicator.none" => "Color only",
+        "settings.log_level" => "Log file detail:",
+        "settings.log_level.error" => "Errors only",
+        "settings.log_level.warn" => "Warnings",
+        "settings.log_level.info" => "Normal",
+        "settings.log_level.debug" => "Detailed",
+
+        // ----- Settings Dialog - Buttons -----
+        "button.save" => "Save",
+        "button.cancel" => "Cancel",
+        "button.ok" => "OK",
+        "button.close" => "Close",
+        "button.reset_timer" => "Reset Timer",
+        "button.undo_reset" => "Undo Reset",
+
+        // ----- Settings Dialog - Messages -----
+        "settings.error.current_incorrect" => "Current passcode is incorrect!",
+        "settings.error.passcode_length" => "New passcode must be exactly 4 digits!",
+        "settings.error.passcode_mismatch" => "New passcode and confirmation do not match!",
+        "settings.error.save_failed" => "Settings could not be saved. Nothing was changed.",
+        "settings.success.saved" => "Settings saved successfully!",
+        "settings.error" => "Error",
+        "settings.success" => "Settings",
+
+        // ----- Passcode Dialog -----
+        "passcode.subtitle" => "Enter 4-digit code to continue",
+        "passcode.show" => "Show",
+        "passcode.incorrect" => "Incorrect passcode",
+
+        // ----- Stats Dialog -----
+        "stats.title" => "Today's Statistics",
+        "stats.title_week" => "This Week's Statistics",
+        "stats.title_month" => "This Month's Statistics",
+        "stats.range_today" => "Today",
+        "stats.range_week" => "This Week",
+        "stats.range_month" => "This Month",
+        "stats.period" => "Period:",
+        "stats.days_used" => "Days Used:",
+        "stats.total_time" => "Total Time:",
+        "stats.average" => "Average per Day:",
+        "stats.days_limit_reached" => "Time Ran Out:",
+        "stats.days_limit_reached_value" => "{} of {} days",
+        "stats.day" => "Day:",
+        "stats.daily_limit" => "Daily Limit:",
+        "stats.time_used" => "Time Used:",
+        "stats.time_remaining" => "Time Remaining:",
+        "stats.next_reduction" => "Next Reduction:",
+        "stats.percent_used" => "Of Limit:",
+        "stats.over_by" => "over by {}",
+        "stats.no_screen_time" => "No screen time today",
+        "stats.extensions" => "Extensions:",
+        "stats.extensions_none" => "None",
+        "extend.source.overlay" => "Lock screen",
+        "extend.source.tray" => "Tray",
+        "extend.source.telegram" => "Telegram",
+        "extend.source.mqtt" => "MQTT",
+        "extend.source.self" => "Self-service",
+        "extend.source.cli" => "Command line",
+        "stats.idle" => "Idle:",
+        "stats.idle_value" => "{} min ({} min counted)",
+        "stats.rampdown_off" => "Off",
+        "stats.rampdown_floor_reached" => "Minimum reached",
+        "stats.pause_mode" => "Pause Mode",
+        "stats.pause_used" => "Pause Used:",
+        "stats.pause_remaining" => "Pause Remaining:",
+        "stats.pauses_today" => "Pauses Today:",
+        "stats.log" => "Log:",
+        "stats.pause_disabled" => "Pause feature is disabled",
+        "stats.timer_reset" => "Timer has been reset to the daily limit. You can undo this for 5 minutes.",
+        "stats.reset_confirm" => "Reset today's remaining time to the full daily limit?",
+        "stats.reset_undone" => "The reset was undone and the previous remaining time restored.",
+        "stats.timer_reset_title" => "Timer Reset",
+
+        // ----- Tray Menu -----
+        "tray.tooltip" => "Screen Time Manager",
+        "tray.tooltip.left" => "{time} left",
+        "tray.stats" => "Today's Stats...",
+        "tray.settings" => "Settings...",
+        "tray.extend_15" => "Extend +15 min",
+        "tray.extend_45" => "Extend +45 min",
+        "tray.extend_custom" => "Extend by...",
+        "tray.resume" => "Resume Timer",
+        "tray.pause_idle" => "Pause (Idle paused)",
+        "tray.pause_disabled" => "Pause (Disabled)",
+        "tray.pause_budget_used" => "Pause (Budget used)",
+        "tray.pause_time_low" => "Pause (Time too low)",
+        "tray.idle_paused" => "Idle: Paused",
+        "tray.focus" => "Focus Mode",
+        "tray.focus_30" => "30 minutes",
+        "tray.focus_60" => "1 hour",
+        "tray.focus_120" => "2 hours",
+        "tray.focus_until_off" => "Until turned off",
+        "tray.focus_end" => "End Focus Mode",
+        "tray.show_mini_overlay" => "Show Timer in Corner",
+        "tray.show_warning" => "Show Warning (5s)",
+        "tray.show_blocking" => "Show Blocking Overlay",
+        "tray.preview_blocking" => "Preview Blocking Screen",
+        "tray.simulation" => "Simulate Countdown ({}x Speed)",
+        "tray.simulation_end" => "End Simulation",
+        "tray.lock" => "Lock Workstation",
+        "tray.sign_out" => "Sign Out",
+        "tray.hibernate" => "Hibernate",
+        "tray.uninstall" => "Uninstall...",
+        "tray.kiosk_remaining" => "{} left today",
+        "tray.show_status" => "Show Status",
+        "tray.hibernate_unavailable" => "Hibernate (not allowed for this account)",
+        "tray.restart_admin" => "Restart as Administrator",
+        "tray.about" => "About",
+        "tray.open_log_folder" => "Open Log Folder",
+        "tray.quit" => "Quit",
+
+        // ----- Blocking Screen -----
+        "blocking.times_up" => "Time's Up!",
+        "blocking.limit_reached" => "Screen time limit reached",
+        "blocking.no_screen_time" => "No screen time today",
+        "blocking.extend_label" => "Extend time (requires passcode):",
+        "blocking.passcode_label" => "Enter passcode to unlock:",
+        "blocking.incorrect" => "Incorrect passcode!",
+        "blocking.locked_out" => "Too many wrong attempts - try again in {}s",
+        "blocking.unlock_wait" => "Unlock available in {}s",
+        "security.clock_blocked" => "The clock was changed. Ask a parent to unlock.",
+        "settings.clock_change_block" => "Block the screen when the clock is changed",
+        "settings.clock_guard" => "Keep counting by the real time when the clock is changed",
+        "settings.pause_requires_passcode" => "Only a parent can pause (asks for the passcode)",
+        "settings.kiosk_mode" => "Kiosk mode: tray shows only the status (manage via Telegram)",
+        "blocking.shutdown_in" => "Shutting down in",
+        "blocking.shutdown_now" => "SHUTTING DOWN IN",
+        "blocking.lock_in" => "LOCKING IN",
+        "expiry.shutdown_message" => "Screen time is up for today. The computer is shutting down.",
+        "blocking.time_exceeded" => "Time limit exceeded",
+        "blocking.preview_hint" => "Preview - press Esc to close",
+        "blocking.extend_15" => "+15 min",
+        "blocking.extend_30" => "+30 min",
+        "blocking.extend_60" => "+60 min",
+        "blocking.extend_custom" => "Other...",
+        "blocking.self_extend" => "Give myself +{} min ({} left today)",
+        "blocking.unlock" => "Unlock",
+        "blocking.shutdown" => "Shut Down",
+        "blocking.shutdown_unavailable" => "Shutdown not allowed",
+        "blocking.lock" => "Lock",
+        "blocking.logoff" => "Sign Out",
+        "blocking.confirm_shutdown" => "Are you sure you want to shut down the computer?",
+        "blocking.confirm_title" => "Confirm Shutdown",
+        "blocking.screen_locked" => "Screen Locked",
+
+        // ----- About Dialog -----
+        "about.text" => "Screen Time Manager v{version}\nBuild {commit} ({date})\n\nA parental control application for managing screen time.\n\n(c) Simon Pamies\n{url}",
+        "uninstall.title" => "Uninstall",
+        "uninstall.confirm" => "Remove Screen Time Manager from this computer?\n\nThis deletes all settings and the usage history and removes the start at sign-in. The passcode is needed.",
+        "uninstall.backup_prompt" => "Save a backup of the settings and history first?\n\nThe backup isn't encrypted, so saved passwords and the bot token are left out.",
+        "uninstall.done" => "Screen Time Manager was removed. Delete the program file to finish.",
+        "uninstall.failed" => "Uninstalling did not finish: {}",
+        "uninstall.quit_first" => "Screen Time Manager is running. Quit it from the tray menu first, then run the uninstall again.",
+        "elevation.status_admin" => "Running with administrator rights.",
+        "elevation.status_user" => "Running without administrator rights.",
+        "about.open_repo" => "Open the project page in your browser?",
+
+        // ----- Pause Reasons -----
+        "pause.disabled" => "Pause feature is disabled",
+        "pause.budget_exhausted" => "Daily pause budget exhausted",
+        "pause.cooldown" => "Cooldown active",
+        "pause.min_active" => "Need more active time since the last pause",
+        "pause.time_too_low" => "Time is too low to pause",
+
+        // ----- Telegram Bot - Command Descriptions -----
+        "tg.cmd.start" => "Start the bot",
+        "tg.cmd.status" => "Show remaining time and status",
+        "tg.cmd.time" => "Quick time check",
+        "tg.cmd.extend" => "Extend time by minutes (e.g., /extend 30)",
+        "tg.cmd.reduce" => "Reduce time by minutes (e.g., /reduce 30)",
+        "tg.cmd.pause" => "Pause the timer",
+        "tg.cmd.resume" => "Resume the timer",
+        "tg.cmd.focus" => "Focus mode, time doesn't count (e.g., /focus 60, /focus, /focus off)",
+        "tg.cmd.history" => "Show today's pauses, extensions and blocks",
+        "tg.cmd.msg" => "Show a message on screen (e.g., /msg Do your homework!)",
+        "tg.cmd.lock" => "Lock the screen",
+        "tg.cmd.stop" => "Lock the screen (alias)",
+        "tg.cmd.reset" => "Reset timer to daily limit",
+        "tg.cmd.unlockcode" => "Get a one-time code to unlock the screen on the PC",
+        "tg.cmd.e30" => "Extend by 30 minutes",
+        "tg.cmd.e60" => "Extend by 60 minutes",
+        "tg.cmd.e120" => "Extend by 120 minutes",
+        "tg.cmd.chatid" => "Get your chat ID for setup",
+        "tg.cmd.help" => "Show this help message",
+
+        // ----- Telegram Bot - Responses -----
+        "tg.status.header" => "Screen Time Status",
+        "tg.status.remaining" => "Remaining:",
+        "tg.status.paused" => "Paused:",
+        "tg.status.pause_budget" => "Pause budget:",
+        "tg.status.yes" => "Yes",
+        "tg.status.no" => "No",
+        "tg.status.idle" => "Yes (idle)",
+        "tg.status.used" => "Used {used} / {limit} min",
+
+        // ----- Database Errors -----
+        "db.unavailable_title" => "Database unavailable",
+        "db.unavailable" => "Screen Time Manager cannot access its database. Settings may fall back to defaults.",
+        "db.recovered_title" => "Database Recovered",
+        "db.recovered" => "The database was damaged and has been reset to default settings. The damaged file was kept here:",
+
+        // ----- Time Extension Errors -----
+        "extend.error.title" => "Extension Not Allowed",
+        "extend.custom_title" => "Extend Time",
+        "extend.custom_prompt" => "Minutes to add, or a time like 20:00",
+        "extend.custom_limit" => "Minutes to add (up to {}), or a time like 20:00",
+        "extend.error.not_positive" => "Please specify a positive number of minutes",
+        "extend.error.max" => "Maximum extension is {} minutes",
+        "extend.error.daily_cap" => "Daily extension limit reached ({} minutes left today)",
+        "extend.error.count" => "Extension limit reached for today",
+        "extend.error.no_screen_time" => "Today is a day without screen time. Unlock the blocking screen with the passcode (or use /grant in Telegram) to allow time anyway",
+        "extend.error.time_in_past" => "That time has already passed today (add \"tomorrow\", e.g. 0:30 tomorrow)",
+        "extend.error.already_until" => "The remaining time already lasts past that time",
+        "extend.error.simulation" => "A simulation is running. Unlock or end it from the tray menu to get back to the real time",
+
+        "tg.extend.success" => "Extended by {} minutes\nNew remaining:",
+        "tg.until.success" => "Screen time until {}",
+        "tg.until.usage" => "Please give a time of day, e.g. /until 20:00 or /until 0:30 tomorrow",
+
+        "tg.reduce.specify_positive" => "Please specify a positive number of minutes",
+        "tg.reduce.max_120" => "Maximum reduction is 120 minutes",
+        "tg.reduce.not_enough" => "Cannot reduce - not enough time remaining",
+        "tg.reduce.success" => "Reduced by {} minutes\nNew remaining:",
+
+        "tg.pause.already_paused" => "Timer is already paused. Use /resume to continue.",
+        "tg.pause.idle_paused" => "Timer is already paused (idle). It will resume automatically when input is detected.",
+        "tg.pause.success" => "Timer paused",
+        "tg.pause.failed" => "Timer was not paused (unexpected state)",
+        "tg.pause.cannot" => "Cannot pause:",
+
+        "tg.resume.idle_auto" => "Timer is idle-paused. It will resume automatically when input is detected.",
+        "tg.resume.not_paused" => "Timer is not paused",
+        "tg.resume.success" => "Timer resumed",
+        "tg.resume.failed" => "Timer is still paused (unexpected state)",
+        "tg.resume.cannot" => "Cannot resume:",
+
+        "tg.history.header" => "Today's Activity",
+        "tg.history.uptime" => "Uptime:",
+        "tg.history.pause_used" => "Pause used:",
+        "tg.history.no_events" => "No pauses, extensions or blocks today",
+        "tg.history.used" => "Used:",
+        "tg.history.pauses" => "Pauses",
+        "tg.history.extensions" => "Extensions",
+        "tg.history.blocks" => "Blocked",
+        "tg.history.focus" => "Focus mode",
+        "tg.history.resets" => "Timer resets",
+        "tg.history.more" => "{} earlier",
+
+        "tg.msg.provide" => "Please provide a message, e.g. /msg Do your homework!",
+        "tg.msg.shown" => "Message shown:",
+        "tg.msg.blocked" => "The screen is blocked - message not shown",
+
+        "tg.reset.success" => "Timer reset to daily limit",
+        "tg.reset.remaining" => "Remaining:",
+
+        "tg.lock.success" => "Screen locked",
+        "tg.focus.started" => "Focus mode on for {} minutes - time doesn't count",
+        "tg.focus.started_open" => "Focus mode on until you turn it off (/focus off) - time doesn't count",
+        "tg.focus.ended" => "Focus mode ended - the timer is running again",
+        "tg.focus.not_active" => "Focus mode is not on",
+        "tg.kiosk.on" => "Kiosk mode on - the tray on the PC only shows the status",
+        "tg.kiosk.off" => "Kiosk mode off - Settings and the other menu items are back in the tray",
+        "tg.kiosk.usage" => "Usage: /kiosk on or /kiosk off",
+        "tg.focus.usage" => "Usage: /focus <minutes, 1-{}>, /focus (until turned off) or /focus off",
+        "tg.status.focus_left" => "Focus mode: {} min left",
+        "tg.status.focus_open" => "Focus mode: on until turned off",
+        "tg.unlockcode.success" => "One-time unlock code: {}\nType it into the lock screen instead of the passcode. Valid for {} minutes, works once.",
+        "tg.unlockcode.failed" => "Could not generate an unlock code",
+
+        "tg.error.unknown_cmd" => "Unknown command. Use /help to see available commands.",
+        "tg.error.unauthorized" => "Unauthorized. This bot is configured for a specific user.",
+        "tg.error.no_admin" => "No admin configured. Please set your chat ID in settings.",
+        "tg.error.slow_down" => "Slow down - please wait {} s before the next command.",
+        "tg.error.save" => "Could not save the change on the PC.",
+        "tg.chatid.your_id" => "Your chat ID is:",
+
+        "tg.notify.started" => "Screen Time Manager started",
+        "tg.notify.shutdown" => "Screen Time Manager is shutting down",
+        "tg.notify.limit_reached" => "Screen time is used up - the screen is now blocked",
+        "tg.notify.limit_warn_only" => "Screen time is used up - showing warnings (warn-only mode, not blocked)",
+        "tg.notify.warning" => "{} minutes of screen time left",
+        "tg.notify.curfew" => "Outside allowed hours - the screen is now blocked",
+        "tg.notify.extended.overlay" => "{} minutes added on the lock screen",
+        "tg.notify.extended.tray" => "{} minutes added from the tray menu",
+        "tg.notify.extended.telegram" => "{} minutes added via Telegram",
+        "tg.notify.extended.mqtt" => "{} minutes added via MQTT",
+        "tg.notify.extended.self" => "{} minutes taken with the self-service button on the lock screen",
+        "tg.notify.extended.cli" => "{} minutes added from the command line",
+        "tg.notify.passcode_lockout" => "{} wrong passcodes in a row - passcode entry locked for {} seconds",
+        "tg.notify.clock_back" => "The computer's clock was set back by {} minutes",
+        "tg.notify.clock_forward" => "The computer's clock was set forward by {} minutes",
+        "tg.notify.database_damaged" => "The screen time database failed its integrity check - settings or history may have been tampered with",
+
+        // ----- Telegram Setup Wizard -----
+        "setup.title" => "Screen Time Manager Setup",
+        "setup.step" => "Step {} of {}",
+        "setup.passcode.title" => "Choose a passcode",
+        "setup.passcode.desc" => "The passcode unlocks the screen, adds time and protects the settings. Until you change it, it is 0000.",
+        "setup.passcode.new" => "New passcode (4 digits):",
+        "setup.passcode.confirm" => "Confirm passcode:",
+        "setup.passcode.keep_default" => "The passcode stays 0000, which anyone can guess. Skip anyway?",
+        "setup.limits.title" => "Daily screen time",
+        "setup.limits.desc" => "How many minutes a day? You can set each day separately later in Settings.",
+        "setup.limits.weekdays" => "Monday-Friday (min):",
+        "setup.limits.weekends" => "Saturday-Sunday (min):",
+        "setup.limits.invalid" => "Enter the minutes as a number from 0 to 1440.",
+        "setup.telegram.title" => "Telegram (optional)",
+        "setup.telegram.desc" => "Get notifications and add time from your phone with a Telegram bot. You can also do this later in Settings.",
+        "setup.telegram.button" => "Set up Telegram...",
+        "setup.autostart.title" => "Start automatically",
+        "setup.autostart.desc" => "Start Screen Time Manager every time you sign in to Windows, so the limits always apply.",
+        "setup.autostart.check" => "Start when I sign in to Windows",
+        "setup.autostart.failed" => "Screen Time Manager could not be added to the programs started at sign-in.",
+        "wizard.title" => "Telegram Setup Wizard",
+        "wizard.step" => "Step",
+        "wizard.of" => "of",
+        "wizard.next" => "Next",
+        "wizard.back" => "Back",
+        "wizard.finish" => "Finish",
+        "wizard.cancel" => "Cancel",
+        "wizard.skip" => "Skip",
+
+        // Step 1: Welcome
+        "wizard.welcome.title" => "Remote Control via Telegram",
+        "wizard.welcome.desc1" => "Control Screen Time Manager from your phone!",
+        "wizard.welcome.desc2" => "With Telegram you can:",
+        "wizard.welcome.feature1" => "Check remaining time",
+        "wizard.welcome.feature2" => "Extend or reduce time remotely",
+        "wizard.welcome.feature3" => "Lock the screen instantly",
+        "wizard.welcome.feature4" => "Receive notifications",
+        "wizard.welcome.ready" => "Let's set it up in 3 easy steps!",
+
+        // Step 2: Create Bot
+        "wizard.bot.title" => "Create Your Bot",
+        "wizard.bot.step1" => "1. Open Telegram on your phone",
+        "wizard.bot.step2" => "2. Search for  @BotFather",
+        "wizard.bot.step3" => "3. Send the message:  /newbot",
+        "wizard.bot.step4" => "4. Choose a name (e.g. \"My Screen Time\")",
+        "wizard.bot.step5" => "5. Choose a username ending in 'bot'",
+        "wizard.bot.step6" => "6. BotFather will give you a token - copy it!",
+        "wizard.bot.hint" => "The token looks like: 123456789:ABCdef...",
+
+        // Step 3: Enter Token
+        "wizard.token.title" => "Enter Your Bot Token",
+        "wizard.token.label" => "Paste the token from BotFather:",
+        "wizard.token.placeholder" => "123456789:ABCdefGHI...",
+        "wizard.token.invalid" => "This doesn't look like a valid token",
+        "wizard.token.valid" => "Token looks good!",
+
+        // Step 4: Connect
+        "wizard.connect.title" => "Connect to Your Bot",
+        "wizard.connect.step1" => "1. Open Telegram",
+        "wizard.connect.step2" => "2. Search for your new bot",
+        "wizard.connect.step3" => "3. Press START or send any message",
+        "wizard.connect.waiting" => "Waiting for your message...",
+        "wizard.connect.detected" => "Connection detected!",
+        "wizard.connect.chatid" => "Your Chat ID:",
+
+        // Step 5: Success
+        "wizard.success.title" => "Setup Complete!",
+        "wizard.success.desc" => "Your Telegram bot is ready to use.",
+        "wizard.success.test" => "A test message was sent to your phone.",
+        "wizard.success.commands" => "Try these commands in Telegram:",
+        "wizard.success.cmd1" => "/status - Check remaining time",
+        "wizard.success.cmd2" => "/extend 30 - Add 30 minutes",
+        "wizard.success.cmd3" => "/lock - Lock the screen",
+        "wizard.success.cmd4" => "/help - See all commands",
+
+        // Fallback - return empty string for unknown keys (should not happen in practice)
+        _ => "",
+    }
+}
+
+// ============================================================================
+// German strings
+// ============================================================================
+
+fn de(key: &str) -> &'static str {
+    match key {
+        // ----- Window Titles -----
+        "window.settings" => "Bildschirmzeit Einstellungen",
+        "settings.default_passcode_title" => " - Code noch nicht geändert!",
+        "passcode.default_title" => "Code ändern",
+        "passcode.default_warning" => "Der Code ist noch der voreingestellte, damit kann jeder den Bildschirm entsperren. Öffnen Sie die Einstellungen und legen Sie unter \"Code ändern\" einen eigenen fest.",
+        "window.passcode" => "Code eingeben",
+        "window.stats" => "Heutige Statistik",
+        "window.blocking" => "Bildschirmzeit - Zeit abgelaufen!",
+        "window.about" => "Info",
+
+        // ----- Settings Dialog - Section Titles -----
+        "settings.daily_limits" => "Tägliche Zeitlimits (Std:Min)",
+        "settings.rampdown_step" => "Wöchentl. -Min:",
+        "settings.fill_all_days" => "Mo → alle Tage",
+        "settings.fill_weekdays" => "Mo → Di-Fr",
+        "settings.fill_weekend" => "Sa → So",
+        "settings.rampdown_floor" => "Bis min.:",
+        "settings.defer_warnings_fullscreen" => "Bei Vollbild-Spielen/-Videos leise benachrichtigen statt Banner",
+        "settings.respect_focus_assist" => "Bei aktivem Benachrichtigungsassistenten nur still benachrichtigen",
+        "settings.warning_position" => "Banner-Position:",
+        "settings.warning_position.top" => "Oben",
+        "settings.warning_position.center" => "Mitte",
+        "settings.warning_position.bottom" => "Unten",
+        "settings.warning_margin" => "Abstand (px):",
+        "settings.winddown" => "Ausklingen (Min.):",
+        "settings.winddown_interval" => "Erinnern alle (Min.):",
+        "settings.lockout_attempts" => "Sperre nach (falsch):",
+        "settings.lockout_delay" => "Sperrdauer (Sek.):",
+        "settings.min_block_duration" => "Min. Sperre (Sek.):",
+        "settings.max_extensions" => "Verläng./Tag:",
+        "settings.max_extensions_hint" => "0 = unbegrenzt",
+        "settings.self_extend" => "Selbst nehmen:",
+        "settings.self_extend_hint" => "Min., mal/Tag (0 = aus)",
+        "winddown.warning" => "Nur noch {} Minute(n) - Zeit zum Abschließen.",
+        "overlay.warning_title" => "Bildschirmzeit",
+        "overlay.live_countdown" => "Noch {}",
+        "settings.allowed_hours" => "Erlaubte Zeiten (HH:MM-HH:MM, leer = jederzeit)",
+        "settings.allowed_hours_weekdays" => "Mo - Fr:",
+        "settings.allowed_hours_weekends" => "Sa - So:",
+        "settings.productive_apps" => "Nicht zählen in:",
+        "settings.counted_users" => "Nur zählen für:",
+        "settings.error.allowed_hours" => "Erlaubte Zeiten müssen wie 07:00-20:00 aussehen (oder leer bleiben).",
+        "settings.error.kiosk_needs_telegram" => "Der Kiosk-Modus blendet die Einstellungen im Tray aus. Bitte zuerst Telegram mit deiner Chat-ID einrichten; /kiosk off schaltet ihn wieder aus.",
+        "settings.error.daily_limit" => "Minuten oder Stunden wie 2:30 oder 2h 30m eingeben (höchstens 24:00).",
+        "curfew.message" => "Bildschirmzeit ist gerade nicht erlaubt. Erlaubte Zeiten heute: {}",
+        "overtime.warning" => "Deine Bildschirmzeit für heute ist aufgebraucht. Bitte komm zum Ende.",
+        "settings.warning1" => "Erste Warnung",
+        "settings.warning2" => "Zweite Warnung",
+        "settings.blocking_message" => "Sperrbildschirm-Nachricht",
+        "settings.block_background_image" => "Hintergrundbild:",
+        "settings.secondary_block_message" => "Andere Bildschirme:",
+        "settings.passcode" => "Code ändern (leer lassen zum Behalten)",
+        "settings.telegram" => "Telegram Bot",
+        "tg.state.off" => "Bot: läuft nicht",
+        "tg.state.connecting" => "Bot: verbindet...",
+        "tg.state.connected" => "Bot: verbunden als @{}",
+        "tg.state.invalid_token" => "Bot: Verbindung fehlgeschlagen - Telegram hat den Bot-Token abgelehnt",
+        "tg.state.unreachable" => "Bot: Telegram nicht erreichbar, neuer Versuch ({})",
+        "tg.invalid_token_title" => "Telegram-Bot funktioniert nicht",
+        "settings.lock_screen" => "Bildschirmsperre",
+        "settings.idle" => "Leerlauferkennung",
+        "settings.language" => "Sprache",
+
+        // ----- Settings Dialog - Labels -----
+        "settings.minutes_before" => "Minuten vorher:",
+        "settings.show_for" => "Anzeigen (Sek.):",
+        "settings.warning_weekend" => "Wochenende:",
+        "settings.message" => "Nachricht:",
+        "settings.current" => "Aktuell:",
+        "settings.new" => "Neu:",
+        "settings.confirm" => "Bestätigen:",
+        "settings.enable_telegram" => "Telegram Bot aktivieren",
+        "settings.bot_token" => "Bot Token:",
+        "settings.chat_id" => "Chat ID:",
+        "settings.webhook_url" => "Webhook:",
+        "settings.email" => "Täglicher E-Mail-Bericht",
+        "settings.email_enabled" => "Täglichen Bericht senden um:",
+        "settings.smtp_host" => "SMTP-Server:",
+        "settings.smtp_port" => "Port:",
+        "settings.smtp_username" => "Benutzer:",
+        "settings.smtp_password" => "Passwort:",
+        "settings.smtp_password_hint" => "Vertraulich - wird für diesen Windows-Benutzer verschlüsselt",
+        "settings.smtp_password_saved" => "Vertraulich - verschlüsselt gespeichert; leer lassen zum Behalten",
+        "settings.email_to" => "Senden an:",
+        "settings.mqtt" => "MQTT (Home Assistant)",
+        "settings.mqtt_enabled" => "MQTT aktivieren",
+        "settings.mqtt_prefix" => "Topic:",
+        "settings.mqtt_host" => "Broker:",
+        "settings.mqtt_port" => "Port:",
+        "settings.mqtt_username" => "Benutzer:",
+        "settings.mqtt_password" => "Passwort:",
+        "settings.mqtt_password_hint" => "Vertraulich - wird für diesen Windows-Benutzer verschlüsselt",
+        "settings.mqtt_password_saved" => "Vertraulich - verschlüsselt gespeichert; leer lassen zum Behalten",
+        "settings.http" => "Metriken (Prometheus)",
+        "settings.http_enabled" => "/metrics bereitstellen auf",
+        "settings.http_token" => "Token:",
+        "settings.http_token_hint" => "Erforderlich - als \"Authorization: Bearer <Token>\" senden",
+        "settings.http_token_saved" => "Verschlüsselt gespeichert; leer lassen zum Behalten",
+        "settings.backup" => "Sicherung & Wiederherstellung",
+        "settings.backup_password" => "Passwort:",
+        "settings.backup_hint" => "Optional - verschlüsselt die Sicherung; nötig, um Passwörter mitzusichern",
+        "settings.backup_create" => "Sichern...",
+        "settings.backup_restore" => "Wiederherstellen...",
+        "backup.confirm_restore" => "Alle Einstellungen und den Verlauf auf diesem Computer durch die Sicherung ersetzen?",
+        "backup.restored" => "Sicherung wiederhergestellt ({} Einträge). Starte Screen Time Manager neu, damit alle Einstellungen gelten.",
+        "backup.created" => "Sicherung gespeichert und verschlüsselt.",
+        "backup.created_plain" => "Sicherung gespeichert. Ohne Passwort ist sie nicht verschlüsselt; Passcode, gespeicherte Passwörter und der Telegram-Bot-Token wurden weggelassen.",
+        "backup.failed" => "Die Sicherung konnte nicht abgeschlossen werden: {}",
+        "backup.file_type" => "Screen-Time-Sicherung (*.stmbackup)",
+        "backup.all_files" => "Alle Dateien",
+        "email.subject" => "Bildschirmzeit-Bericht ({})",
+        "settings.setup_wizard" => "Einrichtungsassistent...",
+        "settings.shutdown_timeout" => "Abschaltzeit:",
+        "settings.shutdown_timeout_hint" => "Min. (0 = aus)",
+        "settings.expiry_delay" => "Vorwarnung:",
+        "settings.expiry_delay_hint" => "Sek. (0 = sofort)",
+        "settings.block_text_size" => "Größe der Sperre:",
+        "settings.block_size.normal" => "Normal",
+        "settings.block_size.large" => "Groß",
+        "settings.block_size.larger" => "Größer",
+        "settings.block_size.largest" => "Am größten",
+        "settings.enforcement" => "Wenn die Zeit abläuft:",
+        "settings.enforcement.block" => "Bildschirm sperren",
+        "settings.enforcement.lock" => "Sperren und Windows sperren",
+        "settings.enforcement.shutdown" => "Sperren und herunterfahren",
+        "settings.enforcement.warn_only" => "Nur Warnungen anzeigen",
+        "settings.auto_pause_idle" => "Auto-Pause bei Leerlauf",
+        "settings.idle_timeout" => "Leerlaufzeit (Min):",
+        "settings.display_mode" => "Countdown-Anzeige:",
+        "settings.display_mode.mini" => "Ecken-Uhr",
+        "settings.display_mode.large" => "Großer Countdown",
+        "settings.display_mode.hidden" => "Ausgeblendet",
+        "settings.display_corner" => "Countdown-Position:",
+        "settings.display_corner.top_right" => "Oben rechts",
+        "settings.display_corner.top_left" => "Oben links",
+        "settings.display_corner.bottom_right" => "Unten rechts",
+        "settings.display_corner.bottom_left" => "Unten links",
+        "settings.mini_overlay_size" => "Größe der Ecken-Uhr:",
+        "settings.mini_size.small" => "Klein",
+        "settings.mini_size.medium" => "Mittel",
+        "settings.mini_size.large" => "Groß",
+        "settings.mini_overlay_format" => "Ecken-Uhr zeigt:",
+        "settings.mini_format.compact" => "1:30:45",
+        "settings.mini_format.hhmm" => "1:31 (Stunden:Minuten)",
+        "settings.mini_format.minutes" => "91m (Minuten)",
+        "settings.mini_format.fraction" => "91/120 (vom Tageslimit)",
+        "settings.block_buttons" => "Sperrbildschirm-Tasten:",
+        "settings.block_allow_extend" => "Verlängern",
+        "settings.block_allow_shutdown" => "Herunterfahren",
+        "settings.block_allow_lock" => "Sperren",
+        "settings.block_allow_logoff" => "Abmelden",
+        "settings.high_contrast" => "Kontrastreiche Overlays (schwarz/weiß, größere Schrift)",
+        "settings.tray_click" => "Klick auf Tray-Symbol:",
+        "settings.status_hotkey" => "Status-Tastenkürzel:",
+        "settings.status_hotkey_hint" => "leer = aus",
+        "settings.error.hotkey" => "Bitte ein Tastenkürzel wie Strg+Alt+T eingeben (Ctrl, Alt, Shift oder Win plus Buchstabe, Ziffer oder F1-F24) oder das Feld leer lassen",
+        "hotkey.conflict_title" => "Tastenkürzel nicht verfügbar",
+        "hotkey.conflict" => "{} wird bereits von einem anderen Programm verwendet. Wählen Sie in den Einstellungen ein anderes Status-Tastenkürzel.",
+        "settings.tray_click.balloon" => "Status anzeigen",
+        "settings.tray_click.stats" => "Heutige Statistik öffnen",
+        "settings.pause_indicator" => "Timer in der Pause:",
+        "settings.pause_indicator.bars" => "Pausenzeichen",
+        "settings.pause_indicator.text" => "II (Text)",
+        "settings.pause_indicator.none" => "Nur Farbe",
+        "settings.log_level" => "Protokoll-Detail:",
+        "settings.log_level.error" => "Nur Fehler",
+        "settings.log_level.warn" => "Warnungen",
+        "settings.log_level.info" => "Normal",
+        "settings.log_level.debug" => "Ausführlich",
+
+        // ----- Settings Dialog - Buttons -----
+        "button.save" => "Speichern",
+        "button.cancel" => "Abbrechen",
+        "button.ok" => "OK",
+        "button.close" => "Schließen",
+        "button.reset_timer" => "Timer zurücksetzen",
+        "button.undo_reset" => "Rückgängig",
+
+        // ----- Settings Dialog - Messages -----
+        "settings.error.current_incorrect" => "Aktueller Code ist falsch!",
+        "settings.error.passcode_length" => "Neuer Code muss genau 4 Ziffern haben!",
+        "settings.error.passcode_mismatch" => "Neuer Code und Bestätigung stimmen nicht überein!",
+        "settings.error.save_failed" => "Einstellungen konnten nicht gespeichert werden. Es wurde nichts geändert.",
+        "settings.success.saved" => "Einstellungen erfolgreich gespeichert!",
+        "settings.error" => "Fehler",
+        "settings.success" => "Einstellungen",
+
+        // ----- Passcode Dialog -----
+        "passcode.subtitle" => "4-stelligen Code eingeben",
+        "passcode.show" => "Zeigen",
+        "passcode.incorrect" => "Falscher Code",
+
+        // ----- Stats Dialog -----
+        "stats.title" => "Heutige Statistik",
+        "stats.title_week" => "Statistik dieser Woche",
+        "stats.title_month" => "Statistik dieses Monats",
+        "stats.range_today" => "Heute",
+        "stats.range_week" => "Diese Woche",
+        "stats.range_month" => "Dieser Monat",
+        "stats.period" => "Zeitraum:",
+        "stats.days_used" => "Genutzte Tage:",
+        "stats.total_time" => "Gesamtzeit:",
+        "stats.average" => "Schnitt pro Tag:",
+        "stats.days_limit_reached" => "Zeit aufgebraucht:",
+        "stats.days_limit_reached_value" => "an {} von {} Tagen",
+        "stats.day" => "Tag:",
+        "stats.daily_limit" => "Tageslimit:",
+        "stats.time_used" => "Zeit genutzt:",
+        "stats.time_remaining" => "Zeit verbleibend:",
+        "stats.next_reduction" => "Nächste Senkung:",
+        "stats.percent_used" => "Vom Limit:",
+        "stats.over_by" => "{} drüber",
+        "stats.no_screen_time" => "Heute keine Bildschirmzeit",
+        "stats.extensions" => "Verlängerungen:",
+        "stats.extensions_none" => "Keine",
+        "extend.source.overlay" => "Sperrbildschirm",
+        "extend.source.tray" => "Tray",
+        "extend.source.telegram" => "Telegram",
+        "extend.source.mqtt" => "MQTT",
+        "extend.source.self" => "Selbstbedienung",
+        "extend.source.cli" => "Kommandozeile",
+        "stats.idle" => "Inaktiv:",
+        "stats.idle_value" => "{} Min. ({} Min. gezählt)",
+        "stats.rampdown_off" => "Aus",
+        "stats.rampdown_floor_reached" => "Minimum erreicht",
+        "stats.pause_mode" => "Pause-Modus",
+        "stats.pause_used" => "Pause genutzt:",
+        "stats.pause_remaining" => "Pause verbleibend:",
+        "stats.pauses_today" => "Pausen heute:",
+        "stats.log" => "Protokoll:",
+        "stats.pause_disabled" => "Pause-Funktion ist deaktiviert",
+        "stats.timer_reset" => "Timer wurde auf das Tageslimit zurückgesetzt. Das kann 5 Minuten lang rückgängig gemacht werden.",
+        "stats.reset_confirm" => "Die verbleibende Zeit für heute auf das volle Tageslimit zurücksetzen?",
+        "stats.reset_undone" => "Das Zurücksetzen wurde rückgängig gemacht und die vorherige Restzeit wiederhergestellt.",
+        "stats.timer_reset_title" => "Timer zurückgesetzt",
+
+        // ----- Tray Menu -----
+        "tray.tooltip" => "Bildschirmzeit Manager",
+        "tray.tooltip.left" => "{time} übrig",
+        "tray.stats" => "Heutige Statistik...",
+        "tray.settings" => "Einstellungen...",
+        "tray.extend_15" => "+15 Min verlängern",
+        "tray.extend_45" => "+45 Min verlängern",
+        "tray.extend_custom" => "Verlängern um...",
+        "tray.resume" => "Timer fortsetzen",
+        "tray.pause_idle" => "Pause (Leerlauf)",
+        "tray.pause_disabled" => "Pause (Deaktiviert)",
+        "tray.pause_budget_used" => "Pause (Budget aufgebraucht)",
+        "tray.pause_time_low" => "Pause (Zeit zu niedrig)",
+        "tray.idle_paused" => "Leerlauf: Pausiert",
+        "tray.focus" => "Fokus-Modus",
+        "tray.focus_30" => "30 Minuten",
+        "tray.focus_60" => "1 Stunde",
+        "tray.focus_120" => "2 Stunden",
+        "tray.focus_until_off" => "Bis zum Ausschalten",
+        "tray.focus_end" => "Fokus-Modus beenden",
+        "tray.show_mini_overlay" => "Timer in der Ecke anzeigen",
+        "tray.show_warning" => "Warnung anzeigen (5s)",
+        "tray.show_blocking" => "Sperrbildschirm anzeigen",
+        "tray.preview_blocking" => "Sperrbildschirm-Vorschau",
+        "tray.simulation" => "Countdown simulieren ({}-fach)",
+        "tray.simulation_end" => "Simulation beenden",
+        "tray.lock" => "Computer sperren",
+        "tray.sign_out" => "Abmelden",
+        "tray.hibernate" => "Ruhezustand",
+        "tray.uninstall" => "Deinstallieren...",
+        "tray.kiosk_remaining" => "Heute noch {}",
+        "tray.show_status" => "Status anzeigen",
+        "tray.hibernate_unavailable" => "Ruhezustand (für dieses Konto nicht erlaubt)",
+        "tray.restart_admin" => "Als Administrator neu starten",
+        "tray.about" => "Info",
+        "tray.open_log_folder" => "Protokollordner öffnen",
+        "tray.quit" => "Beenden",
+
+        // ----- Blocking Screen -----
+        "blocking.times_up" => "Zeit abgelaufen!",
+        "blocking.limit_reached" => "Bildschirmzeit-Limit erreicht",
+        "blocking.no_screen_time" => "Heute keine Bildschirmzeit",
+        "blocking.extend_label" => "Zeit verlängern (Code erforderlich):",
+        "blocking.passcode_label" => "Code zum Entsperren eingeben:",
+        "blocking.incorrect" => "Falscher Code!",
+        "blocking.locked_out" => "Zu viele Fehlversuche - erneut versuchen in {}s",
+        "blocking.unlock_wait" => "Entsperren möglich in {}s",
+        "security.clock_blocked" => "Die Uhr wurde verstellt. Bitte einen Elternteil entsperren lassen.",
+        "settings.clock_change_block" => "Bildschirm sperren, wenn die Uhr verstellt wird",
+        "settings.clock_guard" => "Bei verstellter Uhr nach der echten Zeit weiterzählen",
+        "settings.pause_requires_passcode" => "Nur Eltern können pausieren (fragt nach dem Code)",
+        "settings.kiosk_mode" => "Kiosk-Modus: Tray zeigt nur den Status (Verwaltung per Telegram)",
+        "blocking.shutdown_in" => "Herunterfahren in",
+        "blocking.shutdown_now" => "HERUNTERFAHREN IN",
+        "blocking.lock_in" => "SPERRE IN",
+        "expiry.shutdown_message" => "Die Bildschirmzeit für heute ist aufgebraucht. Der Computer wird heruntergefahren.",
+        "blocking.time_exceeded" => "Zeitlimit überschritten",
+        "blocking.preview_hint" => "Vorschau - mit Esc schließen",
+        "blocking.extend_15" => "+15 Min",
+        "blocking.extend_30" => "+30 Min",
+        "blocking.extend_60" => "+60 Min",
+        "blocking.extend_custom" => "Andere...",
+        "blocking.self_extend" => "Ich nehme mir +{} Min. ({} heute übrig)",
+        "blocking.unlock" => "Entsperren",
+        "blocking.shutdown" => "Herunterfahren",
+        "blocking.shutdown_unavailable" => "Nicht erlaubt",
+        "blocking.lock" => "Sperren",
+        "blocking.logoff" => "Abmelden",
+        "blocking.confirm_shutdown" => "Möchten Sie den Computer wirklich herunterfahren?",
+        "blocking.confirm_title" => "Herunterfahren bestätigen",
+        "blocking.screen_locked" => "Bildschirm gesperrt",
+
+        // ----- About Dialog -----
+        "about.text" => "Bildschirmzeit Manager v{version}\nBuild {commit} ({date})\n\nEine Kindersicherungs-App zur Verwaltung der Bildschirmzeit.\n\n(c) Simon Pamies\n{url}",
+        "uninstall.title" => "Deinstallieren",
+        "uninstall.confirm" => "Bildschirmzeit Manager von diesem Computer entfernen?\n\nAlle Einstellungen und der Nutzungsverlauf werden gelöscht und der Start bei der Anmeldung entfernt. Der Code wird benötigt.",
+        "uninstall.backup_prompt" => "Vorher eine Sicherung der Einstellungen und des Verlaufs speichern?\n\nDie Sicherung ist nicht verschlüsselt, gespeicherte Passwörter und der Bot-Token fehlen darin.",
+        "uninstall.done" => "Bildschirmzeit Manager wurde entfernt. Lösche zum Abschluss die Programmdatei.",
+        "uninstall.failed" => "Deinstallieren wurde nicht abgeschlossen: {}",
+        "uninstall.quit_first" => "Bildschirmzeit Manager läuft noch. Bitte zuerst über das Tray-Menü beenden und die Deinstallation dann erneut starten.",
+        "elevation.status_admin" => "Läuft mit Administratorrechten.",
+        "elevation.status_user" => "Läuft ohne Administratorrechte.",
+        "about.open_repo" => "Projektseite im Browser öffnen?",
+
+        // ----- Pause Reasons -----
+        "pause.disabled" => "Pause-Funktion ist deaktiviert",
+        "pause.budget_exhausted" => "Tägliches Pause-Budget aufgebraucht",
+        "pause.cooldown" => "Abklingzeit aktiv",
+        "pause.min_active" => "Seit der letzten Pause mehr aktive Zeit erforderlich",
+        "pause.time_too_low" => "Zeit zu niedrig für Pause",
+
+        // ----- Telegram Bot - Command Descriptions -----
+        "tg.cmd.start" => "Bot starten",
+        "tg.cmd.status" => "Verbleibende Zeit und Status anzeigen",
+        "tg.cmd.time" => "Schnelle Zeitabfrage",
+        "tg.cmd.extend" => "Zeit verlängern (z.B. /extend 30)",
+        "tg.cmd.reduce" => "Zeit verringern (z.B. /reduce 30)",
+        "tg.cmd.pause" => "Timer pausieren",
+        "tg.cmd.resume" => "Timer fortsetzen",
+        "tg.cmd.focus" => "Fokus-Modus, die Zeit zählt nicht (z.B. /focus 60, /focus, /focus off)",
+        "tg.cmd.history" => "Heutige Pausen, Verlängerungen und Sperren anzeigen",
+        "tg.cmd.msg" => "Nachricht anzeigen (z.B. /msg Mach deine Hausaufgaben!)",
+        "tg.cmd.lock" => "Bildschirm sperren",
+        "tg.cmd.stop" => "Bildschirm sperren (Alias)",
+        "tg.cmd.reset" => "Timer auf Tageslimit zurücksetzen",
+        "tg.cmd.unlockcode" => "Einmal-Code zum Entsperren am PC anfordern",
+        "tg.cmd.e30" => "Um 30 Minuten verlängern",
+        "tg.cmd.e60" => "Um 60 Minuten verlängern",
+        "tg.cmd.e120" => "Um 120 Minuten verlängern",
+        "tg.cmd.chatid" => "Chat-ID für Einrichtung abrufen",
+        "tg.cmd.help" => "Diese Hilfe anzeigen",
+
+        // ----- Telegram Bot - Responses -----
+        "tg.status.header" => "Bildschirmzeit Status",
+        "tg.status.remaining" => "Verbleibend:",
+        "tg.status.paused" => "Pausiert:",
+        "tg.status.pause_budget" => "Pause-Budget:",
+        "tg.status.yes" => "Ja",
+        "tg.status.no" => "Nein",
+        "tg.status.idle" => "Ja (Leerlauf)",
+        "tg.status.used" => "Genutzt {used} / {limit} Min",
+
+        // ----- Database Errors -----
+        "db.unavailable_title" => "Datenbank nicht verfügbar",
+        "db.unavailable" => "Screen Time Manager kann nicht auf seine Datenbank zugreifen. Einstellungen fallen eventuell auf Standardwerte zurück.",
+        "db.recovered_title" => "Datenbank wiederhergestellt",
+        "db.recovered" => "Die Datenbank war beschädigt und wurde auf Standardeinstellungen zurückgesetzt. Die beschädigte Datei wurde hier aufbewahrt:",
+
+        // ----- Time Extension Errors -----
+        "extend.error.title" => "Verlängerung nicht erlaubt",
+        "extend.custom_title" => "Zeit verlängern",
+        "extend.custom_prompt" => "Minuten dazu, oder eine Uhrzeit wie 20:00",
+        "extend.custom_limit" => "Minuten dazu (bis zu {}), oder eine Uhrzeit wie 20:00",
+        "extend.error.not_positive" => "Bitte geben Sie eine positive Minutenzahl an",
+        "extend.error.max" => "Maximale Verlängerung ist {} Minuten",
+        "extend.error.daily_cap" => "Tägliches Verlängerungslimit erreicht (heute noch {} Minuten)",
+        "extend.error.count" => "Für heute sind keine Verlängerungen mehr möglich",
+        "extend.error.no_screen_time" => "Heute ist ein Tag ohne Bildschirmzeit. Entsperren Sie den Sperrbildschirm mit dem Passcode (oder nutzen Sie /grant in Telegram), um trotzdem Zeit zu geben",
+        "extend.error.time_in_past" => "Diese Uhrzeit ist heute schon vorbei (mit \"morgen\", z.B. 0:30 morgen)",
+        "extend.error.already_until" => "Die verbleibende Zeit reicht schon über diese Uhrzeit hinaus",
+        "extend.error.simulation" => "Eine Simulation läuft. Entsperren oder im Tray-Menü beenden, um zur echten Zeit zurückzukehren",
+
+        "tg.extend.success" => "Um {} Minuten verlängert\nNeu verbleibend:",
+        "tg.until.success" => "Bildschirmzeit bis {}",
+        "tg.until.usage" => "Bitte eine Uhrzeit angeben, z.B. /until 20:00 oder /until 0:30 morgen",
+
+        "tg.reduce.specify_positive" => "Bitte geben Sie eine positive Minutenzahl an",
+        "tg.reduce.max_120" => "Maximale Verringerung ist 120 Minuten",
+        "tg.reduce.not_enough" => "Kann nicht verringern - nicht genug Zeit verbleibend",
+        "tg.reduce.success" => "Um {} Minuten verringert\nNeu verbleibend:",
+
+        "tg.pause.already_paused" => "Timer ist bereits pausiert. Verwenden Sie /resume zum Fortsetzen.",
+        "tg.pause.idle_paused" => "Timer ist bereits pausiert (Leerlauf). Er wird automatisch fortgesetzt, wenn Eingabe erkannt wird.",
+        "tg.pause.success" => "Timer pausiert",
+        "tg.pause.failed" => "Timer wurde nicht pausiert (unerwarteter Zustand)",
+        "tg.pause.cannot" => "Kann nicht pausieren:",
+
+        "tg.resume.idle_auto" => "Timer ist im Leerlauf pausiert. Er wird automatisch fortgesetzt, wenn Eingabe erkannt wird.",
+        "tg.resume.not_paused" => "Timer ist nicht pausiert",
+        "tg.resume.success" => "Timer fortgesetzt",
+        "tg.resume.failed" => "Timer ist noch pausiert (unerwarteter Zustand)",
+        "tg.resume.cannot" => "Kann nicht fortsetzen:",
+
+        "tg.history.header" => "Heutige Aktivität",
+        "tg.history.uptime" => "Laufzeit:",
+        "tg.history.pause_used" => "Pause genutzt:",
+        "tg.history.no_events" => "Heute keine Pausen, Verlängerungen oder Sperren",
+        "tg.history.used" => "Genutzt:",
+        "tg.history.pauses" => "Pausen",
+        "tg.history.extensions" => "Verlängerungen",
+        "tg.history.blocks" => "Gesperrt",
+        "tg.history.focus" => "Fokus-Modus",
+        "tg.history.resets" => "Timer zurückgesetzt",
+        "tg.history.more" => "{} frühere",
+
+        "tg.msg.provide" => "Bitte geben Sie eine Nachricht an, z.B. /msg Mach deine Hausaufgaben!",
+        "tg.msg.shown" => "Nachricht angezeigt:",
+        "tg.msg.blocked" => "Der Bildschirm ist gesperrt - Nachricht nicht angezeigt",
+
+        "tg.reset.success" => "Timer auf Tageslimit zurückgesetzt",
+        "tg.reset.remaining" => "Verbleibend:",
+
+        "tg.lock.success" => "Bildschirm gesperrt",
+        "tg.focus.started" => "Fokus-Modus für {} Minuten an - die Zeit zählt nicht",
+        "tg.focus.started_open" => "Fokus-Modus an, bis du ihn ausschaltest (/focus off) - die Zeit zählt nicht",
+        "tg.focus.ended" => "Fokus-Modus beendet - der Timer läuft wieder",
+        "tg.focus.not_active" => "Fokus-Modus ist nicht an",
+        "tg.kiosk.on" => "Kiosk-Modus an - der Tray am PC zeigt nur noch den Status",
+        "tg.kiosk.off" => "Kiosk-Modus aus - Einstellungen und die anderen Menüpunkte sind wieder im Tray",
+        "tg.kiosk.usage" => "Verwendung: /kiosk on oder /kiosk off",
+        "tg.focus.usage" => "Verwendung: /focus <Minuten, 1-{}>, /focus (bis zum Ausschalten) oder /focus off",
+        "tg.status.focus_left" => "Fokus-Modus: noch {} Min",
+        "tg.status.focus_open" => "Fokus-Modus: an bis zum Ausschalten",
+        "tg.unlockcode.success" => "Einmal-Code zum Entsperren: {}\nStatt des Passcodes in die Sperre eingeben. {} Minuten gültig, nur einmal verwendbar.",
+        "tg.unlockcode.failed" => "Entsperr-Code konnte nicht erstellt werden",
+
+        "tg.error.unknown_cmd" => "Unbekannter Befehl. Verwenden Sie /help für verfügbare Befehle.",
+        "tg.error.unauthorized" => "Nicht autorisiert. Dieser Bot ist für einen bestimmten Benutzer konfiguriert.",
+        "tg.error.no_admin" => "Kein Admin konfiguriert. Bitte setzen Sie Ihre Chat-ID in den Einstellungen.",
+        "tg.error.slow_down" => "Nicht so schnell - bitte {} s bis zum nächsten Befehl warten.",
+        "tg.error.save" => "Die Änderung konnte am PC nicht gespeichert werden.",
+        "tg.chatid.your_id" => "Ihre Chat-ID ist:",
+
+        "tg.notify.started" => "Bildschirmzeit Manager gestartet",
+        "tg.notify.shutdown" => "Bildschirmzeit Manager wird heruntergefahren",
+        "tg.notify.limit_reached" => "Bildschirmzeit aufgebraucht - der Bildschirm ist jetzt gesperrt",
+        "tg.notify.limit_warn_only" => "Bildschirmzeit aufgebraucht - es werden Warnungen angezeigt (nur Warnen, nicht gesperrt)",
+        "tg.notify.warning" => "Noch {} Minuten Bildschirmzeit",
+        "tg.notify.curfew" => "Außerhalb der erlaubten Zeiten - der Bildschirm ist jetzt gesperrt",
+        "tg.notify.extended.overlay" => "{} Minuten am Sperrbildschirm hinzugefügt",
+        "tg.notify.extended.tray" => "{} Minuten über das Tray-Menü hinzugefügt",
+        "tg.notify.extended.telegram" => "{} Minuten über Telegram hinzugefügt",
+        "tg.notify.extended.mqtt" => "{} Minuten über MQTT hinzugefügt",
+        "tg.notify.extended.self" => "{} Minuten mit dem Selbstbedienungs-Knopf auf dem Sperrbildschirm genommen",
+        "tg.notify.extended.cli" => "{} Minuten über die Kommandozeile hinzugefügt",
+        "tg.notify.passcode_lockout" => "{} falsche Codes hintereinander - Code-Eingabe für {} Sekunden gesperrt",
+        "tg.notify.clock_back" => "Die Uhr des Computers wurde um {} Minuten zurückgestellt",
+        "tg.notify.clock_forward" => "Die Uhr des Computers wurde um {} Minuten vorgestellt",
+        "tg.notify.database_damaged" => "Die Bildschirmzeit-Datenbank hat die Integritätsprüfung nicht bestanden - Einstellungen oder Verlauf wurden eventuell manipuliert",
+
+        // ----- Telegram Setup Wizard -----
+        "setup.title" => "Bildschirmzeit Manager einrichten",
+        "setup.step" => "Schritt {} von {}",
+        "setup.passcode.title" => "Code festlegen",
+        "setup.passcode.desc" => "Der Code entsperrt den Bildschirm, gibt mehr Zeit und schützt die Einstellungen. Bis Sie ihn ändern, lautet er 0000.",
+        "setup.passcode.new" => "Neuer Code (4 Ziffern):",
+        "setup.passcode.confirm" => "Code bestätigen:",
+        "setup.passcode.keep_default" => "Der Code bleibt 0000 und ist damit leicht zu erraten. Trotzdem überspringen?",
+        "setup.limits.title" => "Tägliche Bildschirmzeit",
+        "setup.limits.desc" => "Wie viele Minuten pro Tag? Jeden Tag einzeln können Sie später in den Einstellungen festlegen.",
+        "setup.limits.weekdays" => "Montag-Freitag (Min.):",
+        "setup.limits.weekends" => "Samstag-Sonntag (Min.):",
+        "setup.limits.invalid" => "Bitte die Minuten als Zahl von 0 bis 1440 eingeben.",
+        "setup.telegram.title" => "Telegram (optional)",
+        "setup.telegram.desc" => "Mit einem Telegram-Bot erhalten Sie Benachrichtigungen und geben vom Handy aus mehr Zeit. Das geht auch später in den Einstellungen.",
+        "setup.telegram.button" => "Telegram einrichten...",
+        "setup.autostart.title" => "Automatisch starten",
+        "setup.autostart.desc" => "Bildschirmzeit Manager bei jeder Windows-Anmeldung starten, damit die Limits immer gelten.",
+        "setup.autostart.check" => "Bei der Windows-Anmeldung starten",
+        "setup.autostart.failed" => "Bildschirmzeit Manager konnte nicht zu den Autostart-Programmen hinzugefügt werden.",
+        "wizard.title" => "Telegram Einrichtungsassistent",
+        "wizard.step" => "Schritt",
+        "wizard.of" => "von",
+        "wizard.next" => "Weiter",
+        "wizard.back" => "Zurück",
+        "wizard.finish" => "Fertig",
+        "wizard.cancel" => "Abbrechen",
+        "wizard.skip" => "Überspringen",
+
+        // Step 1: Welcome
+        "wizard.welcome.title" => "Fernsteuerung via Telegram",
+        "wizard.welcome.desc1" => "Steuern Sie den Bildschirmzeit Manager vom Handy!",
+        "wizard.welcome.desc2" => "Mit Telegram können Sie:",
+        "wizard.welcome.feature1" => "Verbleibende Zeit prüfen",
+        "wizard.welcome.feature2" => "Zeit ferngesteuert verlängern oder verkürzen",
+        "wizard.welcome.feature3" => "Bildschirm sofort sperren",
+        "wizard.welcome.feature4" => "Benachrichtigungen erhalten",
+        "wizard.welcome.ready" => "Richten wir es in 3 einfachen Schritten ein!",
+
+        // Step 2: Create Bot
+        "wizard.bot.title" => "Erstellen Sie Ihren Bot",
+        "wizard.bot.step1" => "1. Öffnen Sie Telegram auf Ihrem Handy",
+        "wizard.bot.step2" => "2. Suchen Sie nach  @BotFather",
+        "wizard.bot.step3" => "3. Senden Sie die Nachricht:  /newbot",
+        "wizard.bot.step4" => "4. Wählen Sie einen Namen (z.B. \"Meine Bildschirmzeit\")",
+        "wizard.bot.step5" => "5. Wählen Sie einen Benutzernamen mit 'bot' am Ende",
+        "wizard.bot.step6" => "6. BotFather gibt Ihnen einen Token - kopieren Sie ihn!",
+        "wizard.bot.hint" => "Der Token sieht so aus: 123456789:ABCdef...",
+
+        // Step 3: Enter Token
+        "wizard.token.title" => "Bot-Token eingeben",
+        "wizard.token.label" => "Fügen Sie den Token von BotFather ein:",
+        "wizard.token.placeholder" => "123456789:ABCdefGHI...",
+        "wizard.token.invalid" => "Das sieht nicht wie ein gültiger Token aus",
+        "wizard.token.valid" => "Token sieht gut aus!",
+
+        // Step 4: Connect
+        "wizard.connect.title" => "Mit Ihrem Bot verbinden",
+        "wizard.connect.step1" => "1. Öffnen Sie Telegram",
+        "wizard.connect.step2" => "2. Suchen Sie nach Ihrem neuen Bot",
+        "wizard.connect.step3" => "3. Drücken Sie START oder senden Sie eine Nachricht",
+        "wizard.connect.waiting" => "Warte auf Ihre Nachricht...",
+        "wizard.connect.detected" => "Verbindung erkannt!",
+        "wizard.connect.chatid" => "Ihre Chat-ID:",
+
+        // Step 5: Success
+        "wizard.success.title" => "Einrichtung abgeschlossen!",
+        "wizard.success.desc" => "Ihr Telegram-Bot ist einsatzbereit.",
+        "wizard.success.test" => "Eine Testnachricht wurde an Ihr Handy gesendet.",
+        "wizard.success.commands" => "Probieren Sie diese Befehle in Telegram:",
+        "wizard.success.cmd1" => "/status - Verbleibende Zeit prüfen",
+        "wizard.success.cmd2" => "/extend 30 - 30 Minuten hinzufügen",
+        "wizard.success.cmd3" => "/lock - Bildschirm sperren",
+        "wizard.success.cmd4" => "/help - Alle Befehle anzeigen",
+
+        // Fallback to English
+        _ => en(key),
+    }
+}
@@ -0,0 +1,227 @@
+//! IPC module
+//! Lets scripts control the running app: `screen-time-manager.exe --command "extend 30"
+//! --passcode 1234` forwards the command over a named pipe and prints the reply. The app sets
+//! a named event once the pipe is listening, so a command sent right after launch waits for it
+//! instead of failing.
+
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::os::windows::io::{AsRawHandle, FromRawHandle};
+use std::time::{Duration, Instant};
+use windows::{
+    core::PCWSTR,
+    Win32::{
+        Foundation::{CloseHandle, GetLastError, ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED, HANDLE, WAIT_OBJECT_0},
+        Storage::FileSystem::{FlushFileBuffers, PIPE_ACCESS_DUPLEX},
+        System::Pipes::{
+            ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE,
+            PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+        },
+        System::Threading::{CreateEventW, OpenEventW, SetEvent, WaitForSingleObject, SYNCHRONIZATION_SYNCHRONIZE},
+    },
+};
+
+use crate::blocking::{self, ExtendSource};
+use crate::constants::{PIPE_NAME, READY_EVENT_NAME};
+use crate::{database, i18n};
+
+/// Command-line flags of a forwarded command
+pub const COMMAND_ARG: &str = "--command";
+pub const PASSCODE_ARG: &str = "--passcode";
+
+/// How long a forwarded command waits for the app to start listening
+const READY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pause between attempts while the app isn't listening yet or all pipe instances are busy
+const RETRY_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Longest request we read (passcode and command line)
+const MAX_REQUEST_BYTES: u64 = 1024;
+
+/// Pipe buffer size in each direction
+const PIPE_BUFFER_BYTES: u32 = 4096;
+
+/// Start listening for forwarded commands in a background thread, and signal that it's
+/// ready once the first pipe instance exists
+pub fn start_server_thread() {
+    let ready = unsafe { CreateEventW(None, true, false, PCWSTR(i18n::to_wide(READY_EVENT_NAME).as_ptr())) };
+    let ready = match ready {
+        Ok(event) => event.0 as isize,
+        Err(e) => {
+            log::warn!("Could not create the ready event: {}", e.message());
+            return;
+        }
+    };
+
+    std::thread::spawn(move || {
+        let name = i18n::to_wide(PIPE_NAME);
+        loop {
+            let pipe = unsafe {
+                CreateNamedPipeW(
+                    PCWSTR(name.as_ptr()),
+                    PIPE_ACCESS_DUPLEX,
+                    PIPE_TYPE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                    PIPE_UNLIMITED_INSTANCES,
+                    PIPE_BUFFER_BYTES,
+                    PIPE_BUFFER_BYTES,
+                    0,
+                    None,
+                )
+            };
+            if pipe.is_invalid() {
+                log::error!("Could not create the command pipe: {}", std::io::Error::last_os_error());
+                return;
+            }
+            // Set on every round; only the first one matters. The handle stays open for
+            // the app's lifetime so the event exists as long as the pipe does.
+            let _ = unsafe { SetEvent(HANDLE(ready as *mut std::ffi::c_void)) };
+
+            // A client that connected between creating and waiting is reported as an error
+            let connected = unsafe { ConnectNamedPipe(pipe, None) }.is_ok() || unsafe { GetLastError() } == ERROR_PIPE_CONNECTED;
+            if !connected {
+                let _ = unsafe { CloseHandle(pipe) };
+                continue;
+            }
+            // Each client gets its own thread, so one that never sends anything can't hold up the others
+            let pipe = pipe.0 as isize;
+            std::thread::spawn(move || {
+                let mut file = unsafe { std::fs::File::from_raw_handle(pipe as _) };
+                if let Err(e) = handle_client(&mut file) {
+                    log::warn!("Command failed: {}", e);
+                }
+                let handle = HANDLE(file.as_raw_handle());
+                // Let the client read the reply before the pipe is closed
+                let _ = unsafe { FlushFileBuffers(handle) };
+                let _ = unsafe { DisconnectNamedPipe(handle) };
+            });
+        }
+    });
+}
+
+/// Read one request (passcode and command on their own lines) and write the reply
+fn handle_client(pipe: &mut std::fs::File) -> std::io::Result<()> {
+    let mut request = String::new();
+    let mut reader = BufReader::new(Read::by_ref(pipe).take(MAX_REQUEST_BYTES));
+    for _ in 0..2 {
+        if reader.read_line(&mut request)? == 0 {
+            break;
+        }
+    }
+    let reply = run_request(&request);
+    pipe.write_all(reply.as_bytes())
+}
+
+/// Check the passcode and run the command. Wrong passcodes count toward the lockout just
+/// like on the lock screen.
+fn run_request(request: &str) -> String {
+    let Some((passcode, command)) = parse_request(request) else {
+        return format!("usage: {} \"<command>\" {} <passcode>", COMMAND_ARG, PASSCODE_ARG);
+    };
+    let lockout = blocking::passcode_lockout_remaining();
+    if lockout > 0 {
+        return i18n::t("blocking.locked_out").replace("{}", &lockout.to_string());
+    }
+    if database::get_passcode().is_none_or(|stored| stored != passcode) {
+        blocking::record_failed_passcode();
+        return i18n::t("passcode.incorrect").to_string();
+    }
+    blocking::reset_failed_passcodes();
+    log::info!("Running forwarded command: {}", command);
+    crate::telegram::run_remote_command(command, ExtendSource::CommandLine)
+        .unwrap_or_else(|| format!("unknown command: {}", command))
+}
+
+/// Split a request into the passcode and the command (None if either is missing)
+fn parse_request(request: &str) -> Option<(&str, &str)> {
+    let mut lines = request.lines().map(str::trim);
+    let passcode = lines.next().filter(|p| !p.is_empty())?;
+    let command = lines.next().filter(|c| !c.is_empty())?;
+    Some((passcode, command))
+}
+
+/// The command and passcode given on the command line, if this launch forwards a command
+pub fn command_from_args(args: &[String]) -> Option<(String, String)> {
+    let value = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned();
+    let command = value(COMMAND_ARG)?;
+    Some((command, value(PASSCODE_ARG).unwrap_or_default()))
+}
+
+/// Send a command to the running app and return its reply. Waits up to READY_TIMEOUT for
+/// the app to be listening (e.g. when it was launched just before).
+pub fn forward(command: &str, passcode: &str) -> std::io::Result<String> {
+    let deadline = Instant::now() + READY_TIMEOUT;
+    let not_ready = || {
+        std::io::Error::new(
+            ErrorKind::TimedOut,
+            format!("Screen Time Manager isn't running or didn't get ready within {} seconds", READY_TIMEOUT.as_secs()),
+        )
+    };
+    if !wait_until_ready(deadline) {
+        return Err(not_ready());
+    }
+
+    let mut pipe = loop {
+        match std::fs::OpenOptions::new().read(true).write(true).open(PIPE_NAME) {
+            Ok(pipe) => break pipe,
+            // Between two clients there's briefly no free pipe instance
+            Err(e) if e.kind() == ErrorKind::NotFound || e.raw_os_error() == Some(ERROR_PIPE_BUSY.0 as i32) => {
+                if Instant::now() >= deadline {
+                    return Err(not_ready());
+                }
+                std::thread::sleep(RETRY_INTERVAL);
+            }
+            Err(e) => return Err(e),
+        }
+    };
+    pipe.write_all(format!("{}\n{}\n", passcode.trim(), command.trim()).as_bytes())?;
+    let mut reply = String::new();
+    pipe.read_to_string(&mut reply)?;
+    Ok(reply)
+}
+
+/// Wait until the app has set its ready event, or the deadline passes. The event only
+/// exists once the app runs, so opening it is retried too.
+fn wait_until_ready(deadline: Instant) -> bool {
+    let name = i18n::to_wide(READY_EVENT_NAME);
+    loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if let Ok(event) = unsafe { OpenEventW(SYNCHRONIZATION_SYNCHRONIZE, false, PCWSTR(name.as_ptr())) } {
+            let signaled = unsafe { WaitForSingleObject(event, left.as_millis() as u32) } == WAIT_OBJECT_0;
+            let _ = unsafe { CloseHandle(event) };
+            return signaled;
+        }
+        if left.is_zero() {
+            return false;
+        }
+        std::thread::sleep(RETRY_INTERVAL.min(left));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_needs_a_passcode_and_a_command() {
+        assert_eq!(parse_request("1234\nextend 30\n"), Some(("1234", "extend 30")));
+        assert_eq!(parse_request(" 1234 \r\n lock \r\n"), Some(("1234", "lock")));
+        assert_eq!(parse_request("\nextend 30\n"), None);
+        assert_eq!(parse_request("1234\n"), None);
+        assert_eq!(parse_request(""), None);
+    }
+
+    #[test]
+    fn command_and_passcode_come_from_their_flags() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            command_from_args(&args(&["app.exe", "--command", "extend 30", "--passcode", "1234"])),
+            Some(("extend 30".to_string(), "1234".to_string()))
+        );
+        assert_eq!(
+            command_from_args(&args(&["app.exe", "--passcode", "1234", "--command", "pause"])),
+            Some(("pause".to_string(), "1234".to_string()))
+        );
+        assert_eq!(command_from_args(&args(&["app.exe", "--command", "lock"])), Some(("lock".to_string(), String::new())));
+        assert_eq!(command_from_args(&args(&["app.exe", "--headless"])), None);
+        assert_eq!(command_from_args(&args(&["app.exe", "--command"])), None);
+    }
+}
@@ -0,0 +1,69 @@
+//! Locale helper module
+//! Weekday names and dates formatted for the selected UI language via the Windows NLS API
+
+use windows::{
+    core::PCWSTR,
+    Win32::{
+        Foundation::SYSTEMTIME,
+        Globalization::{GetDateFormatEx, GetLocaleInfoEx, DATE_SHORTDATE, LOCALE_SDAYNAME1},
+    },
+};
+
+use crate::i18n;
+
+/// Locale name matching the current UI language (neutral names like "de" are accepted by NLS)
+fn locale_name() -> Vec<u16> {
+    i18n::to_wide(i18n::current().code())
+}
+
+/// Get the localized weekday name by index (0 = Monday, 6 = Sunday).
+/// Display only: settings keys like `limit_monday` stay English.
+pub fn weekday_name(index: usize) -> String {
+    if index < 7 {
+        let locale = locale_name();
+        let mut buffer = [0u16; 64];
+        // LOCALE_SDAYNAME1 is Monday, matching our index order
+        let len = unsafe {
+            GetLocaleInfoEx(PCWSTR(locale.as_ptr()), LOCALE_SDAYNAME1 + index as u32, Some(&mut buffer))
+        };
+        if len > 1 {
+            return String::from_utf16_lossy(&buffer[..len as usize - 1]);
+        }
+    }
+
+    // Fall back to the built-in translations
+    i18n::weekday(index).to_string()
+}
+
+/// Format an ISO date (YYYY-MM-DD, as used in database keys) as a short date for the
+/// current UI language (e.g. "10/15/2026" or "15.10.2026"). Falls back to the ISO string.
+pub fn short_date(iso: &str) -> String {
+    let mut parts = iso.splitn(3, '-').map(|p| p.parse::<u16>());
+    let (Some(Ok(year)), Some(Ok(month)), Some(Ok(day))) = (parts.next(), parts.next(), parts.next()) else {
+        return iso.to_string();
+    };
+    let date = SYSTEMTIME { wYear: year, wMonth: month, wDay: day, ..Default::default() };
+
+    let locale = locale_name();
+    let mut buffer = [0u16; 64];
+    let len = unsafe {
+        GetDateFormatEx(
+            PCWSTR(locale.as_ptr()),
+            DATE_SHORTDATE,
+            Some(&date),
+            PCWSTR::null(),
+            Some(&mut buffer),
+            PCWSTR::null(),
+        )
+    };
+    if len > 1 {
+        String::from_utf16_lossy(&buffer[..len as usize - 1])
+    } else {
+        iso.to_string()
+    }
+}
+
+/// Format today's date as a short date for the current UI language
+pub fn today_short_date() -> String {
+    short_date(&crate::database::get_today_date())
+}
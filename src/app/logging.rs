@@ -0,0 +1,147 @@
+//! Logging module
+//! Writes log messages to app.log in the data directory (next to the database), since the
+//! app has no console. The file is moved to app.log.1 once it reaches MAX_LOG_BYTES.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::database;
+
+/// Size at which app.log is moved to app.log.1 and started over
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Our own log target prefix; dependencies (teloxide, ...) are only logged from warnings up
+const OWN_TARGET: &str = env!("CARGO_CRATE_NAME");
+
+struct FileLogger {
+    /// Open log file and the bytes it holds
+    file: Mutex<Option<(File, u64)>>,
+}
+
+static LOGGER: FileLogger = FileLogger { file: Mutex::new(None) };
+
+/// Folder of the log file (the data directory, shared with the database)
+pub fn log_dir() -> PathBuf {
+    database::get_database_path()
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn log_path() -> PathBuf {
+    log_dir().join("app.log")
+}
+
+fn open_log() -> Option<(File, u64)> {
+    let file = OpenOptions::new().create(true).append(true).open(log_path()).ok()?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    Some((file, size))
+}
+
+/// Start logging to the file (from info up until `apply_level` reads the setting).
+/// Called once at startup before the database is opened, so its recovery is logged too.
+pub fn init() {
+    *LOGGER.file.lock().unwrap() = open_log();
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(LevelFilter::Info);
+    }
+}
+
+/// Close the log file for good (before the data directory is deleted); later messages are dropped
+pub fn close() {
+    *LOGGER.file.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Apply the log level setting (at startup and after the settings were saved)
+pub fn apply_level() {
+    log::set_max_level(parse_level(&database::get_log_level()));
+}
+
+/// Level filter for a log level setting ("off", "error", "warn", "info", "debug");
+/// anything else logs from info up
+fn parse_level(value: &str) -> LevelFilter {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "off" => LevelFilter::Off,
+        "error" => LevelFilter::Error,
+        "warn" | "warning" => LevelFilter::Warn,
+        "debug" => LevelFilter::Debug,
+        "trace" => LevelFilter::Trace,
+        _ => LevelFilter::Info,
+    }
+}
+
+/// Short source name for a log target: "screen_time_manager::telegram" -> "telegram"
+fn module_name(target: &str) -> &str {
+    target
+        .strip_prefix(OWN_TARGET)
+        .map(|rest| rest.trim_start_matches("::"))
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(target)
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+            && (metadata.target().starts_with(OWN_TARGET) || metadata.level() <= Level::Warn)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let st = crate::clock::local_time();
+        let line = format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02} {:<5} [{}] {}\n",
+            st.wYear, st.wMonth, st.wDay, st.wHour, st.wMinute, st.wSecond,
+            record.level(),
+            module_name(record.target()),
+            record.args()
+        );
+
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        if file.as_ref().is_some_and(|(_, size)| *size >= MAX_LOG_BYTES) {
+            // Close the file before moving it (Windows can't rename an open file)
+            *file = None;
+            let _ = std::fs::rename(log_path(), log_dir().join("app.log.1"));
+            *file = open_log();
+        }
+        if let Some((handle, size)) = file.as_mut() {
+            if handle.write_all(line.as_bytes()).is_ok() {
+                *size += line.len() as u64;
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Some((handle, _)) = self.file.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            let _ = handle.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_levels_fall_back_to_info() {
+        assert_eq!(parse_level("error"), LevelFilter::Error);
+        assert_eq!(parse_level(" Warning "), LevelFilter::Warn);
+        assert_eq!(parse_level("debug"), LevelFilter::Debug);
+        assert_eq!(parse_level("off"), LevelFilter::Off);
+        assert_eq!(parse_level(""), LevelFilter::Info);
+        assert_eq!(parse_level("verbose"), LevelFilter::Info);
+    }
+
+    #[test]
+    fn own_targets_are_shortened_to_the_module() {
+        assert_eq!(module_name(&format!("{}::telegram", OWN_TARGET)), "telegram");
+        assert_eq!(module_name(OWN_TARGET), OWN_TARGET);
+        assert_eq!(module_name("teloxide::dispatching"), "teloxide::dispatching");
+    }
+}
@@ -0,0 +1,1296 @@
+//! Mini overlay module
+//! Small, always-visible display showing remaining time
+
+use std::mem::zeroed;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicPtr, AtomicU32, AtomicU64, Ordering};
+use std::sync::Mutex;
+use windows::{
+    core::w,
+    Win32::{
+        Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM},
+        Graphics::Gdi::{
+            BeginPaint, CreateFontW, CreateSolidBrush, DeleteObject, EndPaint, FillRect,
+            FrameRect, InflateRect, InvalidateRect, HDC, SelectObject, SetBkMode, SetTextColor, DrawTextW,
+            DT_CALCRECT, DT_CENTER, DT_SINGLELINE, DT_VCENTER, FW_BOLD, PAINTSTRUCT, TRANSPARENT,
+        },
+        System::SystemInformation::{GetTickCount, GetTickCount64},
+        UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO},
+        UI::WindowsAndMessaging::*,
+    },
+};
+
+use crate::blocking::REMAINING_SECONDS;
+use crate::constants::*;
+use crate::database;
+use crate::dpi::scale;
+use crate::i18n;
+use crate::pause::{evaluate_pause, PauseBlockedReason, PauseState};
+use crate::theme;
+use crate::timefmt::format_time_as;
+
+/// Global state for mini overlay window
+pub static MINI_OVERLAY_HWND: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+pub static MINI_OVERLAY_VISIBLE: AtomicBool = AtomicBool::new(false);
+
+// Pause state tracking
+pub static IS_PAUSED: AtomicBool = AtomicBool::new(false);
+pub static PAUSE_START_TIMESTAMP: AtomicI64 = AtomicI64::new(0);
+pub static CURRENT_PAUSE_DURATION: AtomicI32 = AtomicI32::new(0);
+pub static SESSION_ACTIVE_SECONDS: AtomicI32 = AtomicI32::new(0);
+// Active time since the last pause ended (gates the next pause; total stays in SESSION_ACTIVE_SECONDS)
+pub static ACTIVE_SINCE_RESUME: AtomicI32 = AtomicI32::new(0);
+
+// Focus mode: a parent grant (e.g. for homework) during which no time is counted and no
+// warnings appear. Unlike a pause it has no budget or cooldown.
+pub static FOCUS_MODE: AtomicBool = AtomicBool::new(false);
+// When focus mode started and when it ends (0 = until turned off)
+static FOCUS_STARTED: AtomicI64 = AtomicI64::new(0);
+static FOCUS_DEADLINE: AtomicI64 = AtomicI64::new(0);
+
+// Idle detection state (independent from manual pause)
+pub static IS_IDLE_PAUSED: AtomicBool = AtomicBool::new(false);
+// Start of the current idle interval (time of the last input, 0 = not idle) and the seconds
+// the timer counted down since the last input; logged even with idle detection off
+static IDLE_SINCE: AtomicI64 = AtomicI64::new(0);
+static COUNTED_SINCE_INPUT: AtomicI64 = AtomicI64::new(0);
+
+// Date the current counters belong to (YYYY-MM-DD), used to detect day rollover
+static CURRENT_DATE: Mutex<Option<String>> = Mutex::new(None);
+
+// Timestamp when the system went to sleep (0 when awake)
+static SUSPEND_TIMESTAMP: AtomicI64 = AtomicI64::new(0);
+
+// Timer ticks since the counters were last saved periodically
+static TICKS_SINCE_SAVE: AtomicU32 = AtomicU32::new(0);
+
+// Monotonic time (GetTickCount64, ms) up to which the countdown has been accounted (0 = not yet)
+static LAST_TICK_MS: AtomicU64 = AtomicU64::new(0);
+
+// Seconds used after the time ran out in warn-only mode
+static OVERTIME_SECONDS: AtomicU32 = AtomicU32::new(0);
+
+// Simulation: the countdown runs sim_speed times faster so a parent can try the warnings and
+// the block. The counters from before it started (remaining, session active, active since
+// resume) are restored when it ends.
+static SIMULATING: AtomicBool = AtomicBool::new(false);
+static SIM_SNAPSHOT: Mutex<Option<(i32, i32, i32)>> = Mutex::new(None);
+
+/// Warn-only mode: seconds between reminders once the time is used up, and how long each one shows
+const OVERTIME_REMINDER_SECONDS: u32 = 300;
+const OVERTIME_BANNER_SECONDS: u32 = 15;
+
+/// Longest gap between two timer ticks that is caught up on; a longer one (sleep the
+/// resume handler accounts for, a hung machine) counts as a single second
+const MAX_CATCH_UP_SECONDS: u64 = 30;
+
+/// Wind-down: how long each repeated reminder shows
+const WINDDOWN_BANNER_SECONDS: u32 = 5;
+
+/// Timer ID for updating the mini overlay
+pub const TIMER_MINI_UPDATE: usize = 10;
+
+/// Mini overlay base dimensions (at 96 DPI / 100% scaling)
+const MINI_WIDTH_BASE: i32 = 140;
+const MINI_HEIGHT_BASE: i32 = 36;
+const MINI_MARGIN_BASE: i32 = 10;
+const MINI_FONT_BASE: i32 = 22;
+
+/// Layered window opacity (raised to 255 by high contrast or disabled transparency)
+pub const MINI_ALPHA: u8 = 200;
+
+/// Size of the large countdown relative to the corner timer, in percent
+const LARGE_DISPLAY_PERCENT: i32 = 300;
+
+// User size preference in percent, applied on top of DPI scaling
+static MINI_SCALE_PERCENT: AtomicI32 = AtomicI32::new(100);
+
+// Large countdown instead of the small corner timer (display_mode "large")
+static LARGE_DISPLAY: AtomicBool = AtomicBool::new(false);
+
+// Screen corner the timer sits in ("top_right", "top_left", "bottom_right" or "bottom_left")
+static DISPLAY_CORNER: Mutex<String> = Mutex::new(String::new());
+
+// Width the current text needs when it doesn't fit the default width (0 = default width)
+static MINI_TEXT_WIDTH: AtomicI32 = AtomicI32::new(0);
+
+/// Scale a base dimension by DPI, the user's mini overlay size preference and the display mode
+fn mini_scale(value: i32) -> i32 {
+    let mode_percent = if LARGE_DISPLAY.load(Ordering::SeqCst) { LARGE_DISPLAY_PERCENT } else { 100 };
+    scale(value) * MINI_SCALE_PERCENT.load(Ordering::SeqCst) / 100 * mode_percent / 100
+}
+
+/// Re-read the size, display mode and corner settings
+fn load_display_settings() {
+    MINI_SCALE_PERCENT.store(database::get_mini_overlay_scale(), Ordering::SeqCst);
+    LARGE_DISPLAY.store(database::get_display_mode() == "large", Ordering::SeqCst);
+    if let Ok(mut corner) = DISPLAY_CORNER.lock() {
+        *corner = database::get_display_corner();
+    }
+}
+
+/// Size and position of the mini overlay for the current scale, in the chosen corner
+/// of the work area (so a bottom corner stays clear of the taskbar)
+fn mini_bounds() -> (i32, i32, i32, i32) {
+    let mini_width = mini_scale(MINI_WIDTH_BASE).max(MINI_TEXT_WIDTH.load(Ordering::SeqCst));
+    let mini_height = mini_scale(MINI_HEIGHT_BASE);
+    let mini_margin = scale(MINI_MARGIN_BASE);
+
+    let mut work_area = RECT::default();
+    let found = unsafe {
+        SystemParametersInfoW(
+            SPI_GETWORKAREA,
+            0,
+            Some(&mut work_area as *mut RECT as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    };
+    if found.is_err() {
+        work_area = unsafe {
+            RECT { left: 0, top: 0, right: GetSystemMetrics(SM_CXSCREEN), bottom: GetSystemMetrics(SM_CYSCREEN) }
+        };
+    }
+
+    let corner = DISPLAY_CORNER.lock().map(|c| c.clone()).unwrap_or_default();
+    let x = if corner.ends_with("left") {
+        work_area.left + mini_margin
+    } else {
+        work_area.right - mini_width - mini_margin
+    };
+    let y = if corner.starts_with("bottom") {
+        work_area.bottom - mini_height - mini_margin
+    } else {
+        work_area.top + mini_margin
+    };
+
+    (x, y, mini_width, mini_height)
+}
+
+/// Create the mini overlay window
+pub unsafe fn create_mini_overlay(hinstance: windows::Win32::Foundation::HMODULE) {
+    let class_name = w!("ScreenTimeMiniOverlayClass");
+
+    // Apply DPI, user size and display mode scaling, positioned in the chosen corner
+    load_display_settings();
+    let (x, y, mini_width, mini_height) = mini_bounds();
+
+    let ex_style = WS_EX_TOPMOST | WS_EX_LAYERED | WS_EX_TOOLWINDOW | WS_EX_TRANSPARENT;
+
+    let hwnd = CreateWindowExW(
+        ex_style,
+        class_name,
+        w!("Screen Time"),
+        WS_POPUP,
+        x,
+        y,
+        mini_width,
+        mini_height,
+        None,
+        None,
+        hinstance,
+        None,
+    )
+    .expect("Failed to create mini overlay window");
+
+    // Set transparency (slightly see-through)
+    SetLayeredWindowAttributes(hwnd, COLORREF(0), theme::alpha(MINI_ALPHA), LWA_ALPHA)
+        .expect("Failed to set layered window attributes");
+
+    MINI_OVERLAY_HWND.store(hwnd.0, Ordering::SeqCst);
+}
+
+/// Show the mini overlay and start the update timer.
+/// The update timer also drives the countdown, so it runs even when the user has
+/// hidden the overlay from the tray menu (the window then just stays hidden).
+pub unsafe fn show_mini_overlay() {
+    let hwnd = HWND(MINI_OVERLAY_HWND.load(Ordering::SeqCst));
+    if hwnd.0.is_null() {
+        return;
+    }
+
+    // The countdown was stopped (e.g. behind the blocking overlay): don't catch up on that gap
+    if !MINI_OVERLAY_VISIBLE.swap(true, Ordering::SeqCst) {
+        LAST_TICK_MS.store(0, Ordering::SeqCst);
+    }
+
+    if database::is_mini_overlay_enabled() {
+        let _ = InvalidateRect(hwnd, None, true);
+        let _ = ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+    }
+
+    // Update every second
+    let _ = SetTimer(hwnd, TIMER_MINI_UPDATE, 1000, None);
+}
+
+/// Toggle whether the user wants to see the mini overlay (persisted).
+/// Only the window is hidden; the countdown keeps running.
+pub unsafe fn set_mini_overlay_enabled(enabled: bool) {
+    database::set_setting("mini_overlay_visible", if enabled { "1" } else { "0" });
+    // Showing it from the tray when Settings hid it brings back the small corner timer
+    if enabled && database::get_display_mode() == "hidden" {
+        database::set_setting("display_mode", "mini");
+    }
+
+    let hwnd = HWND(MINI_OVERLAY_HWND.load(Ordering::SeqCst));
+    if hwnd.0.is_null() || !MINI_OVERLAY_VISIBLE.load(Ordering::SeqCst) {
+        return;
+    }
+
+    if enabled {
+        let _ = InvalidateRect(hwnd, None, true);
+        let _ = ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+    } else {
+        let _ = ShowWindow(hwnd, SW_HIDE);
+    }
+}
+
+/// Re-read the size, display mode and corner settings and move the mini overlay in place
+pub unsafe fn resize_mini_overlay() {
+    let hwnd = HWND(MINI_OVERLAY_HWND.load(Ordering::SeqCst));
+    if hwnd.0.is_null() {
+        return;
+    }
+
+    load_display_settings();
+    // The format may have changed too; the next paint widens the window again if needed
+    MINI_TEXT_WIDTH.store(0, Ordering::SeqCst);
+    let (x, y, mini_width, mini_height) = mini_bounds();
+
+    let _ = SetWindowPos(
+        hwnd,
+        HWND_TOPMOST,
+        x,
+        y,
+        mini_width,
+        mini_height,
+        SWP_NOACTIVATE,
+    );
+    let _ = InvalidateRect(hwnd, None, true);
+}
+
+/// Apply changed display settings: resize and move the window, then show or hide it
+/// for the chosen display mode (only while the countdown is running)
+pub unsafe fn apply_display_settings() {
+    resize_mini_overlay();
+
+    let hwnd = HWND(MINI_OVERLAY_HWND.load(Ordering::SeqCst));
+    if hwnd.0.is_null() || !MINI_OVERLAY_VISIBLE.load(Ordering::SeqCst) {
+        return;
+    }
+
+    if database::is_mini_overlay_enabled() {
+        let _ = ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+    } else {
+        let _ = ShowWindow(hwnd, SW_HIDE);
+    }
+}
+
+/// Hide the mini overlay
+pub unsafe fn hide_mini_overlay() {
+    let hwnd = HWND(MINI_OVERLAY_HWND.load(Ordering::SeqCst));
+    if hwnd.0.is_null() {
+        return;
+    }
+
+    MINI_OVERLAY_VISIBLE.store(false, Ordering::SeqCst);
+
+    let _ = KillTimer(hwnd, TIMER_MINI_UPDATE);
+    let _ = ShowWindow(hwnd, SW_HIDE);
+}
+
+/// Update the mini overlay display
+pub unsafe fn update_mini_overlay() {
+    let hwnd = HWND(MINI_OVERLAY_HWND.load(Ordering::SeqCst));
+    if hwnd.0.is_null() {
+        return;
+    }
+
+    let _ = InvalidateRect(hwnd, None, true);
+}
+
+/// Today's remaining time as the corner timer shows it (also used by the tray tooltip)
+pub fn format_remaining(seconds: i32) -> String {
+    format_time_as(seconds, &database::get_mini_overlay_format(), Some(database::get_effective_daily_limit()))
+}
+
+/// Get color based on remaining time
+fn get_time_color(seconds: i32) -> u32 {
+    if seconds < 0 {
+        theme::fg(COLOR_TEXT_LIGHT)
+    } else if seconds <= 60 {
+        // Red when less than 1 minute
+        theme::alert(0x004444FF)
+    } else if seconds <= 300 {
+        // Orange when less than 5 minutes
+        theme::alert(COLOR_ACCENT)
+    } else {
+        // White otherwise
+        theme::fg(COLOR_TEXT_WHITE)
+    }
+}
+
+/// Check if the remaining time is inside the wind-down window (presentation only; the
+/// countdown itself runs at normal speed)
+fn in_winddown(remaining: i32) -> bool {
+    let minutes = database::get_winddown_minutes() as i32;
+    remaining > 0 && remaining <= minutes * 60
+}
+
+/// Check if a wind-down reminder is due, counting whole intervals from the start of the window
+fn winddown_reminder_due(remaining: i32) -> bool {
+    if !in_winddown(remaining) {
+        return false;
+    }
+    let window = database::get_winddown_minutes() as i32 * 60;
+    let interval = database::get_winddown_interval_minutes() as i32 * 60;
+    let elapsed = window - remaining;
+    elapsed > 0 && elapsed % interval == 0
+}
+
+// ============================================================================
+// Pause Mode Functions
+// ============================================================================
+
+/// Check if timer is currently paused
+pub fn is_paused() -> bool {
+    IS_PAUSED.load(Ordering::SeqCst)
+}
+
+/// Check if pause is currently available and return reason if not
+pub fn can_pause() -> Result<(), PauseBlockedReason> {
+    let state = PauseState {
+        paused: IS_PAUSED.load(Ordering::SeqCst),
+        enabled: database::is_pause_enabled(),
+        remaining: REMAINING_SECONDS.load(Ordering::SeqCst),
+        pause_used: database::get_pause_used_today(),
+        last_pause_end: database::get_last_pause_end(),
+        active_since_resume: ACTIVE_SINCE_RESUME.load(Ordering::SeqCst),
+    };
+    evaluate_pause(database::get_pause_config(), state, database::get_current_timestamp())
+}
+
+
+/// Get remaining pause budget in seconds
+pub fn get_remaining_pause_budget() -> i32 {
+    let config = database::get_pause_config();
+    let budget_seconds = (config.daily_budget_minutes * 60) as i32;
+    let used = database::get_pause_used_today();
+    (budget_seconds - used).max(0)
+}
+
+/// Get maximum pause duration for current pause (considering budget and config)
+pub fn get_max_pause_duration() -> i32 {
+    let config = database::get_pause_config();
+    let max_single = (config.max_duration_minutes * 60) as i32;
+    let remaining_budget = get_remaining_pause_budget();
+    max_single.min(remaining_budget)
+}
+
+/// Toggle pause state - returns true if now paused, false if resumed
+pub fn toggle_pause() -> Result<bool, PauseBlockedReason> {
+    if IS_PAUSED.load(Ordering::SeqCst) {
+        // Currently paused - resume
+        resume_timer();
+        Ok(false)
+    } else {
+        // Not paused - try to pause
+        can_pause()?;
+        pause_timer();
+        Ok(true)
+    }
+}
+
+/// Pause the timer
+fn pause_timer() {
+    crate::blocking::cancel_pending_shutdown();
+    let timestamp = database::get_current_timestamp();
+    PAUSE_START_TIMESTAMP.store(timestamp, Ordering::SeqCst);
+    CURRENT_PAUSE_DURATION.store(0, Ordering::SeqCst);
+    IS_PAUSED.store(true, Ordering::SeqCst);
+    database::save_active_pause(timestamp, 0);
+
+    // Update display immediately
+    unsafe {
+        let hwnd = HWND(MINI_OVERLAY_HWND.load(Ordering::SeqCst));
+        if !hwnd.0.is_null() {
+            let _ = InvalidateRect(hwnd, None, true);
+        }
+    }
+}
+
+/// Recompute the current pause duration from its start timestamp (capped at the
+/// allowed maximum), so missed ticks during sleep or a busy UI thread still count
+fn update_pause_duration() -> i32 {
+    let start = PAUSE_START_TIMESTAMP.load(Ordering::SeqCst);
+    let elapsed = (database::get_current_timestamp() - start).clamp(0, i32::MAX as i64) as i32;
+    let duration = elapsed.min(get_max_pause_duration());
+    CURRENT_PAUSE_DURATION.store(duration, Ordering::SeqCst);
+    duration
+}
+
+/// Restore a pause that was active when the app was last closed.
+/// With wall-clock pauses the time the app was closed counts towards the pause
+/// (auto-resuming if the maximum has passed); otherwise the pause ends and only
+/// the time counted before closing is charged.
+pub fn restore_pause_state() {
+    let Some((start, counted)) = database::get_active_pause() else {
+        return;
+    };
+
+    PAUSE_START_TIMESTAMP.store(start, Ordering::SeqCst);
+    IS_PAUSED.store(true, Ordering::SeqCst);
+
+    if database::is_pause_wall_clock() {
+        if update_pause_duration() >= get_max_pause_duration() {
+            resume_timer();
+        }
+    } else {
+        CURRENT_PAUSE_DURATION.store(counted.min(get_max_pause_duration()), Ordering::SeqCst);
+        resume_timer();
+    }
+}
+
+/// Resume the timer (end pause)
+fn resume_timer() {
+    end_pause(&database::get_today_date());
+}
+
+/// End the pause and charge it to `date`
+fn end_pause(date: &str) {
+    let pause_duration = update_pause_duration();
+
+    // Update total pause used that day
+    let total_used = database::get_pause_used(date) + pause_duration;
+    database::save_pause_used(date, total_used);
+
+    // Log the pause event
+    database::log_pause_event(date, pause_duration);
+
+    // Save last pause end timestamp
+    let timestamp = database::get_current_timestamp();
+    database::save_last_pause_end(timestamp);
+
+    // The next pause needs a fresh stretch of active time
+    ACTIVE_SINCE_RESUME.store(0, Ordering::SeqCst);
+    database::save_active_since_pause(0);
+
+    // Reset pause state
+    IS_PAUSED.store(false, Ordering::SeqCst);
+    PAUSE_START_TIMESTAMP.store(0, Ordering::SeqCst);
+    CURRENT_PAUSE_DURATION.store(0, Ordering::SeqCst);
+    database::clear_active_pause();
+
+    // Update display immediately
+    unsafe {
+        let hwnd = HWND(MINI_OVERLAY_HWND.load(Ordering::SeqCst));
+        if !hwnd.0.is_null() {
+            let _ = InvalidateRect(hwnd, None, true);
+        }
+    }
+}
+
+/// Force resume (called when max duration reached)
+fn force_resume() {
+    resume_timer();
+}
+
+// ============================================================================
+// Day Rollover and Power Events
+// ============================================================================
+
+/// Switch the counters to a new day if the date changed since the last check.
+/// Returns true if a rollover happened.
+pub fn check_day_rollover() -> bool {
+    let today = database::get_today_date();
+    {
+        let mut current = CURRENT_DATE.lock().unwrap();
+        let previous = match current.as_deref() {
+            None => {
+                *current = Some(today);
+                return false;
+            }
+            Some(date) if date == today => return false,
+            Some(date) => date.to_string(),
+        };
+        // A pause can't carry over into the new day's budget: it ends on the day it was
+        // taken, before the date moves on
+        if IS_PAUSED.load(Ordering::SeqCst) {
+            end_pause(&previous);
+        }
+        *current = Some(today);
+    }
+
+    // Nor can a simulation carry over into the new day
+    stop_simulation();
+
+    // A new week may lower the daily limits before today's budget is read
+    database::apply_limit_rampdown();
+    reload_counters();
+
+    true
+}
+
+/// Read today's remaining time and active-time counters from the database (on a new day or
+/// after a backup was restored, so the next save doesn't overwrite them) and block or
+/// unblock to match
+pub fn reload_counters() {
+    let remaining = database::load_remaining_time().unwrap_or_else(|| {
+        (database::get_daily_limit(database::get_current_weekday()) * 60) as i32
+    });
+    REMAINING_SECONDS.store(remaining, Ordering::SeqCst);
+    SESSION_ACTIVE_SECONDS.store(database::get_session_active_time(), Ordering::SeqCst);
+    ACTIVE_SINCE_RESUME.store(database::get_active_since_pause(), Ordering::SeqCst);
+
+    unsafe {
+        // A block from before doesn't hold once there's time again (a curfew block is re-checked separately)
+        let blocking_hwnd = HWND(crate::blocking::BLOCKING_HWND.load(Ordering::SeqCst));
+        if remaining > 0
+            && !crate::curfew::is_curfew_blocking()
+            && !blocking_hwnd.0.is_null()
+            && IsWindowVisible(blocking_hwnd).as_bool()
+        {
+            crate::blocking::hide_blocking_overlay();
+        } else if remaining <= 0 && !crate::curfew::is_curfew_blocking() {
+            // No time left, e.g. a day without screen time (limit 0), blocks right away
+            enforce_time_up();
+        }
+        update_mini_overlay();
+    }
+}
+
+/// Persist today's remaining time, active-time counters and any running pause.
+/// Called periodically, before sleep, on quit and at Windows shutdown so a restart picks up where we left off.
+/// Skipped during a simulation, whose accelerated counters must never reach the database.
+pub fn save_counters() {
+    if is_simulating() {
+        return;
+    }
+    database::save_remaining_time(REMAINING_SECONDS.load(Ordering::SeqCst));
+    database::save_session_active_time(SESSION_ACTIVE_SECONDS.load(Ordering::SeqCst));
+    database::save_active_since_pause(ACTIVE_SINCE_RESUME.load(Ordering::SeqCst));
+    crate::security::save_clock_baseline();
+    if IS_PAUSED.load(Ordering::SeqCst) {
+        database::save_active_pause(PAUSE_START_TIMESTAMP.load(Ordering::SeqCst), update_pause_duration());
+    }
+}
+
+/// Count one timer tick toward the next periodic save.
+/// Returns true (and starts over) once `interval` ticks have passed, whatever the remaining time is.
+fn autosave_due(ticks: &AtomicU32, interval: u32) -> bool {
+    if ticks.fetch_add(1, Ordering::SeqCst) + 1 >= interval {
+        ticks.store(0, Ordering::SeqCst);
+        true
+    } else {
+        false
+    }
+}
+
+/// Whole seconds to count between the monotonic readings `last_ms` and `now_ms`, and the
+/// reading to count from next time (leftover milliseconds carry over, so nothing drifts).
+/// The first reading and gaps over MAX_CATCH_UP_SECONDS count as one second.
+fn elapsed_seconds(last_ms: u64, now_ms: u64) -> (u32, u64) {
+    if last_ms == 0 || now_ms < last_ms {
+        return (1, now_ms);
+    }
+    let seconds = (now_ms - last_ms) / 1000;
+    if seconds > MAX_CATCH_UP_SECONDS {
+        return (1, now_ms);
+    }
+    (seconds as u32, last_ms + seconds * 1000)
+}
+
+/// Seconds that really passed since the last timer tick
+fn take_elapsed_seconds() -> u32 {
+    let now = unsafe { GetTickCount64() };
+    let (seconds, next) = elapsed_seconds(LAST_TICK_MS.load(Ordering::SeqCst), now);
+    LAST_TICK_MS.store(next, Ordering::SeqCst);
+    seconds
+}
+
+/// Account one second of the countdown in the current state (running, paused, focus, ...).
+/// Returns whether the second was counted against the limit.
+unsafe fn count_second() -> bool {
+    let paused = IS_PAUSED.load(Ordering::SeqCst);
+    let idle_paused = IS_IDLE_PAUSED.load(Ordering::SeqCst);
+    let mut counted = false;
+
+    if FOCUS_MODE.load(Ordering::SeqCst) {
+        // Focus mode - nothing is counted and no warnings appear until it ends
+        if focus_seconds_left() == Some(0) {
+            stop_focus_mode();
+        }
+    } else if paused {
+        // Timer is manually paused - track pause duration from its start time
+        let duration = update_pause_duration();
+        let max_duration = get_max_pause_duration();
+
+        // Check if max pause duration reached
+        if duration >= max_duration {
+            // Auto-resume
+            force_resume();
+        }
+    } else if idle_paused {
+        // Timer is idle-paused - don't decrement time, don't track session time
+    } else if !crate::session::counts() {
+        // Another account is at the console (fast user switching) or this one isn't counted
+    } else if !crate::apps::foreground_counts() {
+        // A productive app (e.g. for homework) is in front - time doesn't count
+        SESSION_ACTIVE_SECONDS.fetch_add(1, Ordering::SeqCst);
+    } else {
+        // Timer is running normally. Decrement atomically so an extension from the
+        // Telegram thread isn't overwritten (which would lose it and block right away).
+        let decremented = REMAINING_SECONDS
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| (current > 0).then(|| current - 1));
+        if let Ok(current) = decremented {
+            let new_time = current - 1;
+            counted = true;
+
+            // Increment session active time
+            SESSION_ACTIVE_SECONDS.fetch_add(1, Ordering::SeqCst);
+            ACTIVE_SINCE_RESUME.fetch_add(1, Ordering::SeqCst);
+
+            // Check for warning 1 (e.g., 10 minutes remaining; weekends can differ)
+            let weekday = database::get_current_weekday();
+            let (warn1_mins, warn1_msg, warn1_secs) = database::get_warning_config_for_day(1, weekday);
+            if new_time == (warn1_mins * 60) as i32 {
+                crate::overlay::show_overlay(&warn1_msg, warn1_secs, false);
+                crate::notifications::dispatch(crate::notifications::Event::Warning { minutes: warn1_mins });
+            }
+
+            // Check for warning 2 (e.g., 5 minutes remaining)
+            let (warn2_mins, warn2_msg, warn2_secs) = database::get_warning_config_for_day(2, weekday);
+            if new_time == (warn2_mins * 60) as i32 {
+                crate::overlay::show_overlay(&warn2_msg, warn2_secs, true);
+                crate::notifications::dispatch(crate::notifications::Event::Warning { minutes: warn2_mins });
+            }
+
+            // Wind-down: repeat a short reminder between the configured warnings
+            if winddown_reminder_due(new_time)
+                && new_time != (warn1_mins * 60) as i32
+                && new_time != (warn2_mins * 60) as i32
+            {
+                let minutes_left = (new_time + 59) / 60;
+                crate::overlay::show_overlay(
+                    &i18n::t("winddown.warning").replace("{}", &minutes_left.to_string()),
+                    WINDDOWN_BANNER_SECONDS,
+                    false,
+                );
+            }
+
+            // Trigger blocking overlay (or the warn-only banner) when time reaches 0
+            if new_time == 0 {
+                limit_reached();
+            }
+        } else if database::is_warn_only() {
+            // Warn-only mode: time is used up but the computer is still in use
+            SESSION_ACTIVE_SECONDS.fetch_add(1, Ordering::SeqCst);
+            ACTIVE_SINCE_RESUME.fetch_add(1, Ordering::SeqCst);
+            let overtime = OVERTIME_SECONDS.fetch_add(1, Ordering::SeqCst) + 1;
+            if overtime % OVERTIME_REMINDER_SECONDS == 0 {
+                crate::overlay::show_overlay(i18n::t("overtime.warning"), OVERTIME_BANNER_SECONDS, false);
+            }
+        }
+    }
+    counted
+}
+
+/// Today's time just ran out: log it, tell the parent and enforce it
+unsafe fn limit_reached() {
+    if is_simulating() {
+        // Only shown on this PC; the block is neither logged nor reported
+    } else if database::is_warn_only() {
+        crate::notifications::dispatch(crate::notifications::Event::LimitReachedWarnOnly);
+    } else {
+        database::log_block_event("limit");
+        crate::notifications::dispatch(crate::notifications::Event::LimitReached);
+    }
+    enforce_time_up();
+    if !is_simulating() && !database::is_warn_only() {
+        crate::blocking::apply_expiry_action();
+    }
+}
+
+/// Show the blocking overlay, or in warn-only mode the warning banner
+/// (which then comes back every few minutes while the computer is used)
+pub unsafe fn enforce_time_up() {
+    OVERTIME_SECONDS.store(0, Ordering::SeqCst);
+    if database::is_warn_only() {
+        crate::overlay::show_overlay(i18n::t("overtime.warning"), OVERTIME_BANNER_SECONDS, false);
+    } else {
+        let message = if database::is_no_screen_time_today() {
+            i18n::t("blocking.no_screen_time").to_string()
+        } else {
+            database::get_blocking_message()
+        };
+        if is_simulating() {
+            // Not persisted, so a restart during a simulation doesn't block for real
+            crate::blocking::show_blocking_overlay_with_time(&message, -1);
+        } else {
+            crate::blocking::show_blocking_overlay(&message, "limit");
+        }
+    }
+}
+
+// ============================================================================
+// Focus Mode
+// ============================================================================
+
+/// Check if focus mode is on
+pub fn is_focus_mode() -> bool {
+    FOCUS_MODE.load(Ordering::SeqCst)
+}
+
+/// Seconds of focus mode left, or None if it runs until turned off
+pub fn focus_seconds_left() -> Option<i64> {
+    let deadline = FOCUS_DEADLINE.load(Ordering::SeqCst);
+    (deadline > 0).then(|| (deadline - database::get_current_timestamp()).max(0))
+}
+
+/// Pick up focus mode that was on when the app was closed (ending it if it ran out meanwhile)
+pub fn restore_focus_state() {
+    let Some((start, deadline)) = database::get_active_focus() else {
+        return;
+    };
+
+    FOCUS_STARTED.store(start, Ordering::SeqCst);
+    FOCUS_DEADLINE.store(deadline, Ordering::SeqCst);
+    FOCUS_MODE.store(true, Ordering::SeqCst);
+    if focus_seconds_left() == Some(0) {
+        stop_focus_mode();
+    }
+}
+
+/// Start focus mode for `minutes` (0 = until turned off). The caller checks the passcode.
+/// A running pause ends first so it doesn't eat into the pause budget, and a block because
+/// the time ran out is lifted (a curfew block stays).
+pub fn start_focus_mode(minutes: u32, source: &str) {
+    crate::blocking::cancel_pending_shutdown();
+    if IS_PAUSED.load(Ordering::SeqCst) {
+        resume_timer();
+    }
+
+    let now = database::get_current_timestamp();
+    let deadline = if minutes > 0 { now + minutes as i64 * 60 } else { 0 };
+    FOCUS_STARTED.store(now, Ordering::SeqCst);
+    FOCUS_DEADLINE.store(deadline, Ordering::SeqCst);
+    FOCUS_MODE.store(true, Ordering::SeqCst);
+    database::save_active_focus(now, deadline);
+
+    let length = if minutes > 0 { format!("{}m", minutes) } else { "open".to_string() };
+    database::log_focus_event(&format!("on:{}:{}", length, source));
+
+    unsafe {
+        if !crate::curfew::is_curfew_blocking() {
+            crate::blocking::release_block();
+        }
+        crate::overlay::hide_overlay();
+        show_mini_overlay();
+        update_mini_overlay();
+    }
+}
+
+/// End focus mode; counting picks up where it stopped (blocking again if the time was used up)
+pub fn stop_focus_mode() {
+    if !FOCUS_MODE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    FOCUS_DEADLINE.store(0, Ordering::SeqCst);
+    database::clear_active_focus();
+
+    let minutes = (database::get_current_timestamp() - FOCUS_STARTED.load(Ordering::SeqCst)).max(0) / 60;
+    database::log_focus_event(&format!("off:{}m", minutes));
+
+    unsafe {
+        if REMAINING_SECONDS.load(Ordering::SeqCst) <= 0 && !crate::overlay::is_blocking_active() {
+            enforce_time_up();
+        }
+        update_mini_overlay();
+    }
+}
+
+/// Called when the system is about to sleep or hibernate
+pub fn handle_system_suspend() {
+    stop_simulation();
+    save_counters();
+    SUSPEND_TIMESTAMP.store(database::get_current_timestamp(), Ordering::SeqCst);
+}
+
+/// Called when the system wakes up: re-sync day, pause and (optionally) sleep time
+pub fn handle_system_resume() {
+    let suspended_at = SUSPEND_TIMESTAMP.swap(0, Ordering::SeqCst);
+    // The time asleep is handled below, not by the countdown catching up
+    LAST_TICK_MS.store(0, Ordering::SeqCst);
+    crate::security::reset_clock_baseline();
+    let rolled_over = check_day_rollover();
+
+    // Pause duration is derived from timestamps, so this catches up on the time asleep
+    if IS_PAUSED.load(Ordering::SeqCst) && update_pause_duration() >= get_max_pause_duration() {
+        force_resume();
+    }
+
+    let counts_sleep = database::does_sleep_count() && !rolled_over && !IS_PAUSED.load(Ordering::SeqCst);
+    if counts_sleep && suspended_at > 0 {
+        let slept = (database::get_current_timestamp() - suspended_at).clamp(0, i32::MAX as i64) as i32;
+        let current = REMAINING_SECONDS.load(Ordering::SeqCst);
+        if current > 0 && slept > 0 {
+            let new_time = (current - slept).max(0);
+            REMAINING_SECONDS.store(new_time, Ordering::SeqCst);
+            database::save_remaining_time(new_time);
+            if new_time == 0 {
+                unsafe { limit_reached() };
+            }
+        }
+    }
+
+    unsafe { update_mini_overlay() };
+}
+
+// ============================================================================
+// Simulation
+// ============================================================================
+
+/// Check if a simulation is running
+pub fn is_simulating() -> bool {
+    SIMULATING.load(Ordering::SeqCst)
+}
+
+/// Countdown seconds per real second (1 outside a simulation)
+fn sim_speed() -> u32 {
+    if is_simulating() { database::get_sim_speed() } else { 1 }
+}
+
+/// Start a simulation from the current remaining time. The real counters are saved first
+/// and put back when it ends; nothing is saved while it runs.
+pub fn start_simulation() {
+    if is_simulating() {
+        return;
+    }
+    save_counters();
+    *SIM_SNAPSHOT.lock().unwrap() = Some((
+        REMAINING_SECONDS.load(Ordering::SeqCst),
+        SESSION_ACTIVE_SECONDS.load(Ordering::SeqCst),
+        ACTIVE_SINCE_RESUME.load(Ordering::SeqCst),
+    ));
+    SIMULATING.store(true, Ordering::SeqCst);
+    log::info!("Simulation started at {}x speed", database::get_sim_speed());
+    unsafe { update_mini_overlay() };
+}
+
+/// Stop a running simulation and put the real counters back.
+/// The blocking overlay calls this before it saves the remaining time.
+pub fn stop_simulation() {
+    if !SIMULATING.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    if let Some((remaining, session_active, since_resume)) = SIM_SNAPSHOT.lock().unwrap().take() {
+        REMAINING_SECONDS.store(remaining, Ordering::SeqCst);
+        SESSION_ACTIVE_SECONDS.store(session_active, Ordering::SeqCst);
+        ACTIVE_SINCE_RESUME.store(since_resume, Ordering::SeqCst);
+    }
+    OVERTIME_SECONDS.store(0, Ordering::SeqCst);
+    log::info!("Simulation ended");
+}
+
+/// End a simulation from the tray: restore the counters and take down the simulated
+/// block and warnings
+pub unsafe fn end_simulation() {
+    if crate::overlay::is_blocking_active() && !crate::curfew::is_curfew_blocking() {
+        // Restores the counters before it saves them
+        crate::blocking::hide_blocking_overlay();
+    }
+    stop_simulation();
+    crate::overlay::hide_overlay();
+    update_mini_overlay();
+}
+
+// ============================================================================
+// Idle Detection Functions
+// ============================================================================
+
+/// Get the number of seconds since the last user input (mouse/keyboard)
+fn get_idle_seconds() -> u32 {
+    unsafe {
+        let mut lii: LASTINPUTINFO = zeroed();
+        lii.cbSize = std::mem::size_of::<LASTINPUTINFO>() as u32;
+        if GetLastInputInfo(&mut lii).as_bool() {
+            let now = GetTickCount();
+            now.wrapping_sub(lii.dwTime) / 1000
+        } else {
+            0
+        }
+    }
+}
+
+/// Log an idle interval (no input for at least the idle timeout) once the user is back,
+/// with how much of it was counted against the limit. `counted_seconds` = used up on this tick.
+fn track_idle_interval(idle_seconds: u32, threshold_seconds: u32, counted_seconds: u32) {
+    let now = database::get_current_timestamp();
+    let since = IDLE_SINCE.load(Ordering::SeqCst);
+    if idle_seconds >= threshold_seconds {
+        if since == 0 {
+            IDLE_SINCE.store(now - idle_seconds as i64, Ordering::SeqCst);
+        }
+    } else if since != 0 {
+        let seconds = (now - idle_seconds as i64 - since).max(0);
+        database::log_idle_interval(seconds, COUNTED_SINCE_INPUT.load(Ordering::SeqCst).min(seconds));
+        IDLE_SINCE.store(0, Ordering::SeqCst);
+    }
+
+    if idle_seconds == 0 {
+        COUNTED_SINCE_INPUT.store(0, Ordering::SeqCst);
+    } else {
+        COUNTED_SINCE_INPUT.fetch_add(counted_seconds as i64, Ordering::SeqCst);
+    }
+}
+
+/// Check idle state and update IS_IDLE_PAUSED accordingly.
+/// `counted_seconds` = how far the timer counted down on this tick.
+fn check_idle_state(counted_seconds: u32) {
+    let idle_seconds = get_idle_seconds();
+    let threshold_seconds = database::get_idle_timeout_minutes() * 60;
+
+    // Idle intervals are logged even with idle detection off, so the stats show what was counted
+    track_idle_interval(idle_seconds, threshold_seconds, counted_seconds);
+
+    // Skip if idle detection is disabled
+    if !database::is_idle_enabled() {
+        // If it was idle-paused but feature got disabled, resume
+        if IS_IDLE_PAUSED.load(Ordering::SeqCst) {
+            IS_IDLE_PAUSED.store(false, Ordering::SeqCst);
+        }
+        return;
+    }
+
+    let currently_idle_paused = IS_IDLE_PAUSED.load(Ordering::SeqCst);
+
+    if idle_seconds >= threshold_seconds {
+        // User is idle - pause if not already paused (manual or idle)
+        if !currently_idle_paused && !IS_PAUSED.load(Ordering::SeqCst) {
+            IS_IDLE_PAUSED.store(true, Ordering::SeqCst);
+        }
+    } else if currently_idle_paused {
+        // User is back - resume from idle pause
+        IS_IDLE_PAUSED.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Check if timer is currently idle-paused
+pub fn is_idle_paused() -> bool {
+    IS_IDLE_PAUSED.load(Ordering::SeqCst)
+}
+
+/// Draw the countdown into `rect` with the given font height. Both display modes
+/// ("mini" and "large") paint through this; only the window and font size differ.
+unsafe fn draw_countdown(hwnd: HWND, hdc: HDC, mut rect: RECT, font_height: i32) {
+    let paused = IS_PAUSED.load(Ordering::SeqCst);
+    let idle_paused = IS_IDLE_PAUSED.load(Ordering::SeqCst);
+    let focus = FOCUS_MODE.load(Ordering::SeqCst);
+
+    // Get remaining time and pause info
+    let remaining = REMAINING_SECONDS.load(Ordering::SeqCst);
+
+    // Wind-down: swap to a red background every other second
+    let pulse = !focus && !paused && !idle_paused && in_winddown(remaining) && remaining % 2 == 0;
+
+    // Background color changes when paused
+    let simulating = is_simulating();
+    let bg_color = if focus {
+        0x00113311 // Dark green in focus mode
+    } else if paused {
+        0x00332200 // Brownish when manually paused
+    } else if idle_paused {
+        0x00333333 // Grey when idle-paused
+    } else if pulse {
+        0x00000066 // Dark red on wind-down pulse
+    } else if simulating {
+        0x00442244 // Purple during a simulation
+    } else {
+        0x00222222 // Normal
+    };
+    let bg_brush = CreateSolidBrush(COLORREF(theme::bg(bg_color)));
+    FillRect(hdc, &rect, bg_brush);
+    let _ = DeleteObject(bg_brush);
+
+    // White outline in high-contrast mode (paused/idle states can't rely on background tint)
+    if theme::is_high_contrast() {
+        let border_brush = CreateSolidBrush(COLORREF(theme::fg(COLOR_TEXT_WHITE)));
+        let mut border_rect = rect;
+        for _ in 0..theme::outline(1) {
+            FrameRect(hdc, &border_rect, border_brush);
+            let _ = InflateRect(&mut border_rect, -1, -1);
+        }
+        let _ = DeleteObject(border_brush);
+    }
+
+    let pause_indicator = if paused { database::get_pause_indicator() } else { String::new() };
+    let time_format = database::get_mini_overlay_format();
+
+    let (display_text, color) = if focus {
+        // Format: "F 0:45" (focus time left, or the remaining time if it runs until turned off)
+        let seconds = focus_seconds_left().map(|s| s as i32).unwrap_or(remaining);
+        (format!("F {}", format_time_as(seconds, &time_format, None)), theme::fg(0x0066DD66)) // Green for focus
+    } else if paused {
+        // Show pause indicator and remaining pause time
+        let pause_duration = CURRENT_PAUSE_DURATION.load(Ordering::SeqCst);
+        let max_duration = get_max_pause_duration();
+        let pause_remaining = max_duration - pause_duration;
+
+        // Format: "II 0:45" (pause symbol + remaining pause time); the bars are drawn below
+        let pause_time_str = format_time_as(pause_remaining, &time_format, None);
+        let text = if pause_indicator == "text" { format!("II {}", pause_time_str) } else { pause_time_str };
+        (text, theme::fg(0x0066CCFF)) // Cyan/light blue for paused
+    } else if idle_paused {
+        // Show idle indicator with remaining time
+        let time_str = format_remaining(remaining);
+        (format!("ZZ {}", time_str), theme::fg(0x00888888)) // Grey/muted for idle
+    } else {
+        // Normal display
+        let time_str = format_remaining(remaining);
+        let color = if pulse { theme::fg(COLOR_TEXT_WHITE) } else { get_time_color(remaining) };
+        (time_str, color)
+    };
+    // Format: "SIM 0:45" so a simulation is never mistaken for the real time
+    let display_text = if simulating { format!("SIM {}", display_text) } else { display_text };
+
+    // Draw time (scaled font, ClearType quality = 5)
+    let hfont = CreateFontW(
+        font_height, 0, 0, 0,
+        FW_BOLD.0 as i32,
+        0, 0, 0, 0, 0, 0, 5, 0,
+        w!("Consolas"),
+    );
+
+    let old_font = SelectObject(hdc, hfont);
+    SetTextColor(hdc, COLORREF(color));
+    SetBkMode(hdc, TRANSPARENT);
+
+    let wide_text: Vec<u16> = display_text.encode_utf16().collect();
+    let mut format = DT_CENTER | DT_VCENTER | DT_SINGLELINE;
+
+    // Widen the window when the text doesn't fit (long fractions, the idle prefix);
+    // it stays in its corner and the new size repaints it
+    let mut measure_rect = rect;
+    DrawTextW(hdc, &mut wide_text.clone(), &mut measure_rect, DT_CALCRECT | DT_SINGLELINE);
+    let sign_extra = if paused && pause_indicator == "bars" { (font_height / 7).max(2) * 5 } else { 0 };
+    let needed = measure_rect.right - measure_rect.left + sign_extra + mini_scale(MINI_HEIGHT_BASE) / 2;
+    if needed > rect.right - rect.left {
+        MINI_TEXT_WIDTH.store(needed, Ordering::SeqCst);
+        let (x, y, mini_width, mini_height) = mini_bounds();
+        let _ = SetWindowPos(hwnd, HWND_TOPMOST, x, y, mini_width, mini_height, SWP_NOACTIVATE);
+    }
+
+    // Pause sign as two filled bars, so it doesn't depend on a font having the glyph
+    if paused && pause_indicator == "bars" {
+        let mut text_rect = rect;
+        DrawTextW(hdc, &mut wide_text.clone(), &mut text_rect, DT_CALCRECT | DT_SINGLELINE);
+        let text_width = text_rect.right - text_rect.left;
+
+        let bar_width = (font_height / 7).max(2);
+        let bar_height = font_height * 2 / 3;
+        // Bar, gap, bar, then the same gap again before the time
+        let sign_width = bar_width * 5;
+        let left = rect.left + (rect.right - rect.left - sign_width - text_width) / 2;
+        let top = rect.top + (rect.bottom - rect.top - bar_height) / 2;
+
+        let bar_brush = CreateSolidBrush(COLORREF(color));
+        for x in [left, left + bar_width * 2] {
+            let bar = RECT { left: x, top, right: x + bar_width, bottom: top + bar_height };
+            FillRect(hdc, &bar, bar_brush);
+        }
+        let _ = DeleteObject(bar_brush);
+
+        rect.left = left + sign_width;
+        format = DT_VCENTER | DT_SINGLELINE;
+    }
+
+    DrawTextW(hdc, &mut wide_text.clone(), &mut rect, format);
+
+    SelectObject(hdc, old_font);
+    let _ = DeleteObject(hfont);
+}
+
+/// Window procedure for the mini overlay
+pub unsafe extern "system" fn mini_overlay_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_PAINT => {
+            let mut ps: PAINTSTRUCT = zeroed();
+            let hdc = BeginPaint(hwnd, &mut ps);
+
+            let mut rect: RECT = zeroed();
+            GetClientRect(hwnd, &mut rect).ok();
+
+            // Same drawing in both display modes, with the font scaled to the window
+            let font_base = if theme::is_high_contrast() { MINI_FONT_BASE * 5 / 4 } else { MINI_FONT_BASE };
+            draw_countdown(hwnd, hdc, rect, mini_scale(font_base));
+
+            let _ = EndPaint(hwnd, &ps);
+            LRESULT(0)
+        }
+        WM_TIMER => {
+            if wparam.0 == TIMER_MINI_UPDATE {
+                countdown_tick();
+                let _ = InvalidateRect(hwnd, None, true);
+            }
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+/// One tick of the countdown timer (every second). The timer runs on the mini overlay window,
+/// or headless on the main window, which has no overlay to show.
+pub unsafe fn countdown_tick() {
+    // A changed clock is reported (and blocks the screen if set up to)
+    if crate::security::check() {
+        return;
+    }
+
+    // Start fresh counters when the date changes (before anything is saved)
+    check_day_rollover();
+
+    // Whole seconds that really passed (the timer can fire late or be throttled)
+    let seconds = take_elapsed_seconds();
+
+    // Outside the allowed hours the screen is blocked and no time is counted
+    if crate::curfew::check_curfew() {
+        return;
+    }
+
+    // A simulation counts sim_speed seconds for every real one
+    let speed = sim_speed();
+    let mut counted_seconds = 0;
+    let mut save_due = false;
+    for _ in 0..seconds {
+        for _ in 0..speed {
+            counted_seconds += count_second() as u32;
+        }
+        save_due |= autosave_due(&TICKS_SINCE_SAVE, database::get_autosave_interval_seconds());
+    }
+    // Idle statistics are kept in real seconds
+    let counted_seconds = counted_seconds / speed;
+
+    // Persist periodically (including a running pause) so a crash loses at most one interval
+    if save_due {
+        save_counters();
+    }
+
+    // Always check idle state (even during manual pause, to track transitions)
+    check_idle_state(counted_seconds);
+
+    // Let the user know if the database stopped working (settings fall back to defaults)
+    if database::take_db_error() {
+        crate::tray::show_balloon(
+            crate::i18n::t("db.unavailable_title"),
+            crate::i18n::t("db.unavailable"),
+        );
+    }
+
+    // A wrong bot token would otherwise leave the parent thinking Telegram works
+    if crate::telegram::take_bot_error() {
+        crate::tray::show_balloon(
+            crate::i18n::t("tg.invalid_token_title"),
+            crate::i18n::t("tg.state.invalid_token"),
+        );
+    }
+}
+
+/// Start the countdown on the main window when running headless (there's no mini overlay)
+pub unsafe fn start_headless_countdown(hwnd: HWND) {
+    LAST_TICK_MS.store(0, Ordering::SeqCst);
+    let _ = SetTimer(hwnd, TIMER_MINI_UPDATE, 1000, None);
+}
+
+/// Register the mini overlay window class
+pub unsafe fn register_mini_overlay_class(hinstance: windows::Win32::Foundation::HMODULE) {
+    let class_name = w!("ScreenTimeMiniOverlayClass");
+    let wnd_class = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(mini_overlay_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: class_name,
+        hbrBackground: CreateSolidBrush(COLORREF(0x00222222)),
+        ..zeroed()
+    };
+
+    if RegisterClassW(&wnd_class) == 0 {
+        panic!("Failed to register mini overlay window class");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn periodic_save_comes_every_interval_ticks() {
+        // The save is driven by ticks alone, so an extension (which changes the remaining
+        // time, not the tick count) can't push it back
+        let interval = 15;
+        let ticks = AtomicU32::new(0);
+
+        let first_save = (1..=interval * 2).find(|_| autosave_due(&ticks, interval));
+        assert_eq!(first_save, Some(interval));
+        assert_eq!(ticks.load(Ordering::SeqCst), 0);
+
+        // And keeps saving every interval from then on
+        let saves = (0..interval * 3).filter(|_| autosave_due(&ticks, interval)).count();
+        assert_eq!(saves, 3);
+    }
+
+    #[test]
+    fn pause_running_at_midnight_counts_for_the_day_it_began() {
+        use crate::clock::{with_clock, MockClock};
+        // Thursday 00:05, a pause started ten minutes earlier on Wednesday
+        let midnight = MockClock { timestamp: 1_800_000_000, ..MockClock::at(2026, 10, 15, 4, 0, 5) };
+
+        database::with_test_database(|_| {
+            *CURRENT_DATE.lock().unwrap() = Some("2026-10-14".to_string());
+            PAUSE_START_TIMESTAMP.store(1_800_000_000 - 600, Ordering::SeqCst);
+            IS_PAUSED.store(true, Ordering::SeqCst);
+
+            with_clock(midnight, || {
+                assert!(check_day_rollover());
+                assert!(!IS_PAUSED.load(Ordering::SeqCst));
+                assert_eq!(database::get_pause_used("2026-10-14"), 600);
+                assert_eq!(database::get_pause_used_today(), 0);
+                assert_eq!(CURRENT_DATE.lock().unwrap().as_deref(), Some("2026-10-15"));
+            });
+            *CURRENT_DATE.lock().unwrap() = None;
+        });
+    }
+
+    #[test]
+    fn countdown_follows_the_monotonic_clock() {
+        // On time: one second per tick
+        assert_eq!(elapsed_seconds(10_000, 11_000), (1, 11_000));
+
+        // A throttled tick catches up, and the leftover 500 ms count towards the next one
+        assert_eq!(elapsed_seconds(10_000, 12_500), (2, 12_000));
+        assert_eq!(elapsed_seconds(12_000, 13_000), (1, 13_000));
+
+        // A tick that fires early counts nothing yet and loses nothing
+        assert_eq!(elapsed_seconds(10_000, 10_990), (0, 10_000));
+        assert_eq!(elapsed_seconds(10_000, 11_980), (1, 11_000));
+
+        // Many uneven ticks add up to the real elapsed time
+        let (mut last, mut total) = (1_000u64, 0u32);
+        for now in (1..=100u64).map(|i| 1_000 + i * 1_037) {
+            let (seconds, next) = elapsed_seconds(last, now);
+            total += seconds;
+            last = next;
+        }
+        assert_eq!(total, (100 * 1_037 / 1000) as u32);
+    }
+
+    #[test]
+    fn countdown_restarts_after_a_long_gap() {
+        // First tick, and a gap longer than the catch-up limit (e.g. sleep), count once
+        assert_eq!(elapsed_seconds(0, 5_000), (1, 5_000));
+        assert_eq!(elapsed_seconds(10_000, 10_000 + (MAX_CATCH_UP_SECONDS + 1) * 1000), (1, 41_000));
+        assert_eq!(elapsed_seconds(10_000, 10_000 + MAX_CATCH_UP_SECONDS * 1000), (MAX_CATCH_UP_SECONDS as u32, 40_000));
+    }
+}
@@ -0,0 +1,392 @@
+//! MQTT module
+//! Publishes the timer state for home automation (e.g. Home Assistant) and accepts
+//! extend/pause/resume/lock commands. A minimal MQTT 3.1.1 client (QoS 0) over TCP or TLS.
+
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use crate::blocking::{ExtendSource, REMAINING_SECONDS};
+use crate::database;
+use crate::mini_overlay;
+
+/// Seconds the broker waits for a packet before dropping us (we ping at half of it)
+const KEEP_ALIVE_SECONDS: u16 = 60;
+
+/// Read timeout, which is also how often the state is checked for changes
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Timeout for connecting to the broker
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Reconnect delays after a lost or failed connection (doubling, capped)
+const RECONNECT_MIN: Duration = Duration::from_secs(5);
+const RECONNECT_MAX: Duration = Duration::from_secs(5 * 60);
+
+/// Port that means MQTT over TLS
+const TLS_PORT: u16 = 8883;
+
+/// Packet id of our only subscription
+const SUBSCRIBE_PACKET_ID: u16 = 1;
+
+/// A plain or TLS connection to the broker
+trait Stream: Read + Write + Send {}
+impl<T: Read + Write + Send> Stream for T {}
+
+/// Last published values; None = not published on this connection yet
+#[derive(Default)]
+struct Published {
+    remaining: Option<i32>,
+    paused: Option<bool>,
+    limit: Option<i32>,
+}
+
+/// Start the MQTT client in a background thread (if enabled in settings).
+/// It reconnects with backoff whenever the broker is unreachable.
+pub fn start_mqtt_thread() {
+    let config = database::get_mqtt_config();
+    if !config.enabled {
+        log::info!("Disabled in settings");
+        return;
+    }
+    if config.host.is_empty() {
+        log::warn!("Enabled but no broker configured");
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let mut delay = RECONNECT_MIN;
+        loop {
+            let started = Instant::now();
+            if let Err(e) = run_session(&config) {
+                log::warn!("Connection to {}:{} lost: {}", config.host, config.port, e);
+            }
+            // A connection that stayed up for a while starts the backoff over
+            if started.elapsed() > RECONNECT_MAX {
+                delay = RECONNECT_MIN;
+            }
+            std::thread::sleep(delay);
+            delay = (delay * 2).min(RECONNECT_MAX);
+        }
+    });
+}
+
+/// Connect, subscribe to the command topic, then publish changes and handle commands
+/// until the connection fails
+fn run_session(config: &database::MqttConfig) -> std::io::Result<()> {
+    let address = (config.host.as_str(), config.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::new(ErrorKind::NotFound, "broker address could not be resolved"))?;
+    let tcp = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+    tcp.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    tcp.set_write_timeout(Some(CONNECT_TIMEOUT))?;
+    let read_handle = tcp.try_clone()?;
+
+    let mut stream: Box<dyn Stream> = if config.port == TLS_PORT {
+        let tls = native_tls::TlsConnector::new().map_err(std::io::Error::other)?;
+        Box::new(tls.connect(&config.host, tcp).map_err(|e| std::io::Error::other(e.to_string()))?)
+    } else {
+        Box::new(tcp)
+    };
+
+    let availability = format!("{}/available", config.prefix);
+    let password = crate::secrets::unprotect(&config.password).unwrap_or_default();
+    stream.write_all(&connect_packet(&client_id(), &config.username, &password, &availability))?;
+    let (header, body) = read_packet(stream.as_mut())?;
+    if header >> 4 != 2 || body.get(1) != Some(&0) {
+        return Err(std::io::Error::new(
+            ErrorKind::PermissionDenied,
+            format!("broker refused the connection (code {:?})", body.get(1)),
+        ));
+    }
+    log::info!("Connected to {}:{}", config.host, config.port);
+
+    let command_topic = format!("{}/cmd", config.prefix);
+    stream.write_all(&subscribe_packet(&command_topic))?;
+    stream.write_all(&publish_packet(&availability, "online", true))?;
+
+    // From here on the read timeout doubles as the polling interval. It is set on the socket
+    // under the TLS layer, which keeps a partly received record buffered when a read times out,
+    // so waiting for the first byte of a packet can simply be retried.
+    read_handle.set_read_timeout(Some(POLL_INTERVAL))?;
+    let mut published = Published::default();
+    let mut last_sent = Instant::now();
+
+    loop {
+        if publish_state(stream.as_mut(), &config.prefix, &mut published)? {
+            last_sent = Instant::now();
+        }
+        if last_sent.elapsed() >= Duration::from_secs(KEEP_ALIVE_SECONDS as u64 / 2) {
+            stream.write_all(&[0xC0, 0x00])?; // PINGREQ
+            last_sent = Instant::now();
+        }
+
+        let mut first = [0u8; 1];
+        match stream.read(&mut first) {
+            Ok(0) => return Err(std::io::Error::new(ErrorKind::UnexpectedEof, "broker closed the connection")),
+            Ok(_) => {}
+            Err(e) if is_timeout(&e) => continue,
+            Err(e) => return Err(e),
+        }
+        // The rest of the packet is on its way; a poll timeout in the middle of it would
+        // lose the bytes read so far and leave the stream out of step
+        read_handle.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+        let body = read_body(stream.as_mut());
+        read_handle.set_read_timeout(Some(POLL_INTERVAL))?;
+        let body = body?;
+
+        if let Some(command) = incoming_command(first[0], &body, &command_topic) {
+            let reply = crate::telegram::run_remote_command(&command, ExtendSource::Mqtt)
+                .unwrap_or_else(|| format!("unknown command: {}", command.trim()));
+            stream.write_all(&publish_packet(&format!("{}/result", command_topic), &reply, false))?;
+            last_sent = Instant::now();
+        }
+    }
+}
+
+/// The command in a packet from the broker, if it's a PUBLISH to the command topic (SUBACK,
+/// PINGRESP are ignored). Retained messages are dropped: the broker replays them on every
+/// (re)connect, which would run an old "extend 30" again each time.
+fn incoming_command(header: u8, body: &[u8], command_topic: &str) -> Option<String> {
+    if header >> 4 != 3 || header & 0x01 != 0 {
+        return None;
+    }
+    let (topic, payload) = parse_publish(header, body)?;
+    (topic == command_topic).then(|| String::from_utf8_lossy(&payload).into_owned())
+}
+
+/// Whether a read ended because no data arrived in time (the error kind differs by platform
+/// and TLS backend)
+fn is_timeout(e: &std::io::Error) -> bool {
+    matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+}
+
+/// Publish remaining minutes, pause state and today's limit when they changed.
+/// Retained, so Home Assistant sees the current state right after it (re)connects.
+fn publish_state(stream: &mut dyn Stream, prefix: &str, published: &mut Published) -> std::io::Result<bool> {
+    let remaining_seconds = REMAINING_SECONDS.load(Ordering::SeqCst).max(0);
+    let remaining = (remaining_seconds + 59) / 60;
+    let paused = mini_overlay::is_paused();
+    let limit = database::get_effective_daily_limit();
+    let mut sent = false;
+
+    if published.remaining != Some(remaining) {
+        stream.write_all(&publish_packet(&format!("{}/remaining", prefix), &remaining.to_string(), true))?;
+        published.remaining = Some(remaining);
+        sent = true;
+    }
+    if published.paused != Some(paused) {
+        let payload = if paused { "ON" } else { "OFF" };
+        stream.write_all(&publish_packet(&format!("{}/paused", prefix), payload, true))?;
+        published.paused = Some(paused);
+        sent = true;
+    }
+    if published.limit != Some(limit) {
+        stream.write_all(&publish_packet(&format!("{}/limit", prefix), &limit.to_string(), true))?;
+        published.limit = Some(limit);
+        sent = true;
+    }
+    Ok(sent)
+}
+
+/// Client id unique per computer, so two PCs on one broker don't kick each other off
+fn client_id() -> String {
+    let computer = std::env::var("COMPUTERNAME").unwrap_or_default();
+    format!("screen-time-manager-{}", computer.to_ascii_lowercase())
+}
+
+// ============================================================================
+// Packet encoding (MQTT 3.1.1)
+// ============================================================================
+
+/// Append a length-prefixed UTF-8 string
+fn push_string(buffer: &mut Vec<u8>, text: &str) {
+    buffer.extend_from_slice(&(text.len() as u16).to_be_bytes());
+    buffer.extend_from_slice(text.as_bytes());
+}
+
+/// Fixed header (packet type and flags, variable-length "remaining length") plus body
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut packet = vec![header];
+    let mut length = body.len();
+    loop {
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if length == 0 {
+            break;
+        }
+    }
+    packet.extend_from_slice(body);
+    packet
+}
+
+/// CONNECT with a clean session and a retained "offline" last will on the availability topic
+fn connect_packet(client_id: &str, username: &str, password: &str, availability: &str) -> Vec<u8> {
+    let mut flags = 0x02 | 0x04 | 0x20; // Clean session, will, will retain
+    if !username.is_empty() {
+        flags |= 0x80;
+        if !password.is_empty() {
+            flags |= 0x40;
+        }
+    }
+
+    let mut body = Vec::new();
+    push_string(&mut body, "MQTT");
+    body.push(4); // Protocol level 3.1.1
+    body.push(flags);
+    body.extend_from_slice(&KEEP_ALIVE_SECONDS.to_be_bytes());
+    push_string(&mut body, client_id);
+    push_string(&mut body, availability);
+    push_string(&mut body, "offline");
+    if !username.is_empty() {
+        push_string(&mut body, username);
+        if !password.is_empty() {
+            push_string(&mut body, password);
+        }
+    }
+    packet(0x10, &body)
+}
+
+/// SUBSCRIBE to one topic at QoS 0
+fn subscribe_packet(topic: &str) -> Vec<u8> {
+    let mut body = SUBSCRIBE_PACKET_ID.to_be_bytes().to_vec();
+    push_string(&mut body, topic);
+    body.push(0);
+    packet(0x82, &body)
+}
+
+/// PUBLISH at QoS 0
+fn publish_packet(topic: &str, payload: &str, retain: bool) -> Vec<u8> {
+    let mut body = Vec::new();
+    push_string(&mut body, topic);
+    body.extend_from_slice(payload.as_bytes());
+    packet(if retain { 0x31 } else { 0x30 }, &body)
+}
+
+/// Read a whole packet: (first header byte, body)
+fn read_packet(stream: &mut (impl Read + ?Sized)) -> std::io::Result<(u8, Vec<u8>)> {
+    let mut first = [0u8; 1];
+    stream.read_exact(&mut first)?;
+    Ok((first[0], read_body(stream)?))
+}
+
+/// Read the remaining length and the body following a header byte
+fn read_body(stream: &mut (impl Read + ?Sized)) -> std::io::Result<Vec<u8>> {
+    let mut length = 0usize;
+    let mut multiplier = 1usize;
+    loop {
+        let mut byte = [0u8; 1];
+        stream.read_exact(&mut byte)?;
+        length += (byte[0] & 0x7F) as usize * multiplier;
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+        multiplier *= 128;
+        if multiplier > 128 * 128 * 128 {
+            return Err(std::io::Error::new(ErrorKind::InvalidData, "malformed packet length"));
+        }
+    }
+    let mut body = vec![0u8; length];
+    stream.read_exact(&mut body)?;
+    Ok(body)
+}
+
+/// Split an incoming PUBLISH into topic and payload (skipping the packet id for QoS > 0)
+fn parse_publish(header: u8, body: &[u8]) -> Option<(String, Vec<u8>)> {
+    let topic_length = u16::from_be_bytes([*body.first()?, *body.get(1)?]) as usize;
+    let topic = std::str::from_utf8(body.get(2..2 + topic_length)?).ok()?.to_string();
+    let qos = (header >> 1) & 0x03;
+    let payload_start = 2 + topic_length + if qos > 0 { 2 } else { 0 };
+    Some((topic, body.get(payload_start..)?.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn remaining_length_uses_continuation_bytes() {
+        assert_eq!(packet(0xC0, &[]), vec![0xC0, 0x00]);
+        assert_eq!(&packet(0x30, &[0; 127])[..2], &[0x30, 0x7F]);
+        assert_eq!(&packet(0x30, &[0; 128])[..3], &[0x30, 0x80, 0x01]);
+        assert_eq!(&packet(0x30, &[0; 16_384])[..4], &[0x30, 0x80, 0x80, 0x01]);
+    }
+
+    #[test]
+    fn packets_read_back_as_written() {
+        for body_length in [0, 5, 127, 128, 300, 20_000] {
+            let payload = "x".repeat(body_length);
+            let encoded = publish_packet("stm/remaining", &payload, true);
+            let (header, body) = read_packet(&mut Cursor::new(encoded)).unwrap();
+            assert_eq!(header, 0x31);
+            let (topic, read_payload) = parse_publish(header, &body).unwrap();
+            assert_eq!(topic, "stm/remaining");
+            assert_eq!(read_payload, payload.as_bytes());
+        }
+    }
+
+    #[test]
+    fn connect_packet_carries_will_and_credentials() {
+        let encoded = connect_packet("stm-pc", "ha", "secret", "stm/available");
+        let (header, body) = read_packet(&mut Cursor::new(encoded)).unwrap();
+        assert_eq!(header, 0x10);
+        assert_eq!(&body[..7], b"\x00\x04MQTT\x04");
+        assert_eq!(body[7], 0x80 | 0x40 | 0x20 | 0x04 | 0x02);
+        assert_eq!(&body[8..10], &KEEP_ALIVE_SECONDS.to_be_bytes());
+
+        let mut expected = Vec::new();
+        for field in ["stm-pc", "stm/available", "offline", "ha", "secret"] {
+            push_string(&mut expected, field);
+        }
+        assert_eq!(&body[10..], &expected[..]);
+
+        // Without a user neither user nor password is sent
+        let (_, anonymous) = read_packet(&mut Cursor::new(connect_packet("stm-pc", "", "secret", "a"))).unwrap();
+        assert_eq!(anonymous[7], 0x20 | 0x04 | 0x02);
+    }
+
+    #[test]
+    fn subscribe_packet_asks_for_qos_0() {
+        let (header, body) = read_packet(&mut Cursor::new(subscribe_packet("stm/cmd"))).unwrap();
+        assert_eq!(header, 0x82);
+        assert_eq!(&body[..2], &SUBSCRIBE_PACKET_ID.to_be_bytes());
+        assert_eq!(&body[2..], b"\x00\x07stm/cmd\x00");
+    }
+
+    #[test]
+    fn publish_with_qos_1_skips_the_packet_id() {
+        let mut body = Vec::new();
+        push_string(&mut body, "stm/cmd");
+        body.extend_from_slice(&[0x00, 0x2A]);
+        body.extend_from_slice(b"extend 15");
+        let (topic, payload) = parse_publish(0x32, &body).unwrap();
+        assert_eq!(topic, "stm/cmd");
+        assert_eq!(payload, b"extend 15");
+
+        // Truncated packets are rejected instead of read past the end
+        assert_eq!(parse_publish(0x30, &[0x00, 0x09, b's']), None);
+    }
+
+    #[test]
+    fn retained_commands_are_dropped() {
+        let (header, body) = read_packet(&mut Cursor::new(publish_packet("stm/cmd", "extend 30", false))).unwrap();
+        assert_eq!(incoming_command(header, &body, "stm/cmd"), Some("extend 30".to_string()));
+        assert_eq!(incoming_command(header, &body, "stm/other"), None);
+
+        let (header, body) = read_packet(&mut Cursor::new(publish_packet("stm/cmd", "extend 30", true))).unwrap();
+        assert_eq!(incoming_command(header, &body, "stm/cmd"), None);
+    }
+
+    #[test]
+    fn overlong_length_is_rejected() {
+        let result = read_body(&mut Cursor::new(vec![0xFF, 0xFF, 0xFF, 0xFF, 0x01]));
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+}
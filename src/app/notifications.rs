@@ -0,0 +1,100 @@
+//! Notifications module
+//! Events the parent hears about, fanned out to every enabled backend (Telegram, webhook)
+
+use std::sync::atomic::Ordering;
+
+use crate::blocking::{ExtendSource, REMAINING_SECONDS};
+use crate::i18n;
+
+/// Something worth telling the parent about
+#[derive(Clone, Copy, Debug)]
+pub enum Event {
+    /// A configured warning fired with this many minutes left
+    Warning { minutes: u32 },
+    /// The time ran out and the screen is blocked
+    LimitReached,
+    /// The time ran out in warn-only mode (not blocked)
+    LimitReachedWarnOnly,
+    /// The screen was blocked outside the allowed hours
+    Curfew,
+    /// Time was added
+    Extended { minutes: i32, source: ExtendSource },
+    /// Passcode entry was locked out after this many wrong attempts
+    PasscodeLockout { attempts: u32, seconds: i64 },
+    /// The system clock was changed by this many seconds (negative = set back)
+    ClockChanged { seconds: i64 },
+    /// The database failed its integrity check
+    DatabaseDamaged,
+}
+
+impl Event {
+    /// Stable, language-independent event name for machine-readable backends
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::Warning { .. } => "warning",
+            Event::LimitReached => "limit_reached",
+            Event::LimitReachedWarnOnly => "limit_warn_only",
+            Event::Curfew => "curfew",
+            Event::Extended { .. } => "extended",
+            Event::PasscodeLockout { .. } => "passcode_lockout",
+            Event::ClockChanged { .. } => "clock_changed",
+            Event::DatabaseDamaged => "database_damaged",
+        }
+    }
+
+    /// Localized, human-readable description
+    pub fn message(&self) -> String {
+        match self {
+            Event::Warning { minutes } => i18n::t("tg.notify.warning").replace("{}", &minutes.to_string()),
+            Event::LimitReached => i18n::t("tg.notify.limit_reached").to_string(),
+            Event::LimitReachedWarnOnly => i18n::t("tg.notify.limit_warn_only").to_string(),
+            Event::Curfew => i18n::t("tg.notify.curfew").to_string(),
+            Event::Extended { minutes, source } => {
+                let key = match source {
+                    ExtendSource::BlockingOverlay => "tg.notify.extended.overlay",
+                    ExtendSource::Tray => "tg.notify.extended.tray",
+                    ExtendSource::Telegram => "tg.notify.extended.telegram",
+                    ExtendSource::Mqtt => "tg.notify.extended.mqtt",
+                    ExtendSource::SelfService => "tg.notify.extended.self",
+                    ExtendSource::CommandLine => "tg.notify.extended.cli",
+                };
+                i18n::t(key).replace("{}", &minutes.to_string())
+            }
+            Event::PasscodeLockout { attempts, seconds } => i18n::t("tg.notify.passcode_lockout")
+                .replacen("{}", &attempts.to_string(), 1)
+                .replacen("{}", &seconds.to_string(), 1),
+            Event::ClockChanged { seconds } => {
+                let key = if *seconds < 0 { "tg.notify.clock_back" } else { "tg.notify.clock_forward" };
+                i18n::t(key).replace("{}", &(seconds.abs() / 60).to_string())
+            }
+            Event::DatabaseDamaged => i18n::t("tg.notify.database_damaged").to_string(),
+        }
+    }
+
+    fn emoji(&self) -> &'static str {
+        match self {
+            Event::Warning { .. } | Event::LimitReachedWarnOnly => "⚠️",
+            Event::LimitReached => "🔒",
+            Event::Curfew => "🌙",
+            Event::Extended { .. } => "⏳",
+            Event::PasscodeLockout { .. } | Event::DatabaseDamaged => "🚨",
+            Event::ClockChanged { .. } => "🕐",
+        }
+    }
+}
+
+/// Send an event to every enabled backend. Never blocks on the network, so it is safe
+/// to call from the UI thread.
+pub fn dispatch(event: Event) {
+    // A simulation plays out on this PC only
+    if crate::mini_overlay::is_simulating() {
+        return;
+    }
+    let message = event.message();
+
+    // The parent already got a reply for extensions made from Telegram
+    if !matches!(event, Event::Extended { source: ExtendSource::Telegram, .. }) {
+        crate::telegram::enqueue_notification(&format!("{} {}", event.emoji(), message));
+    }
+    crate::webhook::notify(event.kind(), REMAINING_SECONDS.load(Ordering::SeqCst), &message);
+}
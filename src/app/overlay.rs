@@ -0,0 +1,347 @@
+//! Warning overlay module
+//! Displays a click-through banner that auto-hides after a duration
+
+use std::mem::zeroed;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, Ordering};
+use std::sync::Mutex;
+use windows::{
+    core::{w, PCWSTR},
+    Win32::{
+        Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM},
+        Graphics::Gdi::{
+            BeginPaint, CreateFontW, CreateSolidBrush, DeleteObject, EndPaint, FillRect,
+            FrameRect, GetMonitorInfoW, GetStockObject, InflateRect, InvalidateRect, MonitorFromWindow, SelectObject, SetBkMode, SetTextColor, DrawTextW,
+            BLACK_BRUSH, DT_CENTER, DT_SINGLELINE, DT_VCENTER, FW_BOLD, HBRUSH, PAINTSTRUCT,
+            MONITORINFO, MONITOR_DEFAULTTONEAREST, TRANSPARENT,
+        },
+        Media::Audio::{PlaySoundW, SND_ALIAS, SND_ASYNC},
+        System::Threading::GetCurrentProcessId,
+        UI::Shell::{
+            SHQueryUserNotificationState, QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_QUIET_TIME, QUNS_RUNNING_D3D_FULL_SCREEN,
+        },
+        UI::WindowsAndMessaging::*,
+    },
+};
+
+use crate::constants::*;
+use crate::database;
+use crate::dpi::scale;
+use crate::i18n;
+use crate::theme;
+
+/// Global state for overlay window
+pub static OVERLAY_HWND: AtomicPtr<std::ffi::c_void> = AtomicPtr::new(std::ptr::null_mut());
+pub static OVERLAY_TEXT: Mutex<Option<String>> = Mutex::new(None);
+
+/// Timer ID for overlay auto-hide
+pub const TIMER_OVERLAY_HIDE: usize = 1;
+
+/// Timer ID for the fade-out steps after auto-hide
+pub const TIMER_OVERLAY_FADE: usize = 2;
+
+/// Timer ID for refreshing a live countdown every second
+pub const TIMER_OVERLAY_LIVE: usize = 3;
+
+/// The banner shows the remaining time, rebuilt every second
+static LIVE_COUNTDOWN: AtomicBool = AtomicBool::new(false);
+
+/// Interval between fade-out steps in milliseconds
+const FADE_STEP_MS: u32 = 30;
+
+/// Current alpha while fading out
+static FADE_ALPHA: AtomicU32 = AtomicU32::new(0);
+
+/// Layered window opacity (raised to 255 by high contrast or disabled transparency)
+pub const OVERLAY_ALPHA: u8 = 230;
+
+/// Banner bounds (x, y, width, height): full screen width, vertical position from settings
+unsafe fn overlay_bounds() -> (i32, i32, i32, i32) {
+    let screen_width = GetSystemMetrics(SM_CXSCREEN);
+    let screen_height = GetSystemMetrics(SM_CYSCREEN);
+
+    // Apply DPI scaling to height and margin
+    let overlay_height = scale(120);
+    let margin = scale(database::get_warning_margin()).min((screen_height - overlay_height).max(0));
+    let overlay_y = match database::get_warning_position().as_str() {
+        "top" => margin,
+        "bottom" => screen_height - overlay_height - margin,
+        _ => (screen_height - overlay_height) / 2,
+    };
+
+    (0, overlay_y, screen_width, overlay_height)
+}
+
+pub unsafe fn create_overlay_window(hinstance: windows::Win32::Foundation::HMODULE) {
+    let overlay_class_name = w!("ScreenTimeOverlayClass");
+
+    let (overlay_x, overlay_y, overlay_width, overlay_height) = overlay_bounds();
+
+    let ex_style = WS_EX_TOPMOST | WS_EX_LAYERED | WS_EX_TOOLWINDOW | WS_EX_TRANSPARENT;
+
+    let overlay_hwnd = CreateWindowExW(
+        ex_style,
+        overlay_class_name,
+        w!("Screen Time Overlay"),
+        WS_POPUP,
+        overlay_x,
+        overlay_y,
+        overlay_width,
+        overlay_height,
+        None,
+        None,
+        hinstance,
+        None,
+    )
+    .expect("Failed to create overlay window");
+
+    SetLayeredWindowAttributes(overlay_hwnd, COLORREF(0), theme::alpha(OVERLAY_ALPHA), LWA_ALPHA)
+        .expect("Failed to set layered window attributes");
+
+    OVERLAY_HWND.store(overlay_hwnd.0, Ordering::SeqCst);
+}
+
+/// Check if the foreground window is a fullscreen app (game, video, presentation)
+pub unsafe fn is_fullscreen_foreground() -> bool {
+    match SHQueryUserNotificationState() {
+        Ok(state) => {
+            state == QUNS_BUSY || state == QUNS_RUNNING_D3D_FULL_SCREEN || state == QUNS_PRESENTATION_MODE
+        }
+        Err(_) => {
+            // Fallback: the foreground window covers its whole monitor
+            let fg = GetForegroundWindow();
+            if fg.0.is_null() || fg == GetShellWindow() || fg == GetDesktopWindow() {
+                return false;
+            }
+            let mut pid = 0u32;
+            GetWindowThreadProcessId(fg, Some(&mut pid));
+            if pid == GetCurrentProcessId() {
+                return false;
+            }
+
+            let mut rect: RECT = zeroed();
+            let mut mi = MONITORINFO {
+                cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+                ..zeroed()
+            };
+            GetWindowRect(fg, &mut rect).is_ok()
+                && GetMonitorInfoW(MonitorFromWindow(fg, MONITOR_DEFAULTTONEAREST), &mut mi).as_bool()
+                && rect.left <= mi.rcMonitor.left
+                && rect.top <= mi.rcMonitor.top
+                && rect.right >= mi.rcMonitor.right
+                && rect.bottom >= mi.rcMonitor.bottom
+        }
+    }
+}
+
+/// Check if Windows asks apps to stay quiet (Focus Assist / quiet hours, presentation mode)
+pub unsafe fn is_quiet_time() -> bool {
+    matches!(SHQueryUserNotificationState(), Ok(state) if state == QUNS_QUIET_TIME || state == QUNS_PRESENTATION_MODE)
+}
+
+/// Check if non-critical alerts should be held back for Focus Assist right now
+pub unsafe fn should_stay_quiet() -> bool {
+    database::is_respect_focus_assist() && is_quiet_time()
+}
+
+/// Play a system sound for a warning, unless Focus Assist asks for quiet
+pub unsafe fn play_alert_sound(alias: PCWSTR) {
+    if should_stay_quiet() {
+        return;
+    }
+    let _ = PlaySoundW(alias, None, SND_ALIAS | SND_ASYNC);
+}
+
+/// Check if the blocking overlay is up; warnings are not shown on top of it
+pub fn is_blocking_active() -> bool {
+    let blocking = HWND(crate::blocking::BLOCKING_HWND.load(Ordering::SeqCst));
+    !blocking.0.is_null() && unsafe { IsWindowVisible(blocking) }.as_bool()
+}
+
+/// Banner text of a live countdown, e.g. "04:59 remaining"
+fn live_countdown_text() -> String {
+    let seconds = crate::blocking::REMAINING_SECONDS.load(Ordering::SeqCst);
+    i18n::t("overlay.live_countdown").replace("{}", &crate::timefmt::format_countdown(seconds))
+}
+
+/// Shows the warning overlay with the specified text for a given duration.
+/// With `live_countdown` the banner shows the remaining time instead, counting down every
+/// second; `text` is still used for the notification shown in place of the banner.
+pub unsafe fn show_overlay(text: &str, duration_seconds: u32, live_countdown: bool) {
+    let overlay_hwnd = HWND(OVERLAY_HWND.load(Ordering::SeqCst));
+    if overlay_hwnd.0.is_null() || is_blocking_active() {
+        return;
+    }
+
+    // Focus Assist is on: a silent notification (Windows may hold it back) instead of the
+    // banner. Only warnings are downgraded; blocking at zero still happens
+    if should_stay_quiet() {
+        crate::tray::show_quiet_balloon(i18n::t("overlay.warning_title"), text);
+        return;
+    }
+
+    // Don't cover a fullscreen game or video; a tray notification and a short sound instead
+    if database::is_defer_warnings_fullscreen() && is_fullscreen_foreground() {
+        play_alert_sound(w!("SystemAsterisk"));
+        crate::tray::show_balloon(i18n::t("overlay.warning_title"), text);
+        return;
+    }
+
+    // A new warning may arrive mid-fade: stop fading and restore full opacity
+    let _ = KillTimer(overlay_hwnd, TIMER_OVERLAY_FADE);
+    let _ = SetLayeredWindowAttributes(overlay_hwnd, COLORREF(0), theme::alpha(OVERLAY_ALPHA), LWA_ALPHA);
+
+    LIVE_COUNTDOWN.store(live_countdown, Ordering::SeqCst);
+    if live_countdown {
+        *OVERLAY_TEXT.lock().unwrap() = Some(live_countdown_text());
+        let _ = SetTimer(overlay_hwnd, TIMER_OVERLAY_LIVE, 1000, None);
+    } else {
+        *OVERLAY_TEXT.lock().unwrap() = Some(text.to_string());
+        let _ = KillTimer(overlay_hwnd, TIMER_OVERLAY_LIVE);
+    }
+    let _ = InvalidateRect(overlay_hwnd, None, true);
+
+    // Position may have changed in settings since the last warning
+    let (x, y, width, height) = overlay_bounds();
+    SetWindowPos(
+        overlay_hwnd,
+        HWND_TOPMOST,
+        x, y, width, height,
+        SWP_SHOWWINDOW,
+    ).ok();
+
+    let _ = ShowWindow(overlay_hwnd, SW_SHOWNOACTIVATE);
+    play_alert_sound(w!("SystemExclamation"));
+    let _ = SetTimer(overlay_hwnd, TIMER_OVERLAY_HIDE, duration_seconds * 1000, None);
+}
+
+pub unsafe fn hide_overlay() {
+    let overlay_hwnd = HWND(OVERLAY_HWND.load(Ordering::SeqCst));
+    if overlay_hwnd.0.is_null() {
+        return;
+    }
+
+    let _ = KillTimer(overlay_hwnd, TIMER_OVERLAY_HIDE);
+    let _ = KillTimer(overlay_hwnd, TIMER_OVERLAY_FADE);
+    let _ = KillTimer(overlay_hwnd, TIMER_OVERLAY_LIVE);
+    LIVE_COUNTDOWN.store(false, Ordering::SeqCst);
+    let _ = ShowWindow(overlay_hwnd, SW_HIDE);
+    *OVERLAY_TEXT.lock().unwrap() = None;
+
+    // Restore opacity so the next warning does not start semi-transparent
+    let _ = SetLayeredWindowAttributes(overlay_hwnd, COLORREF(0), theme::alpha(OVERLAY_ALPHA), LWA_ALPHA);
+}
+
+/// Fade the warning overlay out, then hide it (hides instantly if fading is off)
+unsafe fn fade_out_overlay(overlay_hwnd: HWND) {
+    let _ = KillTimer(overlay_hwnd, TIMER_OVERLAY_HIDE);
+    if database::get_warning_fade_ms() == 0 || !IsWindowVisible(overlay_hwnd).as_bool() {
+        hide_overlay();
+        return;
+    }
+
+    FADE_ALPHA.store(theme::alpha(OVERLAY_ALPHA) as u32, Ordering::SeqCst);
+    let _ = SetTimer(overlay_hwnd, TIMER_OVERLAY_FADE, FADE_STEP_MS, None);
+}
+
+/// One fade-out step: lower the alpha proportionally to the configured duration
+unsafe fn fade_step(overlay_hwnd: HWND) {
+    let steps = (database::get_warning_fade_ms() / FADE_STEP_MS).max(1);
+    let decrement = (theme::alpha(OVERLAY_ALPHA) as u32).div_ceil(steps);
+    let alpha = FADE_ALPHA.load(Ordering::SeqCst).saturating_sub(decrement);
+    FADE_ALPHA.store(alpha, Ordering::SeqCst);
+
+    if alpha == 0 {
+        hide_overlay();
+    } else {
+        let _ = SetLayeredWindowAttributes(overlay_hwnd, COLORREF(0), alpha as u8, LWA_ALPHA);
+    }
+}
+
+pub unsafe extern "system" fn overlay_window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_PAINT => {
+            let mut ps: PAINTSTRUCT = zeroed();
+            let hdc = BeginPaint(hwnd, &mut ps);
+
+            let mut rect: RECT = zeroed();
+            GetClientRect(hwnd, &mut rect).ok();
+
+            let bg_brush = CreateSolidBrush(COLORREF(theme::bg(0x00003366)));
+            FillRect(hdc, &rect, bg_brush);
+            let _ = DeleteObject(bg_brush);
+
+            // Bold outline so the banner edge stands out in high-contrast mode
+            if theme::is_high_contrast() {
+                let border_brush = CreateSolidBrush(COLORREF(theme::fg(COLOR_TEXT_WHITE)));
+                let width = theme::outline(2);
+                for _ in 0..width {
+                    FrameRect(hdc, &rect, border_brush);
+                    let _ = InflateRect(&mut rect, -1, -1);
+                }
+                let _ = DeleteObject(border_brush);
+            }
+
+            let overlay_text_guard = OVERLAY_TEXT.lock().unwrap();
+            if let Some(ref text) = *overlay_text_guard {
+                let hfont = CreateFontW(
+                    theme::font(72), 0, 0, 0,
+                    FW_BOLD.0 as i32,
+                    0, 0, 0, 0, 0, 0, 5, 0,
+                    w!("Segoe UI"),
+                );
+
+                let old_font = SelectObject(hdc, hfont);
+                SetTextColor(hdc, COLORREF(theme::fg(COLOR_TEXT_WHITE)));
+                SetBkMode(hdc, TRANSPARENT);
+
+                let wide_text: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+                DrawTextW(
+                    hdc,
+                    &mut wide_text[..wide_text.len() - 1].to_vec(),
+                    &mut rect,
+                    DT_CENTER | DT_VCENTER | DT_SINGLELINE,
+                );
+
+                SelectObject(hdc, old_font);
+                let _ = DeleteObject(hfont);
+            }
+
+            let _ = EndPaint(hwnd, &ps);
+            LRESULT(0)
+        }
+        WM_TIMER => {
+            match wparam.0 {
+                TIMER_OVERLAY_HIDE => fade_out_overlay(hwnd),
+                TIMER_OVERLAY_FADE => fade_step(hwnd),
+                TIMER_OVERLAY_LIVE if LIVE_COUNTDOWN.load(Ordering::SeqCst) => {
+                    *OVERLAY_TEXT.lock().unwrap() = Some(live_countdown_text());
+                    let _ = InvalidateRect(hwnd, None, true);
+                }
+                _ => {}
+            }
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+pub unsafe fn register_overlay_class(hinstance: windows::Win32::Foundation::HMODULE) {
+    let overlay_class_name = w!("ScreenTimeOverlayClass");
+    let overlay_wnd_class = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(overlay_window_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: overlay_class_name,
+        hbrBackground: HBRUSH(GetStockObject(BLACK_BRUSH).0),
+        ..zeroed()
+    };
+
+    if RegisterClassW(&overlay_wnd_class) == 0 {
+        panic!("Failed to register overlay window class");
+    }
+}
@@ -0,0 +1,44 @@
+//! Secrets module
+//! Passwords in the settings table, encrypted for the current Windows user (DPAPI)
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use windows::Win32::{
+    Foundation::{LocalFree, HLOCAL},
+    Security::Cryptography::{CryptProtectData, CryptUnprotectData, CRYPT_INTEGER_BLOB},
+};
+
+/// Prefix of a stored password encrypted with DPAPI
+const PROTECTED_PREFIX: &str = "dpapi:";
+
+/// Encrypt a password for this Windows user (DPAPI) so it isn't stored in plain text
+pub fn protect(password: &str) -> String {
+    if password.is_empty() {
+        return String::new();
+    }
+    let input = CRYPT_INTEGER_BLOB { cbData: password.len() as u32, pbData: password.as_ptr() as *mut u8 };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        if CryptProtectData(&input, None, None, None, None, 0, &mut output).is_err() {
+            log::error!("Encrypting a password failed");
+            return String::new();
+        }
+        let encrypted = std::slice::from_raw_parts(output.pbData, output.cbData as usize);
+        let stored = format!("{}{}", PROTECTED_PREFIX, BASE64.encode(encrypted));
+        let _ = LocalFree(HLOCAL(output.pbData as _));
+        stored
+    }
+}
+
+/// Decrypt a password stored by `protect`
+pub fn unprotect(stored: &str) -> Option<String> {
+    let encrypted = BASE64.decode(stored.strip_prefix(PROTECTED_PREFIX)?).ok()?;
+    let input = CRYPT_INTEGER_BLOB { cbData: encrypted.len() as u32, pbData: encrypted.as_ptr() as *mut u8 };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptUnprotectData(&input, None, None, None, None, 0, &mut output).ok()?;
+        let plain = std::slice::from_raw_parts(output.pbData, output.cbData as usize);
+        let password = String::from_utf8_lossy(plain).into_owned();
+        let _ = LocalFree(HLOCAL(output.pbData as _));
+        Some(password)
+    }
+}
@@ -0,0 +1,287 @@
+//! Security module
+//! Watches for signs of circumvention (the system clock being changed, a damaged database)
+//! and reports them to the parent through the notification backends.
+//! Clock changes are measured in UTC against the monotonic clock (GetTickCount64, which keeps
+//! counting through sleep) and undone for all time accounting via the clock module. A time
+//! zone or daylight saving change leaves UTC alone, so it never counts as a clock change.
+
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use windows::{
+    core::w,
+    Win32::System::Registry::{
+        RegCloseKey, RegCreateKeyExW, RegGetValueW, RegSetValueExW, HKEY, HKEY_CURRENT_USER,
+        KEY_SET_VALUE, REG_OPTION_VOLATILE, REG_QWORD, RRF_RT_REG_QWORD,
+    },
+    Win32::System::SystemInformation::GetTickCount64,
+};
+
+use crate::database;
+use crate::i18n;
+use crate::notifications::{self, Event};
+
+/// Difference between wall-clock and monotonic time that counts as a clock change.
+/// Smaller steps are left alone: time synchronization corrects drift by seconds (and slews
+/// offsets under a few minutes gradually instead of stepping), and the monotonic clock
+/// itself drifts a little against real time.
+const CLOCK_JUMP_THRESHOLD_SECONDS: i64 = 120;
+
+/// How often the database integrity is checked while running
+const INTEGRITY_CHECK_INTERVAL_MS: u64 = 60 * 60 * 1000;
+
+/// Monotonic reading (GetTickCount64, ms) of the last clock check (0 = no baseline yet)
+static LAST_CHECK_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Wall-clock Unix timestamp of the last clock check
+static LAST_CHECK_TIMESTAMP: AtomicI64 = AtomicI64::new(0);
+
+/// Monotonic reading of the last integrity check
+static LAST_INTEGRITY_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Clock change found at startup, reported on the first tick (once the notification
+/// backends are running)
+static STARTUP_JUMP: AtomicI64 = AtomicI64::new(0);
+
+/// A damaged database is reported once per run
+static INTEGRITY_REPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Volatile registry key (gone after a reboot) holding the boot time by the corrected clock,
+/// so a restart of the app in the same Windows session still sees an earlier clock change
+pub const BOOT_KEY: windows::core::PCWSTR = w!("Software\\ScreenTimeManagerSession");
+
+/// Seconds the wall clock moved beyond the monotonic time between two readings
+/// (negative = set back). Readings are (monotonic ms, Unix timestamp in UTC).
+fn clock_jump(last: (u64, i64), now: (u64, i64)) -> i64 {
+    let monotonic = (now.0.saturating_sub(last.0) / 1000) as i64;
+    let wall = now.1 - last.1;
+    wall - monotonic
+}
+
+/// Whether a jump is a change of the clock rather than a synchronization step
+fn is_clock_change(jump: i64) -> bool {
+    jump.abs() >= CLOCK_JUMP_THRESHOLD_SECONDS
+}
+
+/// Current Unix timestamp in UTC straight from the system clock, without the clock guard's
+/// correction, so every reading measures the system clock against the same baseline
+fn system_timestamp() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Clock change found at startup. `saved_boot_time` is the boot time by the corrected clock
+/// from earlier in this Windows session (None after a reboot); after a reboot only a clock
+/// set back before `last_seen` (the newest timestamp saved) can be told apart.
+fn startup_clock_jump(saved_boot_time: Option<i64>, last_seen: i64, now_ms: u64, now: i64) -> i64 {
+    match saved_boot_time {
+        Some(saved) => now - (now_ms / 1000) as i64 - saved,
+        None => (now - last_seen).min(0),
+    }
+}
+
+/// Forget the clock baseline; after sleep the monotonic clock may not have counted the time asleep
+pub fn reset_clock_baseline() {
+    LAST_CHECK_MS.store(0, Ordering::SeqCst);
+}
+
+/// Look for a clock change made while the app wasn't running; call once before the
+/// counters are loaded
+pub unsafe fn check_clock_at_startup() {
+    let now_ms = GetTickCount64();
+    let now = database::get_current_timestamp();
+    let jump = startup_clock_jump(read_boot_time(), database::get_clock_last_seen(), now_ms, now);
+    if is_clock_change(jump) {
+        log::warn!("System clock changed by {}s while the app was closed", jump);
+        STARTUP_JUMP.store(jump, Ordering::SeqCst);
+        if database::is_clock_guard_enabled() {
+            crate::clock::add_offset(jump);
+        }
+    }
+    let now = database::get_current_timestamp();
+    write_boot_time(now - (now_ms / 1000) as i64);
+    LAST_CHECK_MS.store(now_ms, Ordering::SeqCst);
+    LAST_CHECK_TIMESTAMP.store(system_timestamp(), Ordering::SeqCst);
+}
+
+/// Remember the corrected time, so a clock set back while the app is closed can be noticed
+pub fn save_clock_baseline() {
+    database::set_setting("clock_last_seen", &database::get_current_timestamp().to_string());
+}
+
+/// Boot time saved earlier in this Windows session
+unsafe fn read_boot_time() -> Option<i64> {
+    let mut value: i64 = 0;
+    let mut size = std::mem::size_of::<i64>() as u32;
+    RegGetValueW(
+        HKEY_CURRENT_USER,
+        BOOT_KEY,
+        w!("BootTime"),
+        RRF_RT_REG_QWORD,
+        None,
+        Some(&mut value as *mut i64 as *mut _),
+        Some(&mut size),
+    )
+    .ok()
+    .ok()
+    .map(|_| value)
+}
+
+unsafe fn write_boot_time(boot_time: i64) {
+    let mut key = HKEY::default();
+    let created = RegCreateKeyExW(
+        HKEY_CURRENT_USER,
+        BOOT_KEY,
+        0,
+        None,
+        REG_OPTION_VOLATILE,
+        KEY_SET_VALUE,
+        None,
+        &mut key,
+        None,
+    );
+    if created.is_err() {
+        log::warn!("Could not create the session key: {:?}", created);
+        return;
+    }
+    let _ = RegSetValueExW(key, w!("BootTime"), 0, REG_QWORD, Some(&boot_time.to_le_bytes()));
+    let _ = RegCloseKey(key);
+}
+
+/// Run the periodic checks; called on every countdown tick.
+/// Returns true if the screen was blocked because the clock was changed.
+pub unsafe fn check() -> bool {
+    // With the guard off, time follows the system clock again
+    let offset = crate::clock::offset();
+    if offset != 0 && !database::is_clock_guard_enabled() {
+        crate::clock::add_offset(-offset);
+        LAST_CHECK_MS.store(0, Ordering::SeqCst);
+        write_boot_time(database::get_current_timestamp() - (GetTickCount64() / 1000) as i64);
+    }
+    let startup_jump = STARTUP_JUMP.swap(0, Ordering::SeqCst);
+    if startup_jump != 0 {
+        notifications::dispatch(Event::ClockChanged { seconds: startup_jump });
+    }
+    let now_ms = GetTickCount64();
+    check_integrity(now_ms);
+    check_clock(now_ms)
+}
+
+/// Compare the wall clock against the monotonic clock and report a jump
+unsafe fn check_clock(now_ms: u64) -> bool {
+    let now = (now_ms, system_timestamp());
+    let last = (
+        LAST_CHECK_MS.swap(now.0, Ordering::SeqCst),
+        LAST_CHECK_TIMESTAMP.swap(now.1, Ordering::SeqCst),
+    );
+    if last.0 == 0 {
+        return false;
+    }
+    let jump = clock_jump(last, now);
+    if !is_clock_change(jump) {
+        return false;
+    }
+
+    log::warn!("System clock changed by {}s", jump);
+    notifications::dispatch(Event::ClockChanged { seconds: jump });
+    // Keep counting by the real time: no new day or way around the allowed hours
+    if database::is_clock_guard_enabled() {
+        crate::clock::add_offset(jump);
+    }
+    if !database::is_clock_change_block() {
+        return false;
+    }
+    database::log_block_event("clock");
+    crate::blocking::show_blocking_overlay(i18n::t("security.clock_blocked"), "clock");
+    true
+}
+
+/// Run SQLite's quick check once an hour and report a failure (once)
+fn check_integrity(now_ms: u64) {
+    let last = LAST_INTEGRITY_MS.load(Ordering::SeqCst);
+    if last != 0 && now_ms.saturating_sub(last) < INTEGRITY_CHECK_INTERVAL_MS {
+        return;
+    }
+    LAST_INTEGRITY_MS.store(now_ms, Ordering::SeqCst);
+    if !database::check_integrity() && !INTEGRITY_REPORTED.swap(true, Ordering::SeqCst) {
+        log::error!("Database integrity check failed");
+        notifications::dispatch(Event::DatabaseDamaged);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steady_clocks_show_no_jump() {
+        assert_eq!(clock_jump((10_000, 1_000), (70_000, 1_060)), 0);
+        assert_eq!(clock_jump((10_000, 1_000), (11_999, 1_001)), 0);
+    }
+
+    #[test]
+    fn clock_set_back_or_forward_is_measured() {
+        assert_eq!(clock_jump((10_000, 100_000), (11_000, 96_401)), -3_600);
+        assert_eq!(clock_jump((10_000, 100_000), (11_000, 186_401)), 86_400);
+    }
+
+    /// UTC Unix timestamp of a local wall-clock time in a zone `offset_hours` ahead of UTC
+    fn utc_from_local((year, month, day): (i32, u32, u32), (hour, minute, second): (i64, i64, i64), offset_hours: i64) -> i64 {
+        crate::dates::days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second - offset_hours * 3600
+    }
+
+    #[test]
+    fn daylight_saving_transitions_are_not_clock_changes() {
+        // (local time, UTC offset) a minute of real time apart, across real transitions
+        let transitions = [
+            // Europe springs forward on 2026-03-29: 01:59:30 CET, then 03:00:30 CEST
+            (((2026, 3, 29), (1, 59, 30), 1), ((2026, 3, 29), (3, 0, 30), 2)),
+            // and falls back on 2026-10-25: 02:59:30 CEST, then 02:00:30 CET
+            (((2026, 10, 25), (2, 59, 30), 2), ((2026, 10, 25), (2, 0, 30), 1)),
+            // The US falls back on 2026-11-01: 01:59:30 EDT, then 01:00:30 EST
+            (((2026, 11, 1), (1, 59, 30), -4), ((2026, 11, 1), (1, 0, 30), -5)),
+        ];
+        for ((date, time, offset), (next_date, next_time, next_offset)) in transitions {
+            let before = utc_from_local(date, time, offset);
+            let after = utc_from_local(next_date, next_time, next_offset);
+            // The local clock moves by about an hour...
+            assert!(is_clock_change((after + next_offset * 3600) - (before + offset * 3600)));
+            // ...but the UTC readings keep pace with the monotonic clock
+            assert_eq!(clock_jump((10_000, before), (70_000, after)), 0, "{:?}", date);
+        }
+    }
+
+    #[test]
+    fn moving_to_another_time_zone_is_not_a_clock_change() {
+        // Switching the zone from Berlin to New York at 12:00 local: 06:00:30 there a minute later
+        let before = utc_from_local((2026, 7, 1), (12, 0, 0), 2);
+        let after = utc_from_local((2026, 7, 1), (6, 1, 0), -4);
+        assert_eq!(clock_jump((10_000, before), (70_000, after)), 0);
+    }
+
+    #[test]
+    fn synchronization_steps_are_ignored() {
+        // Time sync corrects drift by seconds, at most a couple of minutes
+        for step in [1, -1, 30, -45, 119, -119] {
+            let jump = clock_jump((10_000, 100_000), (11_000, 100_001 + step));
+            assert_eq!(jump, step);
+            assert!(!is_clock_change(jump), "{}s counted as a clock change", step);
+        }
+        assert!(is_clock_change(120));
+        assert!(is_clock_change(-3_600));
+    }
+
+    #[test]
+    fn startup_compares_boot_time_within_a_session() {
+        // Booted at 1_000_000; an hour of uptime later the clock reads two hours later
+        assert_eq!(startup_clock_jump(Some(1_000_000), 0, 3_600_000, 1_007_200), 3_600);
+        assert_eq!(startup_clock_jump(Some(1_000_000), 0, 3_600_000, 1_003_600), 0);
+    }
+
+    #[test]
+    fn startup_after_reboot_only_sees_the_clock_set_back() {
+        assert_eq!(startup_clock_jump(None, 2_000_000, 60_000, 1_900_000), -100_000);
+        assert_eq!(startup_clock_jump(None, 2_000_000, 60_000, 9_000_000), 0);
+        assert_eq!(startup_clock_jump(None, 0, 60_000, 9_000_000), 0);
+    }
+}
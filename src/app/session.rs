@@ -0,0 +1,113 @@
+//! Session module
+//! Fast user switching on a shared PC: time only counts while this app's session is in use
+//! (at the console or over Remote Desktop) and its account is listed in "Count time only for"
+//! (empty = everyone). Session changes arrive as WM_WTSSESSION_CHANGE in `tray::window_proc`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use windows::core::PWSTR;
+use windows::Win32::{
+    Foundation::HWND,
+    System::RemoteDesktop::{
+        ProcessIdToSessionId, WTSActive, WTSConnectState, WTSFreeMemory, WTSQuerySessionInformationW,
+        WTSRegisterSessionNotification, WTSUnRegisterSessionNotification, NOTIFY_FOR_ALL_SESSIONS,
+        WTS_CONNECTSTATE_CLASS, WTS_CURRENT_SERVER_HANDLE,
+    },
+    System::Threading::GetCurrentProcessId,
+    System::WindowsProgramming::GetUserNameW,
+    UI::WindowsAndMessaging::{
+        WTS_CONSOLE_CONNECT, WTS_CONSOLE_DISCONNECT, WTS_REMOTE_CONNECT, WTS_REMOTE_DISCONNECT, WTS_SESSION_LOGOFF,
+        WTS_SESSION_LOGON, WTS_SESSION_UNLOCK,
+    },
+};
+
+use crate::accounts;
+use crate::database;
+
+/// Whether the countdown runs for the account in front of the screen right now
+static COUNTING: AtomicBool = AtomicBool::new(true);
+
+/// Start listening for logons, logoffs and user switches on `hwnd`
+pub unsafe fn register(hwnd: HWND) {
+    if let Err(e) = WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_ALL_SESSIONS) {
+        log::warn!("Could not register for session notifications: {}", e.message());
+    }
+    refresh();
+}
+
+pub unsafe fn unregister(hwnd: HWND) {
+    let _ = WTSUnRegisterSessionNotification(hwnd);
+}
+
+/// Handle WM_WTSSESSION_CHANGE: another account signed in, out or switched to the console,
+/// a Remote Desktop connection came or went, or this session was unlocked (headless, the
+/// block is enforced again)
+pub unsafe fn handle_change(event: u32, session: u32) {
+    if matches!(
+        event,
+        WTS_SESSION_LOGON
+            | WTS_SESSION_LOGOFF
+            | WTS_CONSOLE_CONNECT
+            | WTS_CONSOLE_DISCONNECT
+            | WTS_REMOTE_CONNECT
+            | WTS_REMOTE_DISCONNECT
+    ) {
+        refresh();
+    } else if event == WTS_SESSION_UNLOCK && own_session() == Some(session) {
+        crate::headless::enforce_block();
+    }
+}
+
+/// Whether time counts right now
+pub fn counts() -> bool {
+    COUNTING.load(Ordering::SeqCst)
+}
+
+/// Re-evaluate after a session change or a settings save
+pub unsafe fn refresh() {
+    let own = own_session();
+    let in_use = own.and_then(session_in_use);
+    let user = current_user();
+    let counting = accounts::counts_in_session(&database::get_counted_users(), user.as_deref(), in_use);
+
+    if COUNTING.swap(counting, Ordering::SeqCst) != counting {
+        log::info!(
+            "Time {} counted for {} (session {:?}, in use: {:?})",
+            if counting { "is" } else { "is no longer" },
+            user.as_deref().unwrap_or("?"),
+            own,
+            in_use
+        );
+    }
+    // The corner timer shows this account's remaining time again after a switch back
+    crate::mini_overlay::update_mini_overlay();
+}
+
+/// Session this process runs in
+fn own_session() -> Option<u32> {
+    let mut session = 0u32;
+    unsafe { ProcessIdToSessionId(GetCurrentProcessId(), &mut session) }.ok()?;
+    Some(session)
+}
+
+/// Whether `session` is the one being used: connected at the console or over Remote
+/// Desktop, not switched away from (disconnected)
+fn session_in_use(session: u32) -> Option<bool> {
+    unsafe {
+        let mut buffer = PWSTR::null();
+        let mut bytes = 0u32;
+        WTSQuerySessionInformationW(WTS_CURRENT_SERVER_HANDLE, session, WTSConnectState, &mut buffer, &mut bytes).ok()?;
+        let state = (bytes as usize >= std::mem::size_of::<WTS_CONNECTSTATE_CLASS>())
+            .then(|| *(buffer.0 as *const WTS_CONNECTSTATE_CLASS));
+        WTSFreeMemory(buffer.0 as _);
+        state.map(|state| state == WTSActive)
+    }
+}
+
+/// Account this process runs as
+fn current_user() -> Option<String> {
+    let mut name = [0u16; 257];
+    let mut len = name.len() as u32;
+    unsafe { GetUserNameW(PWSTR(name.as_mut_ptr()), &mut len) }.ok()?;
+    // The length includes the terminating null
+    Some(String::from_utf16_lossy(&name[..(len as usize).saturating_sub(1)]))
+}
@@ -0,0 +1,962 @@
+//! Telegram bot module for Screen Time Manager
+//! Provides remote monitoring and control via Telegram commands
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use teloxide::prelude::*;
+use teloxide::error_handlers::LoggingErrorHandler;
+use teloxide::utils::command::BotCommands;
+
+use crate::blocking;
+use crate::database;
+use crate::i18n;
+use crate::locale;
+use crate::mini_overlay;
+use crate::overlay;
+
+/// Shutdown signal for graceful termination
+pub static BOT_SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+/// Bot instance for sending notifications
+static BOT_INSTANCE: OnceLock<Bot> = OnceLock::new();
+
+/// Admin chat ID for notifications
+static ADMIN_CHAT_ID: OnceLock<i64> = OnceLock::new();
+
+/// Connection state of the bot (shown in the settings dialog)
+static BOT_STATUS: Mutex<BotStatus> = Mutex::new(BotStatus::Off);
+
+/// Set when Telegram rejected the token, until the UI has told the user
+static BOT_ERROR_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Last state-changing command per chat (rate limiting /extend, /pause, ...)
+static LAST_MUTATING_COMMAND: OnceLock<Mutex<HashMap<i64, Instant>>> = OnceLock::new();
+
+/// Last read-only command per chat (/status, /time, ...)
+static LAST_QUERY_COMMAND: OnceLock<Mutex<HashMap<i64, Instant>>> = OnceLock::new();
+
+/// Minimum interval between read-only commands from one chat
+const QUERY_COMMAND_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Most notifications kept while offline; older ones are dropped first
+const NOTIFICATION_QUEUE_MAX: usize = 50;
+
+/// Queued notifications older than this are stale and discarded (seconds)
+const NOTIFICATION_MAX_AGE: i64 = 24 * 60 * 60;
+
+/// Retry delay after a failed send, doubled up to the maximum
+const NOTIFICATION_RETRY_MIN: u64 = 5;
+const NOTIFICATION_RETRY_MAX: u64 = 300;
+
+/// Connection state of the bot
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BotStatus {
+    /// Disabled or not configured
+    Off,
+    /// Checking the token with Telegram
+    Connecting,
+    Connected { username: String },
+    /// Telegram rejected the token; the bot stays off until it's changed
+    InvalidToken,
+    /// Telegram couldn't be reached (retrying with backoff)
+    Unreachable { error: String },
+}
+
+impl BotStatus {
+    /// Localized one-line description for the settings dialog
+    pub fn describe(&self) -> String {
+        match self {
+            BotStatus::Off => i18n::t("tg.state.off").to_string(),
+            BotStatus::Connecting => i18n::t("tg.state.connecting").to_string(),
+            BotStatus::Connected { username } => i18n::t("tg.state.connected").replace("{}", username),
+            BotStatus::InvalidToken => i18n::t("tg.state.invalid_token").to_string(),
+            BotStatus::Unreachable { error } => i18n::t("tg.state.unreachable").replace("{}", error),
+        }
+    }
+}
+
+/// Current connection state of the bot
+pub fn bot_status() -> BotStatus {
+    BOT_STATUS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn set_bot_status(status: BotStatus) {
+    *BOT_STATUS.lock().unwrap_or_else(|e| e.into_inner()) = status;
+}
+
+/// Returns true (once) if Telegram rejected the bot token since the last call
+pub fn take_bot_error() -> bool {
+    BOT_ERROR_PENDING.swap(false, Ordering::SeqCst)
+}
+
+/// Whether an error means the token is wrong (retrying won't help) rather than a network problem
+fn is_auth_error(error: &teloxide::RequestError) -> bool {
+    matches!(error, teloxide::RequestError::Api(teloxide::ApiError::InvalidToken))
+}
+
+#[derive(BotCommands, Clone, Debug)]
+#[command(rename_rule = "lowercase", description = "Screen Time Manager commands:")]
+enum Command {
+    #[command(description = "Start the bot")]
+    Start,
+    #[command(description = "Show remaining time and status")]
+    Status,
+    #[command(description = "Quick time check")]
+    Time,
+    #[command(description = "Extend time by minutes (e.g., /extend 30)")]
+    Extend(i32),
+    #[command(description = "Give time on a day without screen time (e.g., /grant 30)")]
+    Grant(i32),
+    #[command(description = "Allow screen time until a time of day (e.g., /until 20:00)")]
+    Until(String),
+    #[command(description = "Reduce time by minutes (e.g., /reduce 30)")]
+    Reduce(i32),
+    #[command(description = "Pause the timer")]
+    Pause,
+    #[command(description = "Resume the timer")]
+    Resume,
+    #[command(description = "Focus mode, time doesn't count (e.g., /focus 60, /focus, /focus off)")]
+    Focus(String),
+    #[command(description = "Show today's pauses, extensions and blocks")]
+    History,
+    #[command(description = "Show a message on screen (e.g., /msg Do your homework!)")]
+    Msg(String),
+    #[command(description = "Lock the screen")]
+    Lock,
+    #[command(description = "Lock the screen (alias)")]
+    Stop,
+    #[command(description = "Reset timer to daily limit")]
+    Reset,
+    #[command(description = "Get a one-time code to unlock the screen on the PC")]
+    Unlockcode,
+    #[command(description = "Hide or show the parent items in the tray menu (/kiosk on, /kiosk off)")]
+    Kiosk(String),
+    #[command(description = "Extend by 30 minutes")]
+    E30,
+    #[command(description = "Extend by 60 minutes")]
+    E60,
+    #[command(description = "Extend by 120 minutes")]
+    E120,
+    #[command(description = "Get your chat ID for setup")]
+    Chatid,
+    #[command(description = "Show this help message")]
+    Help,
+}
+
+/// Start the Telegram bot in a background thread
+pub fn start_bot_thread() {
+    let config = database::get_telegram_config();
+
+    if !config.enabled {
+        log::info!("Bot is disabled in settings");
+        return;
+    }
+
+    let Some(token) = config.bot_token else {
+        log::warn!("Bot enabled but no token configured");
+        return;
+    };
+
+    if token.is_empty() {
+        log::warn!("Bot token is empty");
+        return;
+    }
+
+    let admin_chat_id = config.admin_chat_id;
+
+    // Store admin chat ID for notifications
+    if let Some(id) = admin_chat_id {
+        let _ = ADMIN_CHAT_ID.set(id);
+    }
+
+    set_bot_status(BotStatus::Connecting);
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
+        rt.block_on(async {
+            run_bot(token, admin_chat_id).await;
+        });
+    });
+}
+
+/// Queue a notification for the admin chat. It is sent by the bot as soon as the
+/// network allows, so events are not lost while the PC is offline.
+pub fn enqueue_notification(text: &str) {
+    if ADMIN_CHAT_ID.get().is_none() {
+        return;
+    }
+    // Messages may arrive late, so stamp them with the time of the event
+    let st = crate::clock::local_time();
+    let stamped = format!("[{:02}:{:02}] {}", st.wHour, st.wMinute, text);
+    database::queue_notification(&stamped, NOTIFICATION_QUEUE_MAX);
+}
+
+/// Send queued notifications, retrying with backoff until they go through
+async fn drain_notification_queue(bot: Bot, chat_id: i64) {
+    let mut retry_delay = NOTIFICATION_RETRY_MIN;
+
+    while !BOT_SHUTDOWN.load(Ordering::SeqCst) {
+        let mut delay = 2;
+        for (id, text) in database::get_queued_notifications(NOTIFICATION_MAX_AGE) {
+            match bot.send_message(ChatId(chat_id), &text).await {
+                Ok(_) => {
+                    database::remove_queued_notification(id);
+                    retry_delay = NOTIFICATION_RETRY_MIN;
+                }
+                Err(e) => {
+                    log::warn!("Notification not sent, retrying in {}s: {}", retry_delay, e);
+                    delay = retry_delay;
+                    retry_delay = (retry_delay * 2).min(NOTIFICATION_RETRY_MAX);
+                    break;
+                }
+            }
+        }
+
+        if !sleep_unless_shutdown(delay).await {
+            return;
+        }
+    }
+}
+
+/// Sleep for `seconds` in short steps so shutdown is not delayed by a long backoff.
+/// Returns false if the app is shutting down.
+async fn sleep_unless_shutdown(seconds: u64) -> bool {
+    for _ in 0..seconds * 10 {
+        if BOT_SHUTDOWN.load(Ordering::SeqCst) {
+            return false;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+    true
+}
+
+/// Check the token with Telegram (getMe), retrying with backoff while it can't be reached.
+/// Returns false if the token was rejected (no retry) or the app is shutting down.
+async fn connect(bot: &Bot) -> bool {
+    let mut retry_delay = NOTIFICATION_RETRY_MIN;
+    loop {
+        match bot.get_me().await {
+            Ok(me) => {
+                log::info!("Connected as @{}", me.username());
+                set_bot_status(BotStatus::Connected { username: me.username().to_string() });
+                return true;
+            }
+            Err(e) if is_auth_error(&e) => {
+                log::error!("Telegram rejected the bot token: {}", e);
+                set_bot_status(BotStatus::InvalidToken);
+                BOT_ERROR_PENDING.store(true, Ordering::SeqCst);
+                return false;
+            }
+            Err(e) => {
+                log::warn!("Could not reach Telegram, retrying in {}s: {}", retry_delay, e);
+                set_bot_status(BotStatus::Unreachable { error: e.to_string() });
+                if !sleep_unless_shutdown(retry_delay).await {
+                    return false;
+                }
+                retry_delay = (retry_delay * 2).min(NOTIFICATION_RETRY_MAX);
+            }
+        }
+    }
+}
+
+/// Signal the bot to shut down gracefully
+pub fn signal_shutdown() {
+    BOT_SHUTDOWN.store(true, Ordering::SeqCst);
+
+    // Send shutdown notification if possible
+    if let (Some(bot), Some(&chat_id)) = (BOT_INSTANCE.get(), ADMIN_CHAT_ID.get()) {
+        let bot = bot.clone();
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().ok();
+            if let Some(rt) = rt {
+                rt.block_on(async {
+                    let _ = bot.send_message(ChatId(chat_id), i18n::t("tg.notify.shutdown")).await;
+                });
+            }
+        });
+        // Give a moment for the message to send
+        std::thread::sleep(std::time::Duration::from_millis(500));
+    }
+}
+
+/// Main bot loop
+async fn run_bot(token: String, admin_chat_id: Option<i64>) {
+    let bot = Bot::new(&token);
+
+    // A wrong token is reported to the user instead of the bot silently doing nothing
+    if !connect(&bot).await {
+        return;
+    }
+
+    // Store bot instance for notifications
+    let _ = BOT_INSTANCE.set(bot.clone());
+
+    // Send startup notification
+    if let Some(chat_id) = admin_chat_id {
+        let _ = bot.send_message(ChatId(chat_id), i18n::t("tg.notify.started")).await;
+
+        // Deliver event notifications, including any left over from an offline period
+        tokio::spawn(drain_notification_queue(bot.clone(), chat_id));
+    }
+
+    // Command handler
+    let command_handler = Update::filter_message()
+        .filter_command::<Command>()
+        .endpoint(move |bot: Bot, msg: Message, cmd: Command| {
+            handle_command(bot, msg, cmd, admin_chat_id)
+        });
+
+    // Fallback handler: show plain text as on-screen message (authorized users only)
+    let fallback_handler = Update::filter_message()
+        .endpoint(move |bot: Bot, msg: Message| async move {
+            if let Some(text) = msg.text() {
+                if text.starts_with('/') {
+                    bot.send_message(
+                        msg.chat.id,
+                        i18n::t("tg.error.unknown_cmd")
+                    ).await?;
+                } else if !text.is_empty() {
+                    // Check authorization
+                    let authorized = admin_chat_id
+                        .map(|id| msg.chat.id.0 == id)
+                        .unwrap_or(false);
+                    if authorized {
+                        bot.send_message(msg.chat.id, cmd_msg(text)).await?;
+                    }
+                }
+            }
+            Ok(())
+        });
+
+    // Combine handlers - commands first, then fallback
+    let handler = dptree::entry()
+        .branch(command_handler)
+        .branch(fallback_handler);
+
+    // Create dispatcher with default error handler that logs errors
+    let mut dispatcher = Dispatcher::builder(bot, handler)
+        .default_handler(|upd| async move {
+            log::debug!("Unhandled update: {:?}", upd);
+        })
+        .error_handler(LoggingErrorHandler::with_custom_text("[Telegram] Error in handler"))
+        .build();
+
+    // Get shutdown token for graceful shutdown
+    let shutdown_token = dispatcher.shutdown_token();
+
+    // Spawn a task to monitor shutdown signal
+    tokio::spawn(async move {
+        while !BOT_SHUTDOWN.load(Ordering::SeqCst) {
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+        shutdown_token.shutdown().ok();
+    });
+
+    // Run dispatcher
+    dispatcher.dispatch().await;
+}
+
+/// Handle incoming commands
+async fn handle_command(
+    bot: Bot,
+    msg: Message,
+    cmd: Command,
+    admin_chat_id: Option<i64>,
+) -> ResponseResult<()> {
+    let sender_id = msg.chat.id.0;
+
+    // For /start and /chatid commands, always respond (helps with setup)
+    match &cmd {
+        Command::Start => {
+            let welcome = format!(
+                "Welcome to Screen Time Manager Bot!\n\n\
+                 Your chat ID is: {}\n\n\
+                 Use /help to see available commands.",
+                sender_id
+            );
+            bot.send_message(msg.chat.id, welcome).await?;
+            return Ok(());
+        }
+        Command::Chatid => {
+            bot.send_message(msg.chat.id, format!("{} {}", i18n::t("tg.chatid.your_id"), sender_id)).await?;
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // Authorization check for all other commands
+    if let Some(admin_id) = admin_chat_id {
+        if sender_id != admin_id {
+            bot.send_message(msg.chat.id, i18n::t("tg.error.unauthorized")).await?;
+            return Ok(());
+        }
+    } else {
+        // No admin configured - reject all commands except /start and /chatid
+        bot.send_message(msg.chat.id, i18n::t("tg.error.no_admin")).await?;
+        return Ok(());
+    }
+
+    // Rate limit per chat; this also debounces rapid /pause /resume toggling
+    if let Some(wait) = rate_limit(sender_id, is_mutating(&cmd)) {
+        let reply = i18n::t("tg.error.slow_down").replace("{}", &wait.as_secs().max(1).to_string());
+        bot.send_message(msg.chat.id, format!("⏳ {}", reply)).await?;
+        return Ok(());
+    }
+
+    let response = match cmd {
+        Command::Start => unreachable!(), // Handled above
+        Command::Status => cmd_status(),
+        Command::Time => cmd_time(),
+        Command::Extend(mins) => cmd_extend(mins),
+        Command::Grant(mins) => extend_reply(mins, blocking::grant_time(mins, blocking::ExtendSource::Telegram)),
+        Command::Until(arg) => cmd_until(&arg),
+        Command::Reduce(mins) => cmd_reduce(mins),
+        Command::Pause => cmd_pause(),
+        Command::Resume => cmd_resume(),
+        Command::Focus(arg) => cmd_focus(&arg),
+        Command::History => daily_summary(),
+        Command::Msg(text) => cmd_msg(&text),
+        Command::Lock => cmd_lock(),
+        Command::Stop => cmd_lock(),
+        Command::Reset => cmd_reset(),
+        Command::Unlockcode => cmd_unlock_code(),
+        Command::Kiosk(arg) => cmd_kiosk(&arg),
+        Command::E30 => cmd_extend(30),
+        Command::E60 => cmd_extend(60),
+        Command::E120 => cmd_extend(120),
+        Command::Chatid => unreachable!(), // Handled above
+        Command::Help => Command::descriptions().to_string(),
+    };
+
+    bot.send_message(msg.chat.id, response).await?;
+    Ok(())
+}
+
+/// Whether a command changes state (these are rate limited more strictly)
+fn is_mutating(cmd: &Command) -> bool {
+    !matches!(
+        cmd,
+        Command::Status | Command::Time | Command::History | Command::Help | Command::Start | Command::Chatid
+    )
+}
+
+/// Record a command from `chat_id`, or return how long to wait if it came too soon
+fn rate_limit(chat_id: i64, mutating: bool) -> Option<Duration> {
+    let (limiter, interval) = if mutating {
+        (&LAST_MUTATING_COMMAND, Duration::from_secs(database::get_telegram_rate_limit_seconds()))
+    } else {
+        (&LAST_QUERY_COMMAND, QUERY_COMMAND_INTERVAL)
+    };
+
+    let mut last = limiter.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+    let now = Instant::now();
+    if let Some(previous) = last.get(&chat_id) {
+        let elapsed = now.duration_since(*previous);
+        if elapsed < interval {
+            return Some(interval - elapsed);
+        }
+    }
+    last.insert(chat_id, now);
+    None
+}
+
+// ============================================================================
+// Command Implementations
+// ============================================================================
+
+/// Traffic-light emoji for the remaining time (red under a minute, orange under five)
+fn time_emoji(remaining: i32) -> &'static str {
+    if remaining <= 60 {
+        "🔴"
+    } else if remaining <= 300 {
+        "🟠"
+    } else {
+        "🟢"
+    }
+}
+
+fn cmd_status() -> String {
+    let remaining = blocking::get_remaining_seconds();
+    let status_emoji = time_emoji(remaining);
+
+    let [remaining_line, paused_line, budget_line] = status_lines();
+    let mut status = format!(
+        "{}\n\
+         ━━━━━━━━━━━━━━━━━━\n\
+         {} {}\n\
+         ⏸ {}\n\
+         🔋 {}",
+        i18n::t("tg.status.header"),
+        status_emoji,
+        remaining_line,
+        paused_line,
+        budget_line
+    );
+    if mini_overlay::is_focus_mode() {
+        let focus = match mini_overlay::focus_seconds_left() {
+            Some(seconds) => i18n::t("tg.status.focus_left").replace("{}", &((seconds + 59) / 60).to_string()),
+            None => i18n::t("tg.status.focus_open").to_string(),
+        };
+        status.push_str(&format!("\n📚 {}", focus));
+    }
+    status
+}
+
+/// Remaining time, pause state and pause budget, one "Label: value" line each.
+/// Shared by /status and the tray status balloon.
+pub fn status_lines() -> [String; 3] {
+    let remaining = blocking::get_remaining_seconds();
+    let pause_budget = mini_overlay::get_remaining_pause_budget();
+
+    let pause_status = if mini_overlay::is_paused() {
+        i18n::t("tg.status.yes")
+    } else if mini_overlay::is_idle_paused() {
+        i18n::t("tg.status.idle")
+    } else {
+        i18n::t("tg.status.no")
+    };
+
+    [
+        format!("{} {}:{:02}", i18n::t("tg.status.remaining"), remaining / 60, remaining % 60),
+        format!("{} {}", i18n::t("tg.status.paused"), pause_status),
+        format!("{} {} min", i18n::t("tg.status.pause_budget"), pause_budget / 60),
+    ]
+}
+
+/// Time used today against today's limit (extensions included), e.g. "Used 45 / 120 min".
+/// Shown in the tray tooltip.
+pub fn usage_line() -> String {
+    i18n::t("tg.status.used")
+        .replace("{used}", &(blocking::get_used_seconds() / 60).to_string())
+        .replace("{limit}", &database::get_effective_daily_limit().to_string())
+}
+
+fn cmd_time() -> String {
+    let remaining = blocking::get_remaining_seconds();
+    let mins = remaining / 60;
+    let secs = remaining % 60;
+
+    format!("{} {}:{:02} remaining", time_emoji(remaining), mins, secs)
+}
+
+fn cmd_extend(minutes: i32) -> String {
+    extend_reply(minutes, blocking::extend_time_checked(minutes, blocking::ExtendSource::Telegram))
+}
+
+/// Describe the result of an extension (shared by Telegram and other remote channels)
+fn extend_reply(minutes: i32, extended: Result<i32, blocking::ExtendError>) -> String {
+    let remaining = match extended {
+        Ok(remaining) => remaining,
+        Err(e) => return format!("❌ {}", e.message()),
+    };
+
+    // Lift the block if it's showing (after the minimum block duration)
+    unsafe {
+        blocking::release_block();
+    }
+
+    let new_mins = remaining / 60;
+    let new_secs = remaining % 60;
+
+    format!("✅ {} {} min\n{} {}:{:02}",
+        i18n::t("tg.extend.success").replace("{}", ""),
+        minutes,
+        i18n::t("tg.status.remaining"),
+        new_mins, new_secs)
+}
+
+fn cmd_until(arg: &str) -> String {
+    let Some((hour, minute, tomorrow)) = blocking::parse_until(arg) else {
+        return format!("❌ {}", i18n::t("tg.until.usage"));
+    };
+    let remaining = match blocking::extend_until(hour, minute, tomorrow, blocking::ExtendSource::Telegram) {
+        Ok(remaining) => remaining,
+        Err(e) => return format!("❌ {}", e.message()),
+    };
+
+    // Lift the block if it's showing (after the minimum block duration)
+    unsafe {
+        blocking::release_block();
+    }
+
+    format!("✅ {}\n{} {}:{:02}",
+        i18n::t("tg.until.success").replace("{}", &format!("{:02}:{:02}", hour, minute)),
+        i18n::t("tg.status.remaining"),
+        remaining / 60, remaining % 60)
+}
+
+/// Run a plain-text command from another remote channel (e.g. MQTT): "extend <minutes>",
+/// "pause", "resume" or "lock". Returns the same reply the bot would send, or None if the
+/// command is unknown.
+pub fn run_remote_command(text: &str, source: blocking::ExtendSource) -> Option<String> {
+    let mut parts = text.split_whitespace();
+    let command = parts.next()?.trim_start_matches('/').to_ascii_lowercase();
+    let reply = match (command.as_str(), parts.next()) {
+        ("extend", Some(minutes)) => {
+            let minutes = minutes.parse().ok()?;
+            extend_reply(minutes, blocking::extend_time_checked(minutes, source))
+        }
+        ("pause", None) => cmd_pause(),
+        ("resume", None) => cmd_resume(),
+        ("lock", None) => cmd_lock(),
+        _ => return None,
+    };
+    Some(reply)
+}
+
+fn cmd_reduce(minutes: i32) -> String {
+    if minutes <= 0 {
+        return i18n::t("tg.reduce.specify_positive").to_string();
+    }
+    if minutes > 120 {
+        return i18n::t("tg.reduce.max_120").to_string();
+    }
+
+    let current = blocking::get_remaining_seconds();
+    let reduction_seconds = minutes * 60;
+
+    if reduction_seconds >= current {
+        return format!("{} ({}:{:02})",
+            i18n::t("tg.reduce.not_enough"),
+            current / 60, current % 60);
+    }
+
+    blocking::reduce_time(minutes);
+
+    // Get new remaining time
+    let remaining = blocking::get_remaining_seconds();
+    let new_mins = remaining / 60;
+    let new_secs = remaining % 60;
+
+    format!("⏬ {} {} min\n{} {}:{:02}",
+        i18n::t("tg.reduce.success").replace("{}", ""),
+        minutes,
+        i18n::t("tg.status.remaining"),
+        new_mins, new_secs)
+}
+
+fn cmd_pause() -> String {
+    if mini_overlay::is_paused() {
+        return format!("⏸ {}", i18n::t("tg.pause.already_paused"));
+    }
+    if mini_overlay::is_idle_paused() {
+        return format!("⏸ {}", i18n::t("tg.pause.idle_paused"));
+    }
+
+    match mini_overlay::toggle_pause() {
+        Ok(true) => format!("⏸ {}", i18n::t("tg.pause.success")),
+        Ok(false) => i18n::t("tg.pause.failed").to_string(),
+        Err(reason) => format!("{} {}", i18n::t("tg.pause.cannot"), format_pause_reason(reason)),
+    }
+}
+
+fn cmd_resume() -> String {
+    if mini_overlay::is_idle_paused() {
+        return format!("▶️ {}", i18n::t("tg.resume.idle_auto"));
+    }
+    if !mini_overlay::is_paused() {
+        return format!("▶️ {}", i18n::t("tg.resume.not_paused"));
+    }
+
+    match mini_overlay::toggle_pause() {
+        Ok(false) => format!("▶️ {}", i18n::t("tg.resume.success")),
+        Ok(true) => i18n::t("tg.resume.failed").to_string(),
+        Err(reason) => format!("{} {}", i18n::t("tg.resume.cannot"), format_pause_reason(reason)),
+    }
+}
+
+/// Start focus mode for some minutes (no argument = until turned off) or end it with "off"
+fn cmd_focus(arg: &str) -> String {
+    let arg = arg.trim();
+    if arg.eq_ignore_ascii_case("off") {
+        if !mini_overlay::is_focus_mode() {
+            return format!("📚 {}", i18n::t("tg.focus.not_active"));
+        }
+        mini_overlay::stop_focus_mode();
+        return format!("📚 {}", i18n::t("tg.focus.ended"));
+    }
+
+    let minutes = if arg.is_empty() {
+        0
+    } else {
+        match arg.parse::<u32>() {
+            Ok(m) if (1..=FOCUS_MAX_MINUTES).contains(&m) => m,
+            _ => return i18n::t("tg.focus.usage").replace("{}", &FOCUS_MAX_MINUTES.to_string()),
+        }
+    };
+
+    mini_overlay::start_focus_mode(minutes, "telegram");
+    if minutes > 0 {
+        format!("📚 {}", i18n::t("tg.focus.started").replace("{}", &minutes.to_string()))
+    } else {
+        format!("📚 {}", i18n::t("tg.focus.started_open"))
+    }
+}
+
+/// Turn kiosk mode on or off (the tray menu is rebuilt on every click, so it applies at once)
+fn cmd_kiosk(arg: &str) -> String {
+    let on = match arg.trim().to_ascii_lowercase().as_str() {
+        "on" => true,
+        "off" => false,
+        _ => return i18n::t("tg.kiosk.usage").to_string(),
+    };
+    if !database::set_setting("kiosk_mode", if on { "1" } else { "0" }) {
+        return format!("❌ {}", i18n::t("tg.error.save"));
+    }
+    log::info!("Kiosk mode turned {} from Telegram", if on { "on" } else { "off" });
+    format!("🔒 {}", i18n::t(if on { "tg.kiosk.on" } else { "tg.kiosk.off" }))
+}
+
+/// Longest focus mode that can be started with a time from Telegram
+const FOCUS_MAX_MINUTES: u32 = 720;
+
+/// Today's usage, pauses, extensions and blocks: the /history reply and the emailed daily report
+pub fn daily_summary() -> String {
+    use std::sync::atomic::Ordering;
+
+    let log = database::get_pause_log_today();
+    let pause_used = database::get_pause_used_today();
+    let pause_config = database::get_pause_config();
+    let session_active = mini_overlay::SESSION_ACTIVE_SECONDS.load(Ordering::SeqCst);
+
+    let mut response = format!(
+        "📊 {} ({})\n━━━━━━━━━━━━━━━━━━\n",
+        i18n::t("tg.history.header"),
+        locale::today_short_date()
+    );
+
+    // Format uptime
+    let hours = session_active / 3600;
+    let minutes = (session_active % 3600) / 60;
+    let seconds = session_active % 60;
+    if hours > 0 {
+        response.push_str(&format!("⏱ {} {}h {}m {}s\n", i18n::t("tg.history.uptime"), hours, minutes, seconds));
+    } else {
+        response.push_str(&format!("⏱ {} {}m {}s\n", i18n::t("tg.history.uptime"), minutes, seconds));
+    }
+
+    // Used vs limit (extensions explain used > limit)
+    let limit = database::get_daily_limit(database::get_current_weekday()) as i32;
+    let extended = database::get_extended_today();
+    let used = blocking::get_used_seconds() / 60;
+    response.push_str(&format!("📏 {} {} / {} min", i18n::t("tg.history.used"), used, limit));
+    if extended > 0 {
+        response.push_str(&format!(" (+{} min)", extended));
+    }
+    response.push('\n');
+
+    response.push_str(&format!(
+        "⏸ {} {} / {} min\n",
+        i18n::t("tg.history.pause_used"),
+        pause_used / 60,
+        pause_config.daily_budget_minutes
+    ));
+
+    let extensions = database::get_extension_log_today();
+    let blocks = database::get_block_log_today();
+    let focus = database::get_focus_log_today();
+    let resets = database::get_reset_log_today();
+
+    if log.is_empty() && extensions.is_empty() && blocks.is_empty() && focus.is_empty() && resets.is_empty() {
+        response.push('\n');
+        response.push_str(i18n::t("tg.history.no_events"));
+        return response;
+    }
+
+    push_history_section(&mut response, i18n::t("tg.history.pauses"), &log, |e| e.to_string());
+    // "HH:MM:SS:+15m:telegram" -> "HH:MM +15m (telegram)"
+    push_history_section(&mut response, i18n::t("tg.history.extensions"), &extensions, |e| {
+        let parts: Vec<&str> = e.splitn(5, ':').collect();
+        match parts.as_slice() {
+            [h, m, _, amount, source] => format!("{}:{} {} ({})", h, m, amount, blocking::extend_source_label(source)),
+            _ => e.to_string(),
+        }
+    });
+    // "HH:MM:SS:curfew" -> "HH:MM (curfew)"
+    push_history_section(&mut response, i18n::t("tg.history.blocks"), &blocks, |e| {
+        let parts: Vec<&str> = e.splitn(4, ':').collect();
+        match parts.as_slice() {
+            [h, m, _, reason] => format!("{}:{} ({})", h, m, reason),
+            _ => e.to_string(),
+        }
+    });
+
+    // "HH:MM:SS:on:60m:tray" -> "HH:MM on 60m tray"
+    push_history_section(&mut response, i18n::t("tg.history.focus"), &focus, |e| {
+        let parts: Vec<&str> = e.splitn(4, ':').collect();
+        match parts.as_slice() {
+            [h, m, _, details] => format!("{}:{} {}", h, m, details.replace(':', " ")),
+            _ => e.to_string(),
+        }
+    });
+
+    push_history_section(&mut response, i18n::t("tg.history.resets"), &resets, format_reset_entry);
+
+    // Stay well inside Telegram's 4096 character message limit
+    if response.chars().count() > HISTORY_MAX_CHARS {
+        response = response.chars().take(HISTORY_MAX_CHARS).collect();
+        response.push_str("\n…");
+    }
+
+    response
+}
+
+/// Maximum entries listed per /history section (older ones are summarized)
+const HISTORY_MAX_ENTRIES: usize = 10;
+
+/// Maximum length of the /history reply
+const HISTORY_MAX_CHARS: usize = 3800;
+
+/// Append a titled list of today's log entries, keeping only the most recent ones
+/// "HH:MM:SS:3600s->7200s:reset" -> "HH:MM reset 60 → 120 min"
+fn format_reset_entry(entry: &str) -> String {
+    let minutes = |seconds: &str| seconds.trim_end_matches('s').parse::<i32>().ok().map(|s| s.max(0) / 60);
+    let parts: Vec<&str> = entry.splitn(5, ':').collect();
+    match parts.as_slice() {
+        [h, m, _, change, action] => match change.split_once("->").map(|(from, to)| (minutes(from), minutes(to))) {
+            Some((Some(from), Some(to))) => format!("{}:{} {} {} → {} min", h, m, action, from, to),
+            _ => entry.to_string(),
+        },
+        _ => entry.to_string(),
+    }
+}
+
+fn push_history_section(response: &mut String, title: &str, entries: &[String], format: impl Fn(&str) -> String) {
+    if entries.is_empty() {
+        return;
+    }
+
+    response.push_str(&format!("\n{} ({}):\n", title, entries.len()));
+    let skipped = entries.len().saturating_sub(HISTORY_MAX_ENTRIES);
+    if skipped > 0 {
+        response.push_str(&format!("• … {}\n", i18n::t("tg.history.more").replace("{}", &skipped.to_string())));
+    }
+    for entry in &entries[skipped..] {
+        response.push_str(&format!("• {}\n", format(entry)));
+    }
+}
+
+fn cmd_msg(text: &str) -> String {
+    if text.is_empty() {
+        return i18n::t("tg.msg.provide").to_string();
+    }
+    // Banners are not shown over the blocking overlay
+    if overlay::is_blocking_active() {
+        return format!("🔒 {}", i18n::t("tg.msg.blocked"));
+    }
+
+    unsafe {
+        overlay::show_overlay(text, 10, false);
+    }
+
+    format!("📢 {}: \"{}\"", i18n::t("tg.msg.shown"), text)
+}
+
+fn cmd_reset() -> String {
+    let weekday = database::get_current_weekday();
+    let daily_limit_minutes = database::get_daily_limit(weekday);
+    let daily_limit_seconds = (daily_limit_minutes * 60) as i32;
+
+    // A reset is real: a running simulation would put the old time back when it ends
+    mini_overlay::stop_simulation();
+    let previous = blocking::REMAINING_SECONDS.swap(daily_limit_seconds, std::sync::atomic::Ordering::SeqCst);
+    database::save_remaining_time(daily_limit_seconds);
+    database::log_timer_reset(previous, daily_limit_seconds, "telegram");
+    if daily_limit_seconds > 0 {
+        blocking::cancel_pending_shutdown();
+    }
+
+    unsafe {
+        mini_overlay::update_mini_overlay();
+        if daily_limit_seconds > 0 {
+            // Lift the block if it's showing (after the minimum block duration)
+            blocking::release_block();
+        } else {
+            // A day without screen time stays blocked
+            mini_overlay::enforce_time_up();
+        }
+    }
+
+    format!(
+        "🔄 {} ({} min)\n{} {}:{:02}",
+        i18n::t("tg.reset.success"),
+        daily_limit_minutes,
+        i18n::t("tg.reset.remaining"),
+        daily_limit_seconds / 60,
+        daily_limit_seconds % 60
+    )
+}
+
+fn cmd_lock() -> String {
+    let message = database::get_blocking_message();
+    database::log_block_event("telegram");
+
+    unsafe {
+        blocking::show_blocking_overlay(&message, "telegram");
+    }
+
+    format!("🔒 {}", i18n::t("tg.lock.success"))
+}
+
+/// Generate a one-time code the child can type into the blocking overlay
+fn cmd_unlock_code() -> String {
+    match blocking::generate_unlock_code() {
+        Some(code) => {
+            let minutes = blocking::UNLOCK_CODE_VALIDITY.as_secs() / 60;
+            format!(
+                "🔑 {}",
+                i18n::t("tg.unlockcode.success")
+                    .replacen("{}", &code, 1)
+                    .replacen("{}", &minutes.to_string(), 1)
+            )
+        }
+        None => format!("❌ {}", i18n::t("tg.unlockcode.failed")),
+    }
+}
+
+/// Format pause blocked reason for display
+fn format_pause_reason(reason: crate::pause::PauseBlockedReason) -> String {
+    match reason {
+        crate::pause::PauseBlockedReason::Disabled => i18n::t("pause.disabled").to_string(),
+        crate::pause::PauseBlockedReason::BudgetExhausted => i18n::t("pause.budget_exhausted").to_string(),
+        crate::pause::PauseBlockedReason::CooldownActive { seconds_remaining } => {
+            format!("{} ({}s)", i18n::t("pause.cooldown"), seconds_remaining)
+        }
+        crate::pause::PauseBlockedReason::MinActiveTimeNotMet { seconds_remaining } => {
+            format!("{} ({}s)", i18n::t("pause.min_active"), seconds_remaining)
+        }
+        crate::pause::PauseBlockedReason::TimeTooLow => i18n::t("pause.time_too_low").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_emoji_is_valid_utf8_emoji() {
+        assert_eq!(time_emoji(30), "\u{1F534}");
+        assert_eq!(time_emoji(60), "\u{1F534}");
+        assert_eq!(time_emoji(240), "\u{1F7E0}");
+        assert_eq!(time_emoji(3600), "\u{1F7E2}");
+        // Red circle as UTF-8 bytes, not the mojibake a wrong encoding would produce
+        assert_eq!(time_emoji(0).as_bytes(), [0xF0, 0x9F, 0x94, 0xB4]);
+    }
+
+    #[test]
+    fn reset_entries_show_minutes() {
+        assert_eq!(format_reset_entry("18:05:12:600s->7200s:reset"), "18:05 reset 10 → 120 min");
+        assert_eq!(format_reset_entry("18:06:40:7200s->540s:undo"), "18:06 undo 120 → 9 min");
+        assert_eq!(format_reset_entry("19:00:00:-1s->3600s:telegram"), "19:00 telegram 0 → 60 min");
+        assert_eq!(format_reset_entry("garbled"), "garbled");
+    }
+
+    #[test]
+    fn only_a_rejected_token_stops_retrying() {
+        assert!(is_auth_error(&teloxide::RequestError::Api(teloxide::ApiError::InvalidToken)));
+        assert!(!is_auth_error(&teloxide::RequestError::Api(teloxide::ApiError::BotBlocked)));
+        let io = std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out");
+        assert!(!is_auth_error(&teloxide::RequestError::Io(io)));
+    }
+}
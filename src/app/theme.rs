@@ -0,0 +1,125 @@
+//! Theme helper module
+//! High-contrast colors, larger fonts and opacity for the overlays
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use windows::{
+    core::w,
+    Win32::{
+        Foundation::{COLORREF, HWND},
+        Graphics::Gdi::InvalidateRect,
+        System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD},
+        UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW},
+        UI::WindowsAndMessaging::{
+            SetLayeredWindowAttributes, SystemParametersInfoW, LWA_ALPHA, SPI_GETHIGHCONTRAST,
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+        },
+    },
+};
+
+use crate::database;
+use crate::dpi::scale;
+
+/// Cached high-contrast state (setting or Windows high-contrast theme)
+static HIGH_CONTRAST: AtomicBool = AtomicBool::new(false);
+
+/// Cached Windows "Transparency effects" state
+static TRANSPARENCY: AtomicBool = AtomicBool::new(true);
+
+/// High-contrast palette (BGR)
+const HC_BACKGROUND: u32 = 0x00000000;
+const HC_FOREGROUND: u32 = 0x00FFFFFF;
+const HC_ALERT: u32 = 0x0000FFFF; // Yellow
+
+/// Check whether Windows has a high-contrast theme active
+unsafe fn system_high_contrast() -> bool {
+    let mut hc = HIGHCONTRASTW {
+        cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+        ..Default::default()
+    };
+    SystemParametersInfoW(
+        SPI_GETHIGHCONTRAST,
+        hc.cbSize,
+        Some(&mut hc as *mut _ as *mut _),
+        SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+    )
+    .is_ok()
+        && (hc.dwFlags.0 & HCF_HIGHCONTRASTON.0) != 0
+}
+
+/// Check whether Windows "Transparency effects" are enabled (Settings > Personalization > Colors)
+unsafe fn system_transparency() -> bool {
+    let mut value: u32 = 1;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let result = RegGetValueW(
+        HKEY_CURRENT_USER,
+        w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+        w!("EnableTransparency"),
+        RRF_RT_REG_DWORD,
+        None,
+        Some(&mut value as *mut u32 as *mut _),
+        Some(&mut size),
+    );
+    result.is_err() || value != 0
+}
+
+/// Re-read the high-contrast setting and the Windows accessibility state.
+/// Called at startup, after saving settings and on WM_SETTINGCHANGE.
+pub fn refresh() {
+    let high_contrast = database::is_high_contrast_enabled() || unsafe { system_high_contrast() };
+    HIGH_CONTRAST.store(high_contrast, Ordering::SeqCst);
+    TRANSPARENCY.store(unsafe { system_transparency() }, Ordering::SeqCst);
+
+    // Re-apply opacity and repaint the layered overlays that already exist
+    unsafe {
+        let mini = HWND(crate::mini_overlay::MINI_OVERLAY_HWND.load(Ordering::SeqCst));
+        if !mini.0.is_null() {
+            let _ = SetLayeredWindowAttributes(mini, COLORREF(0), alpha(crate::mini_overlay::MINI_ALPHA), LWA_ALPHA);
+            let _ = InvalidateRect(mini, None, true);
+        }
+        let warning = HWND(crate::overlay::OVERLAY_HWND.load(Ordering::SeqCst));
+        if !warning.0.is_null() {
+            let _ = SetLayeredWindowAttributes(warning, COLORREF(0), alpha(crate::overlay::OVERLAY_ALPHA), LWA_ALPHA);
+            let _ = InvalidateRect(warning, None, true);
+        }
+        let blocking = HWND(crate::blocking::BLOCKING_HWND.load(Ordering::SeqCst));
+        if !blocking.0.is_null() {
+            let _ = InvalidateRect(blocking, None, true);
+        }
+    }
+}
+
+/// Check if high-contrast mode is active
+pub fn is_high_contrast() -> bool {
+    HIGH_CONTRAST.load(Ordering::SeqCst)
+}
+
+/// Background color: black in high-contrast mode
+pub fn bg(normal: u32) -> u32 {
+    if is_high_contrast() { HC_BACKGROUND } else { normal }
+}
+
+/// Text/accent color: white in high-contrast mode
+pub fn fg(normal: u32) -> u32 {
+    if is_high_contrast() { HC_FOREGROUND } else { normal }
+}
+
+/// Error/urgent color: yellow in high-contrast mode
+pub fn alert(normal: u32) -> u32 {
+    if is_high_contrast() { HC_ALERT } else { normal }
+}
+
+/// DPI-scaled font height, 25% larger in high-contrast mode
+pub fn font(size: i32) -> i32 {
+    if is_high_contrast() { scale(size * 5 / 4) } else { scale(size) }
+}
+
+/// DPI-scaled outline width, doubled in high-contrast mode
+pub fn outline(width: i32) -> i32 {
+    if is_high_contrast() { scale(width * 2) } else { scale(width) }
+}
+
+/// Layered window alpha: fully opaque in high-contrast mode or when
+/// Windows transparency effects are turned off
+pub fn alpha(normal: u8) -> u8 {
+    if is_high_contrast() || !TRANSPARENCY.load(Ordering::SeqCst) { 255 } else { normal }
+}
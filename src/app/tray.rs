@@ -0,0 +1,669 @@
+//! System tray module for Screen Time Manager
+//! Handles the system tray icon and context menu
+
+use std::mem::zeroed;
+use windows::{
+    core::{w, PCWSTR},
+    Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+        System::LibraryLoader::GetModuleHandleW,
+        System::Power::{IsPwrHibernateAllowed, SetSuspendState},
+        System::Shutdown::{ExitWindowsEx, LockWorkStation, EWX_LOGOFF, SHUTDOWN_REASON},
+        UI::{
+            Input::KeyboardAndMouse::{GetDoubleClickTime, GetKeyState, VK_SHIFT},
+            Shell::{
+                ShellExecuteW, Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_MESSAGE, NIF_TIP, NIIF_INFO, NIIF_NOSOUND, NIIF_RESPECT_QUIET_TIME, NIIF_WARNING,
+                NIM_ADD, NIM_DELETE, NIM_MODIFY, NOTIFYICONDATAW, NOTIFY_ICON_DATA_FLAGS, NOTIFY_ICON_INFOTIP_FLAGS,
+            },
+            WindowsAndMessaging::*,
+        },
+    },
+};
+
+use crate::blocking::{
+    extend_custom, extend_time_checked, get_remaining_seconds, hide_blocking_overlay, show_blocking_overlay, show_extend_error, ExtendSource,
+    BLOCKING_HWND,
+};
+use crate::constants::*;
+use crate::database::{get_blocking_message, get_current_weekday, get_tray_left_click, get_warning_config_for_day, is_kiosk_mode, is_mini_overlay_enabled, is_no_screen_time_today, is_pause_enabled};
+use crate::dialogs::{show_settings_dialog, show_stats_dialog, verify_passcode_for_quit};
+use crate::i18n;
+use crate::mini_overlay::{is_paused, is_idle_paused, can_pause, toggle_pause, get_remaining_pause_budget, set_mini_overlay_enabled, format_remaining};
+use crate::overlay::{show_overlay, OVERLAY_HWND};
+use crate::pause::PauseBlockedReason;
+use crate::telegram;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Timer that runs the left-click action once the double-click time has passed
+const TIMER_TRAY_CLICK: usize = 1;
+
+/// Set by a double-click so the button-up that follows it doesn't start another single click
+static TRAY_DOUBLE_CLICKED: AtomicBool = AtomicBool::new(false);
+
+/// Window the tray icon belongs to and its current tooltip (None while there's no icon).
+/// Balloons are also shown from other threads (e.g. a Telegram /msg), hence the Mutex.
+static TRAY_ICON: Mutex<Option<(isize, [u16; 128])>> = Mutex::new(None);
+
+/// Notification data addressing the tray icon of `hwnd`, changing only what `flags` name
+unsafe fn icon_data(hwnd: isize, flags: NOTIFY_ICON_DATA_FLAGS) -> NOTIFYICONDATAW {
+    let mut nid: NOTIFYICONDATAW = zeroed();
+    nid.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+    nid.hWnd = HWND(hwnd as *mut std::ffi::c_void);
+    nid.uID = 1;
+    nid.uFlags = flags;
+    nid
+}
+
+/// Add the system tray icon
+pub unsafe fn add_tray_icon(hwnd: HWND) {
+    let hinstance = GetModuleHandleW(None).expect("Failed to get module handle");
+
+    // Load the embedded icon at the small-icon size so the tray gets the proper
+    // frame from the .ico instead of a downscaled 32x32 one
+    let hicon = LoadImageW(
+        hinstance,
+        PCWSTR(IDI_APP_ICON as usize as *const u16),
+        IMAGE_ICON,
+        GetSystemMetrics(SM_CXSMICON),
+        GetSystemMetrics(SM_CYSMICON),
+        LR_DEFAULTCOLOR,
+    )
+    .map(|h| HICON(h.0))
+    .or_else(|_| LoadIconW(None, IDI_APPLICATION))
+    .expect("Failed to load icon");
+
+    let tooltip = i18n::t("tray.tooltip");
+    let mut tip_buffer: [u16; 128] = [0; 128];
+    for (i, c) in tooltip.encode_utf16().enumerate() {
+        if i >= 127 { break; }
+        tip_buffer[i] = c;
+    }
+
+    let mut nid = icon_data(hwnd.0 as isize, NIF_ICON | NIF_MESSAGE | NIF_TIP);
+    nid.uCallbackMessage = WM_TRAYICON;
+    nid.hIcon = hicon;
+    nid.szTip = tip_buffer;
+
+    if !Shell_NotifyIconW(NIM_ADD, &nid).as_bool() {
+        panic!("Failed to add tray icon");
+    }
+
+    *TRAY_ICON.lock().unwrap() = Some((hwnd.0 as isize, tip_buffer));
+}
+
+/// Remove the system tray icon
+pub unsafe fn remove_tray_icon() {
+    if let Some((hwnd, _)) = TRAY_ICON.lock().unwrap().take() {
+        let _ = Shell_NotifyIconW(NIM_DELETE, &icon_data(hwnd, Default::default()));
+    }
+}
+
+/// Show today's usage and the time left (in the corner timer's format) under the app name
+/// in the tray tooltip ("Used 45 / 120 min · 1:15:00 left").
+/// Called when the pointer moves over the icon, so the tooltip is current whenever it appears
+/// (the corner timer is click-through and can't show one itself).
+unsafe fn update_tray_tooltip() {
+    let left = i18n::t("tray.tooltip.left").replace("{time}", &format_remaining(get_remaining_seconds()));
+    let tooltip = format!("{}\n{} · {}", i18n::t("tray.tooltip"), telegram::usage_line(), left);
+    let mut tip_buffer: [u16; 128] = [0; 128];
+    for (i, c) in tooltip.encode_utf16().take(tip_buffer.len() - 1).enumerate() {
+        tip_buffer[i] = c;
+    }
+    let hwnd = {
+        let mut icon = TRAY_ICON.lock().unwrap();
+        match icon.as_mut() {
+            // Mouse moves arrive constantly while hovering; only touch the shell when the text changed
+            Some((hwnd, tip)) if *tip != tip_buffer => {
+                *tip = tip_buffer;
+                *hwnd
+            }
+            _ => return,
+        }
+    };
+    let mut nid = icon_data(hwnd, NIF_TIP);
+    nid.szTip = tip_buffer;
+    let _ = Shell_NotifyIconW(NIM_MODIFY, &nid);
+}
+
+/// Show a warning balloon notification from the tray icon
+pub unsafe fn show_balloon(title: &str, text: &str) {
+    notify_balloon(title, text, NIIF_WARNING);
+}
+
+/// Show a warning balloon without a sound that Windows may hold back during quiet time
+/// (Focus Assist); used for warnings downgraded by "respect Focus Assist"
+pub unsafe fn show_quiet_balloon(title: &str, text: &str) {
+    notify_balloon(title, text, NIIF_WARNING | NIIF_NOSOUND | NIIF_RESPECT_QUIET_TIME);
+}
+
+/// Show a status balloon with the remaining time, pause state and pause budget.
+/// Read-only, so unlike the stats dialog it doesn't ask for the passcode.
+pub unsafe fn show_status_balloon() {
+    let text = telegram::status_lines().join("\n");
+    notify_balloon(i18n::t("tg.status.header"), &text, NIIF_INFO);
+}
+
+unsafe fn notify_balloon(title: &str, text: &str, flags: NOTIFY_ICON_INFOTIP_FLAGS) {
+    let icon = *TRAY_ICON.lock().unwrap();
+    if let Some((hwnd, _)) = icon {
+        let mut nid = icon_data(hwnd, NIF_INFO);
+        nid.dwInfoFlags = flags;
+        for (i, c) in title.encode_utf16().take(nid.szInfoTitle.len() - 1).enumerate() {
+            nid.szInfoTitle[i] = c;
+        }
+        for (i, c) in text.encode_utf16().take(nid.szInfo.len() - 1).enumerate() {
+            nid.szInfo[i] = c;
+        }
+        let _ = Shell_NotifyIconW(NIM_MODIFY, &nid);
+    }
+}
+
+/// Build the About text with the version, commit and build date filled in
+fn about_text() -> String {
+    let text = i18n::t("about.text")
+        .replace("{version}", env!("CARGO_PKG_VERSION"))
+        .replace("{commit}", env!("BUILD_COMMIT"))
+        .replace("{date}", env!("BUILD_DATE"))
+        .replace("{url}", env!("CARGO_PKG_REPOSITORY"));
+    format!("{}\n\n{}", text, crate::elevation::status_text())
+}
+
+/// Show the context menu when right-clicking the tray icon
+pub unsafe fn show_context_menu(hwnd: HWND) {
+    let hmenu = CreatePopupMenu().expect("Failed to create popup menu");
+
+    // Determine pause menu item text and state
+    let paused = is_paused();
+    let pause_enabled = is_pause_enabled();
+
+    let (pause_text, pause_flags) = if paused {
+        // Currently manually paused - show resume option (always available)
+        (i18n::t("tray.resume"), MF_BYPOSITION | MF_STRING)
+    } else if is_idle_paused() {
+        // Currently idle-paused - grey out manual pause (already paused via idle)
+        (i18n::t("tray.pause_idle"), MF_BYPOSITION | MF_STRING | MF_GRAYED)
+    } else if !pause_enabled {
+        // Pause feature disabled
+        (i18n::t("tray.pause_disabled"), MF_BYPOSITION | MF_STRING | MF_GRAYED)
+    } else {
+        // Check if pause is available
+        match can_pause() {
+            Ok(()) => {
+                let budget_mins = get_remaining_pause_budget() / 60;
+                let text = format!("Pause Timer ({}m left)", budget_mins);
+                // Need to leak the string for the menu (will be cleaned up with menu)
+                let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+                let ptr = wide.as_ptr();
+                std::mem::forget(wide);
+                return show_context_menu_with_pause(hwnd, hmenu, PCWSTR(ptr), MF_BYPOSITION | MF_STRING);
+            }
+            Err(PauseBlockedReason::BudgetExhausted) => {
+                (i18n::t("tray.pause_budget_used"), MF_BYPOSITION | MF_STRING | MF_GRAYED)
+            }
+            Err(PauseBlockedReason::CooldownActive { seconds_remaining }) => {
+                let mins = (seconds_remaining + 59) / 60; // Round up
+                let text = format!("Pause ({}m cooldown)", mins);
+                let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+                let ptr = wide.as_ptr();
+                std::mem::forget(wide);
+                return show_context_menu_with_pause(hwnd, hmenu, PCWSTR(ptr), MF_BYPOSITION | MF_STRING | MF_GRAYED);
+            }
+            Err(PauseBlockedReason::MinActiveTimeNotMet { seconds_remaining }) => {
+                let mins = (seconds_remaining + 59) / 60;
+                let text = format!("Pause (wait {}m)", mins);
+                let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+                let ptr = wide.as_ptr();
+                std::mem::forget(wide);
+                return show_context_menu_with_pause(hwnd, hmenu, PCWSTR(ptr), MF_BYPOSITION | MF_STRING | MF_GRAYED);
+            }
+            Err(PauseBlockedReason::TimeTooLow) => {
+                (i18n::t("tray.pause_time_low"), MF_BYPOSITION | MF_STRING | MF_GRAYED)
+            }
+            Err(PauseBlockedReason::Disabled) => {
+                (i18n::t("tray.pause_disabled"), MF_BYPOSITION | MF_STRING | MF_GRAYED)
+            }
+        }
+    };
+
+    let pause_wide: Vec<u16> = pause_text.encode_utf16().chain(std::iter::once(0)).collect();
+    show_context_menu_with_pause(hwnd, hmenu, PCWSTR(pause_wide.as_ptr()), pause_flags);
+}
+
+/// Helper to show context menu with pause item
+unsafe fn show_context_menu_with_pause(hwnd: HWND, hmenu: HMENU, pause_text: PCWSTR, pause_flags: MENU_ITEM_FLAGS) {
+    if is_kiosk_mode() {
+        return show_kiosk_menu(hwnd, hmenu, pause_text, pause_flags);
+    }
+
+    let stats_text = i18n::wide("tray.stats");
+    InsertMenuW(hmenu, 0, MF_BYPOSITION | MF_STRING, IDM_TODAYS_STATS as usize, PCWSTR(stats_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    let settings_text = i18n::wide("tray.settings");
+    InsertMenuW(hmenu, 1, MF_BYPOSITION | MF_STRING, IDM_SETTINGS as usize, PCWSTR(settings_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    InsertMenuW(hmenu, 2, MF_BYPOSITION | MF_SEPARATOR, 0, PCWSTR::null())
+        .expect("Failed to insert separator");
+    // No extensions on a day without screen time; unlocking the blocking screen overrides it
+    let extend_flags = if is_no_screen_time_today() {
+        MF_BYPOSITION | MF_STRING | MF_GRAYED
+    } else {
+        MF_BYPOSITION | MF_STRING
+    };
+    let extend15_text = i18n::wide("tray.extend_15");
+    InsertMenuW(hmenu, 3, extend_flags, IDM_EXTEND_15 as usize, PCWSTR(extend15_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    let extend45_text = i18n::wide("tray.extend_45");
+    InsertMenuW(hmenu, 4, extend_flags, IDM_EXTEND_45 as usize, PCWSTR(extend45_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    let extend_custom_text = i18n::wide("tray.extend_custom");
+    InsertMenuW(hmenu, 5, extend_flags, IDM_EXTEND_CUSTOM as usize, PCWSTR(extend_custom_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    InsertMenuW(hmenu, 6, MF_BYPOSITION | MF_SEPARATOR, 0, PCWSTR::null())
+        .expect("Failed to insert separator");
+
+    // Pause menu item with dynamic text
+    InsertMenuW(hmenu, 7, pause_flags, IDM_PAUSE_TOGGLE as usize, pause_text)
+        .expect("Failed to insert pause menu item");
+
+    let mut idx = 8;
+
+    // Focus mode: end it, or start it for a chosen time
+    if crate::mini_overlay::is_focus_mode() {
+        let focus_end_text = i18n::wide("tray.focus_end");
+        InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_FOCUS_END as usize, PCWSTR(focus_end_text.as_ptr()))
+            .expect("Failed to insert menu item");
+    } else {
+        let focus_menu = CreatePopupMenu().expect("Failed to create popup menu");
+        let focus_items = [
+            (IDM_FOCUS_30, "tray.focus_30"),
+            (IDM_FOCUS_60, "tray.focus_60"),
+            (IDM_FOCUS_120, "tray.focus_120"),
+            (IDM_FOCUS_UNTIL_OFF, "tray.focus_until_off"),
+        ];
+        for (id, key) in focus_items {
+            let text = i18n::wide(key);
+            let _ = AppendMenuW(focus_menu, MF_STRING, id as usize, PCWSTR(text.as_ptr()));
+        }
+        let focus_text = i18n::wide("tray.focus");
+        InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_POPUP, focus_menu.0 as usize, PCWSTR(focus_text.as_ptr()))
+            .expect("Failed to insert menu item");
+    }
+    idx += 1;
+
+    // Show idle status if idle-paused
+    if is_idle_paused() {
+        let idle_text = i18n::wide("tray.idle_paused");
+        InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING | MF_GRAYED, 0, PCWSTR(idle_text.as_ptr()))
+            .expect("Failed to insert idle status");
+        idx += 1;
+    }
+
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_SEPARATOR, 0, PCWSTR::null())
+        .expect("Failed to insert separator");
+    idx += 1;
+    let mini_flags = if is_mini_overlay_enabled() {
+        MF_BYPOSITION | MF_STRING | MF_CHECKED
+    } else {
+        MF_BYPOSITION | MF_STRING
+    };
+    let mini_text = i18n::wide("tray.show_mini_overlay");
+    InsertMenuW(hmenu, idx, mini_flags, IDM_TOGGLE_MINI_OVERLAY as usize, PCWSTR(mini_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    idx += 1;
+    let warning_text = i18n::wide("tray.show_warning");
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_SHOW_OVERLAY as usize, PCWSTR(warning_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    idx += 1;
+    let blocking_text = i18n::wide("tray.show_blocking");
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_SHOW_BLOCKING as usize, PCWSTR(blocking_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    idx += 1;
+    let preview_text = i18n::wide("tray.preview_blocking");
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_PREVIEW_BLOCKING as usize, PCWSTR(preview_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    idx += 1;
+    // Simulation: only offered while Shift is held when opening the menu (and always while running)
+    let simulating = crate::mini_overlay::is_simulating();
+    if simulating || GetKeyState(VK_SHIFT.0 as i32) < 0 {
+        let sim_text = if simulating {
+            i18n::t("tray.simulation_end").to_string()
+        } else {
+            i18n::t("tray.simulation").replace("{}", &crate::database::get_sim_speed().to_string())
+        };
+        let sim_text = i18n::to_wide(&sim_text);
+        InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_SIMULATION as usize, PCWSTR(sim_text.as_ptr()))
+            .expect("Failed to insert menu item");
+        idx += 1;
+    }
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_SEPARATOR, 0, PCWSTR::null())
+        .expect("Failed to insert separator");
+    idx += 1;
+    let lock_text = i18n::wide("tray.lock");
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_LOCK_WORKSTATION as usize, PCWSTR(lock_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    idx += 1;
+    let sign_out_text = i18n::wide("tray.sign_out");
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_SIGN_OUT as usize, PCWSTR(sign_out_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    idx += 1;
+    // Grey out hibernate when it's disabled on this machine (e.g. powercfg /h off)
+    // or this account may not shut down (then the item says so)
+    let can_shut_down = crate::elevation::can_shut_down();
+    let hibernate_flags = if IsPwrHibernateAllowed().as_bool() && can_shut_down {
+        MF_BYPOSITION | MF_STRING
+    } else {
+        MF_BYPOSITION | MF_STRING | MF_GRAYED
+    };
+    let hibernate_text = i18n::wide(if can_shut_down { "tray.hibernate" } else { "tray.hibernate_unavailable" });
+    InsertMenuW(hmenu, idx, hibernate_flags, IDM_HIBERNATE as usize, PCWSTR(hibernate_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    idx += 1;
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_SEPARATOR, 0, PCWSTR::null())
+        .expect("Failed to insert separator");
+    idx += 1;
+    let log_folder_text = i18n::wide("tray.open_log_folder");
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_OPEN_LOG_FOLDER as usize, PCWSTR(log_folder_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    idx += 1;
+    // Only offered where the UAC prompt can actually elevate this account; otherwise the
+    // greyed-out shutdown items above are all there is
+    if crate::elevation::can_elevate() {
+        let restart_text = i18n::wide("tray.restart_admin");
+        InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_RESTART_ADMIN as usize, PCWSTR(restart_text.as_ptr()))
+            .expect("Failed to insert menu item");
+        idx += 1;
+    }
+    let about_text = i18n::wide("tray.about");
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_ABOUT as usize, PCWSTR(about_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    idx += 1;
+    let uninstall_text = i18n::wide("tray.uninstall");
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_UNINSTALL as usize, PCWSTR(uninstall_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    idx += 1;
+    let quit_text = i18n::wide("tray.quit");
+    InsertMenuW(hmenu, idx, MF_BYPOSITION | MF_STRING, IDM_QUIT as usize, PCWSTR(quit_text.as_ptr()))
+        .expect("Failed to insert menu item");
+
+    track_menu(hwnd, hmenu);
+}
+
+/// Kiosk mode menu: the remaining time, the status balloon, pausing and About. Parent
+/// actions aren't just passcode-gated here but left out; they're done from Telegram.
+unsafe fn show_kiosk_menu(hwnd: HWND, hmenu: HMENU, pause_text: PCWSTR, pause_flags: MENU_ITEM_FLAGS) {
+    let remaining_text = i18n::to_wide(&i18n::t("tray.kiosk_remaining").replace("{}", &format_remaining(get_remaining_seconds())));
+    InsertMenuW(hmenu, 0, MF_BYPOSITION | MF_STRING | MF_GRAYED, 0, PCWSTR(remaining_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    let status_text = i18n::wide("tray.show_status");
+    InsertMenuW(hmenu, 1, MF_BYPOSITION | MF_STRING, IDM_SHOW_STATUS as usize, PCWSTR(status_text.as_ptr()))
+        .expect("Failed to insert menu item");
+    InsertMenuW(hmenu, 2, pause_flags, IDM_PAUSE_TOGGLE as usize, pause_text)
+        .expect("Failed to insert pause menu item");
+    InsertMenuW(hmenu, 3, MF_BYPOSITION | MF_SEPARATOR, 0, PCWSTR::null())
+        .expect("Failed to insert separator");
+    let about_text = i18n::wide("tray.about");
+    InsertMenuW(hmenu, 4, MF_BYPOSITION | MF_STRING, IDM_ABOUT as usize, PCWSTR(about_text.as_ptr()))
+        .expect("Failed to insert menu item");
+
+    track_menu(hwnd, hmenu);
+}
+
+/// Show a built context menu at the cursor and destroy it afterwards
+unsafe fn track_menu(hwnd: HWND, hmenu: HMENU) {
+    let mut point = zeroed();
+    GetCursorPos(&mut point).expect("Failed to get cursor position");
+
+    let _ = SetForegroundWindow(hwnd);
+
+    let _ = TrackPopupMenu(
+        hmenu,
+        TPM_LEFTALIGN | TPM_RIGHTBUTTON | TPM_BOTTOMALIGN,
+        point.x,
+        point.y,
+        0,
+        hwnd,
+        None,
+    );
+
+    DestroyMenu(hmenu).ok();
+}
+
+/// Main window procedure for handling tray events
+pub unsafe extern "system" fn window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_TRAYICON => {
+            let event = lparam.0 as u32;
+            match event {
+                WM_MOUSEMOVE => {
+                    update_tray_tooltip();
+                }
+                WM_RBUTTONUP => {
+                    show_context_menu(hwnd);
+                }
+                // Wait out the double-click time so a double-click doesn't also run the click action
+                WM_LBUTTONUP if !TRAY_DOUBLE_CLICKED.swap(false, Ordering::SeqCst) => {
+                    SetTimer(hwnd, TIMER_TRAY_CLICK, GetDoubleClickTime(), None);
+                }
+                WM_LBUTTONDBLCLK => {
+                    let _ = KillTimer(hwnd, TIMER_TRAY_CLICK);
+                    TRAY_DOUBLE_CLICKED.store(true, Ordering::SeqCst);
+                    if !is_kiosk_mode() && verify_passcode_for_quit(hwnd) {
+                        show_settings_dialog(hwnd);
+                    }
+                }
+                _ => {}
+            }
+            LRESULT(0)
+        }
+        WM_TIMER if wparam.0 == crate::mini_overlay::TIMER_MINI_UPDATE => {
+            // Headless the countdown runs on this window
+            crate::mini_overlay::countdown_tick();
+            LRESULT(0)
+        }
+        WM_TIMER if wparam.0 == TIMER_TRAY_CLICK => {
+            let _ = KillTimer(hwnd, TIMER_TRAY_CLICK);
+            if get_tray_left_click() == "stats" && !is_kiosk_mode() {
+                if verify_passcode_for_quit(hwnd) {
+                    show_stats_dialog(hwnd);
+                }
+            } else {
+                show_status_balloon();
+            }
+            LRESULT(0)
+        }
+        WM_HOTKEY if wparam.0 as i32 == crate::hotkey::HOTKEY_STATUS => {
+            crate::hotkey::show_status();
+            LRESULT(0)
+        }
+        WM_COMMAND => {
+            let menu_id = (wparam.0 & 0xFFFF) as u16;
+            // Kiosk mode also ignores parent commands that didn't come from its own menu
+            if is_kiosk_mode() && !matches!(menu_id, IDM_SHOW_STATUS | IDM_PAUSE_TOGGLE | IDM_ABOUT) {
+                return LRESULT(0);
+            }
+            match menu_id {
+                IDM_SHOW_STATUS => {
+                    show_status_balloon();
+                }
+                IDM_PAUSE_TOGGLE => {
+                    // No passcode by default (it's a child feature), unless the parent made
+                    // pausing parent-only; resuming never needs it
+                    if !is_paused() && crate::database::is_pause_passcode_required() && !verify_passcode_for_quit(hwnd) {
+                        return LRESULT(0);
+                    }
+                    match toggle_pause() {
+                        Ok(_is_now_paused) => {
+                            // Success - UI will update automatically
+                        }
+                        Err(_reason) => {
+                            // Should not happen since menu item should be grayed out
+                            // But just in case, do nothing
+                        }
+                    }
+                }
+                IDM_FOCUS_30 | IDM_FOCUS_60 | IDM_FOCUS_120 | IDM_FOCUS_UNTIL_OFF if verify_passcode_for_quit(hwnd) => {
+                    let minutes = match menu_id {
+                        IDM_FOCUS_30 => 30,
+                        IDM_FOCUS_60 => 60,
+                        IDM_FOCUS_120 => 120,
+                        _ => 0,
+                    };
+                    crate::mini_overlay::start_focus_mode(minutes, "tray");
+                }
+                IDM_FOCUS_END if verify_passcode_for_quit(hwnd) => {
+                    crate::mini_overlay::stop_focus_mode();
+                }
+                // Hiding the timer needs the passcode; showing it again doesn't
+                IDM_TOGGLE_MINI_OVERLAY if !is_mini_overlay_enabled() || verify_passcode_for_quit(hwnd) => {
+                    set_mini_overlay_enabled(!is_mini_overlay_enabled());
+                }
+                IDM_SHOW_OVERLAY => {
+                    let (_, message, _) = get_warning_config_for_day(1, get_current_weekday());
+                    show_overlay(&message, 5, false);
+                }
+                IDM_SHOW_BLOCKING => {
+                    let message = get_blocking_message();
+                    crate::database::log_block_event("tray");
+                    show_blocking_overlay(&message, "tray");
+                }
+                IDM_PREVIEW_BLOCKING => {
+                    crate::blocking::show_blocking_overlay_preview(&get_blocking_message());
+                }
+                IDM_SIMULATION if crate::mini_overlay::is_simulating() => {
+                    crate::mini_overlay::end_simulation();
+                }
+                IDM_SIMULATION if verify_passcode_for_quit(hwnd) => {
+                    crate::mini_overlay::start_simulation();
+                }
+                IDM_TODAYS_STATS if verify_passcode_for_quit(hwnd) => {
+                    show_stats_dialog(hwnd);
+                }
+                IDM_SETTINGS if verify_passcode_for_quit(hwnd) => {
+                    show_settings_dialog(hwnd);
+                }
+                IDM_EXTEND_15 if verify_passcode_for_quit(hwnd) => {
+                    if let Err(e) = extend_time_checked(15, ExtendSource::Tray) {
+                        show_extend_error(hwnd, e);
+                    }
+                }
+                IDM_EXTEND_45 if verify_passcode_for_quit(hwnd) => {
+                    if let Err(e) = extend_time_checked(45, ExtendSource::Tray) {
+                        show_extend_error(hwnd, e);
+                    }
+                }
+                IDM_EXTEND_CUSTOM if verify_passcode_for_quit(hwnd) => {
+                    extend_custom(hwnd, ExtendSource::Tray);
+                }
+                IDM_LOCK_WORKSTATION if verify_passcode_for_quit(hwnd) => {
+                    let _ = LockWorkStation();
+                }
+                IDM_SIGN_OUT if verify_passcode_for_quit(hwnd) => {
+                    let _ = ExitWindowsEx(EWX_LOGOFF, SHUTDOWN_REASON(0));
+                }
+                IDM_HIBERNATE if verify_passcode_for_quit(hwnd) && crate::elevation::can_shut_down() => {
+                    let _ = SetSuspendState(true, false, false);
+                }
+                IDM_ABOUT => {
+                    let about_msg = i18n::to_wide(&format!("{}\n\n{}", about_text(), i18n::t("about.open_repo")));
+                    let about_title = i18n::wide("window.about");
+                    let choice = MessageBoxW(
+                        hwnd,
+                        PCWSTR(about_msg.as_ptr()),
+                        PCWSTR(about_title.as_ptr()),
+                        MB_YESNO | MB_ICONINFORMATION,
+                    );
+                    if choice == IDYES {
+                        let url = i18n::to_wide(env!("CARGO_PKG_REPOSITORY"));
+                        ShellExecuteW(hwnd, w!("open"), PCWSTR(url.as_ptr()), None, None, SW_SHOWNORMAL);
+                    }
+                }
+                // The folder also holds the database, so it's parent-only
+                IDM_OPEN_LOG_FOLDER if verify_passcode_for_quit(hwnd) => {
+                    let folder = i18n::to_wide(&crate::logging::log_dir().to_string_lossy());
+                    ShellExecuteW(hwnd, w!("open"), PCWSTR(folder.as_ptr()), None, None, SW_SHOWNORMAL);
+                }
+                // The elevated copy waits for this instance to exit before it takes over
+                IDM_RESTART_ADMIN if verify_passcode_for_quit(hwnd) && crate::elevation::restart_as_admin(hwnd) => {
+                    crate::mini_overlay::save_counters();
+                    DestroyWindow(hwnd).ok();
+                }
+                // Passcode and backup are handled by the uninstall prompts. Exits at once
+                // instead of the normal shutdown, which would write to the deleted database.
+                IDM_UNINSTALL if crate::dialogs::show_uninstall(hwnd) => {
+                    remove_tray_icon();
+                    std::process::exit(0);
+                }
+                IDM_QUIT if verify_passcode_for_quit(hwnd) => {
+                    crate::mini_overlay::save_counters();
+                    DestroyWindow(hwnd).ok();
+                }
+                _ => {}
+            }
+            LRESULT(0)
+        }
+        WM_DISPLAYCHANGE | WM_DPICHANGED => {
+            // Resolution or display scaling changed: re-measure the always-on overlays
+            crate::dpi::refresh_dpi();
+            crate::mini_overlay::resize_mini_overlay();
+            crate::blocking::refresh_blocking_layout();
+            LRESULT(0)
+        }
+        WM_SETTINGCHANGE => {
+            // Windows high-contrast or transparency settings may have changed
+            crate::theme::refresh();
+            LRESULT(0)
+        }
+        WM_QUERYENDSESSION => {
+            // Windows is shutting down or the user is signing out: flush before we get killed
+            crate::mini_overlay::save_counters();
+            LRESULT(1)
+        }
+        WM_ENDSESSION => {
+            if wparam.0 != 0 {
+                crate::mini_overlay::save_counters();
+            }
+            LRESULT(0)
+        }
+        WM_WTSSESSION_CHANGE => {
+            crate::session::handle_change(wparam.0 as u32, lparam.0 as u32);
+            LRESULT(0)
+        }
+        WM_POWERBROADCAST => {
+            match wparam.0 as u32 {
+                PBT_APMSUSPEND => crate::mini_overlay::handle_system_suspend(),
+                PBT_APMRESUMEAUTOMATIC => crate::mini_overlay::handle_system_resume(),
+                _ => {}
+            }
+            LRESULT(1)
+        }
+        WM_DESTROY => {
+            // Signal Telegram bot to shut down (sends shutdown notification)
+            telegram::signal_shutdown();
+            crate::hotkey::unregister();
+            crate::session::unregister(hwnd);
+
+            // Persist counters so the next start continues today's session
+            crate::mini_overlay::save_counters();
+
+            let overlay_hwnd = HWND(OVERLAY_HWND.load(Ordering::SeqCst));
+            if !overlay_hwnd.0.is_null() {
+                DestroyWindow(overlay_hwnd).ok();
+            }
+            let blocking_hwnd = HWND(BLOCKING_HWND.load(Ordering::SeqCst));
+            if !blocking_hwnd.0.is_null() {
+                hide_blocking_overlay();
+                DestroyWindow(blocking_hwnd).ok();
+            }
+            remove_tray_icon();
+            PostQuitMessage(0);
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
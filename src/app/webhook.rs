@@ -0,0 +1,45 @@
+//! Webhook module
+//! Posts notification events as JSON to a configured URL (Discord, Slack, IFTTT, ...)
+
+use std::time::Duration;
+
+use crate::database;
+use crate::json;
+
+/// How long one delivery attempt may take
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Post an event to the configured webhook URL, if any. The request runs on its own
+/// thread; failures are only logged.
+pub fn notify(event: &str, remaining_seconds: i32, message: &str) {
+    let url = database::get_webhook_url();
+    if url.is_empty() {
+        return;
+    }
+
+    let body = payload(event, remaining_seconds, message);
+    let event = event.to_string();
+    std::thread::spawn(move || {
+        let result = ureq::post(&url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .set("Content-Type", "application/json")
+            .send_string(&body);
+        if let Err(e) = result {
+            log::warn!("Sending '{}' failed: {}", event, e);
+        }
+    });
+}
+
+/// JSON body. The message is repeated as `content` and `text`, the fields Discord and
+/// Slack display, so the same URL format works for both.
+fn payload(event: &str, remaining_seconds: i32, message: &str) -> String {
+    let message = json::escape(message);
+    format!(
+        "{{\"event\":\"{}\",\"remaining_seconds\":{},\"message\":\"{}\",\"content\":\"{}\",\"text\":\"{}\"}}",
+        json::escape(event),
+        remaining_seconds,
+        message,
+        message,
+        message
+    )
+}
@@ -0,0 +1,348 @@
+//! Wizard module
+//! First-run setup: passcode, daily limits, Telegram and starting with Windows.
+//! Shown at startup until it has been finished once; every step can be skipped.
+
+use std::mem::zeroed;
+use std::sync::atomic::{AtomicI32, Ordering};
+use windows::{
+    core::{w, PCWSTR},
+    Win32::{
+        Foundation::{COLORREF, HWND, LPARAM, LRESULT, WPARAM},
+        Graphics::Gdi::{CreateFontW, CreateSolidBrush, DeleteObject, FW_BOLD, FW_NORMAL, HFONT, HGDIOBJ},
+        System::LibraryLoader::GetModuleHandleW,
+        System::Registry::{
+            RegCloseKey, RegCreateKeyExW, RegSetValueExW, HKEY, HKEY_CURRENT_USER, KEY_SET_VALUE,
+            REG_OPTION_NON_VOLATILE, REG_SZ,
+        },
+        UI::{
+            Controls::{BST_CHECKED, EM_SETLIMITTEXT},
+            Input::KeyboardAndMouse::{EnableWindow, SetFocus},
+            WindowsAndMessaging::*,
+        },
+    },
+};
+
+use crate::database::{self, WEEKDAY_KEYS};
+use crate::dpi::scale;
+use crate::i18n;
+
+// Control IDs
+const ID_STEP_LABEL: i32 = 4001;
+const ID_TITLE: i32 = 4002;
+const ID_DESC: i32 = 4003;
+const ID_BACK: i32 = 4010;
+const ID_SKIP: i32 = 4011;
+const ID_NEXT: i32 = 4012;
+const ID_NEW_PASSCODE: i32 = 4101;
+const ID_CONFIRM_PASSCODE: i32 = 4102;
+const ID_WEEKDAY_LIMIT: i32 = 4201;
+const ID_WEEKEND_LIMIT: i32 = 4202;
+const ID_TELEGRAM: i32 = 4301;
+const ID_AUTOSTART: i32 = 4401;
+
+/// Steps in order: (title key, description key, controls shown on the step)
+const STEPS: [(&str, &str, &[i32]); 4] = [
+    ("setup.passcode.title", "setup.passcode.desc", &[ID_NEW_PASSCODE, ID_NEW_PASSCODE + 50, ID_CONFIRM_PASSCODE, ID_CONFIRM_PASSCODE + 50]),
+    ("setup.limits.title", "setup.limits.desc", &[ID_WEEKDAY_LIMIT, ID_WEEKDAY_LIMIT + 50, ID_WEEKEND_LIMIT, ID_WEEKEND_LIMIT + 50]),
+    ("setup.telegram.title", "setup.telegram.desc", &[ID_TELEGRAM]),
+    ("setup.autostart.title", "setup.autostart.desc", &[ID_AUTOSTART]),
+];
+
+/// Registry key of the programs started at sign-in, and our value in it
+pub const RUN_KEY: PCWSTR = w!("Software\\Microsoft\\Windows\\CurrentVersion\\Run");
+pub const RUN_VALUE: PCWSTR = w!("ScreenTimeManager");
+
+/// Current step (index into STEPS)
+static STEP: AtomicI32 = AtomicI32::new(0);
+
+/// Show the setup wizard if it hasn't been finished yet (a fresh installation)
+pub unsafe fn show_if_needed() {
+    if !database::is_setup_complete() {
+        show_setup_wizard();
+    }
+}
+
+/// Show the setup wizard and wait until it is finished or closed
+pub unsafe fn show_setup_wizard() {
+    STEP.store(0, Ordering::SeqCst);
+    let hinstance = GetModuleHandleW(None).expect("Failed to get module handle");
+    let class_name = w!("ScreenTimeSetupWizard");
+
+    let wnd_class = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(wizard_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: class_name,
+        hbrBackground: CreateSolidBrush(COLORREF(0x00F0F0F0)),
+        hCursor: LoadCursorW(None, IDC_ARROW).ok().unwrap_or_default(),
+        ..zeroed()
+    };
+    RegisterClassW(&wnd_class);
+
+    let screen_width = GetSystemMetrics(SM_CXSCREEN);
+    let screen_height = GetSystemMetrics(SM_CYSCREEN);
+    let dialog_width = scale(460);
+    let dialog_height = scale(380);
+    let title = i18n::wide("setup.title");
+
+    let Ok(hwnd) = CreateWindowExW(
+        WS_EX_TOPMOST | WS_EX_DLGMODALFRAME,
+        class_name,
+        PCWSTR(title.as_ptr()),
+        WS_POPUP | WS_CAPTION | WS_SYSMENU,
+        (screen_width - dialog_width) / 2,
+        (screen_height - dialog_height) / 2,
+        dialog_width,
+        dialog_height,
+        None,
+        None,
+        hinstance,
+        None,
+    ) else {
+        return;
+    };
+
+    let _ = ShowWindow(hwnd, SW_SHOW);
+    let _ = SetForegroundWindow(hwnd);
+
+    // Tab moves between the fields
+    crate::dialogs::run_modal(hwnd, HWND::default(), true);
+}
+
+unsafe extern "system" fn wizard_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match msg {
+        WM_CREATE => {
+            create_controls(hwnd);
+            show_step(hwnd);
+            LRESULT(0)
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xFFFF) as i32;
+            match id {
+                // Enter and Esc arrive as IDOK / IDCANCEL
+                ID_NEXT if apply_step(hwnd) => next_step(hwnd),
+                id if id == IDOK.0 && apply_step(hwnd) => next_step(hwnd),
+                id if id == IDCANCEL.0 => {
+                    DestroyWindow(hwnd).ok();
+                }
+                ID_SKIP if confirm_skip(hwnd) => next_step(hwnd),
+                ID_BACK => {
+                    let step = STEP.load(Ordering::SeqCst);
+                    STEP.store((step - 1).max(0), Ordering::SeqCst);
+                    show_step(hwnd);
+                }
+                ID_TELEGRAM => crate::dialogs::show_telegram_wizard(hwnd),
+                _ => {}
+            }
+            LRESULT(0)
+        }
+        WM_CLOSE => {
+            // Closed early: the wizard comes back at the next start
+            DestroyWindow(hwnd).ok();
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            for id in [ID_TITLE, ID_DESC] {
+                if let Ok(control) = GetDlgItem(hwnd, id) {
+                    let font = SendMessageW(control, WM_GETFONT, WPARAM(0), LPARAM(0));
+                    let _ = DeleteObject(HGDIOBJ(font.0 as _));
+                }
+            }
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+/// Create the controls of all steps; show_step picks the visible ones
+unsafe fn create_controls(hwnd: HWND) {
+    let hinstance = GetModuleHandleW(None).unwrap();
+    let title_font = CreateFontW(scale(20), 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+    let font = CreateFontW(scale(14), 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, 0, 0, 0, 5, 0, w!("Segoe UI"));
+
+    let control = |class: PCWSTR, text: &str, style: WINDOW_STYLE, x: i32, y: i32, width: i32, height: i32, id: i32, font: HFONT| {
+        let wide = i18n::to_wide(text);
+        let ex_style = if class == w!("EDIT") { WINDOW_EX_STYLE(0x200) } else { WINDOW_EX_STYLE(0) };
+        if let Ok(h) = CreateWindowExW(
+            ex_style, class, PCWSTR(wide.as_ptr()),
+            WS_CHILD | style, scale(x), scale(y), scale(width), scale(height),
+            hwnd, HMENU(id as _), hinstance, None,
+        ) {
+            SendMessageW(h, WM_SETFONT, WPARAM(font.0 as usize), LPARAM(1));
+        }
+    };
+
+    control(w!("STATIC"), "", WS_VISIBLE, 25, 15, 400, 18, ID_STEP_LABEL, font);
+    control(w!("STATIC"), "", WS_VISIBLE, 25, 40, 400, 28, ID_TITLE, title_font);
+    control(w!("STATIC"), "", WS_VISIBLE, 25, 75, 400, 60, ID_DESC, font);
+
+    // Labels get the field's ID + 50 so show_step can toggle them together
+    let passcode_style = WS_BORDER | WS_TABSTOP | WINDOW_STYLE(ES_PASSWORD as u32 | ES_NUMBER as u32 | ES_CENTER as u32);
+    control(w!("STATIC"), i18n::t("setup.passcode.new"), WINDOW_STYLE(0), 25, 152, 180, 20, ID_NEW_PASSCODE + 50, font);
+    control(w!("EDIT"), "", passcode_style, 210, 150, 80, 24, ID_NEW_PASSCODE, font);
+    control(w!("STATIC"), i18n::t("setup.passcode.confirm"), WINDOW_STYLE(0), 25, 187, 180, 20, ID_CONFIRM_PASSCODE + 50, font);
+    control(w!("EDIT"), "", passcode_style, 210, 185, 80, 24, ID_CONFIRM_PASSCODE, font);
+    for id in [ID_NEW_PASSCODE, ID_CONFIRM_PASSCODE] {
+        if let Ok(edit) = GetDlgItem(hwnd, id) {
+            SendMessageW(edit, EM_SETLIMITTEXT, WPARAM(4), LPARAM(0));
+        }
+    }
+
+    let limit_style = WS_BORDER | WS_TABSTOP | WINDOW_STYLE(ES_NUMBER as u32 | ES_CENTER as u32);
+    let weekday_limit = database::get_daily_limit(0).to_string();
+    let weekend_limit = database::get_daily_limit(5).to_string();
+    control(w!("STATIC"), i18n::t("setup.limits.weekdays"), WINDOW_STYLE(0), 25, 152, 180, 20, ID_WEEKDAY_LIMIT + 50, font);
+    control(w!("EDIT"), &weekday_limit, limit_style, 210, 150, 80, 24, ID_WEEKDAY_LIMIT, font);
+    control(w!("STATIC"), i18n::t("setup.limits.weekends"), WINDOW_STYLE(0), 25, 187, 180, 20, ID_WEEKEND_LIMIT + 50, font);
+    control(w!("EDIT"), &weekend_limit, limit_style, 210, 185, 80, 24, ID_WEEKEND_LIMIT, font);
+
+    control(w!("BUTTON"), i18n::t("setup.telegram.button"), WS_TABSTOP | WINDOW_STYLE(BS_PUSHBUTTON as u32), 25, 150, 180, 30, ID_TELEGRAM, font);
+
+    control(w!("BUTTON"), i18n::t("setup.autostart.check"), WS_TABSTOP | WINDOW_STYLE(BS_AUTOCHECKBOX as u32), 25, 150, 400, 22, ID_AUTOSTART, font);
+    if let Ok(check) = GetDlgItem(hwnd, ID_AUTOSTART) {
+        SendMessageW(check, BM_SETCHECK, WPARAM(1), LPARAM(0));
+    }
+
+    let button_style = WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(BS_PUSHBUTTON as u32);
+    control(w!("BUTTON"), i18n::t("wizard.back"), button_style, 25, 290, 95, 32, ID_BACK, font);
+    control(w!("BUTTON"), i18n::t("wizard.skip"), button_style, 225, 290, 95, 32, ID_SKIP, font);
+    control(w!("BUTTON"), i18n::t("wizard.next"), button_style | WINDOW_STYLE(BS_DEFPUSHBUTTON as u32), 330, 290, 95, 32, ID_NEXT, font);
+}
+
+/// Show the current step's texts and controls
+unsafe fn show_step(hwnd: HWND) {
+    let step = STEP.load(Ordering::SeqCst) as usize;
+    let (title, desc, _) = STEPS[step];
+
+    let step_text = i18n::t("setup.step")
+        .replacen("{}", &(step + 1).to_string(), 1)
+        .replacen("{}", &STEPS.len().to_string(), 1);
+    set_text(hwnd, ID_STEP_LABEL, &step_text);
+    set_text(hwnd, ID_TITLE, i18n::t(title));
+    set_text(hwnd, ID_DESC, i18n::t(desc));
+
+    for (index, (_, _, controls)) in STEPS.iter().enumerate() {
+        for &id in controls.iter() {
+            if let Ok(control) = GetDlgItem(hwnd, id) {
+                let _ = ShowWindow(control, if index == step { SW_SHOW } else { SW_HIDE });
+            }
+        }
+    }
+
+    if let Ok(back) = GetDlgItem(hwnd, ID_BACK) {
+        let _ = EnableWindow(back, step > 0);
+    }
+    let last = step == STEPS.len() - 1;
+    set_text(hwnd, ID_NEXT, i18n::t(if last { "wizard.finish" } else { "wizard.next" }));
+
+    if let Some(&first) = STEPS[step].2.first() {
+        if let Ok(control) = GetDlgItem(hwnd, first) {
+            let _ = SetFocus(control);
+        }
+    }
+}
+
+/// Go to the next step, or finish after the last one
+unsafe fn next_step(hwnd: HWND) {
+    let step = STEP.load(Ordering::SeqCst) + 1;
+    if step as usize >= STEPS.len() {
+        database::set_setting("setup_complete", "1");
+        DestroyWindow(hwnd).ok();
+        return;
+    }
+    STEP.store(step, Ordering::SeqCst);
+    show_step(hwnd);
+}
+
+/// Save the current step. Returns false (and explains why) if it can't be saved yet.
+unsafe fn apply_step(hwnd: HWND) -> bool {
+    match STEP.load(Ordering::SeqCst) {
+        0 => {
+            let new = get_text(hwnd, ID_NEW_PASSCODE);
+            let confirm = get_text(hwnd, ID_CONFIRM_PASSCODE);
+            if new.is_empty() && confirm.is_empty() {
+                return confirm_skip(hwnd);
+            }
+            if new.len() != 4 {
+                return show_error(hwnd, "settings.error.passcode_length");
+            }
+            if new != confirm {
+                return show_error(hwnd, "settings.error.passcode_mismatch");
+            }
+            database::set_setting("passcode", &new)
+        }
+        1 => {
+            let parse = |id| get_text(hwnd, id).parse::<u32>().ok().filter(|&minutes| minutes <= 1440);
+            let (Some(weekdays), Some(weekends)) = (parse(ID_WEEKDAY_LIMIT), parse(ID_WEEKEND_LIMIT)) else {
+                return show_error(hwnd, "setup.limits.invalid");
+            };
+            let (weekdays, weekends) = (weekdays.to_string(), weekends.to_string());
+            let updates: Vec<(&str, &str)> = WEEKDAY_KEYS
+                .iter()
+                .enumerate()
+                .map(|(day, &key)| (key, if day < 5 { weekdays.as_str() } else { weekends.as_str() }))
+                .collect();
+            database::set_settings_batch(&updates).is_ok()
+        }
+        3 => {
+            let checked = GetDlgItem(hwnd, ID_AUTOSTART)
+                .map(|check| SendMessageW(check, BM_GETCHECK, WPARAM(0), LPARAM(0)).0 == BST_CHECKED.0 as isize)
+                .unwrap_or(false);
+            if checked && !enable_autostart() {
+                show_error(hwnd, "setup.autostart.failed");
+            }
+            true
+        }
+        _ => true,
+    }
+}
+
+/// Skipping the passcode step leaves the default passcode, so ask first
+unsafe fn confirm_skip(hwnd: HWND) -> bool {
+    if STEP.load(Ordering::SeqCst) != 0 || !database::is_default_passcode() {
+        return true;
+    }
+    let msg = i18n::wide("setup.passcode.keep_default");
+    let title = i18n::wide("setup.title");
+    MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_YESNO | MB_ICONWARNING | MB_DEFBUTTON2) == IDYES
+}
+
+/// Start the app when the user signs in to Windows
+unsafe fn enable_autostart() -> bool {
+    let Ok(exe) = std::env::current_exe() else {
+        return false;
+    };
+    let command = i18n::to_wide(&format!("\"{}\"", exe.display()));
+    let data: Vec<u8> = command.iter().flat_map(|c| c.to_le_bytes()).collect();
+
+    let mut key = HKEY::default();
+    if RegCreateKeyExW(HKEY_CURRENT_USER, RUN_KEY, 0, None, REG_OPTION_NON_VOLATILE, KEY_SET_VALUE, None, &mut key, None).is_err() {
+        return false;
+    }
+    let result = RegSetValueExW(key, RUN_VALUE, 0, REG_SZ, Some(&data));
+    let _ = RegCloseKey(key);
+    result.is_ok()
+}
+
+unsafe fn show_error(hwnd: HWND, key: &str) -> bool {
+    let msg = i18n::wide(key);
+    let title = i18n::wide("settings.error");
+    MessageBoxW(hwnd, PCWSTR(msg.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONERROR);
+    false
+}
+
+unsafe fn get_text(hwnd: HWND, id: i32) -> String {
+    let Ok(control) = GetDlgItem(hwnd, id) else {
+        return String::new();
+    };
+    let mut buffer = [0u16; 16];
+    let len = GetWindowTextW(control, &mut buffer);
+    String::from_utf16_lossy(&buffer[..len as usize]).trim().to_string()
+}
+
+unsafe fn set_text(hwnd: HWND, id: i32, text: &str) {
+    if let Ok(control) = GetDlgItem(hwnd, id) {
+        let wide = i18n::to_wide(text);
+        let _ = SetWindowTextW(control, PCWSTR(wide.as_ptr()));
+    }
+}
@@ -0,0 +1,58 @@
+//! Date math module
+//! Calendar arithmetic on plain (year, month, day) dates, without the Win32 time functions
+
+/// Days since 1970-01-01 of a calendar date (proleptic Gregorian calendar)
+pub fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Calendar date of a day count since 1970-01-01
+pub fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+    (year, month, day)
+}
+
+/// The date `days` days after (or before, if negative) the given date
+pub fn offset_date(year: i32, month: u32, day: u32, days: i64) -> (i32, u32, u32) {
+    civil_from_days(days_from_civil(year, month, day) + days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_cross_months_years_and_leap_days() {
+        assert_eq!(offset_date(2026, 12, 31, 1), (2027, 1, 1));
+        assert_eq!(offset_date(2026, 12, 31, -365), (2025, 12, 31));
+        assert_eq!(offset_date(2032, 2, 28, 1), (2032, 2, 29));
+        assert_eq!(offset_date(2032, 2, 29, 1), (2032, 3, 1));
+        assert_eq!(offset_date(2100, 2, 28, 1), (2100, 3, 1));
+        assert_eq!(offset_date(2000, 3, 1, -1), (2000, 2, 29));
+        assert_eq!(offset_date(1970, 1, 1, 0), (1970, 1, 1));
+    }
+
+    #[test]
+    fn day_counts_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2026, 10, 15), 20_741);
+        for days in [-1, 0, 59, 365, 11_016, 20_741, 47_541] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+}
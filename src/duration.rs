@@ -0,0 +1,76 @@
+//! Duration helper module
+//! Limits are stored as whole minutes; the settings fields show and accept hours too
+
+/// Parse a limit typed by the user into minutes.
+/// Accepts plain minutes ("150"), hours and minutes ("2:30") and unit suffixes
+/// ("2h 30m", "2h", "45m"). Returns None for anything else.
+pub fn parse_duration(text: &str) -> Option<u32> {
+    let text = text.trim().to_ascii_lowercase();
+    if text.is_empty() {
+        return None;
+    }
+
+    if let Some((h, m)) = text.split_once(':') {
+        let (h, m) = (h.trim(), m.trim());
+        // Minutes after the colon are always two digits, so "2:5" is rejected
+        if h.is_empty() || m.len() != 2 {
+            return None;
+        }
+        let (h, m): (u32, u32) = (h.parse().ok()?, m.parse().ok()?);
+        return if m < 60 { h.checked_mul(60)?.checked_add(m) } else { None };
+    }
+
+    if !text.contains(['h', 'm']) {
+        return text.parse().ok();
+    }
+
+    let (hours, rest) = match text.split_once('h') {
+        Some((h, rest)) => (h.trim().parse::<u32>().ok()?, rest.trim()),
+        None => (0, text.as_str()),
+    };
+    let minutes = if rest.is_empty() {
+        0
+    } else {
+        rest.strip_suffix('m')?.trim().parse::<u32>().ok()?
+    };
+    hours.checked_mul(60)?.checked_add(minutes)
+}
+
+/// Format minutes for a settings field as "H:MM" (e.g. 150 -> "2:30")
+pub fn format_duration(minutes: u32) -> String {
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_minutes_hours_and_suffixes() {
+        assert_eq!(parse_duration("150"), Some(150));
+        assert_eq!(parse_duration(" 2:30 "), Some(150));
+        assert_eq!(parse_duration("0:45"), Some(45));
+        assert_eq!(parse_duration("2h 30m"), Some(150));
+        assert_eq!(parse_duration("2H30M"), Some(150));
+        assert_eq!(parse_duration("2h"), Some(120));
+        assert_eq!(parse_duration("45m"), Some(45));
+        assert_eq!(parse_duration("0"), Some(0));
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for text in ["", "abc", "2:5", "2:60", ":30", "2h 30", "-5", "1.5", "2m 30h", "h"] {
+            assert_eq!(parse_duration(text), None, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn formats_back_to_what_it_parses() {
+        assert_eq!(format_duration(150), "2:30");
+        assert_eq!(format_duration(45), "0:45");
+        assert_eq!(format_duration(1440), "24:00");
+        for minutes in [0, 5, 59, 60, 61, 600, 1440] {
+            assert_eq!(parse_duration(&format_duration(minutes)), Some(minutes));
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! JSON module
+//! Escaping for the small JSON documents the app writes by hand (webhooks, backups)
+
+/// Escape a string for use inside a JSON string literal (no serde dependency for this)
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_backslashes_and_control_characters_are_escaped() {
+        assert_eq!(escape("plain text äö"), "plain text äö");
+        assert_eq!(escape(r#"say "hi" \o/"#), r#"say \"hi\" \\o/"#);
+        assert_eq!(escape("a\nb\r\tc"), "a\\nb\\r\\tc");
+        assert_eq!(escape("\u{1}\u{1f}"), "\\u0001\\u001f");
+    }
+}
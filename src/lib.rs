@@ -0,0 +1,15 @@
+//! Screen Time Manager - platform-independent logic
+//!
+//! Pure functions shared with the Windows app: limit parsing, time formatting, date math,
+//! the allowed-hours window, the pause rules, which accounts are counted and JSON escaping.
+//! Nothing here touches the Win32 API, the database or the clock, so `cargo test` runs these
+//! units on any OS (e.g. a Linux CI runner). The app in app.rs uses them under the same
+//! `crate::` paths as its own modules.
+
+pub mod accounts;
+pub mod allowed_hours;
+pub mod dates;
+pub mod duration;
+pub mod json;
+pub mod pause;
+pub mod timefmt;
@@ -0,0 +1,157 @@
+//! Pause rules module
+//! Whether a pause may start, given the configured limits and today's pause usage
+
+/// Pause limits configured by the parent (all in minutes)
+pub struct PauseConfig {
+    pub daily_budget_minutes: u32,
+    pub max_duration_minutes: u32,
+    pub cooldown_minutes: u32,
+    pub min_active_time_minutes: u32,
+}
+
+/// Reason why pause is not available
+#[derive(Debug, Clone)]
+pub enum PauseBlockedReason {
+    Disabled,
+    BudgetExhausted,
+    CooldownActive { seconds_remaining: i32 },
+    MinActiveTimeNotMet { seconds_remaining: i32 },
+    TimeTooLow,
+}
+
+/// Everything the pause decision depends on besides the configured limits
+#[derive(Debug, Clone, Copy)]
+pub struct PauseState {
+    pub paused: bool,
+    pub enabled: bool,
+    pub remaining: i32,
+    pub pause_used: i32,
+    pub last_pause_end: i64,
+    pub active_since_resume: i32,
+}
+
+/// Decide whether a pause may start (or end) right now; `now` is a Unix timestamp
+pub fn evaluate_pause(config: PauseConfig, state: PauseState, now: i64) -> Result<(), PauseBlockedReason> {
+    // Already paused - can always unpause
+    if state.paused {
+        return Ok(());
+    }
+
+    // Check if pause feature is enabled
+    if !state.enabled {
+        return Err(PauseBlockedReason::Disabled);
+    }
+
+    // Check if remaining time is too low (< 1 minute)
+    if state.remaining < 60 {
+        return Err(PauseBlockedReason::TimeTooLow);
+    }
+
+    // Check daily budget
+    let budget_seconds = (config.daily_budget_minutes * 60) as i32;
+    if state.pause_used >= budget_seconds {
+        return Err(PauseBlockedReason::BudgetExhausted);
+    }
+
+    // Check cooldown
+    let cooldown_seconds = (config.cooldown_minutes * 60) as i64;
+    let time_since_last_pause = now - state.last_pause_end;
+
+    if state.last_pause_end > 0 && time_since_last_pause < cooldown_seconds {
+        let remaining_cooldown = (cooldown_seconds - time_since_last_pause) as i32;
+        return Err(PauseBlockedReason::CooldownActive {
+            seconds_remaining: remaining_cooldown,
+        });
+    }
+
+    // Check minimum active time since the last pause
+    let active_since_resume = state.active_since_resume;
+    let min_active_seconds = (config.min_active_time_minutes * 60) as i32;
+
+    if active_since_resume < min_active_seconds {
+        let remaining_active = min_active_seconds - active_since_resume;
+        return Err(PauseBlockedReason::MinActiveTimeNotMet {
+            seconds_remaining: remaining_active,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000_000;
+
+    fn pause_config() -> PauseConfig {
+        PauseConfig {
+            daily_budget_minutes: 45,
+            max_duration_minutes: 20,
+            cooldown_minutes: 15,
+            min_active_time_minutes: 10,
+        }
+    }
+
+    fn pause_state() -> PauseState {
+        PauseState {
+            paused: false,
+            enabled: true,
+            remaining: 3600,
+            pause_used: 0,
+            last_pause_end: 0,
+            active_since_resume: 600,
+        }
+    }
+
+    #[test]
+    fn pause_allowed_when_all_limits_are_met() {
+        assert!(evaluate_pause(pause_config(), pause_state(), NOW).is_ok());
+    }
+
+    #[test]
+    fn pause_blocked_when_disabled() {
+        let state = PauseState { enabled: false, ..pause_state() };
+        assert!(matches!(evaluate_pause(pause_config(), state, NOW), Err(PauseBlockedReason::Disabled)));
+    }
+
+    #[test]
+    fn pause_blocked_when_time_is_too_low() {
+        let state = PauseState { remaining: 59, ..pause_state() };
+        assert!(matches!(evaluate_pause(pause_config(), state, NOW), Err(PauseBlockedReason::TimeTooLow)));
+    }
+
+    #[test]
+    fn pause_blocked_when_budget_is_exhausted() {
+        let state = PauseState { pause_used: 45 * 60, ..pause_state() };
+        assert!(matches!(evaluate_pause(pause_config(), state, NOW), Err(PauseBlockedReason::BudgetExhausted)));
+    }
+
+    #[test]
+    fn pause_blocked_during_cooldown() {
+        let state = PauseState { last_pause_end: NOW - 60, ..pause_state() };
+        assert!(matches!(
+            evaluate_pause(pause_config(), state, NOW),
+            Err(PauseBlockedReason::CooldownActive { seconds_remaining: 840 })
+        ));
+
+        // The cooldown ends exactly after its length
+        let state = PauseState { last_pause_end: NOW - 15 * 60, ..pause_state() };
+        assert!(evaluate_pause(pause_config(), state, NOW).is_ok());
+    }
+
+    #[test]
+    fn pause_blocked_until_min_active_time() {
+        let state = PauseState { active_since_resume: 540, ..pause_state() };
+        assert!(matches!(
+            evaluate_pause(pause_config(), state, NOW),
+            Err(PauseBlockedReason::MinActiveTimeNotMet { seconds_remaining: 60 })
+        ));
+    }
+
+    #[test]
+    fn ending_a_pause_is_always_allowed() {
+        let state = PauseState { paused: true, enabled: false, remaining: 0, ..pause_state() };
+        assert!(evaluate_pause(pause_config(), state, NOW).is_ok());
+    }
+}
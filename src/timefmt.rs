@@ -0,0 +1,83 @@
+//! Time formatting module
+//! How remaining time is written on the lock screen, the corner timer and the banners
+
+/// Format a countdown as MM:SS (H:MM:SS for an hour or more), as on the lock screen
+pub fn format_countdown(seconds: i32) -> String {
+    let seconds = seconds.max(0);
+    if seconds >= 3600 {
+        format!("{}:{:02}:{:02}", seconds / 3600, (seconds % 3600) / 60, seconds % 60)
+    } else {
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// Format seconds into a compact string (e.g., "1:30:45" or "30:45")
+pub fn format_time_compact(seconds: i32) -> String {
+    if seconds < 0 {
+        return String::from("--:--");
+    }
+
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    let secs = seconds % 60;
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{}:{:02}", minutes, secs)
+    }
+}
+
+/// Format seconds in the corner timer format chosen in settings.
+/// "hhmm" and "minutes" round up, so "0m" only shows once the time is used up.
+/// "fraction" shows the remaining minutes against `limit_minutes`; times without a limit
+/// (pause, focus) pass `None` and show plain minutes instead.
+pub fn format_time_as(seconds: i32, format: &str, limit_minutes: Option<i32>) -> String {
+    if seconds < 0 {
+        return String::from("--:--");
+    }
+
+    let minutes_up = (seconds + 59) / 60;
+    match (format, limit_minutes) {
+        ("hhmm", _) => format!("{}:{:02}", minutes_up / 60, minutes_up % 60),
+        ("minutes", _) | ("fraction", None) => format!("{}m", minutes_up),
+        ("fraction", Some(limit)) => format!("{}/{}", minutes_up, limit),
+        _ => format_time_compact(seconds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn countdown_pads_minutes_and_adds_hours() {
+        assert_eq!(format_countdown(0), "00:00");
+        assert_eq!(format_countdown(65), "01:05");
+        assert_eq!(format_countdown(600), "10:00");
+        assert_eq!(format_countdown(3661), "1:01:01");
+        assert_eq!(format_countdown(-5), "00:00");
+    }
+
+    #[test]
+    fn compact_time_drops_hours_below_one_hour() {
+        assert_eq!(format_time_compact(0), "0:00");
+        assert_eq!(format_time_compact(59), "0:59");
+        assert_eq!(format_time_compact(30 * 60 + 45), "30:45");
+        assert_eq!(format_time_compact(3600), "1:00:00");
+        assert_eq!(format_time_compact(5445), "1:30:45");
+        assert_eq!(format_time_compact(-1), "--:--");
+    }
+
+    #[test]
+    fn chosen_format_rounds_minutes_up() {
+        assert_eq!(format_time_as(5445, "compact", Some(120)), "1:30:45");
+        assert_eq!(format_time_as(5445, "hhmm", Some(120)), "1:31");
+        assert_eq!(format_time_as(45 * 60, "hhmm", Some(120)), "0:45");
+        assert_eq!(format_time_as(30, "minutes", Some(120)), "1m");
+        assert_eq!(format_time_as(0, "minutes", Some(120)), "0m");
+        assert_eq!(format_time_as(45 * 60, "fraction", Some(120)), "45/120");
+        assert_eq!(format_time_as(45 * 60, "fraction", None), "45m");
+        assert_eq!(format_time_as(-1, "fraction", Some(120)), "--:--");
+    }
+}